@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+use criterion::{black_box, BatchSize, BenchmarkId, Criterion};
+use phys_rs::{
+    body::BodyID,
+    broad_phase::{
+        BroadPhase, BroadPhaseLayer, BroadPhaseLayerInterfaceTable, BroadPhaseQuadTree,
+        BroadPhaseSweepAndPrune, ObjectVsBroadPhaseLayerFilterTable,
+    },
+    collision::{ObjectLayer, ObjectLayerPairFilterTable},
+    geometry::AABox,
+    math::{vec3, Vec3},
+};
+
+const BODY_COUNTS: [usize; 3] = [1_000, 10_000, 100_000];
+
+struct Random(u32);
+
+impl Random {
+    fn next(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0 as f32 / u32::MAX as f32
+    }
+
+    fn vec3(&mut self) -> Vec3 {
+        vec3(self.next(), self.next(), self.next())
+    }
+}
+
+/// Static bodies in the first layer and moving bodies in the second, which collide with both.
+struct Scene {
+    layer_interface: Arc<BroadPhaseLayerInterfaceTable>,
+    pair_filter: ObjectLayerPairFilterTable,
+    object_vs_broad_phase_filter: ObjectVsBroadPhaseLayerFilterTable,
+    bodies: Vec<(BodyID, AABox, ObjectLayer)>,
+    active: Vec<BodyID>,
+}
+
+impl Scene {
+    /// Scatters `count` unit boxes so that the number of overlaps per body does not depend on
+    /// the number of bodies, a quarter of the bodies is moving.
+    fn new(count: usize) -> Self {
+        let mut layer_interface = BroadPhaseLayerInterfaceTable::new(2, 2);
+        layer_interface.map_object_to_broad_phase_layer(ObjectLayer(1), BroadPhaseLayer(1));
+        let mut pair_filter = ObjectLayerPairFilterTable::new(2);
+        pair_filter.enable_collision(ObjectLayer(0), ObjectLayer(1));
+        pair_filter.enable_collision(ObjectLayer(1), ObjectLayer(1));
+        let object_vs_broad_phase_filter =
+            ObjectVsBroadPhaseLayerFilterTable::new(&layer_interface, &pair_filter, 2);
+
+        let mut rng = Random(0x1234_5678);
+        let size = (count as f32).cbrt() * 4.0;
+        let bodies: Vec<_> = (0..count)
+            .map(|index| {
+                let center = rng.vec3() * size;
+                let bounds = AABox::from_center_and_half_extent(center, Vec3::splat(0.5));
+                let layer = ObjectLayer(index.is_multiple_of(4) as u16);
+                (BodyID::new(index as u32, 0), bounds, layer)
+            })
+            .collect();
+        let active = bodies
+            .iter()
+            .filter(|(.., layer)| *layer == ObjectLayer(1))
+            .map(|&(body_id, ..)| body_id)
+            .collect();
+        Self {
+            layer_interface: Arc::new(layer_interface),
+            pair_filter,
+            object_vs_broad_phase_filter,
+            bodies,
+            active,
+        }
+    }
+
+    /// Creates an empty broadphase of every implementation.
+    fn broad_phases(&self) -> [(&'static str, Box<dyn BroadPhase>); 2] {
+        let layer_interface = self.layer_interface.clone();
+        [
+            (
+                "quad_tree",
+                Box::new(BroadPhaseQuadTree::new(layer_interface.clone())),
+            ),
+            (
+                "sweep_and_prune",
+                Box::new(BroadPhaseSweepAndPrune::new(layer_interface)),
+            ),
+        ]
+    }
+
+    /// Moves every active body a little.
+    fn moved(&self, step: usize) -> Vec<(BodyID, AABox)> {
+        let offset = Vec3::splat(if step.is_multiple_of(2) { 0.1 } else { -0.1 });
+        self.active
+            .iter()
+            .map(|&body_id| {
+                let bounds = self.bodies[body_id.index() as usize].1;
+                (body_id, bounds.translated(offset))
+            })
+            .collect()
+    }
+
+    fn find_colliding_pairs(&self, broad_phase: &dyn BroadPhase) -> usize {
+        let mut count = 0;
+        broad_phase.find_colliding_pairs(
+            &self.active,
+            0.0,
+            &self.object_vs_broad_phase_filter,
+            &self.pair_filter,
+            &(),
+            &mut |_, _| count += 1,
+        );
+        count
+    }
+}
+
+pub fn broad_phase_add_bodies(c: &mut Criterion) {
+    let mut group = c.benchmark_group("broad_phase_add_bodies");
+    group.sample_size(10);
+    for count in BODY_COUNTS {
+        let scene = Scene::new(count);
+        for implementation in 0..2 {
+            let name = scene.broad_phases()[implementation].0;
+            group.bench_function(BenchmarkId::new(name, count), |b| {
+                b.iter_batched_ref(
+                    || {
+                        scene
+                            .broad_phases()
+                            .into_iter()
+                            .nth(implementation)
+                            .unwrap()
+                            .1
+                    },
+                    |broad_phase| {
+                        broad_phase.add_bodies(&scene.bodies);
+                        broad_phase.optimize();
+                    },
+                    BatchSize::LargeInput,
+                )
+            });
+        }
+    }
+    group.finish();
+}
+
+pub fn broad_phase_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("broad_phase_update");
+    group.sample_size(10);
+    for count in BODY_COUNTS {
+        let scene = Scene::new(count);
+        for (name, mut broad_phase) in scene.broad_phases() {
+            broad_phase.add_bodies(&scene.bodies);
+            broad_phase.optimize();
+            let mut step = 0;
+            group.bench_function(BenchmarkId::new(name, count), |b| {
+                b.iter(|| {
+                    step += 1;
+                    broad_phase.notify_bodies_aabb_changed(&scene.moved(step));
+                    broad_phase.update();
+                    black_box(scene.find_colliding_pairs(&*broad_phase))
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+pub fn broad_phase_find_colliding_pairs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("broad_phase_find_colliding_pairs");
+    group.sample_size(10);
+    for count in BODY_COUNTS {
+        let scene = Scene::new(count);
+        for (name, mut broad_phase) in scene.broad_phases() {
+            broad_phase.add_bodies(&scene.bodies);
+            broad_phase.optimize();
+            group.bench_function(BenchmarkId::new(name, count), |b| {
+                b.iter(|| black_box(scene.find_colliding_pairs(&*broad_phase)))
+            });
+        }
+    }
+    group.finish();
+}
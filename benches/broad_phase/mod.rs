@@ -0,0 +1,3 @@
+#[allow(clippy::module_inception)]
+mod broad_phase;
+pub use broad_phase::*;
@@ -1,10 +1,16 @@
 use criterion::{criterion_group, criterion_main};
 
+mod broad_phase;
+use broad_phase::*;
+
 mod math;
 use math::*;
 
 criterion_group!(
     benches,
+    broad_phase_add_bodies,
+    broad_phase_update,
+    broad_phase_find_colliding_pairs,
     vec3_dot,
     vec3_dot_into_vec3,
     vec3_dot_into_vec4,
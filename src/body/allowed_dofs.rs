@@ -0,0 +1,76 @@
+use core::ops::{BitAnd, BitOr, BitOrAssign};
+
+use crate::math::Vec3;
+
+/// The world space axes along which a body can move and around which it can rotate, as a set
+/// of bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AllowedDOFs(pub u8);
+
+impl AllowedDOFs {
+    pub const NONE: Self = Self(0);
+    pub const TRANSLATION_X: Self = Self(1 << 0);
+    pub const TRANSLATION_Y: Self = Self(1 << 1);
+    pub const TRANSLATION_Z: Self = Self(1 << 2);
+    pub const ROTATION_X: Self = Self(1 << 3);
+    pub const ROTATION_Y: Self = Self(1 << 4);
+    pub const ROTATION_Z: Self = Self(1 << 5);
+    pub const ALL: Self = Self(0b11_1111);
+    /// Movement in the XY plane and rotation around the Z axis.
+    pub const PLANE_2D: Self =
+        Self(Self::TRANSLATION_X.0 | Self::TRANSLATION_Y.0 | Self::ROTATION_Z.0);
+
+    /// Returns whether all degrees of freedom in `other` are allowed.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns one for every axis along which the body can move and zero for the others.
+    #[inline]
+    pub fn translation_mask(self) -> Vec3 {
+        self.mask(0)
+    }
+
+    /// Returns one for every axis around which the body can rotate and zero for the others.
+    #[inline]
+    pub fn rotation_mask(self) -> Vec3 {
+        self.mask(3)
+    }
+
+    #[inline]
+    fn mask(self, shift: u8) -> Vec3 {
+        let axis = |axis: u8| ((self.0 >> (shift + axis)) & 1) as f32;
+        Vec3::new(axis(0), axis(1), axis(2))
+    }
+}
+
+impl Default for AllowedDOFs {
+    #[inline]
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl BitOr for AllowedDOFs {
+    type Output = Self;
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for AllowedDOFs {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for AllowedDOFs {
+    type Output = Self;
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
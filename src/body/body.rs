@@ -0,0 +1,482 @@
+use alloc::sync::Arc;
+use core::fmt;
+
+use crate::{
+    body::{
+        BodyCreationSettings, BodyID, MassProperties, MotionProperties, MotionQuality, MotionType,
+    },
+    collision::{CollisionGroup, ObjectLayer, TransformedShape},
+    geometry::AABox,
+    math::{Quat, Vec3},
+    shape::Shape,
+};
+
+/// Errors that can occur when creating or changing a body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BodyError {
+    /// A position was not finite.
+    InvalidPosition,
+    /// A rotation was not normalized.
+    InvalidRotation,
+    /// The friction was negative or not finite.
+    InvalidFriction,
+    /// The restitution was not between zero and one.
+    InvalidRestitution,
+    /// A velocity was not finite.
+    InvalidVelocity,
+    /// A damping was negative or not finite.
+    InvalidDamping,
+    /// The gravity factor was not finite.
+    InvalidGravityFactor,
+    /// A maximum velocity was negative or zero.
+    InvalidMaxVelocity,
+    /// A dynamic body is not allowed to move in any direction.
+    InvalidAllowedDOFs,
+    /// A dynamic body has a shape without a positive and finite mass.
+    InvalidMass,
+    /// The maximum number of bodies has been reached.
+    TooManyBodies,
+    /// The identifier does not refer to a body, or the body has been destroyed.
+    InvalidBodyID,
+    /// The body has already been added to the broadphase.
+    AlreadyAdded,
+    /// The body has not been added to the broadphase.
+    NotAdded,
+}
+
+#[cfg(not(spirv))]
+impl fmt::Display for BodyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPosition => write!(f, "invalid position"),
+            Self::InvalidRotation => write!(f, "invalid rotation"),
+            Self::InvalidFriction => write!(f, "invalid friction"),
+            Self::InvalidRestitution => write!(f, "invalid restitution"),
+            Self::InvalidVelocity => write!(f, "invalid velocity"),
+            Self::InvalidDamping => write!(f, "invalid damping"),
+            Self::InvalidGravityFactor => write!(f, "invalid gravity factor"),
+            Self::InvalidMaxVelocity => write!(f, "invalid maximum velocity"),
+            Self::InvalidAllowedDOFs => write!(f, "dynamic body has no allowed degrees of freedom"),
+            Self::InvalidMass => write!(f, "dynamic body has no valid mass"),
+            Self::TooManyBodies => write!(f, "too many bodies"),
+            Self::InvalidBodyID => write!(f, "invalid body id"),
+            Self::AlreadyAdded => write!(f, "body is already added"),
+            Self::NotAdded => write!(f, "body is not added"),
+        }
+    }
+}
+
+/// A rigid body, a shape placed in the world that can move and collide.
+///
+/// The position of a body is stored at its center of mass, [`Body::position`] returns the
+/// origin of the shape instead.
+#[derive(Debug)]
+pub struct Body {
+    id: BodyID,
+    center_of_mass_position: Vec3,
+    rotation: Quat,
+    shape: Arc<dyn Shape>,
+    motion_type: MotionType,
+    object_layer: ObjectLayer,
+    /// The motion of the body, for bodies that are not static.
+    motion_properties: Option<MotionProperties>,
+    /// The world space bounding box of the shape.
+    bounds: AABox,
+    /// Whether the body is in the broadphase.
+    is_added: bool,
+    /// Whether the body is simulated, sleeping and static bodies are not.
+    is_active: bool,
+    pub collision_group: CollisionGroup,
+    pub user_data: u64,
+    pub friction: f32,
+    pub restitution: f32,
+}
+
+impl Body {
+    pub(crate) fn new(id: BodyID, settings: &BodyCreationSettings) -> Self {
+        let mut body = Self {
+            id,
+            center_of_mass_position: Vec3::ZERO,
+            rotation: settings.rotation,
+            shape: settings.shape.clone(),
+            motion_type: settings.motion_type,
+            object_layer: settings.object_layer,
+            motion_properties: None,
+            bounds: AABox::new(Vec3::ZERO, Vec3::ZERO),
+            is_added: false,
+            is_active: false,
+            collision_group: settings.collision_group.clone(),
+            user_data: settings.user_data,
+            friction: settings.friction,
+            restitution: settings.restitution,
+        };
+        body.set_position_and_rotation(settings.position, settings.rotation);
+        if settings.motion_type != MotionType::Static {
+            let mass_properties = body.mass_properties_for(settings.motion_type);
+            let mut motion_properties =
+                MotionProperties::new(&mass_properties, settings.allowed_dofs);
+            motion_properties.linear_damping = settings.linear_damping;
+            motion_properties.angular_damping = settings.angular_damping;
+            motion_properties.gravity_factor = settings.gravity_factor;
+            motion_properties.max_linear_velocity = settings.max_linear_velocity;
+            motion_properties.max_angular_velocity = settings.max_angular_velocity;
+            motion_properties.allow_sleeping = settings.allow_sleeping;
+            motion_properties.motion_quality = settings.motion_quality;
+            motion_properties.set_linear_velocity_clamped(settings.linear_velocity);
+            motion_properties.set_angular_velocity_clamped(settings.angular_velocity);
+            body.motion_properties = Some(motion_properties);
+        }
+        body
+    }
+
+    #[inline]
+    pub fn id(&self) -> BodyID {
+        self.id
+    }
+
+    /// Returns the position of the origin of the shape.
+    #[inline]
+    pub fn position(&self) -> Vec3 {
+        self.center_of_mass_position - self.rotation * self.shape.center_of_mass()
+    }
+
+    #[inline]
+    pub fn center_of_mass_position(&self) -> Vec3 {
+        self.center_of_mass_position
+    }
+
+    #[inline]
+    pub fn rotation(&self) -> Quat {
+        self.rotation
+    }
+
+    #[inline]
+    pub fn shape(&self) -> &Arc<dyn Shape> {
+        &self.shape
+    }
+
+    #[inline]
+    pub fn motion_type(&self) -> MotionType {
+        self.motion_type
+    }
+
+    #[inline]
+    pub fn is_static(&self) -> bool {
+        self.motion_type == MotionType::Static
+    }
+
+    #[inline]
+    pub fn is_kinematic(&self) -> bool {
+        self.motion_type == MotionType::Kinematic
+    }
+
+    #[inline]
+    pub fn is_dynamic(&self) -> bool {
+        self.motion_type == MotionType::Dynamic
+    }
+
+    #[inline]
+    pub fn object_layer(&self) -> ObjectLayer {
+        self.object_layer
+    }
+
+    /// Returns the motion of the body, or `None` for a static body.
+    #[inline]
+    pub fn motion_properties(&self) -> Option<&MotionProperties> {
+        self.motion_properties.as_ref()
+    }
+
+    /// Returns the motion of the body, or `None` for a static body.
+    #[inline]
+    pub fn motion_properties_mut(&mut self) -> Option<&mut MotionProperties> {
+        self.motion_properties.as_mut()
+    }
+
+    /// Returns how the body is moved and collided, discrete for a static body.
+    #[inline]
+    pub fn motion_quality(&self) -> MotionQuality {
+        self.motion_properties
+            .map_or(MotionQuality::Discrete, |motion| motion.motion_quality)
+    }
+
+    /// Returns the linear velocity of the center of mass, zero for a static body.
+    #[inline]
+    pub fn linear_velocity(&self) -> Vec3 {
+        self.motion_properties
+            .map_or(Vec3::ZERO, |motion| motion.linear_velocity())
+    }
+
+    /// Returns the angular velocity, zero for a static body.
+    #[inline]
+    pub fn angular_velocity(&self) -> Vec3 {
+        self.motion_properties
+            .map_or(Vec3::ZERO, |motion| motion.angular_velocity())
+    }
+
+    /// Returns the velocity of the point of the body at world space `point`.
+    #[inline]
+    pub fn point_velocity(&self, point: Vec3) -> Vec3 {
+        self.linear_velocity()
+            + self
+                .angular_velocity()
+                .cross(point - self.center_of_mass_position)
+    }
+
+    /// Sets the linear velocity of a body that is not static, clamped to its maximum.
+    #[inline]
+    pub fn set_linear_velocity(&mut self, linear_velocity: Vec3) {
+        if let Some(motion) = &mut self.motion_properties {
+            motion.set_linear_velocity_clamped(linear_velocity);
+        }
+    }
+
+    /// Sets the angular velocity of a body that is not static, clamped to its maximum.
+    #[inline]
+    pub fn set_angular_velocity(&mut self, angular_velocity: Vec3) {
+        if let Some(motion) = &mut self.motion_properties {
+            motion.set_angular_velocity_clamped(angular_velocity);
+        }
+    }
+
+    /// Adds a force through the center of mass of a dynamic body, it is applied during the
+    /// next step.
+    #[inline]
+    pub fn add_force(&mut self, force: Vec3) {
+        if let Some(motion) = self.dynamic_motion_properties() {
+            motion.add_force(force);
+        }
+    }
+
+    /// Adds a force at world space `point` of a dynamic body, it is applied during the next
+    /// step.
+    #[inline]
+    pub fn add_force_at_point(&mut self, force: Vec3, point: Vec3) {
+        let torque = (point - self.center_of_mass_position).cross(force);
+        if let Some(motion) = self.dynamic_motion_properties() {
+            motion.add_force(force);
+            motion.add_torque(torque);
+        }
+    }
+
+    /// Adds a torque to a dynamic body, it is applied during the next step.
+    #[inline]
+    pub fn add_torque(&mut self, torque: Vec3) {
+        if let Some(motion) = self.dynamic_motion_properties() {
+            motion.add_torque(torque);
+        }
+    }
+
+    /// Changes the velocity of a dynamic body by an impulse through its center of mass.
+    #[inline]
+    pub fn add_impulse(&mut self, impulse: Vec3) {
+        if let Some(motion) = self.dynamic_motion_properties() {
+            motion.add_impulse(impulse);
+        }
+    }
+
+    /// Changes the velocity of a dynamic body by an impulse at world space `point`.
+    #[inline]
+    pub fn add_impulse_at_point(&mut self, impulse: Vec3, point: Vec3) {
+        let angular_impulse = (point - self.center_of_mass_position).cross(impulse);
+        self.add_impulse(impulse);
+        self.add_angular_impulse(angular_impulse);
+    }
+
+    /// Changes the angular velocity of a dynamic body by an angular impulse.
+    #[inline]
+    pub fn add_angular_impulse(&mut self, angular_impulse: Vec3) {
+        let rotation = self.rotation;
+        if let Some(motion) = self.dynamic_motion_properties() {
+            motion.add_angular_impulse(rotation, angular_impulse);
+        }
+    }
+
+    /// Returns the world space bounding box of the body.
+    #[inline]
+    pub fn world_bounds(&self) -> AABox {
+        self.bounds
+    }
+
+    /// Returns whether the body is in the broadphase.
+    #[inline]
+    pub fn is_added(&self) -> bool {
+        self.is_added
+    }
+
+    /// Returns whether the body is simulated, which bodies stop being when they go to sleep.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    /// Returns the shape of the body placed at the body, for collision queries.
+    #[inline]
+    pub fn transformed_shape(&self) -> TransformedShape<'_> {
+        TransformedShape::new(&*self.shape, self.center_of_mass_position, self.rotation)
+            .with_body_id(self.id)
+    }
+
+    /// Moves the origin of the shape to `position`.
+    pub(crate) fn set_position_and_rotation(&mut self, position: Vec3, rotation: Quat) {
+        self.rotation = rotation;
+        self.center_of_mass_position = position + rotation * self.shape.center_of_mass();
+        self.update_bounds();
+    }
+
+    /// Replaces the shape while keeping the origin of the shape in place.
+    pub(crate) fn set_shape(&mut self, shape: Arc<dyn Shape>) {
+        let position = self.position();
+        self.shape = shape;
+        self.set_position_and_rotation(position, self.rotation);
+        if self.motion_properties.is_some() {
+            self.update_mass_properties();
+        }
+    }
+
+    /// Changes the motion type, the velocities are kept when the body was not static.
+    pub(crate) fn set_motion_type(&mut self, motion_type: MotionType) {
+        self.motion_type = motion_type;
+        if motion_type == MotionType::Static {
+            self.motion_properties = None;
+        } else {
+            self.update_mass_properties();
+        }
+    }
+
+    /// Applies gravity, the accumulated forces and damping to the velocities of a dynamic
+    /// body.
+    pub(crate) fn apply_forces_gravity_and_damping(&mut self, gravity: Vec3, delta_time: f32) {
+        let rotation = self.rotation;
+        if let Some(motion) = self.dynamic_motion_properties() {
+            motion.apply_forces_gravity_and_damping(rotation, gravity, delta_time);
+        }
+    }
+
+    /// Moves the body by its velocities over `delta_time`, the bounds are not updated.
+    pub(crate) fn integrate(&mut self, delta_time: f32) {
+        if let Some(motion) = &self.motion_properties {
+            motion.integrate(
+                &mut self.center_of_mass_position,
+                &mut self.rotation,
+                delta_time,
+            );
+        }
+    }
+
+    /// Returns the inverse mass, zero for a body that is not dynamic.
+    #[inline]
+    pub(crate) fn inverse_mass(&self) -> f32 {
+        match self.motion_type {
+            MotionType::Dynamic => self.motion_properties.map_or(0.0, |m| m.inverse_mass()),
+            _ => 0.0,
+        }
+    }
+
+    /// Multiplies `vector` by the world space inverse inertia, zero for a body that is not
+    /// dynamic.
+    #[inline]
+    pub(crate) fn multiply_world_space_inverse_inertia_by_vector(&self, vector: Vec3) -> Vec3 {
+        match (self.motion_type, &self.motion_properties) {
+            (MotionType::Dynamic, Some(motion)) => {
+                motion.multiply_world_space_inverse_inertia_by_vector(self.rotation, vector)
+            }
+            _ => Vec3::ZERO,
+        }
+    }
+
+    /// Changes the velocities of a dynamic body by the result of a constraint, the velocities
+    /// are not clamped.
+    #[inline]
+    pub(crate) fn add_velocity_step(&mut self, linear: Vec3, angular: Vec3) {
+        if let Some(motion) = self.dynamic_motion_properties() {
+            motion.set_linear_velocity(motion.linear_velocity() + linear);
+            motion.set_angular_velocity(motion.angular_velocity() + angular);
+        }
+    }
+
+    /// Moves a dynamic body by the result of the position solver, the bounds are not updated.
+    pub(crate) fn add_position_step(&mut self, linear: Vec3, angular: Vec3) {
+        if !self.is_dynamic() {
+            return;
+        }
+        let Some(motion) = &self.motion_properties else {
+            return;
+        };
+        let allowed_dofs = motion.allowed_dofs();
+        let angular = angular * allowed_dofs.rotation_mask();
+        self.center_of_mass_position += linear * allowed_dofs.translation_mask();
+        let spin = Quat::from_xyzw(angular.x, angular.y, angular.z, 0.0) * self.rotation;
+        self.rotation = Quat::from_vec4(self.rotation.0 + spin.0 * 0.5).normalize();
+    }
+
+    /// Recomputes the mass and inertia from the shape, keeping the velocities.
+    fn update_mass_properties(&mut self) {
+        let mass_properties = self.mass_properties_for(self.motion_type);
+        match &mut self.motion_properties {
+            Some(motion) => motion.set_mass_properties(&mass_properties, motion.allowed_dofs()),
+            None => {
+                self.motion_properties =
+                    Some(MotionProperties::new(&mass_properties, Default::default()));
+            }
+        }
+    }
+
+    /// Returns the mass properties that a body of `motion_type` gets from its shape, only
+    /// dynamic bodies have a mass.
+    fn mass_properties_for(&self, motion_type: MotionType) -> MassProperties {
+        match motion_type {
+            MotionType::Dynamic => self.shape.mass_properties(),
+            _ => MassProperties::default(),
+        }
+    }
+
+    #[inline]
+    fn dynamic_motion_properties(&mut self) -> Option<&mut MotionProperties> {
+        match self.motion_type {
+            MotionType::Dynamic => self.motion_properties.as_mut(),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn set_object_layer(&mut self, object_layer: ObjectLayer) {
+        self.object_layer = object_layer;
+    }
+
+    #[inline]
+    pub(crate) fn set_is_added(&mut self, is_added: bool) {
+        self.is_added = is_added;
+    }
+
+    /// Wakes the body up or puts it to sleep, a sleeping body stops moving. The sleep timer
+    /// is reset either way.
+    pub(crate) fn set_is_active(&mut self, is_active: bool) {
+        self.is_active = is_active;
+        if let Some(motion) = &mut self.motion_properties {
+            motion.reset_sleep_timer();
+            if !is_active {
+                motion.set_linear_velocity(Vec3::ZERO);
+                motion.set_angular_velocity(Vec3::ZERO);
+            }
+        }
+    }
+
+    /// Updates the sleep timer from the velocities of the points of the body and returns
+    /// whether the body has moved slowly for long enough to go to sleep.
+    pub(crate) fn update_sleep_state(
+        &mut self,
+        threshold: f32,
+        time_before_sleep: f32,
+        delta_time: f32,
+    ) -> bool {
+        let radius = self.bounds.extent().length();
+        self.motion_properties.as_mut().is_some_and(|motion| {
+            motion.update_sleep_timer(radius, threshold, delta_time) >= time_before_sleep
+        })
+    }
+
+    #[inline]
+    pub(crate) fn update_bounds(&mut self) {
+        self.bounds = self.transformed_shape().world_bounds();
+    }
+}
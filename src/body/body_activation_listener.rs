@@ -0,0 +1,13 @@
+use crate::body::BodyID;
+
+/// Receives the bodies that wake up or go to sleep.
+///
+/// Bodies are activated when they are added, changed through the
+/// [`BodyInterface`](super::BodyInterface) or touched by an active body, and deactivated when
+/// they are removed or their island falls asleep during
+/// [`PhysicsSystem::update`](crate::physics::PhysicsSystem::update).
+pub trait BodyActivationListener: Send + Sync {
+    fn on_body_activated(&mut self, _body_id: BodyID, _user_data: u64) {}
+
+    fn on_body_deactivated(&mut self, _body_id: BodyID, _user_data: u64) {}
+}
@@ -0,0 +1,120 @@
+use alloc::sync::Arc;
+
+use crate::{
+    body::{
+        AllowedDOFs, BodyError, MotionQuality, MotionType, DEFAULT_ANGULAR_DAMPING,
+        DEFAULT_LINEAR_DAMPING, DEFAULT_MAX_ANGULAR_VELOCITY, DEFAULT_MAX_LINEAR_VELOCITY,
+    },
+    collision::{CollisionGroup, ObjectLayer},
+    math::{Quat, Vec3},
+    shape::Shape,
+};
+
+/// The settings that a body is created from.
+#[derive(Clone, Debug)]
+pub struct BodyCreationSettings {
+    /// The position of the origin of the shape.
+    pub position: Vec3,
+    pub rotation: Quat,
+    pub shape: Arc<dyn Shape>,
+    pub motion_type: MotionType,
+    pub object_layer: ObjectLayer,
+    pub collision_group: CollisionGroup,
+    /// A value that the application can use to find its own data for the body.
+    pub user_data: u64,
+    /// How much the body resists sliding, usually between zero and one.
+    pub friction: f32,
+    /// How much the body bounces, between zero for no bounce and one for a perfect bounce.
+    pub restitution: f32,
+    pub linear_velocity: Vec3,
+    pub angular_velocity: Vec3,
+    /// The fraction of the linear velocity that is lost every second.
+    pub linear_damping: f32,
+    /// The fraction of the angular velocity that is lost every second.
+    pub angular_damping: f32,
+    /// Scales the gravity that is applied to the body.
+    pub gravity_factor: f32,
+    pub max_linear_velocity: f32,
+    pub max_angular_velocity: f32,
+    pub allowed_dofs: AllowedDOFs,
+    /// Whether the body can go to sleep when it stops moving.
+    pub allow_sleeping: bool,
+    /// How the body is moved and collided, this only affects dynamic bodies.
+    pub motion_quality: MotionQuality,
+}
+
+impl BodyCreationSettings {
+    /// Creates settings for a body at rest with a friction of 0.2, no restitution, no collision
+    /// group, discrete motion and the default damping and velocity limits.
+    pub fn new(
+        shape: Arc<dyn Shape>,
+        position: Vec3,
+        rotation: Quat,
+        motion_type: MotionType,
+        object_layer: ObjectLayer,
+    ) -> Self {
+        Self {
+            position,
+            rotation,
+            shape,
+            motion_type,
+            object_layer,
+            collision_group: CollisionGroup::default(),
+            user_data: 0,
+            friction: 0.2,
+            restitution: 0.0,
+            linear_velocity: Vec3::ZERO,
+            angular_velocity: Vec3::ZERO,
+            linear_damping: DEFAULT_LINEAR_DAMPING,
+            angular_damping: DEFAULT_ANGULAR_DAMPING,
+            gravity_factor: 1.0,
+            max_linear_velocity: DEFAULT_MAX_LINEAR_VELOCITY,
+            max_angular_velocity: DEFAULT_MAX_ANGULAR_VELOCITY,
+            allowed_dofs: AllowedDOFs::ALL,
+            allow_sleeping: true,
+            motion_quality: MotionQuality::Discrete,
+        }
+    }
+
+    /// Returns an error when a body cannot be created from these settings.
+    pub fn validate(&self) -> Result<(), BodyError> {
+        if !self.position.is_finite() {
+            return Err(BodyError::InvalidPosition);
+        }
+        if !self.rotation.is_normalized() {
+            return Err(BodyError::InvalidRotation);
+        }
+        if !(self.friction >= 0.0 && self.friction.is_finite()) {
+            return Err(BodyError::InvalidFriction);
+        }
+        if !(0.0..=1.0).contains(&self.restitution) {
+            return Err(BodyError::InvalidRestitution);
+        }
+        if !(self.linear_velocity.is_finite() && self.angular_velocity.is_finite()) {
+            return Err(BodyError::InvalidVelocity);
+        }
+        let is_valid_damping = |damping: f32| damping >= 0.0 && damping.is_finite();
+        if !(is_valid_damping(self.linear_damping) && is_valid_damping(self.angular_damping)) {
+            return Err(BodyError::InvalidDamping);
+        }
+        if !self.gravity_factor.is_finite() {
+            return Err(BodyError::InvalidGravityFactor);
+        }
+        if !(self.max_linear_velocity > 0.0 && self.max_angular_velocity > 0.0) {
+            return Err(BodyError::InvalidMaxVelocity);
+        }
+        if self.motion_type == MotionType::Dynamic && self.allowed_dofs == AllowedDOFs::NONE {
+            return Err(BodyError::InvalidAllowedDOFs);
+        }
+        validate_mass(&*self.shape, self.motion_type)
+    }
+}
+
+/// Returns an error when `shape` has no valid mass for a body of `motion_type`.
+pub(crate) fn validate_mass(shape: &dyn Shape, motion_type: MotionType) -> Result<(), BodyError> {
+    let mass = shape.mass_properties().mass;
+    if motion_type == MotionType::Dynamic && !(mass > 0.0 && mass.is_finite()) {
+        return Err(BodyError::InvalidMass);
+    }
+    Ok(())
+}
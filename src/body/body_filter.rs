@@ -0,0 +1,37 @@
+use crate::body::BodyID;
+
+/// Decides which bodies a query collides with, the unit type accepts all bodies.
+pub trait BodyFilter {
+    #[inline]
+    fn should_collide(&self, _body_id: BodyID) -> bool {
+        true
+    }
+}
+
+impl BodyFilter for () {}
+
+impl<T: BodyFilter + ?Sized> BodyFilter for &T {
+    #[inline]
+    fn should_collide(&self, body_id: BodyID) -> bool {
+        (**self).should_collide(body_id)
+    }
+}
+
+/// Accepts the bodies that both filters accept.
+impl<A: BodyFilter, B: BodyFilter> BodyFilter for (A, B) {
+    #[inline]
+    fn should_collide(&self, body_id: BodyID) -> bool {
+        self.0.should_collide(body_id) && self.1.should_collide(body_id)
+    }
+}
+
+/// Accepts all bodies except one, such as the body that performs the query.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IgnoreSingleBodyFilter(pub BodyID);
+
+impl BodyFilter for IgnoreSingleBodyFilter {
+    #[inline]
+    fn should_collide(&self, body_id: BodyID) -> bool {
+        body_id != self.0
+    }
+}
@@ -0,0 +1,59 @@
+/// Identifies a body.
+///
+/// The identifier consists of the index of the body and a sequence number, which is increased
+/// every time the index is reused. This detects handles to bodies that have been destroyed.
+/// The most significant bit is never used, so that it can be used to tag identifiers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BodyID(u32);
+
+impl BodyID {
+    /// The number of bits used for the index.
+    pub const INDEX_BITS: u32 = 23;
+
+    /// The largest index of a body.
+    pub const MAX_INDEX: u32 = (1 << Self::INDEX_BITS) - 1;
+
+    /// An identifier that never refers to a body.
+    pub const INVALID: Self = Self(u32::MAX);
+
+    /// Creates an identifier from the index of the body and its sequence number.
+    #[inline]
+    pub const fn new(index: u32, sequence: u8) -> Self {
+        debug_assert!(index <= Self::MAX_INDEX);
+        Self(index | (sequence as u32) << Self::INDEX_BITS)
+    }
+
+    /// Creates an identifier from its raw value.
+    #[inline]
+    pub const fn from_raw(value: u32) -> Self {
+        Self(value)
+    }
+
+    /// Returns the raw value of the identifier.
+    #[inline]
+    pub const fn value(self) -> u32 {
+        self.0
+    }
+
+    #[inline]
+    pub const fn index(self) -> u32 {
+        self.0 & Self::MAX_INDEX
+    }
+
+    #[inline]
+    pub const fn sequence(self) -> u8 {
+        (self.0 >> Self::INDEX_BITS) as u8
+    }
+
+    #[inline]
+    pub const fn is_invalid(self) -> bool {
+        self.0 == Self::INVALID.0
+    }
+}
+
+impl Default for BodyID {
+    #[inline]
+    fn default() -> Self {
+        Self::INVALID
+    }
+}
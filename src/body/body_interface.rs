@@ -0,0 +1,387 @@
+use alloc::sync::Arc;
+
+use crate::{
+    body::{
+        body_creation_settings::validate_mass, Body, BodyCreationSettings, BodyError, BodyID,
+        BodyManager, MotionQuality, MotionType,
+    },
+    broad_phase::BroadPhase,
+    collision::ObjectLayer,
+    geometry::AABox,
+    math::{Quat, Vec3},
+    shape::Shape,
+};
+
+/// Creates, adds, removes and destroys bodies, and changes their properties while keeping the
+/// broadphase up to date.
+pub struct BodyInterface<'a> {
+    body_manager: &'a mut BodyManager,
+    broad_phase: &'a mut dyn BroadPhase,
+}
+
+impl<'a> BodyInterface<'a> {
+    #[inline]
+    pub fn new(body_manager: &'a mut BodyManager, broad_phase: &'a mut dyn BroadPhase) -> Self {
+        Self {
+            body_manager,
+            broad_phase,
+        }
+    }
+
+    /// Creates a body without adding it to the broadphase.
+    #[inline]
+    pub fn create_body(&mut self, settings: &BodyCreationSettings) -> Result<BodyID, BodyError> {
+        self.body_manager.create_body(settings)
+    }
+
+    /// Creates a body and adds it to the broadphase.
+    pub fn create_and_add_body(
+        &mut self,
+        settings: &BodyCreationSettings,
+    ) -> Result<BodyID, BodyError> {
+        let body_id = self.create_body(settings)?;
+        self.add_body(body_id)?;
+        Ok(body_id)
+    }
+
+    /// Adds a body to the broadphase, which makes it collide with other bodies. A body that
+    /// is not static is activated.
+    pub fn add_body(&mut self, body_id: BodyID) -> Result<(), BodyError> {
+        let body = self.body_mut(body_id)?;
+        if body.is_added() {
+            return Err(BodyError::AlreadyAdded);
+        }
+        body.set_is_added(true);
+        let entry = (body_id, body.world_bounds(), body.object_layer());
+        self.broad_phase.add_bodies(&[entry]);
+        self.body_manager.activate_bodies(&[body_id]);
+        Ok(())
+    }
+
+    /// Deactivates a body and removes it from the broadphase, the body can be added again
+    /// later.
+    pub fn remove_body(&mut self, body_id: BodyID) -> Result<(), BodyError> {
+        if !self.body(body_id)?.is_added() {
+            return Err(BodyError::NotAdded);
+        }
+        self.body_manager.deactivate_bodies(&[body_id]);
+        self.body_mut(body_id)?.set_is_added(false);
+        self.broad_phase.remove_bodies(&[body_id]);
+        Ok(())
+    }
+
+    /// Wakes up a body that has been added, which does nothing for a static body.
+    pub fn activate_body(&mut self, body_id: BodyID) -> Result<(), BodyError> {
+        if !self.body(body_id)?.is_added() {
+            return Err(BodyError::NotAdded);
+        }
+        self.body_manager.activate_bodies(&[body_id]);
+        Ok(())
+    }
+
+    /// Puts a body to sleep, which stops it until it is activated again.
+    pub fn deactivate_body(&mut self, body_id: BodyID) -> Result<(), BodyError> {
+        if !self.body(body_id)?.is_added() {
+            return Err(BodyError::NotAdded);
+        }
+        self.body_manager.deactivate_bodies(&[body_id]);
+        Ok(())
+    }
+
+    /// Destroys a body that has been removed from the broadphase.
+    #[inline]
+    pub fn destroy_body(&mut self, body_id: BodyID) -> Result<(), BodyError> {
+        self.body_manager.destroy_body(body_id).map(|_| ())
+    }
+
+    /// Returns whether `body_id` refers to a body that has not been destroyed.
+    #[inline]
+    pub fn is_valid(&self, body_id: BodyID) -> bool {
+        self.body_manager.body(body_id).is_some()
+    }
+
+    #[inline]
+    pub fn is_added(&self, body_id: BodyID) -> bool {
+        self.body_manager.body(body_id).is_some_and(Body::is_added)
+    }
+
+    #[inline]
+    pub fn is_active(&self, body_id: BodyID) -> bool {
+        self.body_manager.body(body_id).is_some_and(Body::is_active)
+    }
+
+    /// Returns the position of the origin of the shape of the body.
+    #[inline]
+    pub fn position(&self, body_id: BodyID) -> Option<Vec3> {
+        self.body_manager.body(body_id).map(Body::position)
+    }
+
+    #[inline]
+    pub fn center_of_mass_position(&self, body_id: BodyID) -> Option<Vec3> {
+        self.body_manager
+            .body(body_id)
+            .map(Body::center_of_mass_position)
+    }
+
+    #[inline]
+    pub fn rotation(&self, body_id: BodyID) -> Option<Quat> {
+        self.body_manager.body(body_id).map(Body::rotation)
+    }
+
+    #[inline]
+    pub fn world_bounds(&self, body_id: BodyID) -> Option<AABox> {
+        self.body_manager.body(body_id).map(Body::world_bounds)
+    }
+
+    #[inline]
+    pub fn shape(&self, body_id: BodyID) -> Option<Arc<dyn Shape>> {
+        self.body_manager
+            .body(body_id)
+            .map(|body| body.shape().clone())
+    }
+
+    #[inline]
+    pub fn motion_type(&self, body_id: BodyID) -> Option<MotionType> {
+        self.body_manager.body(body_id).map(Body::motion_type)
+    }
+
+    #[inline]
+    pub fn motion_quality(&self, body_id: BodyID) -> Option<MotionQuality> {
+        self.body_manager.body(body_id).map(Body::motion_quality)
+    }
+
+    #[inline]
+    pub fn object_layer(&self, body_id: BodyID) -> Option<ObjectLayer> {
+        self.body_manager.body(body_id).map(Body::object_layer)
+    }
+
+    #[inline]
+    pub fn friction(&self, body_id: BodyID) -> Option<f32> {
+        self.body_manager.body(body_id).map(|body| body.friction)
+    }
+
+    #[inline]
+    pub fn restitution(&self, body_id: BodyID) -> Option<f32> {
+        self.body_manager.body(body_id).map(|body| body.restitution)
+    }
+
+    #[inline]
+    pub fn user_data(&self, body_id: BodyID) -> Option<u64> {
+        self.body_manager.body(body_id).map(|body| body.user_data)
+    }
+
+    /// Returns the linear velocity of the center of mass, zero for a static body.
+    #[inline]
+    pub fn linear_velocity(&self, body_id: BodyID) -> Option<Vec3> {
+        self.body_manager.body(body_id).map(Body::linear_velocity)
+    }
+
+    /// Returns the angular velocity, zero for a static body.
+    #[inline]
+    pub fn angular_velocity(&self, body_id: BodyID) -> Option<Vec3> {
+        self.body_manager.body(body_id).map(Body::angular_velocity)
+    }
+
+    /// Sets the linear velocity of a body that is not static, clamped to its maximum. A
+    /// velocity that is not zero wakes the body up.
+    pub fn set_linear_velocity(
+        &mut self,
+        body_id: BodyID,
+        linear_velocity: Vec3,
+    ) -> Result<(), BodyError> {
+        if !linear_velocity.is_finite() {
+            return Err(BodyError::InvalidVelocity);
+        }
+        self.body_mut(body_id)?.set_linear_velocity(linear_velocity);
+        self.activate_if_not_zero(body_id, linear_velocity);
+        Ok(())
+    }
+
+    /// Sets the angular velocity of a body that is not static, clamped to its maximum. A
+    /// velocity that is not zero wakes the body up.
+    pub fn set_angular_velocity(
+        &mut self,
+        body_id: BodyID,
+        angular_velocity: Vec3,
+    ) -> Result<(), BodyError> {
+        if !angular_velocity.is_finite() {
+            return Err(BodyError::InvalidVelocity);
+        }
+        self.body_mut(body_id)?
+            .set_angular_velocity(angular_velocity);
+        self.activate_if_not_zero(body_id, angular_velocity);
+        Ok(())
+    }
+
+    /// Adds a force through the center of mass of a dynamic body, it is applied during the
+    /// next step.
+    pub fn add_force(&mut self, body_id: BodyID, force: Vec3) -> Result<(), BodyError> {
+        self.body_mut(body_id)?.add_force(force);
+        self.activate_if_not_zero(body_id, force);
+        Ok(())
+    }
+
+    /// Adds a torque to a dynamic body, it is applied during the next step.
+    pub fn add_torque(&mut self, body_id: BodyID, torque: Vec3) -> Result<(), BodyError> {
+        self.body_mut(body_id)?.add_torque(torque);
+        self.activate_if_not_zero(body_id, torque);
+        Ok(())
+    }
+
+    /// Changes the velocity of a dynamic body by an impulse through its center of mass.
+    pub fn add_impulse(&mut self, body_id: BodyID, impulse: Vec3) -> Result<(), BodyError> {
+        self.body_mut(body_id)?.add_impulse(impulse);
+        self.activate_if_not_zero(body_id, impulse);
+        Ok(())
+    }
+
+    /// Changes the angular velocity of a dynamic body by an angular impulse.
+    pub fn add_angular_impulse(
+        &mut self,
+        body_id: BodyID,
+        angular_impulse: Vec3,
+    ) -> Result<(), BodyError> {
+        self.body_mut(body_id)?.add_angular_impulse(angular_impulse);
+        self.activate_if_not_zero(body_id, angular_impulse);
+        Ok(())
+    }
+
+    /// Moves the origin of the shape of the body to `position`.
+    pub fn set_position(&mut self, body_id: BodyID, position: Vec3) -> Result<(), BodyError> {
+        let rotation = self.body(body_id)?.rotation();
+        self.set_position_and_rotation(body_id, position, rotation)
+    }
+
+    pub fn set_rotation(&mut self, body_id: BodyID, rotation: Quat) -> Result<(), BodyError> {
+        let position = self.body(body_id)?.position();
+        self.set_position_and_rotation(body_id, position, rotation)
+    }
+
+    /// Moves the origin of the shape of the body to `position` and rotates it to `rotation`,
+    /// and wakes it up.
+    pub fn set_position_and_rotation(
+        &mut self,
+        body_id: BodyID,
+        position: Vec3,
+        rotation: Quat,
+    ) -> Result<(), BodyError> {
+        if !position.is_finite() {
+            return Err(BodyError::InvalidPosition);
+        }
+        if !rotation.is_normalized() {
+            return Err(BodyError::InvalidRotation);
+        }
+        self.body_mut(body_id)?
+            .set_position_and_rotation(position, rotation);
+        self.notify_bounds_changed(body_id);
+        self.body_manager.activate_bodies(&[body_id]);
+        Ok(())
+    }
+
+    /// Replaces the shape of the body and wakes it up, the origin of the shape stays in place.
+    pub fn set_shape(&mut self, body_id: BodyID, shape: Arc<dyn Shape>) -> Result<(), BodyError> {
+        let body = self.body_mut(body_id)?;
+        validate_mass(&*shape, body.motion_type())?;
+        body.set_shape(shape);
+        self.notify_bounds_changed(body_id);
+        self.body_manager.activate_bodies(&[body_id]);
+        Ok(())
+    }
+
+    /// Changes the motion type, a body that becomes static is deactivated and other bodies
+    /// are woken up.
+    pub fn set_motion_type(
+        &mut self,
+        body_id: BodyID,
+        motion_type: MotionType,
+    ) -> Result<(), BodyError> {
+        validate_mass(&**self.body(body_id)?.shape(), motion_type)?;
+        if motion_type == MotionType::Static {
+            self.body_manager.deactivate_bodies(&[body_id]);
+        }
+        self.body_mut(body_id)?.set_motion_type(motion_type);
+        self.body_manager.activate_bodies(&[body_id]);
+        Ok(())
+    }
+
+    /// Changes how a body that is not static is moved and collided.
+    pub fn set_motion_quality(
+        &mut self,
+        body_id: BodyID,
+        motion_quality: MotionQuality,
+    ) -> Result<(), BodyError> {
+        if let Some(motion) = self.body_mut(body_id)?.motion_properties_mut() {
+            motion.motion_quality = motion_quality;
+        }
+        Ok(())
+    }
+
+    /// Moves the body to another object layer, which can also move it to another broadphase
+    /// layer.
+    pub fn set_object_layer(
+        &mut self,
+        body_id: BodyID,
+        object_layer: ObjectLayer,
+    ) -> Result<(), BodyError> {
+        let body = self.body_mut(body_id)?;
+        body.set_object_layer(object_layer);
+        if body.is_added() {
+            let entry = (body_id, body.world_bounds(), object_layer);
+            self.broad_phase.remove_bodies(&[body_id]);
+            self.broad_phase.add_bodies(&[entry]);
+        }
+        Ok(())
+    }
+
+    pub fn set_friction(&mut self, body_id: BodyID, friction: f32) -> Result<(), BodyError> {
+        if !(friction >= 0.0 && friction.is_finite()) {
+            return Err(BodyError::InvalidFriction);
+        }
+        self.body_mut(body_id)?.friction = friction;
+        Ok(())
+    }
+
+    pub fn set_restitution(&mut self, body_id: BodyID, restitution: f32) -> Result<(), BodyError> {
+        if !(0.0..=1.0).contains(&restitution) {
+            return Err(BodyError::InvalidRestitution);
+        }
+        self.body_mut(body_id)?.restitution = restitution;
+        Ok(())
+    }
+
+    pub fn set_user_data(&mut self, body_id: BodyID, user_data: u64) -> Result<(), BodyError> {
+        self.body_mut(body_id)?.user_data = user_data;
+        Ok(())
+    }
+
+    #[inline]
+    fn body(&self, body_id: BodyID) -> Result<&Body, BodyError> {
+        self.body_manager
+            .body(body_id)
+            .ok_or(BodyError::InvalidBodyID)
+    }
+
+    #[inline]
+    fn body_mut(&mut self, body_id: BodyID) -> Result<&mut Body, BodyError> {
+        self.body_manager
+            .body_mut(body_id)
+            .ok_or(BodyError::InvalidBodyID)
+    }
+
+    /// Wakes up the body when `value` is not zero.
+    #[inline]
+    fn activate_if_not_zero(&mut self, body_id: BodyID, value: Vec3) {
+        if value != Vec3::ZERO {
+            self.body_manager.activate_bodies(&[body_id]);
+        }
+    }
+
+    /// Passes the new bounds of the body to the broadphase when it has been added.
+    fn notify_bounds_changed(&self, body_id: BodyID) {
+        let body = self.body_manager.body(body_id).unwrap();
+        if body.is_added() {
+            self.broad_phase
+                .notify_bodies_aabb_changed(&[(body_id, body.world_bounds())]);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+
+use crate::body::{Body, BodyActivationListener, BodyCreationSettings, BodyError, BodyID};
+
+/// Owns all bodies and hands out their identifiers.
+///
+/// The indices of destroyed bodies are kept in a free list and reused by new bodies, with a
+/// higher sequence number so that identifiers of the destroyed bodies no longer resolve.
+pub struct BodyManager {
+    /// Indexed by the index of the body.
+    bodies: Vec<Option<Body>>,
+    /// The sequence number of the next body at every index.
+    sequences: Vec<u8>,
+    free_indices: Vec<u32>,
+    max_bodies: usize,
+    body_count: usize,
+    activation_listener: Option<Box<dyn BodyActivationListener>>,
+}
+
+impl BodyManager {
+    /// Creates a manager that holds at most `max_bodies` bodies.
+    pub fn new(max_bodies: usize) -> Self {
+        assert!(max_bodies <= BodyID::MAX_INDEX as usize + 1);
+        Self {
+            bodies: Vec::new(),
+            sequences: Vec::new(),
+            free_indices: Vec::new(),
+            max_bodies,
+            body_count: 0,
+            activation_listener: None,
+        }
+    }
+
+    #[inline]
+    pub fn max_bodies(&self) -> usize {
+        self.max_bodies
+    }
+
+    #[inline]
+    pub fn body_count(&self) -> usize {
+        self.body_count
+    }
+
+    #[inline]
+    pub fn body_activation_listener(&self) -> Option<&dyn BodyActivationListener> {
+        self.activation_listener.as_deref()
+    }
+
+    /// Sets the listener that is told about bodies that wake up or go to sleep.
+    #[inline]
+    pub fn set_body_activation_listener(
+        &mut self,
+        activation_listener: Option<Box<dyn BodyActivationListener>>,
+    ) {
+        self.activation_listener = activation_listener;
+    }
+
+    /// Creates a body that has not been added to the broadphase yet.
+    pub fn create_body(&mut self, settings: &BodyCreationSettings) -> Result<BodyID, BodyError> {
+        settings.validate()?;
+        let index = match self.free_indices.pop() {
+            Some(index) => index,
+            None if self.bodies.len() < self.max_bodies => {
+                self.bodies.push(None);
+                self.sequences.push(0);
+                self.bodies.len() as u32 - 1
+            }
+            None => return Err(BodyError::TooManyBodies),
+        };
+        let body_id = BodyID::new(index, self.sequences[index as usize]);
+        self.bodies[index as usize] = Some(Body::new(body_id, settings));
+        self.body_count += 1;
+        Ok(body_id)
+    }
+
+    /// Destroys a body that is not in the broadphase and returns it.
+    pub fn destroy_body(&mut self, body_id: BodyID) -> Result<Body, BodyError> {
+        match self.body(body_id) {
+            None => return Err(BodyError::InvalidBodyID),
+            Some(body) if body.is_added() => return Err(BodyError::AlreadyAdded),
+            Some(_) => {}
+        }
+        let index = body_id.index() as usize;
+        self.sequences[index] = self.sequences[index].wrapping_add(1);
+        self.free_indices.push(index as u32);
+        self.body_count -= 1;
+        Ok(self.bodies[index].take().unwrap())
+    }
+
+    /// Returns the body with `body_id`, or `None` when it has been destroyed.
+    #[inline]
+    pub fn body(&self, body_id: BodyID) -> Option<&Body> {
+        self.bodies
+            .get(body_id.index() as usize)?
+            .as_ref()
+            .filter(|body| body.id() == body_id)
+    }
+
+    /// Returns the body with `body_id`, or `None` when it has been destroyed.
+    #[inline]
+    pub fn body_mut(&mut self, body_id: BodyID) -> Option<&mut Body> {
+        self.bodies
+            .get_mut(body_id.index() as usize)?
+            .as_mut()
+            .filter(|body| body.id() == body_id)
+    }
+
+    /// Returns two different bodies at once, or `None` when either has been destroyed.
+    pub(crate) fn body_pair_mut(
+        &mut self,
+        body_id1: BodyID,
+        body_id2: BodyID,
+    ) -> Option<(&mut Body, &mut Body)> {
+        let (index1, index2) = (body_id1.index() as usize, body_id2.index() as usize);
+        if index1 == index2 || index1.max(index2) >= self.bodies.len() {
+            return None;
+        }
+        let (low, high) = self.bodies.split_at_mut(index1.max(index2));
+        let (body1, body2) = if index1 < index2 {
+            (&mut low[index1], &mut high[0])
+        } else {
+            (&mut high[0], &mut low[index2])
+        };
+        let body1 = body1.as_mut().filter(|body| body.id() == body_id1)?;
+        let body2 = body2.as_mut().filter(|body| body.id() == body_id2)?;
+        Some((body1, body2))
+    }
+
+    /// Returns all bodies in the order of their index.
+    #[inline]
+    pub fn bodies(&self) -> impl Iterator<Item = &Body> {
+        self.bodies.iter().flatten()
+    }
+
+    /// Wakes up the bodies that are added, not static and sleeping.
+    pub fn activate_bodies(&mut self, body_ids: &[BodyID]) {
+        for &body_id in body_ids {
+            let Some(body) = self.body_mut(body_id) else {
+                continue;
+            };
+            if body.is_active() || !body.is_added() || body.is_static() {
+                continue;
+            }
+            body.set_is_active(true);
+            let user_data = body.user_data;
+            if let Some(listener) = &mut self.activation_listener {
+                listener.on_body_activated(body_id, user_data);
+            }
+        }
+    }
+
+    /// Puts the bodies that are active to sleep, which stops them.
+    pub fn deactivate_bodies(&mut self, body_ids: &[BodyID]) {
+        for &body_id in body_ids {
+            let Some(body) = self.body_mut(body_id) else {
+                continue;
+            };
+            if !body.is_active() {
+                continue;
+            }
+            body.set_is_active(false);
+            let user_data = body.user_data;
+            if let Some(listener) = &mut self.activation_listener {
+                listener.on_body_deactivated(body_id, user_data);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for BodyManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BodyManager")
+            .field("bodies", &self.bodies)
+            .field("sequences", &self.sequences)
+            .field("free_indices", &self.free_indices)
+            .field("max_bodies", &self.max_bodies)
+            .field("body_count", &self.body_count)
+            .finish_non_exhaustive()
+    }
+}
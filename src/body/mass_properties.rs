@@ -0,0 +1,146 @@
+#[cfg(all(no_std, libm, not(test)))]
+use crate::math::libm::F32Ext;
+use crate::math::{Mat33, Quat, Vec3};
+
+/// The maximum number of sweeps of the Jacobi eigenvalue algorithm.
+const MAX_JACOBI_SWEEPS: u32 = 50;
+
+/// The mass and inertia tensor of a body, the inertia is relative to the center of mass.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MassProperties {
+    pub mass: f32,
+    pub inertia: Mat33,
+}
+
+impl MassProperties {
+    #[inline]
+    pub const fn new(mass: f32, inertia: Mat33) -> Self {
+        Self { mass, inertia }
+    }
+
+    /// Sets the mass and inertia of a solid box of `box_size` with `density`.
+    pub fn set_mass_and_inertia_of_solid_box(&mut self, box_size: Vec3, density: f32) {
+        self.mass = box_size.x * box_size.y * box_size.z * density;
+        let sq = box_size * box_size;
+        let inertia = Vec3::new(sq.y + sq.z, sq.x + sq.z, sq.x + sq.y) * (self.mass / 12.0);
+        self.inertia = Mat33::from_diagonal(inertia);
+    }
+
+    /// Changes the mass to `mass` and scales the inertia along with it, which is the same as
+    /// changing the density of the shape.
+    pub fn scale_to_mass(&mut self, mass: f32) {
+        if self.mass > 0.0 {
+            self.inertia *= mass / self.mass;
+        } else {
+            // Without a mass there is no distribution to scale, keep the inertia at zero.
+            self.inertia = Mat33::ZERO;
+        }
+        self.mass = mass;
+    }
+
+    /// Moves the inertia from the center of mass to a point at `-translation` from it, using
+    /// the parallel axis theorem.
+    pub fn translate(&mut self, translation: Vec3) {
+        self.inertia += (Mat33::from_diagonal(Vec3::splat(translation.length_squared()))
+            - Mat33::from_outer_product(translation, translation))
+            * self.mass;
+    }
+
+    /// Rotates the inertia by `rotation`.
+    pub fn rotate(&mut self, rotation: Quat) {
+        let rotation = Mat33::from_quat(rotation);
+        self.inertia = rotation * self.inertia * rotation.transpose();
+    }
+
+    /// Decomposes the inertia into a rotation and the principal moments of inertia, such that
+    /// `inertia = rotation * diagonal * rotationᵀ`. The moments are sorted from largest to
+    /// smallest and the rotation has a determinant of one. Returns `None` when the inertia is
+    /// not finite or the decomposition does not converge.
+    pub fn decompose_principal_moments_of_inertia(&self) -> Option<(Mat33, Vec3)> {
+        if !self.inertia.is_finite() {
+            return None;
+        }
+        let (eigenvectors, eigenvalues) = jacobi_eigen_symmetric(&self.inertia)?;
+
+        let mut order = [0, 1, 2];
+        order.sort_unstable_by(|&a, &b| eigenvalues[b].total_cmp(&eigenvalues[a]));
+        let mut rotation = Mat33::from_cols(
+            eigenvectors.col(order[0]),
+            eigenvectors.col(order[1]),
+            eigenvectors.col(order[2]),
+        );
+        if rotation.determinant() < 0.0 {
+            *rotation.col_mut(2) = -rotation.col(2);
+        }
+        let diagonal = Vec3::new(
+            eigenvalues[order[0]],
+            eigenvalues[order[1]],
+            eigenvalues[order[2]],
+        );
+        Some((rotation, diagonal))
+    }
+
+    /// Scales the mass properties of a shape by `scale`, the density of the shape stays the
+    /// same so the mass changes with the volume.
+    pub fn scale(&mut self, scale: Vec3) {
+        // The covariance of the mass distribution scales along with the axes, convert the
+        // inertia to the covariance and back.
+        let trace = self.inertia.diagonal().dot(Vec3::ONE);
+        let covariance = Mat33::from_diagonal(Vec3::splat(0.5 * trace)) - self.inertia;
+        let volume_scale = (scale.x * scale.y * scale.z).abs();
+        let scale = Mat33::from_diagonal(scale);
+        let covariance = scale * covariance * scale * volume_scale;
+        let trace = covariance.diagonal().dot(Vec3::ONE);
+        self.inertia = Mat33::from_diagonal(Vec3::splat(trace)) - covariance;
+        self.mass *= volume_scale;
+    }
+}
+
+impl Default for MassProperties {
+    #[inline]
+    fn default() -> Self {
+        Self::new(0.0, Mat33::ZERO)
+    }
+}
+
+/// Returns the eigenvectors, as the columns of a matrix, and eigenvalues of the symmetric
+/// matrix `matrix` using the cyclic Jacobi eigenvalue algorithm, or `None` when it does not
+/// converge.
+fn jacobi_eigen_symmetric(matrix: &Mat33) -> Option<(Mat33, Vec3)> {
+    // a[row][column]
+    let mut a: [[f32; 3]; 3] = core::array::from_fn(|row| matrix.row(row).to_array());
+    let mut v = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    let scale = a[0][0].abs() + a[1][1].abs() + a[2][2].abs();
+
+    for _ in 0..MAX_JACOBI_SWEEPS {
+        let off_diagonal = a[0][1] * a[0][1] + a[0][2] * a[0][2] + a[1][2] * a[1][2];
+        if off_diagonal <= (f32::EPSILON * scale) * (f32::EPSILON * scale) {
+            let eigenvectors = Mat33::from_cols(
+                Vec3::new(v[0][0], v[1][0], v[2][0]),
+                Vec3::new(v[0][1], v[1][1], v[2][1]),
+                Vec3::new(v[0][2], v[1][2], v[2][2]),
+            );
+            return Some((eigenvectors, Vec3::new(a[0][0], a[1][1], a[2][2])));
+        }
+
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if a[p][q] == 0.0 {
+                continue;
+            }
+            // Find the rotation that zeroes a[p][q].
+            let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+            let t = 1.0f32.copysign(theta) / (theta.abs() + (theta * theta + 1.0).sqrt());
+            let c = 1.0 / (t * t + 1.0).sqrt();
+            let s = t * c;
+            for row in a.iter_mut().chain(v.iter_mut()) {
+                let (kp, kq) = (row[p], row[q]);
+                row[p] = c * kp - s * kq;
+                row[q] = s * kp + c * kq;
+            }
+            let (row_p, row_q) = (a[p], a[q]);
+            a[p] = core::array::from_fn(|k| c * row_p[k] - s * row_q[k]);
+            a[q] = core::array::from_fn(|k| s * row_p[k] + c * row_q[k]);
+        }
+    }
+    None
+}
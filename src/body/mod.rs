@@ -0,0 +1,39 @@
+pub(crate) mod allowed_dofs;
+pub use allowed_dofs::AllowedDOFs;
+
+pub(crate) mod body_activation_listener;
+pub use body_activation_listener::BodyActivationListener;
+
+#[allow(clippy::module_inception)]
+pub(crate) mod body;
+pub use body::{Body, BodyError};
+
+pub(crate) mod body_creation_settings;
+pub use body_creation_settings::BodyCreationSettings;
+
+pub(crate) mod body_filter;
+pub use body_filter::{BodyFilter, IgnoreSingleBodyFilter};
+
+pub(crate) mod body_id;
+pub use body_id::BodyID;
+
+pub(crate) mod body_interface;
+pub use body_interface::BodyInterface;
+
+pub(crate) mod body_manager;
+pub use body_manager::BodyManager;
+
+pub(crate) mod mass_properties;
+pub use mass_properties::MassProperties;
+
+pub(crate) mod motion_properties;
+pub use motion_properties::{
+    MotionProperties, DEFAULT_ANGULAR_DAMPING, DEFAULT_LINEAR_DAMPING,
+    DEFAULT_MAX_ANGULAR_VELOCITY, DEFAULT_MAX_LINEAR_VELOCITY,
+};
+
+pub(crate) mod motion_quality;
+pub use motion_quality::MotionQuality;
+
+pub(crate) mod motion_type;
+pub use motion_type::MotionType;
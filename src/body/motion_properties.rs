@@ -0,0 +1,321 @@
+use core::f32::consts::PI;
+
+#[cfg(all(no_std, libm, not(test)))]
+use crate::math::libm::F32Ext;
+use crate::{
+    body::{AllowedDOFs, MassProperties, MotionQuality},
+    math::{Mat33, Quat, Vec3},
+};
+
+/// The default linear damping of a body.
+pub const DEFAULT_LINEAR_DAMPING: f32 = 0.05;
+
+/// The default angular damping of a body.
+pub const DEFAULT_ANGULAR_DAMPING: f32 = 0.05;
+
+/// The default maximum linear velocity of a body, in m/s.
+pub const DEFAULT_MAX_LINEAR_VELOCITY: f32 = 500.0;
+
+/// The default maximum angular velocity of a body, a quarter turn per step at 60 Hz in rad/s.
+pub const DEFAULT_MAX_ANGULAR_VELOCITY: f32 = 0.25 * PI * 60.0;
+
+/// The state of a body that can move, its velocities, mass and the forces acting on it.
+///
+/// The inverse inertia is stored as its principal moments together with the rotation from
+/// the principal axes to the space of the body, which is cheaper to transform than a full
+/// matrix.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MotionProperties {
+    linear_velocity: Vec3,
+    angular_velocity: Vec3,
+    inverse_mass: f32,
+    inverse_inertia_diagonal: Vec3,
+    inertia_rotation: Quat,
+    allowed_dofs: AllowedDOFs,
+    /// The force accumulated since the last step.
+    force: Vec3,
+    /// The torque accumulated since the last step.
+    torque: Vec3,
+    /// How long the body has moved slower than the sleep threshold, in seconds.
+    sleep_timer: f32,
+    /// The fraction of the linear velocity that is lost every second.
+    pub linear_damping: f32,
+    /// The fraction of the angular velocity that is lost every second.
+    pub angular_damping: f32,
+    /// Scales the gravity that is applied to the body.
+    pub gravity_factor: f32,
+    pub max_linear_velocity: f32,
+    pub max_angular_velocity: f32,
+    /// Whether the body can go to sleep when it stops moving.
+    pub allow_sleeping: bool,
+    pub motion_quality: MotionQuality,
+}
+
+impl MotionProperties {
+    /// Creates motion properties at rest with the default damping and velocity limits.
+    pub fn new(mass_properties: &MassProperties, allowed_dofs: AllowedDOFs) -> Self {
+        let mut motion_properties = Self {
+            linear_velocity: Vec3::ZERO,
+            angular_velocity: Vec3::ZERO,
+            inverse_mass: 0.0,
+            inverse_inertia_diagonal: Vec3::ZERO,
+            inertia_rotation: Quat::IDENTITY,
+            allowed_dofs,
+            force: Vec3::ZERO,
+            torque: Vec3::ZERO,
+            sleep_timer: 0.0,
+            linear_damping: DEFAULT_LINEAR_DAMPING,
+            angular_damping: DEFAULT_ANGULAR_DAMPING,
+            gravity_factor: 1.0,
+            max_linear_velocity: DEFAULT_MAX_LINEAR_VELOCITY,
+            max_angular_velocity: DEFAULT_MAX_ANGULAR_VELOCITY,
+            allow_sleeping: true,
+            motion_quality: MotionQuality::Discrete,
+        };
+        motion_properties.set_mass_properties(mass_properties, allowed_dofs);
+        motion_properties
+    }
+
+    /// Sets the inverse mass and inertia from `mass_properties`. A zero mass or inertia, or
+    /// one that cannot be decomposed, results in a body that cannot be moved by forces.
+    pub fn set_mass_properties(
+        &mut self,
+        mass_properties: &MassProperties,
+        allowed_dofs: AllowedDOFs,
+    ) {
+        self.allowed_dofs = allowed_dofs;
+        let mass = mass_properties.mass;
+        self.inverse_mass = if mass > 0.0 && mass.is_finite() {
+            1.0 / mass
+        } else {
+            0.0
+        };
+        match mass_properties.decompose_principal_moments_of_inertia() {
+            Some((rotation, diagonal)) => {
+                let inverse = |moment: f32| if moment > 0.0 { 1.0 / moment } else { 0.0 };
+                self.inertia_rotation = Quat::from_mat33(&rotation).normalize();
+                self.inverse_inertia_diagonal = Vec3::new(
+                    inverse(diagonal.x),
+                    inverse(diagonal.y),
+                    inverse(diagonal.z),
+                );
+            }
+            None => {
+                self.inertia_rotation = Quat::IDENTITY;
+                self.inverse_inertia_diagonal = Vec3::ZERO;
+            }
+        }
+        self.linear_velocity = self.lock_translation(self.linear_velocity);
+        self.angular_velocity = self.lock_rotation(self.angular_velocity);
+    }
+
+    #[inline]
+    pub fn allowed_dofs(&self) -> AllowedDOFs {
+        self.allowed_dofs
+    }
+
+    #[inline]
+    pub fn inverse_mass(&self) -> f32 {
+        self.inverse_mass
+    }
+
+    /// Returns the inverse of the principal moments of inertia.
+    #[inline]
+    pub fn inverse_inertia_diagonal(&self) -> Vec3 {
+        self.inverse_inertia_diagonal
+    }
+
+    /// Returns the rotation from the principal axes of inertia to the space of the body.
+    #[inline]
+    pub fn inertia_rotation(&self) -> Quat {
+        self.inertia_rotation
+    }
+
+    #[inline]
+    pub fn linear_velocity(&self) -> Vec3 {
+        self.linear_velocity
+    }
+
+    #[inline]
+    pub fn angular_velocity(&self) -> Vec3 {
+        self.angular_velocity
+    }
+
+    /// Sets the linear velocity, without the components along axes that are not allowed.
+    #[inline]
+    pub fn set_linear_velocity(&mut self, linear_velocity: Vec3) {
+        self.linear_velocity = self.lock_translation(linear_velocity);
+    }
+
+    /// Sets the angular velocity, without the components around axes that are not allowed.
+    #[inline]
+    pub fn set_angular_velocity(&mut self, angular_velocity: Vec3) {
+        self.angular_velocity = self.lock_rotation(angular_velocity);
+    }
+
+    /// Sets the linear velocity and clamps it to the maximum linear velocity.
+    #[inline]
+    pub fn set_linear_velocity_clamped(&mut self, linear_velocity: Vec3) {
+        self.set_linear_velocity(linear_velocity);
+        self.clamp_linear_velocity();
+    }
+
+    /// Sets the angular velocity and clamps it to the maximum angular velocity.
+    #[inline]
+    pub fn set_angular_velocity_clamped(&mut self, angular_velocity: Vec3) {
+        self.set_angular_velocity(angular_velocity);
+        self.clamp_angular_velocity();
+    }
+
+    #[inline]
+    pub fn clamp_linear_velocity(&mut self) {
+        self.linear_velocity = clamp_length(self.linear_velocity, self.max_linear_velocity);
+    }
+
+    #[inline]
+    pub fn clamp_angular_velocity(&mut self) {
+        self.angular_velocity = clamp_length(self.angular_velocity, self.max_angular_velocity);
+    }
+
+    /// Returns the inverse inertia in world space for a body rotated by `body_rotation`.
+    pub fn inverse_inertia_for_rotation(&self, body_rotation: Quat) -> Mat33 {
+        let rotation = Mat33::from_quat(body_rotation * self.inertia_rotation);
+        let mask = Mat33::from_diagonal(self.allowed_dofs.rotation_mask());
+        mask * rotation
+            * Mat33::from_diagonal(self.inverse_inertia_diagonal)
+            * rotation.transpose()
+            * mask
+    }
+
+    /// Multiplies `vector` by the world space inverse inertia of a body rotated by
+    /// `body_rotation`, without building the matrix.
+    #[inline]
+    pub fn multiply_world_space_inverse_inertia_by_vector(
+        &self,
+        body_rotation: Quat,
+        vector: Vec3,
+    ) -> Vec3 {
+        let rotation = body_rotation * self.inertia_rotation;
+        let local = rotation.inverse() * self.lock_rotation(vector);
+        self.lock_rotation(rotation * (self.inverse_inertia_diagonal * local))
+    }
+
+    /// Adds a force through the center of mass, it is applied during the next step.
+    #[inline]
+    pub fn add_force(&mut self, force: Vec3) {
+        self.force += force;
+    }
+
+    /// Adds a torque, it is applied during the next step.
+    #[inline]
+    pub fn add_torque(&mut self, torque: Vec3) {
+        self.torque += torque;
+    }
+
+    #[inline]
+    pub fn accumulated_force(&self) -> Vec3 {
+        self.force
+    }
+
+    #[inline]
+    pub fn accumulated_torque(&self) -> Vec3 {
+        self.torque
+    }
+
+    #[inline]
+    pub fn reset_force_and_torque(&mut self) {
+        self.force = Vec3::ZERO;
+        self.torque = Vec3::ZERO;
+    }
+
+    #[inline]
+    pub fn sleep_timer(&self) -> f32 {
+        self.sleep_timer
+    }
+
+    #[inline]
+    pub fn reset_sleep_timer(&mut self) {
+        self.sleep_timer = 0.0;
+    }
+
+    /// Adds `delta_time` to the sleep timer when all points within `radius` of the center of
+    /// mass move slower than `threshold`, and resets it otherwise or when the body is not
+    /// allowed to sleep. Returns the new sleep timer.
+    pub fn update_sleep_timer(&mut self, radius: f32, threshold: f32, delta_time: f32) -> f32 {
+        let max_point_velocity =
+            self.linear_velocity.length() + self.angular_velocity.length() * radius;
+        if self.allow_sleeping && max_point_velocity < threshold {
+            self.sleep_timer += delta_time;
+        } else {
+            self.sleep_timer = 0.0;
+        }
+        self.sleep_timer
+    }
+
+    /// Changes the linear velocity by an impulse through the center of mass.
+    #[inline]
+    pub fn add_impulse(&mut self, impulse: Vec3) {
+        self.set_linear_velocity_clamped(self.linear_velocity + impulse * self.inverse_mass);
+    }
+
+    /// Changes the angular velocity by an angular impulse for a body rotated by
+    /// `body_rotation`.
+    #[inline]
+    pub fn add_angular_impulse(&mut self, body_rotation: Quat, angular_impulse: Vec3) {
+        let delta =
+            self.multiply_world_space_inverse_inertia_by_vector(body_rotation, angular_impulse);
+        self.set_angular_velocity_clamped(self.angular_velocity + delta);
+    }
+
+    /// Applies gravity and the accumulated force and torque to the velocities, then damps and
+    /// clamps them. The accumulated force and torque are reset afterwards.
+    pub fn apply_forces_gravity_and_damping(
+        &mut self,
+        body_rotation: Quat,
+        gravity: Vec3,
+        delta_time: f32,
+    ) {
+        let acceleration = gravity * self.gravity_factor + self.force * self.inverse_mass;
+        self.set_linear_velocity(self.linear_velocity + acceleration * delta_time);
+        let angular_acceleration =
+            self.multiply_world_space_inverse_inertia_by_vector(body_rotation, self.torque);
+        self.set_angular_velocity(self.angular_velocity + angular_acceleration * delta_time);
+
+        self.linear_velocity *= (1.0 - self.linear_damping * delta_time).max(0.0);
+        self.angular_velocity *= (1.0 - self.angular_damping * delta_time).max(0.0);
+        self.clamp_linear_velocity();
+        self.clamp_angular_velocity();
+        self.reset_force_and_torque();
+    }
+
+    /// Moves `position` and `rotation` by the velocities over `delta_time`, the velocities
+    /// should already have been updated for this step.
+    pub fn integrate(&self, position: &mut Vec3, rotation: &mut Quat, delta_time: f32) {
+        *position += self.linear_velocity * delta_time;
+        // dq/dt = 1/2 ω q, the result is renormalized to prevent drift.
+        let w = self.angular_velocity;
+        let spin = Quat::from_xyzw(w.x, w.y, w.z, 0.0) * *rotation;
+        *rotation = Quat::from_vec4(rotation.0 + spin.0 * (0.5 * delta_time)).normalize();
+    }
+
+    #[inline]
+    fn lock_translation(&self, vector: Vec3) -> Vec3 {
+        vector * self.allowed_dofs.translation_mask()
+    }
+
+    #[inline]
+    fn lock_rotation(&self, vector: Vec3) -> Vec3 {
+        vector * self.allowed_dofs.rotation_mask()
+    }
+}
+
+/// Returns `vector` scaled down to `max_length` when it is longer.
+#[inline]
+fn clamp_length(vector: Vec3, max_length: f32) -> Vec3 {
+    let length_squared = vector.length_squared();
+    if length_squared > max_length * max_length {
+        vector * (max_length / length_squared.sqrt())
+    } else {
+        vector
+    }
+}
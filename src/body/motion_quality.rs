@@ -0,0 +1,12 @@
+/// How a dynamic body is moved and collided every step.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MotionQuality {
+    /// The body is moved by its velocity and collided at its new position, which lets it pass
+    /// through thin bodies when it moves fast enough.
+    #[default]
+    Discrete,
+    /// A body that moves far enough during a step is swept from its old to its new position
+    /// and stops at the first body it hits, which is more expensive. Rotation is not taken
+    /// into account.
+    LinearCast,
+}
@@ -0,0 +1,12 @@
+/// How a body moves.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MotionType {
+    /// The body never moves and has an infinite mass.
+    #[default]
+    Static,
+    /// The body is moved by setting its velocity, it is not affected by forces or collisions
+    /// and has an infinite mass.
+    Kinematic,
+    /// The body is moved by forces and collisions.
+    Dynamic,
+}
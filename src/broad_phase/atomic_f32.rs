@@ -0,0 +1,42 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// A float that can be shared between threads.
+#[derive(Debug)]
+pub(crate) struct AtomicF32(AtomicU32);
+
+impl AtomicF32 {
+    #[inline]
+    pub fn new(value: f32) -> Self {
+        Self(AtomicU32::new(value.to_bits()))
+    }
+
+    #[inline]
+    pub fn load(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    #[inline]
+    pub fn store(&self, value: f32) {
+        self.0.store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Stores `value` if it is smaller than the current value and returns whether it did.
+    #[inline]
+    pub fn fetch_min(&self, value: f32) -> bool {
+        self.0
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                (value < f32::from_bits(bits)).then_some(value.to_bits())
+            })
+            .is_ok()
+    }
+
+    /// Stores `value` if it is larger than the current value and returns whether it did.
+    #[inline]
+    pub fn fetch_max(&self, value: f32) -> bool {
+        self.0
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                (value > f32::from_bits(bits)).then_some(value.to_bits())
+            })
+            .is_ok()
+    }
+}
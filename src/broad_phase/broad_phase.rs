@@ -0,0 +1,129 @@
+use crate::{
+    body::{BodyFilter, BodyID},
+    broad_phase::{
+        BroadPhaseCastResult, BroadPhaseLayer, BroadPhaseLayerFilter, ObjectVsBroadPhaseLayerFilter,
+    },
+    collision::{
+        CollisionCollector, ObjectLayer, ObjectLayerFilter, ObjectLayerPairFilter, RayCast,
+    },
+    geometry::AABox,
+    math::Vec3,
+};
+
+/// Finds the bodies whose bounds overlap, before their shapes are tested against each other.
+///
+/// Every body is stored with its bounds and [`ObjectLayer`], which determines its
+/// [`BroadPhaseLayer`]. Moving bodies only needs a shared reference so that it can be done
+/// from multiple threads, [`BroadPhase::update`] is called once all bodies have moved to
+/// restore the efficiency of the broadphase.
+pub trait BroadPhase: Send + Sync {
+    /// Returns the number of bodies in the broadphase.
+    fn body_count(&self) -> usize;
+
+    fn contains(&self, body_id: BodyID) -> bool;
+
+    /// Returns the bounds that are stored for a body.
+    fn body_bounds(&self, body_id: BodyID) -> Option<AABox>;
+
+    fn object_layer(&self, body_id: BodyID) -> Option<ObjectLayer>;
+
+    fn broad_phase_layer(&self, body_id: BodyID) -> Option<BroadPhaseLayer>;
+
+    /// Adds bodies with their bounds and object layers. Adding many bodies at once is faster
+    /// than adding them one by one.
+    fn add_bodies(&mut self, bodies: &[(BodyID, AABox, ObjectLayer)]);
+
+    /// Removes bodies, bodies that are not in the broadphase are ignored.
+    fn remove_bodies(&mut self, body_ids: &[BodyID]);
+
+    /// Moves bodies to their new bounds. This only needs a shared reference, so that bodies
+    /// can be moved from multiple threads at once.
+    fn notify_bodies_aabb_changed(&self, bodies: &[(BodyID, AABox)]);
+
+    /// Restores the efficiency of the layers that have changed since the last update.
+    fn update(&mut self);
+
+    /// Restores the efficiency of all layers, which should be done after adding many bodies at
+    /// the start.
+    fn optimize(&mut self);
+
+    /// Adds the bodies whose bounds are hit by the ray to the collector.
+    fn cast_ray(
+        &self,
+        ray: &RayCast,
+        collector: &mut dyn CollisionCollector<BroadPhaseCastResult>,
+        broad_phase_layer_filter: &dyn BroadPhaseLayerFilter,
+        object_layer_filter: &dyn ObjectLayerFilter,
+        body_filter: &dyn BodyFilter,
+    );
+
+    /// Adds the bodies whose bounds overlap `bounds` to the collector.
+    fn collide_aabox(
+        &self,
+        bounds: &AABox,
+        collector: &mut dyn CollisionCollector<BodyID>,
+        broad_phase_layer_filter: &dyn BroadPhaseLayerFilter,
+        object_layer_filter: &dyn ObjectLayerFilter,
+        body_filter: &dyn BodyFilter,
+    );
+
+    /// Adds the bodies whose bounds overlap the sphere to the collector.
+    fn collide_sphere(
+        &self,
+        center: Vec3,
+        radius: f32,
+        collector: &mut dyn CollisionCollector<BodyID>,
+        broad_phase_layer_filter: &dyn BroadPhaseLayerFilter,
+        object_layer_filter: &dyn ObjectLayerFilter,
+        body_filter: &dyn BodyFilter,
+    );
+
+    /// Adds the bodies whose bounds contain `point` to the collector.
+    fn collide_point(
+        &self,
+        point: Vec3,
+        collector: &mut dyn CollisionCollector<BodyID>,
+        broad_phase_layer_filter: &dyn BroadPhaseLayerFilter,
+        object_layer_filter: &dyn ObjectLayerFilter,
+        body_filter: &dyn BodyFilter,
+    );
+
+    /// Calls `callback` for every pair of an active body and a body whose bounds overlap and
+    /// whose layers can collide, bodies that `body_filter` rejects are left out. The bounds of
+    /// the active bodies are expanded by `speculative_contact_distance`. Pairs of two active
+    /// bodies are only reported once, by the body with the lowest ID.
+    fn find_colliding_pairs(
+        &self,
+        active_bodies: &[BodyID],
+        speculative_contact_distance: f32,
+        object_vs_broad_phase_layer_filter: &dyn ObjectVsBroadPhaseLayerFilter,
+        object_layer_pair_filter: &dyn ObjectLayerPairFilter,
+        body_filter: &dyn BodyFilter,
+        callback: &mut dyn FnMut(BodyID, BodyID),
+    );
+}
+
+/// Reports the bodies that overlap an active body as pairs.
+pub(crate) struct PairCollector<'a> {
+    pub body_id: BodyID,
+    /// Whether each body is active, indexed by the index of the body.
+    pub active: &'a [bool],
+    pub callback: &'a mut dyn FnMut(BodyID, BodyID),
+}
+
+impl CollisionCollector<BodyID> for PairCollector<'_> {
+    fn add_hit(&mut self, hit: &BodyID) {
+        let other = *hit;
+        if other == self.body_id
+            || (self.active.get(other.index() as usize) == Some(&true) && other < self.body_id)
+        {
+            return;
+        }
+        (self.callback)(self.body_id, other);
+    }
+
+    #[inline]
+    fn early_out_fraction(&self) -> f32 {
+        f32::MAX
+    }
+}
@@ -0,0 +1,53 @@
+use crate::collision::ObjectLayer;
+
+/// A layer of the broadphase, every layer has its own tree.
+///
+/// Bodies that move rarely, such as static bodies, should be in a different layer from bodies
+/// that move every step. This keeps the tree of the static bodies optimal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BroadPhaseLayer(pub u8);
+
+/// Maps every [`ObjectLayer`] onto a [`BroadPhaseLayer`].
+pub trait BroadPhaseLayerInterface: Send + Sync {
+    /// Returns the number of broadphase layers, the layers are `0..broad_phase_layer_count`.
+    fn broad_phase_layer_count(&self) -> u8;
+
+    fn broad_phase_layer(&self, layer: ObjectLayer) -> BroadPhaseLayer;
+}
+
+/// Decides whether bodies in an object layer can collide with a broadphase layer.
+pub trait ObjectVsBroadPhaseLayerFilter: Send + Sync {
+    fn should_collide(&self, layer1: ObjectLayer, layer2: BroadPhaseLayer) -> bool;
+}
+
+/// Decides which broadphase layers a query visits, the unit type accepts all layers.
+pub trait BroadPhaseLayerFilter {
+    #[inline]
+    fn should_collide(&self, _layer: BroadPhaseLayer) -> bool {
+        true
+    }
+}
+
+impl BroadPhaseLayerFilter for () {}
+
+/// Accepts the broadphase layers that collide with `layer` according to an
+/// [`ObjectVsBroadPhaseLayerFilter`].
+#[derive(Clone, Copy)]
+pub struct DefaultBroadPhaseLayerFilter<'a> {
+    pub filter: &'a dyn ObjectVsBroadPhaseLayerFilter,
+    pub layer: ObjectLayer,
+}
+
+impl<'a> DefaultBroadPhaseLayerFilter<'a> {
+    #[inline]
+    pub fn new(filter: &'a dyn ObjectVsBroadPhaseLayerFilter, layer: ObjectLayer) -> Self {
+        Self { filter, layer }
+    }
+}
+
+impl BroadPhaseLayerFilter for DefaultBroadPhaseLayerFilter<'_> {
+    #[inline]
+    fn should_collide(&self, layer: BroadPhaseLayer) -> bool {
+        self.filter.should_collide(self.layer, layer)
+    }
+}
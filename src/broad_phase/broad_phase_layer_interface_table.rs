@@ -0,0 +1,52 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    broad_phase::{BroadPhaseLayer, BroadPhaseLayerInterface},
+    collision::ObjectLayer,
+};
+
+/// A [`BroadPhaseLayerInterface`] that looks up the broadphase layer of every object layer in
+/// a table. All object layers start out in the first broadphase layer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BroadPhaseLayerInterfaceTable {
+    broad_phase_layer_count: u8,
+    broad_phase_layers: Vec<BroadPhaseLayer>,
+}
+
+impl BroadPhaseLayerInterfaceTable {
+    /// Creates a table for the object layers `0..object_layer_count`.
+    pub fn new(object_layer_count: u16, broad_phase_layer_count: u8) -> Self {
+        assert!(broad_phase_layer_count > 0);
+        Self {
+            broad_phase_layer_count,
+            broad_phase_layers: vec![BroadPhaseLayer(0); object_layer_count as usize],
+        }
+    }
+
+    #[inline]
+    pub fn object_layer_count(&self) -> u16 {
+        self.broad_phase_layers.len() as u16
+    }
+
+    /// Puts all bodies in `object_layer` in `broad_phase_layer`.
+    pub fn map_object_to_broad_phase_layer(
+        &mut self,
+        object_layer: ObjectLayer,
+        broad_phase_layer: BroadPhaseLayer,
+    ) {
+        assert!(broad_phase_layer.0 < self.broad_phase_layer_count);
+        self.broad_phase_layers[object_layer.0 as usize] = broad_phase_layer;
+    }
+}
+
+impl BroadPhaseLayerInterface for BroadPhaseLayerInterfaceTable {
+    #[inline]
+    fn broad_phase_layer_count(&self) -> u8 {
+        self.broad_phase_layer_count
+    }
+
+    #[inline]
+    fn broad_phase_layer(&self, layer: ObjectLayer) -> BroadPhaseLayer {
+        self.broad_phase_layers[layer.0 as usize]
+    }
+}
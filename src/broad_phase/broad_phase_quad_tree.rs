@@ -0,0 +1,330 @@
+use alloc::{sync::Arc, vec, vec::Vec};
+use core::fmt;
+
+use crate::{
+    body::{BodyFilter, BodyID},
+    broad_phase::{
+        broad_phase::PairCollector,
+        quad_tree::{BodyLocation, QuadTree},
+        BroadPhase, BroadPhaseCastResult, BroadPhaseLayer, BroadPhaseLayerFilter,
+        BroadPhaseLayerInterface, ObjectVsBroadPhaseLayerFilter,
+    },
+    collision::{
+        CollisionCollector, DefaultObjectLayerFilter, ObjectLayer, ObjectLayerFilter,
+        ObjectLayerPairFilter, RayCast,
+    },
+    geometry::{AABox, AABox4, RayInvDirection},
+    math::Vec3,
+};
+
+/// Where a body is stored in the broadphase.
+#[derive(Clone, Copy, Debug)]
+struct Tracking {
+    body_id: BodyID,
+    object_layer: ObjectLayer,
+    layer: BroadPhaseLayer,
+    location: BodyLocation,
+}
+
+/// A broadphase that keeps the bodies of every [`BroadPhaseLayer`] in a separate quad tree,
+/// an axis aligned bounding box tree with four children per node. The layer of a body is
+/// found from its [`ObjectLayer`] through a [`BroadPhaseLayerInterface`].
+///
+/// Moving bodies through [`BroadPhase::notify_bodies_aabb_changed`] only grows the
+/// nodes of the tree, which can be done from multiple threads at once. Adding and removing
+/// bodies also keeps the tree valid without rebuilding it. This makes the tree less efficient
+/// over time, [`BroadPhase::update`] rebuilds the trees that have changed.
+pub struct BroadPhaseQuadTree {
+    layer_interface: Arc<dyn BroadPhaseLayerInterface>,
+    trees: Vec<QuadTree>,
+    /// Indexed by the index of the body.
+    tracking: Vec<Option<Tracking>>,
+}
+
+impl BroadPhaseQuadTree {
+    pub fn new(layer_interface: Arc<dyn BroadPhaseLayerInterface>) -> Self {
+        let layer_count = layer_interface.broad_phase_layer_count();
+        Self {
+            layer_interface,
+            trees: (0..layer_count).map(|_| QuadTree::new()).collect(),
+            tracking: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn layer_interface(&self) -> &Arc<dyn BroadPhaseLayerInterface> {
+        &self.layer_interface
+    }
+
+    #[inline]
+    fn tracking(&self, body_id: BodyID) -> Option<Tracking> {
+        self.tracking
+            .get(body_id.index() as usize)
+            .copied()
+            .flatten()
+            .filter(|tracking| tracking.body_id == body_id)
+    }
+
+    fn rebuild(&mut self, all: bool) {
+        let tracking = &mut self.tracking;
+        for tree in &mut self.trees {
+            if all || tree.is_dirty() {
+                tree.rebuild(&mut |body_id, location| {
+                    if let Some(tracking) = &mut tracking[body_id.index() as usize] {
+                        tracking.location = location;
+                    }
+                });
+            }
+        }
+    }
+
+    /// Returns whether the object layer of a body in the broadphase passes `filter`.
+    #[inline]
+    fn passes(
+        &self,
+        body_id: BodyID,
+        object_layer_filter: &dyn ObjectLayerFilter,
+        body_filter: &dyn BodyFilter,
+    ) -> bool {
+        let tracking = self.tracking[body_id.index() as usize].unwrap();
+        object_layer_filter.should_collide(tracking.object_layer)
+            && body_filter.should_collide(body_id)
+    }
+
+    fn collide(
+        &self,
+        overlaps: impl Fn(&AABox4) -> [bool; 4],
+        collector: &mut dyn CollisionCollector<BodyID>,
+        broad_phase_layer_filter: &dyn BroadPhaseLayerFilter,
+        object_layer_filter: &dyn ObjectLayerFilter,
+        body_filter: &dyn BodyFilter,
+    ) {
+        let filter = |body_id| self.passes(body_id, object_layer_filter, body_filter);
+        for (layer, tree) in self.trees.iter().enumerate() {
+            if collector.should_early_out() {
+                break;
+            }
+            if broad_phase_layer_filter.should_collide(BroadPhaseLayer(layer as u8)) {
+                tree.collide(&overlaps, &filter, collector);
+            }
+        }
+    }
+}
+
+impl BroadPhase for BroadPhaseQuadTree {
+    #[inline]
+    fn body_count(&self) -> usize {
+        self.trees.iter().map(QuadTree::body_count).sum()
+    }
+
+    #[inline]
+    fn contains(&self, body_id: BodyID) -> bool {
+        self.tracking(body_id).is_some()
+    }
+
+    #[inline]
+    fn body_bounds(&self, body_id: BodyID) -> Option<AABox> {
+        let tracking = self.tracking(body_id)?;
+        Some(self.trees[tracking.layer.0 as usize].body_bounds(tracking.location))
+    }
+
+    #[inline]
+    fn object_layer(&self, body_id: BodyID) -> Option<ObjectLayer> {
+        Some(self.tracking(body_id)?.object_layer)
+    }
+
+    #[inline]
+    fn broad_phase_layer(&self, body_id: BodyID) -> Option<BroadPhaseLayer> {
+        Some(self.tracking(body_id)?.layer)
+    }
+
+    fn add_bodies(&mut self, bodies: &[(BodyID, AABox, ObjectLayer)]) {
+        let Some(max_index) = bodies.iter().map(|(body_id, ..)| body_id.index()).max() else {
+            return;
+        };
+        if self.tracking.len() <= max_index as usize {
+            self.tracking.resize(max_index as usize + 1, None);
+        }
+
+        let layers: Vec<_> = bodies
+            .iter()
+            .map(|&(_, _, object_layer)| self.layer_interface.broad_phase_layer(object_layer))
+            .collect();
+        let tracking = &mut self.tracking;
+        for (layer, tree) in self.trees.iter_mut().enumerate() {
+            let layer = BroadPhaseLayer(layer as u8);
+            let mut group = Vec::new();
+            for (&(body_id, bounds, object_layer), &body_layer) in bodies.iter().zip(&layers) {
+                if body_layer == layer {
+                    debug_assert!(tracking[body_id.index() as usize].is_none());
+                    tracking[body_id.index() as usize] = Some(Tracking {
+                        body_id,
+                        object_layer,
+                        layer,
+                        location: BodyLocation { node: 0, child: 0 },
+                    });
+                    group.push((body_id, bounds));
+                }
+            }
+            tree.add_bodies(&mut group, &mut |body_id, location| {
+                if let Some(tracking) = &mut tracking[body_id.index() as usize] {
+                    tracking.location = location;
+                }
+            });
+        }
+        debug_assert!(bodies
+            .iter()
+            .all(|&(body_id, ..)| self.tracking(body_id).is_some()));
+    }
+
+    fn remove_bodies(&mut self, body_ids: &[BodyID]) {
+        for &body_id in body_ids {
+            let Some(tracking) = self.tracking(body_id) else {
+                continue;
+            };
+            self.trees[tracking.layer.0 as usize].remove_body(tracking.location);
+            self.tracking[body_id.index() as usize] = None;
+        }
+    }
+
+    fn notify_bodies_aabb_changed(&self, bodies: &[(BodyID, AABox)]) {
+        for (body_id, bounds) in bodies {
+            if let Some(tracking) = self.tracking(*body_id) {
+                self.trees[tracking.layer.0 as usize].update_body(tracking.location, bounds);
+            }
+        }
+    }
+
+    fn update(&mut self) {
+        self.rebuild(false);
+    }
+
+    fn optimize(&mut self) {
+        self.rebuild(true);
+    }
+
+    fn cast_ray(
+        &self,
+        ray: &RayCast,
+        collector: &mut dyn CollisionCollector<BroadPhaseCastResult>,
+        broad_phase_layer_filter: &dyn BroadPhaseLayerFilter,
+        object_layer_filter: &dyn ObjectLayerFilter,
+        body_filter: &dyn BodyFilter,
+    ) {
+        let inv_direction = RayInvDirection::new(ray.direction);
+        let filter = |body_id| self.passes(body_id, object_layer_filter, body_filter);
+        for (layer, tree) in self.trees.iter().enumerate() {
+            if collector.should_early_out() {
+                break;
+            }
+            if broad_phase_layer_filter.should_collide(BroadPhaseLayer(layer as u8)) {
+                tree.cast_ray(ray.origin, &inv_direction, &filter, collector);
+            }
+        }
+    }
+
+    fn collide_aabox(
+        &self,
+        bounds: &AABox,
+        collector: &mut dyn CollisionCollector<BodyID>,
+        broad_phase_layer_filter: &dyn BroadPhaseLayerFilter,
+        object_layer_filter: &dyn ObjectLayerFilter,
+        body_filter: &dyn BodyFilter,
+    ) {
+        self.collide(
+            |boxes| boxes.overlaps(bounds),
+            collector,
+            broad_phase_layer_filter,
+            object_layer_filter,
+            body_filter,
+        );
+    }
+
+    fn collide_sphere(
+        &self,
+        center: Vec3,
+        radius: f32,
+        collector: &mut dyn CollisionCollector<BodyID>,
+        broad_phase_layer_filter: &dyn BroadPhaseLayerFilter,
+        object_layer_filter: &dyn ObjectLayerFilter,
+        body_filter: &dyn BodyFilter,
+    ) {
+        self.collide(
+            |boxes| boxes.overlaps_sphere(center, radius),
+            collector,
+            broad_phase_layer_filter,
+            object_layer_filter,
+            body_filter,
+        );
+    }
+
+    fn collide_point(
+        &self,
+        point: Vec3,
+        collector: &mut dyn CollisionCollector<BodyID>,
+        broad_phase_layer_filter: &dyn BroadPhaseLayerFilter,
+        object_layer_filter: &dyn ObjectLayerFilter,
+        body_filter: &dyn BodyFilter,
+    ) {
+        let bounds = AABox::new(point, point);
+        self.collide(
+            |boxes| boxes.overlaps(&bounds),
+            collector,
+            broad_phase_layer_filter,
+            object_layer_filter,
+            body_filter,
+        );
+    }
+
+    fn find_colliding_pairs(
+        &self,
+        active_bodies: &[BodyID],
+        speculative_contact_distance: f32,
+        object_vs_broad_phase_layer_filter: &dyn ObjectVsBroadPhaseLayerFilter,
+        object_layer_pair_filter: &dyn ObjectLayerPairFilter,
+        body_filter: &dyn BodyFilter,
+        callback: &mut dyn FnMut(BodyID, BodyID),
+    ) {
+        let mut active = vec![false; self.tracking.len()];
+        for &body_id in active_bodies {
+            if self.contains(body_id) {
+                active[body_id.index() as usize] = true;
+            }
+        }
+
+        for &body_id in active_bodies {
+            let Some(tracking) = self.tracking(body_id) else {
+                continue;
+            };
+            if !body_filter.should_collide(body_id) {
+                continue;
+            }
+            let bounds = self.trees[tracking.layer.0 as usize]
+                .body_bounds(tracking.location)
+                .expanded_by(Vec3::splat(speculative_contact_distance));
+            let object_layer_filter =
+                DefaultObjectLayerFilter::new(object_layer_pair_filter, tracking.object_layer);
+            let filter = |other| self.passes(other, &object_layer_filter, body_filter);
+            let mut collector = PairCollector {
+                body_id,
+                active: &active,
+                callback: &mut *callback,
+            };
+            for (layer, tree) in self.trees.iter().enumerate() {
+                let layer = BroadPhaseLayer(layer as u8);
+                if object_vs_broad_phase_layer_filter.should_collide(tracking.object_layer, layer) {
+                    tree.collide(|boxes| boxes.overlaps(&bounds), &filter, &mut collector);
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for BroadPhaseQuadTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BroadPhaseQuadTree")
+            .field("trees", &self.trees)
+            .field("tracking", &self.tracking)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{body::BodyID, collision::CollisionResult};
+
+/// A body that was hit by a ray cast against the broadphase.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BroadPhaseCastResult {
+    pub body_id: BodyID,
+    /// The fraction of the ray at which it enters the bounds of the body.
+    pub fraction: f32,
+}
+
+impl CollisionResult for BroadPhaseCastResult {
+    const INITIAL_EARLY_OUT_FRACTION: f32 = 1.0 + f32::EPSILON;
+    const SHOULD_EARLY_OUT_FRACTION: f32 = 0.0;
+
+    #[inline]
+    fn early_out_fraction(&self) -> f32 {
+        self.fraction
+    }
+}
+
+/// A body whose bounds overlap the query of the broadphase, all hits are equally good.
+impl CollisionResult for BodyID {
+    const INITIAL_EARLY_OUT_FRACTION: f32 = f32::MAX;
+    const SHOULD_EARLY_OUT_FRACTION: f32 = -f32::MAX;
+
+    #[inline]
+    fn early_out_fraction(&self) -> f32 {
+        0.0
+    }
+}
@@ -0,0 +1,477 @@
+use alloc::{sync::Arc, vec, vec::Vec};
+use core::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{
+    body::{BodyFilter, BodyID},
+    broad_phase::{
+        atomic_f32::AtomicF32, broad_phase::PairCollector, BroadPhase, BroadPhaseCastResult,
+        BroadPhaseLayer, BroadPhaseLayerFilter, BroadPhaseLayerInterface,
+        ObjectVsBroadPhaseLayerFilter,
+    },
+    collision::{
+        CollisionCollector, ObjectLayer, ObjectLayerFilter, ObjectLayerPairFilter, RayCast,
+    },
+    geometry::{ray_aabox, AABox},
+    math::Vec3,
+};
+
+/// A body in the broadphase.
+#[derive(Debug)]
+struct SapBody {
+    body_id: BodyID,
+    object_layer: ObjectLayer,
+    layer: BroadPhaseLayer,
+    /// The minimum and then the maximum of the x, y and z axes.
+    bounds: [AtomicF32; 6],
+    /// The minimum of the bounds on the sort axis when the layer was last sorted.
+    key: f32,
+}
+
+impl SapBody {
+    #[inline]
+    fn bounds(&self) -> AABox {
+        let load = |axis: usize| self.bounds[axis].load();
+        AABox::new(
+            Vec3::new(load(0), load(1), load(2)),
+            Vec3::new(load(3), load(4), load(5)),
+        )
+    }
+
+    #[inline]
+    fn set_bounds(&self, bounds: &AABox) {
+        for axis in 0..3 {
+            self.bounds[axis].store(bounds.min[axis]);
+            self.bounds[axis + 3].store(bounds.max[axis]);
+        }
+    }
+}
+
+/// The bodies of a broadphase layer, sorted by the minimum of their bounds on one axis.
+///
+/// Bodies that move after sorting keep their place, the layer tracks how far they have moved
+/// from their key instead so that queries can widen the range of keys that they search.
+#[derive(Debug)]
+struct Layer {
+    /// The key and index of every body, sorted by key.
+    sorted: Vec<(f32, u32)>,
+    axis: usize,
+    /// The largest size of a body on the sort axis.
+    max_size: AtomicF32,
+    /// How far the minimum of a body has moved below its key.
+    max_drop: AtomicF32,
+    /// How far the minimum of a body has moved above its key.
+    max_rise: AtomicF32,
+    /// Whether bodies have moved since the layer was last sorted.
+    dirty: AtomicBool,
+}
+
+impl Layer {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            sorted: Vec::new(),
+            axis: 0,
+            max_size: AtomicF32::new(0.0),
+            max_drop: AtomicF32::new(0.0),
+            max_rise: AtomicF32::new(0.0),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns the bodies that can overlap the interval from `min` to `max` on the sort axis.
+    #[inline]
+    fn range(&self, min: f32, max: f32) -> &[(f32, u32)] {
+        let min = min - self.max_rise.load() - self.max_size.load();
+        let max = max + self.max_drop.load();
+        let start = self.sorted.partition_point(|&(key, _)| key < min);
+        let end = self.sorted.partition_point(|&(key, _)| key <= max);
+        &self.sorted[start..end.max(start)]
+    }
+
+    /// Records that `body` has moved to `bounds`.
+    #[inline]
+    fn moved(&self, body: &SapBody, bounds: &AABox) {
+        let min = bounds.min[self.axis];
+        self.max_drop.fetch_max(body.key - min);
+        self.max_rise.fetch_max(min - body.key);
+        self.max_size.fetch_max(bounds.max[self.axis] - min);
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// Sorts the bodies on the axis along which their centers are spread out the most.
+    fn sort(&mut self, bodies: &mut [Option<SapBody>]) {
+        let body = |index: u32| bodies[index as usize].as_ref().unwrap();
+        let mut sum = Vec3::ZERO;
+        let mut sum_squared = Vec3::ZERO;
+        for &(_, index) in &self.sorted {
+            let center = body(index).bounds().center();
+            sum += center;
+            sum_squared += center * center;
+        }
+        let count = self.sorted.len().max(1) as f32;
+        let variance = sum_squared / count - (sum / count) * (sum / count);
+        let axis = if variance.x >= variance.y && variance.x >= variance.z {
+            0
+        } else if variance.y >= variance.z {
+            1
+        } else {
+            2
+        };
+
+        let mut max_size = 0.0f32;
+        for (key, index) in &mut self.sorted {
+            let bounds = body(*index).bounds();
+            *key = bounds.min[axis];
+            max_size = max_size.max(bounds.max[axis] - bounds.min[axis]);
+        }
+        if axis != self.axis {
+            self.axis = axis;
+            self.sorted.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+        } else {
+            // Bodies only move a little between updates, which insertion sort handles in
+            // linear time.
+            for i in 1..self.sorted.len() {
+                let mut j = i;
+                while j > 0 && self.sorted[j - 1].0 > self.sorted[j].0 {
+                    self.sorted.swap(j - 1, j);
+                    j -= 1;
+                }
+            }
+        }
+        for &(key, index) in &self.sorted {
+            bodies[index as usize].as_mut().unwrap().key = key;
+        }
+
+        self.max_size = AtomicF32::new(max_size);
+        self.max_drop = AtomicF32::new(0.0);
+        self.max_rise = AtomicF32::new(0.0);
+        self.dirty = AtomicBool::new(false);
+    }
+}
+
+/// A broadphase that keeps the bodies of every [`BroadPhaseLayer`] in a list that is sorted
+/// along a single axis, which is chosen again every update.
+///
+/// Queries search the range of the list that overlaps them on the sort axis. This works well
+/// for scenes in which the bodies are spread out along one axis, such as levels that are
+/// mostly flat or two dimensional. [`BroadPhaseQuadTree`](super::BroadPhaseQuadTree) is the
+/// better choice for other scenes.
+pub struct BroadPhaseSweepAndPrune {
+    layer_interface: Arc<dyn BroadPhaseLayerInterface>,
+    layers: Vec<Layer>,
+    /// Indexed by the index of the body.
+    bodies: Vec<Option<SapBody>>,
+    body_count: usize,
+}
+
+impl BroadPhaseSweepAndPrune {
+    pub fn new(layer_interface: Arc<dyn BroadPhaseLayerInterface>) -> Self {
+        let layer_count = layer_interface.broad_phase_layer_count();
+        Self {
+            layer_interface,
+            layers: (0..layer_count).map(|_| Layer::new()).collect(),
+            bodies: Vec::new(),
+            body_count: 0,
+        }
+    }
+
+    #[inline]
+    pub fn layer_interface(&self) -> &Arc<dyn BroadPhaseLayerInterface> {
+        &self.layer_interface
+    }
+
+    #[inline]
+    fn body(&self, body_id: BodyID) -> Option<&SapBody> {
+        self.bodies
+            .get(body_id.index() as usize)?
+            .as_ref()
+            .filter(|body| body.body_id == body_id)
+    }
+
+    fn sort(&mut self, all: bool) {
+        for layer in &mut self.layers {
+            if all || layer.dirty.load(Ordering::Relaxed) {
+                layer.sort(&mut self.bodies);
+            }
+        }
+    }
+
+    /// Calls `callback` for the bodies in the layers that pass `broad_phase_layer_filter`
+    /// whose bounds overlap `bounds` on the sort axis and that pass `object_layer_filter` and
+    /// `body_filter`.
+    fn collide(
+        &self,
+        bounds: &AABox,
+        broad_phase_layer_filter: &dyn BroadPhaseLayerFilter,
+        object_layer_filter: &dyn ObjectLayerFilter,
+        body_filter: &dyn BodyFilter,
+        mut callback: impl FnMut(&SapBody) -> bool,
+    ) {
+        for (layer_index, layer) in self.layers.iter().enumerate() {
+            if !broad_phase_layer_filter.should_collide(BroadPhaseLayer(layer_index as u8)) {
+                continue;
+            }
+            let axis = layer.axis;
+            for &(_, index) in layer.range(bounds.min[axis], bounds.max[axis]) {
+                let body = self.bodies[index as usize].as_ref().unwrap();
+                if object_layer_filter.should_collide(body.object_layer)
+                    && body_filter.should_collide(body.body_id)
+                    && !callback(body)
+                {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl BroadPhase for BroadPhaseSweepAndPrune {
+    #[inline]
+    fn body_count(&self) -> usize {
+        self.body_count
+    }
+
+    #[inline]
+    fn contains(&self, body_id: BodyID) -> bool {
+        self.body(body_id).is_some()
+    }
+
+    #[inline]
+    fn body_bounds(&self, body_id: BodyID) -> Option<AABox> {
+        Some(self.body(body_id)?.bounds())
+    }
+
+    #[inline]
+    fn object_layer(&self, body_id: BodyID) -> Option<ObjectLayer> {
+        Some(self.body(body_id)?.object_layer)
+    }
+
+    #[inline]
+    fn broad_phase_layer(&self, body_id: BodyID) -> Option<BroadPhaseLayer> {
+        Some(self.body(body_id)?.layer)
+    }
+
+    fn add_bodies(&mut self, bodies: &[(BodyID, AABox, ObjectLayer)]) {
+        let Some(max_index) = bodies.iter().map(|(body_id, ..)| body_id.index()).max() else {
+            return;
+        };
+        if self.bodies.len() <= max_index as usize {
+            self.bodies.resize_with(max_index as usize + 1, || None);
+        }
+
+        for &(body_id, bounds, object_layer) in bodies {
+            debug_assert!(self.bodies[body_id.index() as usize].is_none());
+            let layer_id = self.layer_interface.broad_phase_layer(object_layer);
+            let layer = &mut self.layers[layer_id.0 as usize];
+            let key = bounds.min[layer.axis];
+            let body = SapBody {
+                body_id,
+                object_layer,
+                layer: layer_id,
+                bounds: [0; 6].map(|_| AtomicF32::new(0.0)),
+                key,
+            };
+            body.set_bounds(&bounds);
+            layer.max_size.fetch_max(bounds.max[layer.axis] - key);
+            layer.sorted.push((key, body_id.index()));
+            self.bodies[body_id.index() as usize] = Some(body);
+        }
+        self.body_count += bodies.len();
+
+        // The new bodies form a single run at the end, which a stable sort merges in linear
+        // time.
+        for layer in &mut self.layers {
+            layer.sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+        }
+    }
+
+    fn remove_bodies(&mut self, body_ids: &[BodyID]) {
+        let mut removed = vec![false; self.layers.len()];
+        for &body_id in body_ids {
+            if let Some(body) = self.body(body_id) {
+                removed[body.layer.0 as usize] = true;
+                self.bodies[body_id.index() as usize] = None;
+                self.body_count -= 1;
+            }
+        }
+        for (layer, removed) in self.layers.iter_mut().zip(removed) {
+            if removed {
+                let bodies = &self.bodies;
+                layer
+                    .sorted
+                    .retain(|&(_, index)| bodies[index as usize].is_some());
+            }
+        }
+    }
+
+    fn notify_bodies_aabb_changed(&self, bodies: &[(BodyID, AABox)]) {
+        for (body_id, bounds) in bodies {
+            if let Some(body) = self.body(*body_id) {
+                body.set_bounds(bounds);
+                self.layers[body.layer.0 as usize].moved(body, bounds);
+            }
+        }
+    }
+
+    fn update(&mut self) {
+        self.sort(false);
+    }
+
+    fn optimize(&mut self) {
+        self.sort(true);
+    }
+
+    fn cast_ray(
+        &self,
+        ray: &RayCast,
+        collector: &mut dyn CollisionCollector<BroadPhaseCastResult>,
+        broad_phase_layer_filter: &dyn BroadPhaseLayerFilter,
+        object_layer_filter: &dyn ObjectLayerFilter,
+        body_filter: &dyn BodyFilter,
+    ) {
+        let bounds = AABox::from_points([ray.origin, ray.origin + ray.direction]);
+        self.collide(
+            &bounds,
+            broad_phase_layer_filter,
+            object_layer_filter,
+            body_filter,
+            |body| {
+                let bounds = body.bounds();
+                let fraction = ray_aabox(ray.origin, ray.direction, bounds.min, bounds.max);
+                if fraction < collector.early_out_fraction() {
+                    collector.add_hit(&BroadPhaseCastResult {
+                        body_id: body.body_id,
+                        fraction,
+                    });
+                }
+                !collector.should_early_out()
+            },
+        );
+    }
+
+    fn collide_aabox(
+        &self,
+        bounds: &AABox,
+        collector: &mut dyn CollisionCollector<BodyID>,
+        broad_phase_layer_filter: &dyn BroadPhaseLayerFilter,
+        object_layer_filter: &dyn ObjectLayerFilter,
+        body_filter: &dyn BodyFilter,
+    ) {
+        self.collide(
+            bounds,
+            broad_phase_layer_filter,
+            object_layer_filter,
+            body_filter,
+            |body| {
+                if body.bounds().overlaps(bounds) {
+                    collector.add_hit(&body.body_id);
+                }
+                !collector.should_early_out()
+            },
+        );
+    }
+
+    fn collide_sphere(
+        &self,
+        center: Vec3,
+        radius: f32,
+        collector: &mut dyn CollisionCollector<BodyID>,
+        broad_phase_layer_filter: &dyn BroadPhaseLayerFilter,
+        object_layer_filter: &dyn ObjectLayerFilter,
+        body_filter: &dyn BodyFilter,
+    ) {
+        let bounds = AABox::from_center_and_half_extent(center, Vec3::splat(radius));
+        self.collide(
+            &bounds,
+            broad_phase_layer_filter,
+            object_layer_filter,
+            body_filter,
+            |body| {
+                if body.bounds().distance_squared(center) <= radius * radius {
+                    collector.add_hit(&body.body_id);
+                }
+                !collector.should_early_out()
+            },
+        );
+    }
+
+    fn collide_point(
+        &self,
+        point: Vec3,
+        collector: &mut dyn CollisionCollector<BodyID>,
+        broad_phase_layer_filter: &dyn BroadPhaseLayerFilter,
+        object_layer_filter: &dyn ObjectLayerFilter,
+        body_filter: &dyn BodyFilter,
+    ) {
+        self.collide_aabox(
+            &AABox::new(point, point),
+            collector,
+            broad_phase_layer_filter,
+            object_layer_filter,
+            body_filter,
+        );
+    }
+
+    fn find_colliding_pairs(
+        &self,
+        active_bodies: &[BodyID],
+        speculative_contact_distance: f32,
+        object_vs_broad_phase_layer_filter: &dyn ObjectVsBroadPhaseLayerFilter,
+        object_layer_pair_filter: &dyn ObjectLayerPairFilter,
+        body_filter: &dyn BodyFilter,
+        callback: &mut dyn FnMut(BodyID, BodyID),
+    ) {
+        let mut active = vec![false; self.bodies.len()];
+        for &body_id in active_bodies {
+            if self.contains(body_id) {
+                active[body_id.index() as usize] = true;
+            }
+        }
+
+        for &body_id in active_bodies {
+            let Some(body) = self.body(body_id) else {
+                continue;
+            };
+            if !body_filter.should_collide(body_id) {
+                continue;
+            }
+            let bounds = body
+                .bounds()
+                .expanded_by(Vec3::splat(speculative_contact_distance));
+            let mut collector = PairCollector {
+                body_id,
+                active: &active,
+                callback: &mut *callback,
+            };
+            for (layer_index, layer) in self.layers.iter().enumerate() {
+                let layer_id = BroadPhaseLayer(layer_index as u8);
+                if !object_vs_broad_phase_layer_filter.should_collide(body.object_layer, layer_id) {
+                    continue;
+                }
+                let axis = layer.axis;
+                for &(_, index) in layer.range(bounds.min[axis], bounds.max[axis]) {
+                    let other = self.bodies[index as usize].as_ref().unwrap();
+                    if object_layer_pair_filter
+                        .should_collide(body.object_layer, other.object_layer)
+                        && body_filter.should_collide(other.body_id)
+                        && other.bounds().overlaps(&bounds)
+                    {
+                        collector.add_hit(&other.body_id);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for BroadPhaseSweepAndPrune {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BroadPhaseSweepAndPrune")
+            .field("layers", &self.layers)
+            .field("bodies", &self.bodies)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,28 @@
+pub(crate) mod atomic_f32;
+
+#[allow(clippy::module_inception)]
+pub(crate) mod broad_phase;
+pub use broad_phase::BroadPhase;
+
+pub(crate) mod broad_phase_layer;
+pub use broad_phase_layer::{
+    BroadPhaseLayer, BroadPhaseLayerFilter, BroadPhaseLayerInterface, DefaultBroadPhaseLayerFilter,
+    ObjectVsBroadPhaseLayerFilter,
+};
+
+pub(crate) mod broad_phase_layer_interface_table;
+pub use broad_phase_layer_interface_table::BroadPhaseLayerInterfaceTable;
+
+pub(crate) mod broad_phase_quad_tree;
+pub use broad_phase_quad_tree::BroadPhaseQuadTree;
+
+pub(crate) mod broad_phase_query;
+pub use broad_phase_query::BroadPhaseCastResult;
+
+pub(crate) mod broad_phase_sweep_and_prune;
+pub use broad_phase_sweep_and_prune::BroadPhaseSweepAndPrune;
+
+pub(crate) mod object_vs_broad_phase_layer_filter_table;
+pub use object_vs_broad_phase_layer_filter_table::ObjectVsBroadPhaseLayerFilterTable;
+
+pub(crate) mod quad_tree;
@@ -0,0 +1,43 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    broad_phase::{BroadPhaseLayer, BroadPhaseLayerInterface, ObjectVsBroadPhaseLayerFilter},
+    collision::{ObjectLayer, ObjectLayerPairFilter},
+};
+
+/// An [`ObjectVsBroadPhaseLayerFilter`] that is derived from an [`ObjectLayerPairFilter`], an
+/// object layer collides with a broadphase layer if it collides with any of the object layers
+/// in it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObjectVsBroadPhaseLayerFilterTable {
+    /// For every object layer, a mask of the broadphase layers it collides with.
+    masks: Vec<u64>,
+}
+
+impl ObjectVsBroadPhaseLayerFilterTable {
+    /// Creates the table for the object layers `0..object_layer_count`.
+    pub fn new(
+        broad_phase_layer_interface: &dyn BroadPhaseLayerInterface,
+        object_layer_pair_filter: &dyn ObjectLayerPairFilter,
+        object_layer_count: u16,
+    ) -> Self {
+        assert!(broad_phase_layer_interface.broad_phase_layer_count() <= u64::BITS as u8);
+        let mut masks = vec![0; object_layer_count as usize];
+        for (layer1, mask) in masks.iter_mut().enumerate() {
+            for layer2 in 0..object_layer_count {
+                let (layer1, layer2) = (ObjectLayer(layer1 as u16), ObjectLayer(layer2));
+                if object_layer_pair_filter.should_collide(layer1, layer2) {
+                    *mask |= 1 << broad_phase_layer_interface.broad_phase_layer(layer2).0;
+                }
+            }
+        }
+        Self { masks }
+    }
+}
+
+impl ObjectVsBroadPhaseLayerFilter for ObjectVsBroadPhaseLayerFilterTable {
+    #[inline]
+    fn should_collide(&self, layer1: ObjectLayer, layer2: BroadPhaseLayer) -> bool {
+        self.masks[layer1.0 as usize] & (1 << layer2.0) != 0
+    }
+}
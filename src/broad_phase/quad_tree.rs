@@ -0,0 +1,412 @@
+use alloc::{vec, vec::Vec};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{
+    body::BodyID,
+    broad_phase::{atomic_f32::AtomicF32, BroadPhaseCastResult},
+    collision::CollisionCollector,
+    geometry::{ray_aabox4, AABox, AABox4, RayInvDirection},
+    math::{Vec3, Vec4},
+};
+
+/// Marks a child of a node as a body, the remaining bits hold the body ID.
+const LEAF_BIT: u32 = 1 << 31;
+
+/// Marks an unused child of a node. Leaves always have [`LEAF_BIT`] set and there are far
+/// fewer nodes than this, so no child is ever mistaken for it.
+const EMPTY_CHILD: u32 = !LEAF_BIT;
+
+/// The parent of the root node.
+const NO_PARENT: u32 = u32::MAX;
+
+/// Where a body is stored in a [`QuadTree`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct BodyLocation {
+    pub node: u32,
+    pub child: u8,
+}
+
+/// A node of the tree with four children.
+///
+/// The bounds of the children can be changed through a shared reference, so that bodies can
+/// be moved from multiple threads without locking. The bounds only ever grow this way, the
+/// tree is tightened again when it is rebuilt.
+#[derive(Debug)]
+struct Node {
+    /// The minimum and then the maximum of the x, y and z axes of every child.
+    bounds: [[AtomicF32; 4]; 6],
+    children: [u32; 4],
+    parent: u32,
+}
+
+impl Node {
+    #[inline]
+    fn new(parent: u32) -> Self {
+        let lanes = |value: f32| [0; 4].map(|_| AtomicF32::new(value));
+        Self {
+            bounds: [
+                lanes(f32::MAX),
+                lanes(f32::MAX),
+                lanes(f32::MAX),
+                lanes(f32::MIN),
+                lanes(f32::MIN),
+                lanes(f32::MIN),
+            ],
+            children: [EMPTY_CHILD; 4],
+            parent,
+        }
+    }
+
+    #[inline]
+    fn bounds(&self) -> AABox4 {
+        let load = |axis: &[AtomicF32; 4]| {
+            Vec4::new(
+                axis[0].load(),
+                axis[1].load(),
+                axis[2].load(),
+                axis[3].load(),
+            )
+        };
+        AABox4 {
+            min_x: load(&self.bounds[0]),
+            min_y: load(&self.bounds[1]),
+            min_z: load(&self.bounds[2]),
+            max_x: load(&self.bounds[3]),
+            max_y: load(&self.bounds[4]),
+            max_z: load(&self.bounds[5]),
+        }
+    }
+
+    #[inline]
+    fn child_bounds(&self, child: usize) -> AABox {
+        let load = |axis: usize| self.bounds[axis][child].load();
+        AABox::new(
+            Vec3::new(load(0), load(1), load(2)),
+            Vec3::new(load(3), load(4), load(5)),
+        )
+    }
+
+    #[inline]
+    fn set_child_bounds(&self, child: usize, bounds: &AABox) {
+        for axis in 0..3 {
+            self.bounds[axis][child].store(bounds.min[axis]);
+            self.bounds[axis + 3][child].store(bounds.max[axis]);
+        }
+    }
+
+    /// Grows the bounds of `child` to contain `bounds` and returns whether they grew.
+    #[inline]
+    fn encapsulate_child_bounds(&self, child: usize, bounds: &AABox) -> bool {
+        let mut grown = false;
+        for axis in 0..3 {
+            grown |= self.bounds[axis][child].fetch_min(bounds.min[axis]);
+            grown |= self.bounds[axis + 3][child].fetch_max(bounds.max[axis]);
+        }
+        grown
+    }
+
+    /// Returns the bounds of all children together.
+    #[inline]
+    fn union_bounds(&self) -> AABox {
+        let mut bounds = AABox::INVALID;
+        for child in 0..4 {
+            bounds.encapsulate_box(&self.child_bounds(child));
+        }
+        bounds
+    }
+}
+
+/// An axis aligned bounding box tree of bodies with four children per node.
+///
+/// Bodies are added by building a tree for them and inserting it at the root, and moved by
+/// growing the bounds of their ancestors. Both keep the tree valid but make it less efficient
+/// to query, [`QuadTree::rebuild`] builds an optimal tree again.
+#[derive(Debug)]
+pub(crate) struct QuadTree {
+    nodes: Vec<Node>,
+    root: u32,
+    body_count: usize,
+    /// Whether the tree has changed since it was last built.
+    dirty: AtomicBool,
+}
+
+impl QuadTree {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![Node::new(NO_PARENT)],
+            root: 0,
+            body_count: 0,
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    #[inline]
+    pub fn body_count(&self) -> usize {
+        self.body_count
+    }
+
+    #[inline]
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.load(Ordering::Relaxed)
+    }
+
+    /// Returns the bounds of the body at `location`.
+    #[inline]
+    pub fn body_bounds(&self, location: BodyLocation) -> AABox {
+        self.nodes[location.node as usize].child_bounds(location.child as usize)
+    }
+
+    /// Adds `bodies` to the tree and calls `located` with the location of every body.
+    pub fn add_bodies(
+        &mut self,
+        bodies: &mut [(BodyID, AABox)],
+        located: &mut dyn FnMut(BodyID, BodyLocation),
+    ) {
+        if bodies.is_empty() {
+            return;
+        }
+        if self.body_count == 0 {
+            // An empty tree is replaced by an optimal one.
+            self.nodes.clear();
+            self.root = Self::build(bodies, NO_PARENT, &mut self.nodes, located);
+            self.body_count = bodies.len();
+            self.dirty.store(false, Ordering::Relaxed);
+            return;
+        }
+
+        let mut bounds = AABox::INVALID;
+        for (_, body_bounds) in bodies.iter() {
+            bounds.encapsulate_box(body_bounds);
+        }
+        let subtree = Self::build(bodies, NO_PARENT, &mut self.nodes, located);
+        let root = &self.nodes[self.root as usize];
+        if let Some(child) = root.children.iter().position(|&c| c == EMPTY_CHILD) {
+            // Fill a free slot of the root.
+            root.set_child_bounds(child, &bounds);
+            self.nodes[self.root as usize].children[child] = subtree;
+            self.nodes[subtree as usize].parent = self.root;
+        } else {
+            // Put a new root above the old root and the new bodies.
+            let index = self.nodes.len() as u32;
+            let mut node = Node::new(NO_PARENT);
+            node.set_child_bounds(0, &root.union_bounds());
+            node.set_child_bounds(1, &bounds);
+            node.children[0] = self.root;
+            node.children[1] = subtree;
+            self.nodes[self.root as usize].parent = index;
+            self.nodes[subtree as usize].parent = index;
+            self.nodes.push(node);
+            self.root = index;
+        }
+        self.body_count += bodies.len();
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// Removes the body at `location` from the tree.
+    pub fn remove_body(&mut self, location: BodyLocation) {
+        let node = &mut self.nodes[location.node as usize];
+        debug_assert!(node.children[location.child as usize] & LEAF_BIT != 0);
+        node.children[location.child as usize] = EMPTY_CHILD;
+        node.set_child_bounds(location.child as usize, &AABox::INVALID);
+        self.body_count -= 1;
+        if self.body_count == 0 {
+            *self = Self::new();
+        } else {
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Changes the bounds of the body at `location`, which can be done from multiple threads
+    /// at once. The tree only becomes dirty when the body leaves the bounds of its node.
+    pub fn update_body(&self, location: BodyLocation, bounds: &AABox) {
+        self.nodes[location.node as usize].set_child_bounds(location.child as usize, bounds);
+
+        // The ancestors only grow, so that concurrent updates cannot shrink them too far. The
+        // bounds of a node contain those of its children, so the walk stops at the first
+        // ancestor that already contains the body.
+        let mut index = location.node;
+        let mut parent = self.nodes[index as usize].parent;
+        while parent != NO_PARENT {
+            let node = &self.nodes[parent as usize];
+            let child = node.children.iter().position(|&c| c == index).unwrap();
+            if !node.encapsulate_child_bounds(child, bounds) {
+                break;
+            }
+            self.dirty.store(true, Ordering::Relaxed);
+            index = parent;
+            parent = node.parent;
+        }
+    }
+
+    /// Builds an optimal tree from the bodies and calls `located` with the new location of
+    /// every body.
+    pub fn rebuild(&mut self, located: &mut dyn FnMut(BodyID, BodyLocation)) {
+        let mut bodies = Vec::with_capacity(self.body_count);
+        let mut stack = vec![self.root];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index as usize];
+            for (child, &code) in node.children.iter().enumerate() {
+                if code == EMPTY_CHILD {
+                    continue;
+                }
+                if code & LEAF_BIT != 0 {
+                    bodies.push((BodyID::from_raw(code & !LEAF_BIT), node.child_bounds(child)));
+                } else {
+                    stack.push(code);
+                }
+            }
+        }
+
+        *self = Self::new();
+        if !bodies.is_empty() {
+            self.nodes.clear();
+            self.root = Self::build(&mut bodies, NO_PARENT, &mut self.nodes, located);
+            self.body_count = bodies.len();
+        }
+    }
+
+    /// Adds the bodies whose bounds are hit by the ray and that pass `filter` to the collector,
+    /// closest first.
+    pub fn cast_ray(
+        &self,
+        origin: Vec3,
+        inv_direction: &RayInvDirection,
+        filter: &dyn Fn(BodyID) -> bool,
+        collector: &mut dyn CollisionCollector<BroadPhaseCastResult>,
+    ) {
+        let mut stack = vec![(self.root, 0.0)];
+        while let Some((code, fraction)) = stack.pop() {
+            if collector.should_early_out() {
+                break;
+            }
+            if fraction >= collector.early_out_fraction() {
+                continue;
+            }
+            if code & LEAF_BIT != 0 {
+                let body_id = BodyID::from_raw(code & !LEAF_BIT);
+                if filter(body_id) {
+                    collector.add_hit(&BroadPhaseCastResult { body_id, fraction });
+                }
+                continue;
+            }
+
+            let node = &self.nodes[code as usize];
+            let fractions = ray_aabox4(origin, inv_direction, &node.bounds());
+            let mut children = [(0, 0.0); 4];
+            let mut count = 0;
+            for (i, &child) in node.children.iter().enumerate() {
+                if child != EMPTY_CHILD && fractions[i] < collector.early_out_fraction() {
+                    children[count] = (child, fractions[i]);
+                    count += 1;
+                }
+            }
+            // Push the furthest child first so that the closest child is visited first.
+            children[..count].sort_unstable_by(|a, b| b.1.total_cmp(&a.1));
+            stack.extend_from_slice(&children[..count]);
+        }
+    }
+
+    /// Adds the bodies for which `overlaps` returns true and that pass `filter` to the
+    /// collector. `overlaps` tests four boxes at once, bodies are only added when their own
+    /// bounds pass the test.
+    pub fn collide(
+        &self,
+        overlaps: impl Fn(&AABox4) -> [bool; 4],
+        filter: &dyn Fn(BodyID) -> bool,
+        collector: &mut dyn CollisionCollector<BodyID>,
+    ) {
+        let mut stack = vec![self.root];
+        while let Some(index) = stack.pop() {
+            if collector.should_early_out() {
+                break;
+            }
+            let node = &self.nodes[index as usize];
+            let overlaps = overlaps(&node.bounds());
+            for (&child, overlaps) in node.children.iter().zip(overlaps) {
+                if child == EMPTY_CHILD || !overlaps {
+                    continue;
+                }
+                if child & LEAF_BIT != 0 {
+                    let body_id = BodyID::from_raw(child & !LEAF_BIT);
+                    if filter(body_id) {
+                        collector.add_hit(&body_id);
+                    }
+                } else {
+                    stack.push(child);
+                }
+            }
+        }
+    }
+
+    /// Builds the subtree for `bodies` and returns the index of its node. The root is always
+    /// a node, even if it only has a single child.
+    fn build(
+        bodies: &mut [(BodyID, AABox)],
+        parent: u32,
+        nodes: &mut Vec<Node>,
+        located: &mut dyn FnMut(BodyID, BodyLocation),
+    ) -> u32 {
+        let index = nodes.len() as u32;
+        debug_assert!(index < EMPTY_CHILD);
+        nodes.push(Node::new(parent));
+
+        // Split into at most four groups by repeatedly halving the largest one.
+        let mut partitions = vec![(0, bodies.len())];
+        while partitions.len() < 4 {
+            let (i, &(start, end)) = partitions
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, (start, end))| end - start)
+                .unwrap();
+            if end - start <= 1 {
+                break;
+            }
+            let middle = Self::split(&mut bodies[start..end]) + start;
+            partitions[i] = (start, middle);
+            partitions.insert(i + 1, (middle, end));
+        }
+
+        for (i, &(start, end)) in partitions.iter().enumerate() {
+            let group = &mut bodies[start..end];
+            let mut group_bounds = AABox::INVALID;
+            for (_, bounds) in group.iter() {
+                group_bounds.encapsulate_box(bounds);
+            }
+            let child = if let [(body_id, _)] = group {
+                located(
+                    *body_id,
+                    BodyLocation {
+                        node: index,
+                        child: i as u8,
+                    },
+                );
+                LEAF_BIT | body_id.value()
+            } else {
+                Self::build(group, index, nodes, located)
+            };
+            nodes[index as usize].set_child_bounds(i, &group_bounds);
+            nodes[index as usize].children[i] = child;
+        }
+        index
+    }
+
+    /// Reorders `bodies` around the median center on the longest axis and returns the index of
+    /// the median.
+    fn split(bodies: &mut [(BodyID, AABox)]) -> usize {
+        let centers = AABox::from_points(bodies.iter().map(|(_, bounds)| bounds.center()));
+        let size = centers.size();
+        let axis = if size.x >= size.y && size.x >= size.z {
+            0
+        } else if size.y >= size.z {
+            1
+        } else {
+            2
+        };
+        let middle = bodies.len() / 2;
+        bodies.select_nth_unstable_by(middle, |(_, a), (_, b)| {
+            a.center()[axis].total_cmp(&b.center()[axis])
+        });
+        middle
+    }
+}
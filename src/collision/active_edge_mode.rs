@@ -0,0 +1,10 @@
+/// How contacts with the inactive edges of triangles are treated, see
+/// [`fix_normal`](super::fix_normal).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ActiveEdgeMode {
+    /// The normal of a contact with an inactive edge is replaced by the triangle normal.
+    #[default]
+    CollideOnlyWithActive,
+    /// All edges are treated as active.
+    CollideWithAll,
+}
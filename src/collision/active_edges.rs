@@ -0,0 +1,77 @@
+use crate::{geometry::closest_point_on_triangle, math::Vec3};
+
+/// The cosine of 179 degrees, normals that are further apart belong to triangles that are back
+/// to back.
+const COS_BACK_TO_BACK: f32 = -0.999_848;
+
+/// The cosine of 1 degree, normals that are closer are considered to be the same.
+const COS_SAME_NORMAL: f32 = 0.999_848;
+
+/// The edges that are next to each feature of a triangle, indexed by the bit mask of the
+/// vertices that make up the feature. Edge `i` runs from vertex `i` to vertex `i + 1`.
+const FEATURE_EDGES: [u8; 8] = [0, 0b101, 0b011, 0b001, 0b110, 0b100, 0b010, 0];
+
+/// Returns whether the edge between two triangles with normals `normal1` and `normal2` is
+/// active. `edge_direction` is the direction of the edge as it is wound in the first
+/// triangle.
+///
+/// Concave edges are never active, convex edges are only active when the cosine of the angle
+/// between the normals is below `cos_threshold_angle`. Edges of triangles that are back to back
+/// are always active.
+pub fn is_edge_active(
+    normal1: Vec3,
+    normal2: Vec3,
+    edge_direction: Vec3,
+    cos_threshold_angle: f32,
+) -> bool {
+    let cos_angle = normal1.dot(normal2);
+    if cos_angle < COS_BACK_TO_BACK {
+        return true;
+    }
+    if normal1.cross(normal2).dot(edge_direction) < 0.0 {
+        return false;
+    }
+    cos_angle < cos_threshold_angle
+}
+
+/// Corrects the contact `normal` at `point` against the triangle `vertices`, which prevents
+/// objects that slide over a mesh from catching on its internal edges.
+///
+/// When the closest feature of the triangle to `point` is an inactive edge, or a vertex with
+/// only inactive edges, `triangle_normal` is returned instead of `normal`. Both normals point
+/// in the same direction relative to the triangle and do not need to be normalized. Bit `i`
+/// of `active_edges` is set when the edge from vertex `i` to vertex `i + 1` is active.
+///
+/// The normal is kept when it would affect `movement_direction` less than the triangle normal.
+/// This keeps objects that graze a steep triangle from being pushed back.
+pub fn fix_normal(
+    vertices: [Vec3; 3],
+    triangle_normal: Vec3,
+    active_edges: u8,
+    point: Vec3,
+    normal: Vec3,
+    movement_direction: Vec3,
+) -> Vec3 {
+    if active_edges == 0b111 {
+        return normal;
+    }
+
+    let normal_length = normal.length();
+    let triangle_normal_length = triangle_normal.length();
+    if movement_direction.dot(triangle_normal) * normal_length
+        < movement_direction.dot(normal) * triangle_normal_length
+    {
+        return normal;
+    }
+    if triangle_normal.dot(normal) > COS_SAME_NORMAL * normal_length * triangle_normal_length {
+        return normal;
+    }
+
+    let [v0, v1, v2] = vertices;
+    let closest = closest_point_on_triangle(v0 - point, v1 - point, v2 - point);
+    if FEATURE_EDGES[closest.set as usize & 0b111] & active_edges == 0 {
+        triangle_normal
+    } else {
+        normal
+    }
+}
@@ -0,0 +1,9 @@
+/// How hits on the back of a surface are treated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BackFaceMode {
+    /// Hits on the back of a surface are ignored.
+    #[default]
+    IgnoreBackFaces,
+    /// Hits on the back of a surface are reported like any other hit.
+    CollideWithBackFaces,
+}
@@ -0,0 +1,73 @@
+use alloc::vec::Vec;
+
+use crate::{collision::ActiveEdgeMode, math::Vec3, shape::SubShapeID};
+
+/// Settings for colliding two shapes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CollideShapeSettings {
+    /// Shapes that are separated by less than this distance are reported as well, with a
+    /// negative penetration depth. This is used for speculative contacts.
+    pub max_separation_distance: f32,
+    /// Whether the supporting faces of the shapes should be collected, these are needed to
+    /// build a [`ContactManifold`](super::ContactManifold).
+    pub collect_faces: bool,
+    /// The tolerance used to find the penetration depth.
+    pub penetration_tolerance: f32,
+    /// How contacts with the inactive edges of triangles are treated.
+    pub active_edge_mode: ActiveEdgeMode,
+    /// The direction in which the first shape is moving, used to decide whether a contact
+    /// normal from an inactive edge should be replaced, see
+    /// [`fix_normal`](super::fix_normal). Zero when the movement is unknown.
+    pub active_edge_movement_direction: Vec3,
+}
+
+impl Default for CollideShapeSettings {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_separation_distance: 0.0,
+            collect_faces: false,
+            penetration_tolerance: 1.0e-4,
+            active_edge_mode: ActiveEdgeMode::CollideOnlyWithActive,
+            active_edge_movement_direction: Vec3::ZERO,
+        }
+    }
+}
+
+/// A contact between two shapes, everything is in world space.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CollideShapeResult {
+    /// The deepest point of the first shape.
+    pub contact_point1: Vec3,
+    /// The deepest point of the second shape.
+    pub contact_point2: Vec3,
+    /// The normalized direction in which the second shape has to move to resolve the
+    /// collision, this points from the first shape into the second.
+    pub penetration_axis: Vec3,
+    /// The distance the shapes overlap along the axis, negative when they are separated.
+    pub penetration_depth: f32,
+    pub sub_shape_id1: SubShapeID,
+    pub sub_shape_id2: SubShapeID,
+    /// The face of the first shape that is most aligned with the penetration axis, only filled
+    /// when [`CollideShapeSettings::collect_faces`] is set.
+    pub face1: Vec<Vec3>,
+    /// The face of the second shape that is most aligned with the opposite of the penetration
+    /// axis, only filled when [`CollideShapeSettings::collect_faces`] is set.
+    pub face2: Vec<Vec3>,
+}
+
+impl CollideShapeResult {
+    /// Returns the result as if the shapes had been collided in the opposite order.
+    pub fn reversed(&self) -> Self {
+        Self {
+            contact_point1: self.contact_point2,
+            contact_point2: self.contact_point1,
+            penetration_axis: -self.penetration_axis,
+            penetration_depth: self.penetration_depth,
+            sub_shape_id1: self.sub_shape_id2,
+            sub_shape_id2: self.sub_shape_id1,
+            face1: self.face2.clone(),
+            face2: self.face1.clone(),
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use alloc::vec::Vec;
+
+use crate::{
+    collision::{CollideShapeResult, RayCastResult, ShapeCastResult},
+    shape::SubShapeID,
+};
+
+/// A result that can be added to a [`CollisionCollector`].
+///
+/// Every result has an early out fraction, a lower fraction is a better hit. Collectors use it
+/// to tell queries which hits are no longer of interest.
+pub trait CollisionResult: Clone {
+    /// The early out fraction of a collector that has not seen any hits.
+    const INITIAL_EARLY_OUT_FRACTION: f32;
+    /// Queries stop once the early out fraction of the collector drops to this value.
+    const SHOULD_EARLY_OUT_FRACTION: f32;
+
+    /// Returns the early out fraction of this hit.
+    fn early_out_fraction(&self) -> f32;
+}
+
+impl CollisionResult for RayCastResult {
+    const INITIAL_EARLY_OUT_FRACTION: f32 = 1.0 + f32::EPSILON;
+    const SHOULD_EARLY_OUT_FRACTION: f32 = 0.0;
+
+    #[inline]
+    fn early_out_fraction(&self) -> f32 {
+        self.fraction
+    }
+}
+
+/// Deeper contacts are better hits.
+impl CollisionResult for CollideShapeResult {
+    const INITIAL_EARLY_OUT_FRACTION: f32 = f32::MAX;
+    const SHOULD_EARLY_OUT_FRACTION: f32 = -f32::MAX;
+
+    #[inline]
+    fn early_out_fraction(&self) -> f32 {
+        -self.penetration_depth
+    }
+}
+
+/// Earlier hits are better, hits at the start of the cast are ordered by their penetration.
+impl CollisionResult for ShapeCastResult {
+    const INITIAL_EARLY_OUT_FRACTION: f32 = 1.0 + f32::EPSILON;
+    const SHOULD_EARLY_OUT_FRACTION: f32 = -f32::MAX;
+
+    #[inline]
+    fn early_out_fraction(&self) -> f32 {
+        if self.fraction > 0.0 {
+            self.fraction
+        } else {
+            -self.contact.penetration_depth
+        }
+    }
+}
+
+/// A sub shape that contains the point of a point query.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CollidePointResult {
+    pub sub_shape_id: SubShapeID,
+}
+
+/// All hits of a point query are equally good.
+impl CollisionResult for CollidePointResult {
+    const INITIAL_EARLY_OUT_FRACTION: f32 = f32::MAX;
+    const SHOULD_EARLY_OUT_FRACTION: f32 = -f32::MAX;
+
+    #[inline]
+    fn early_out_fraction(&self) -> f32 {
+        0.0
+    }
+}
+
+/// Receives the hits of a collision query.
+///
+/// Queries skip hits that are not better than [`CollisionCollector::early_out_fraction`] where
+/// they can, and stop as soon as [`CollisionCollector::should_early_out`] returns true.
+pub trait CollisionCollector<R: CollisionResult> {
+    /// Adds a hit to the collector.
+    fn add_hit(&mut self, hit: &R);
+
+    /// Returns the early out fraction that a hit needs to beat to be of interest.
+    fn early_out_fraction(&self) -> f32;
+
+    /// Returns whether the query should stop looking for hits.
+    #[inline]
+    fn should_early_out(&self) -> bool {
+        self.early_out_fraction() <= R::SHOULD_EARLY_OUT_FRACTION
+    }
+
+    /// Called before the hits of a body are added, for queries that visit multiple bodies.
+    #[inline]
+    fn on_body(&mut self) {}
+
+    /// Called after the hits of a body have been added.
+    #[inline]
+    fn on_body_end(&mut self) {}
+}
+
+/// Keeps the best hit.
+#[derive(Clone, Debug)]
+pub struct ClosestHitCollector<R> {
+    pub hit: Option<R>,
+    early_out_fraction: f32,
+}
+
+impl<R: CollisionResult> ClosestHitCollector<R> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            hit: None,
+            early_out_fraction: R::INITIAL_EARLY_OUT_FRACTION,
+        }
+    }
+
+    #[inline]
+    pub fn had_hit(&self) -> bool {
+        self.hit.is_some()
+    }
+
+    /// Removes the hit so that the collector can be reused.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<R: CollisionResult> Default for ClosestHitCollector<R> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: CollisionResult> CollisionCollector<R> for ClosestHitCollector<R> {
+    fn add_hit(&mut self, hit: &R) {
+        let early_out_fraction = hit.early_out_fraction();
+        if self.hit.is_none() || early_out_fraction < self.early_out_fraction {
+            self.early_out_fraction = early_out_fraction;
+            self.hit = Some(hit.clone());
+        }
+    }
+
+    #[inline]
+    fn early_out_fraction(&self) -> f32 {
+        self.early_out_fraction
+    }
+}
+
+/// Keeps the first hit and stops the query, for when it only matters whether there is a hit.
+#[derive(Clone, Debug)]
+pub struct AnyHitCollector<R> {
+    pub hit: Option<R>,
+}
+
+impl<R: CollisionResult> AnyHitCollector<R> {
+    #[inline]
+    pub fn new() -> Self {
+        Self { hit: None }
+    }
+
+    #[inline]
+    pub fn had_hit(&self) -> bool {
+        self.hit.is_some()
+    }
+
+    /// Removes the hit so that the collector can be reused.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.hit = None;
+    }
+}
+
+impl<R: CollisionResult> Default for AnyHitCollector<R> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: CollisionResult> CollisionCollector<R> for AnyHitCollector<R> {
+    #[inline]
+    fn add_hit(&mut self, hit: &R) {
+        if self.hit.is_none() {
+            self.hit = Some(hit.clone());
+        }
+    }
+
+    #[inline]
+    fn early_out_fraction(&self) -> f32 {
+        if self.hit.is_some() {
+            R::SHOULD_EARLY_OUT_FRACTION
+        } else {
+            R::INITIAL_EARLY_OUT_FRACTION
+        }
+    }
+}
+
+/// Keeps every hit, in the order they were found.
+#[derive(Clone, Debug)]
+pub struct AllHitCollector<R> {
+    pub hits: Vec<R>,
+}
+
+impl<R: CollisionResult> AllHitCollector<R> {
+    #[inline]
+    pub fn new() -> Self {
+        Self { hits: Vec::new() }
+    }
+
+    #[inline]
+    pub fn had_hit(&self) -> bool {
+        !self.hits.is_empty()
+    }
+
+    /// Removes the hits so that the collector can be reused.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.hits.clear();
+    }
+
+    /// Sorts the hits from best to worst.
+    #[inline]
+    pub fn sort(&mut self) {
+        self.hits
+            .sort_by(|a, b| a.early_out_fraction().total_cmp(&b.early_out_fraction()));
+    }
+}
+
+impl<R: CollisionResult> Default for AllHitCollector<R> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: CollisionResult> CollisionCollector<R> for AllHitCollector<R> {
+    #[inline]
+    fn add_hit(&mut self, hit: &R) {
+        self.hits.push(hit.clone());
+    }
+
+    #[inline]
+    fn early_out_fraction(&self) -> f32 {
+        R::INITIAL_EARLY_OUT_FRACTION
+    }
+}
+
+/// Keeps the best hit of every body, see [`CollisionCollector::on_body`]. Hits that are added
+/// outside of a body are treated as belonging to a single body.
+#[derive(Clone, Debug)]
+pub struct ClosestHitPerBodyCollector<R> {
+    pub hits: Vec<R>,
+    early_out_fraction: f32,
+    previous_early_out_fraction: f32,
+    had_hit: bool,
+}
+
+impl<R: CollisionResult> ClosestHitPerBodyCollector<R> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            hits: Vec::new(),
+            early_out_fraction: R::INITIAL_EARLY_OUT_FRACTION,
+            previous_early_out_fraction: R::INITIAL_EARLY_OUT_FRACTION,
+            had_hit: false,
+        }
+    }
+
+    #[inline]
+    pub fn had_hit(&self) -> bool {
+        !self.hits.is_empty()
+    }
+
+    /// Removes the hits so that the collector can be reused.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Sorts the hits from best to worst.
+    #[inline]
+    pub fn sort(&mut self) {
+        self.hits
+            .sort_by(|a, b| a.early_out_fraction().total_cmp(&b.early_out_fraction()));
+    }
+}
+
+impl<R: CollisionResult> Default for ClosestHitPerBodyCollector<R> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: CollisionResult> CollisionCollector<R> for ClosestHitPerBodyCollector<R> {
+    fn add_hit(&mut self, hit: &R) {
+        let early_out_fraction = hit.early_out_fraction();
+        if !self.had_hit {
+            self.hits.push(hit.clone());
+        } else if early_out_fraction < self.early_out_fraction {
+            *self.hits.last_mut().unwrap() = hit.clone();
+        } else {
+            return;
+        }
+        self.had_hit = true;
+        self.early_out_fraction = early_out_fraction;
+    }
+
+    #[inline]
+    fn early_out_fraction(&self) -> f32 {
+        self.early_out_fraction
+    }
+
+    #[inline]
+    fn on_body(&mut self) {
+        self.previous_early_out_fraction = self.early_out_fraction;
+        self.had_hit = false;
+    }
+
+    #[inline]
+    fn on_body_end(&mut self) {
+        // Hits on the next body only need to beat the hits that came before this body.
+        self.early_out_fraction = self.previous_early_out_fraction;
+        self.had_hit = false;
+    }
+}
@@ -0,0 +1,863 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+
+#[cfg(all(no_std, libm, not(test)))]
+use crate::math::libm::F32Ext;
+use crate::{
+    body::BodyFilter,
+    collision::{
+        fix_normal, ActiveEdgeMode, BackFaceMode, CollideShapeResult, CollideShapeSettings,
+        CollisionCollector, ShapeCast, ShapeCastResult, ShapeCastSettings, TransformedShape,
+    },
+    geometry::{
+        AABox, AddConvexRadius, EpaPenetrationDepth, GjkClosestPoint, PenetrationDepth, Support,
+        TransformedConvexObject, TriangleConvexSupport,
+    },
+    math::{Quat, Vec3},
+    shape::{
+        sub_shape_id_bits_for, CompoundSubShape, HeightFieldShape, MeshShape, MutableCompoundShape,
+        OffsetCenterOfMassShape, RotatedTranslatedShape, ScaledShape, SdfContact, SdfShape,
+        ShapeSubType, SphereShape, StaticCompoundShape, SubShapeID, SupportMode,
+    },
+};
+
+/// Collides the first shape against the second and adds the contacts to the collector.
+pub type CollideShapeFn = fn(
+    &CollisionDispatch,
+    &TransformedShape,
+    &TransformedShape,
+    &CollideShapeSettings,
+    &mut dyn CollisionCollector<CollideShapeResult>,
+);
+
+/// Casts a shape against a target shape and adds the hits to the collector.
+pub type CastShapeFn = fn(
+    &CollisionDispatch,
+    &ShapeCast,
+    &TransformedShape,
+    &ShapeCastSettings,
+    &mut dyn CollisionCollector<ShapeCastResult>,
+);
+
+/// The maximum number of times a cast against a signed distance field advances the shape.
+const MAX_SDF_CAST_STEPS: u32 = 64;
+
+/// The convex shapes that are built in.
+const CONVEX_SUB_TYPES: [ShapeSubType; 6] = [
+    ShapeSubType::Sphere,
+    ShapeSubType::Box,
+    ShapeSubType::Capsule,
+    ShapeSubType::TaperedCapsule,
+    ShapeSubType::Cylinder,
+    ShapeSubType::ConvexHull,
+];
+
+/// The shapes that are collided by colliding their children.
+const DECOMPOSED_SUB_TYPES: [ShapeSubType; 5] = [
+    ShapeSubType::StaticCompound,
+    ShapeSubType::MutableCompound,
+    ShapeSubType::Scaled,
+    ShapeSubType::RotatedTranslated,
+    ShapeSubType::OffsetCenterOfMass,
+];
+
+/// The shapes that can only be collided against convex shapes.
+const CONCAVE_SUB_TYPES: [ShapeSubType; 3] = [
+    ShapeSubType::Mesh,
+    ShapeSubType::HeightField,
+    ShapeSubType::Sdf,
+];
+
+/// Routes collision queries between two shapes to a function that is specialized for their
+/// sub types.
+///
+/// The functions are registered per ordered pair of sub types. When a pair has no function
+/// but the reversed pair does, the shapes are passed in the opposite order and the results
+/// are reversed, so every pair only needs to be registered once. Pairs without a function in
+/// either order, such as two meshes, report no contacts.
+///
+/// Compound and decorated shapes are collided by colliding their children, which requires the
+/// sub types of the children to be known. Shapes defined by the application are made known
+/// with [`CollisionDispatch::register_sub_type`] or, for convex shapes,
+/// [`CollisionDispatch::register_convex_sub_type`].
+#[derive(Clone, Debug)]
+pub struct CollisionDispatch {
+    collide_shape: BTreeMap<(ShapeSubType, ShapeSubType), CollideShapeFn>,
+    cast_shape: BTreeMap<(ShapeSubType, ShapeSubType), CastShapeFn>,
+    sub_types: Vec<ShapeSubType>,
+    convex_sub_types: Vec<ShapeSubType>,
+}
+
+impl Default for CollisionDispatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CollisionDispatch {
+    /// Creates a dispatch table with the functions for all built in shapes.
+    pub fn new() -> Self {
+        let mut dispatch = Self {
+            collide_shape: BTreeMap::new(),
+            cast_shape: BTreeMap::new(),
+            sub_types: Vec::new(),
+            convex_sub_types: Vec::new(),
+        };
+        for sub_type in DECOMPOSED_SUB_TYPES.into_iter().chain(CONCAVE_SUB_TYPES) {
+            dispatch.register_sub_type(sub_type);
+        }
+        for sub_type in CONVEX_SUB_TYPES {
+            dispatch.register_convex_sub_type(sub_type);
+        }
+        dispatch.register_collide_shape(
+            ShapeSubType::Sphere,
+            ShapeSubType::Sphere,
+            collide_sphere_vs_sphere,
+        );
+        dispatch
+    }
+
+    /// Registers `collide` as the function that collides shapes of type `sub_type1` against
+    /// shapes of type `sub_type2`, replacing any previous function.
+    #[inline]
+    pub fn register_collide_shape(
+        &mut self,
+        sub_type1: ShapeSubType,
+        sub_type2: ShapeSubType,
+        collide: CollideShapeFn,
+    ) {
+        self.collide_shape.insert((sub_type1, sub_type2), collide);
+    }
+
+    /// Registers `cast` as the function that casts shapes of type `sub_type1` against shapes
+    /// of type `sub_type2`, replacing any previous function.
+    #[inline]
+    pub fn register_cast_shape(
+        &mut self,
+        sub_type1: ShapeSubType,
+        sub_type2: ShapeSubType,
+        cast: CastShapeFn,
+    ) {
+        self.cast_shape.insert((sub_type1, sub_type2), cast);
+    }
+
+    /// Makes `sub_type` known, so that compound and decorated shapes can be collided against
+    /// it by colliding their children.
+    pub fn register_sub_type(&mut self, sub_type: ShapeSubType) {
+        if self.sub_types.contains(&sub_type) {
+            return;
+        }
+        self.sub_types.push(sub_type);
+        for decomposed in DECOMPOSED_SUB_TYPES {
+            self.register_collide_shape(decomposed, sub_type, collide_decomposed_vs_shape);
+            self.register_cast_shape(decomposed, sub_type, cast_decomposed_vs_shape);
+        }
+        if DECOMPOSED_SUB_TYPES.contains(&sub_type) {
+            for i in 0..self.sub_types.len() {
+                let other = self.sub_types[i];
+                self.register_collide_shape(sub_type, other, collide_decomposed_vs_shape);
+                self.register_cast_shape(sub_type, other, cast_decomposed_vs_shape);
+            }
+        }
+    }
+
+    /// Registers `sub_type` as a convex shape, the shapes must return themselves from
+    /// [`Shape::as_convex`](crate::shape::Shape::as_convex). This registers the built in
+    /// functions against all other convex shapes, meshes, height fields and signed distance
+    /// fields.
+    pub fn register_convex_sub_type(&mut self, sub_type: ShapeSubType) {
+        self.register_sub_type(sub_type);
+        if self.convex_sub_types.contains(&sub_type) {
+            return;
+        }
+        self.convex_sub_types.push(sub_type);
+        for i in 0..self.convex_sub_types.len() {
+            let other = self.convex_sub_types[i];
+            self.register_collide_shape(sub_type, other, collide_convex_vs_convex);
+            self.register_collide_shape(other, sub_type, collide_convex_vs_convex);
+            self.register_cast_shape(sub_type, other, cast_convex_vs_convex);
+            self.register_cast_shape(other, sub_type, cast_convex_vs_convex);
+        }
+        self.register_collide_shape(sub_type, ShapeSubType::Mesh, collide_convex_vs_triangles);
+        self.register_collide_shape(
+            sub_type,
+            ShapeSubType::HeightField,
+            collide_convex_vs_triangles,
+        );
+        self.register_collide_shape(sub_type, ShapeSubType::Sdf, collide_convex_vs_sdf);
+        self.register_cast_shape(sub_type, ShapeSubType::Mesh, cast_convex_vs_triangles);
+        self.register_cast_shape(
+            sub_type,
+            ShapeSubType::HeightField,
+            cast_convex_vs_triangles,
+        );
+        self.register_cast_shape(sub_type, ShapeSubType::Sdf, cast_convex_vs_sdf);
+    }
+
+    /// Collides `shape1` against `shape2` and adds the contacts to `collector`, nothing is
+    /// collided when `body_filter` rejects the body of `shape2`.
+    pub fn collide_shape(
+        &self,
+        shape1: &TransformedShape,
+        shape2: &TransformedShape,
+        settings: &CollideShapeSettings,
+        collector: &mut dyn CollisionCollector<CollideShapeResult>,
+        body_filter: &dyn BodyFilter,
+    ) {
+        if !body_filter.should_collide(shape2.body_id) {
+            return;
+        }
+        let (sub_type1, sub_type2) = (shape1.shape.sub_type(), shape2.shape.sub_type());
+        if let Some(collide) = self.collide_shape.get(&(sub_type1, sub_type2)) {
+            collide(self, shape1, shape2, settings, collector);
+        } else if let Some(collide) = self.collide_shape.get(&(sub_type2, sub_type1)) {
+            collide(
+                self,
+                shape2,
+                shape1,
+                settings,
+                &mut ReversedCollideShapeCollector(collector),
+            );
+        }
+    }
+
+    /// Casts `cast` against `target` and adds the hits to `collector`, nothing is cast when
+    /// `body_filter` rejects the body of `target`.
+    pub fn cast_shape(
+        &self,
+        cast: &ShapeCast,
+        target: &TransformedShape,
+        settings: &ShapeCastSettings,
+        collector: &mut dyn CollisionCollector<ShapeCastResult>,
+        body_filter: &dyn BodyFilter,
+    ) {
+        if !body_filter.should_collide(target.body_id) {
+            return;
+        }
+        let (sub_type1, sub_type2) = (cast.shape.shape.sub_type(), target.shape.sub_type());
+        if let Some(cast_shape) = self.cast_shape.get(&(sub_type1, sub_type2)) {
+            cast_shape(self, cast, target, settings, collector);
+        } else if let Some(cast_shape) = self.cast_shape.get(&(sub_type2, sub_type1)) {
+            // Moving the target the other way results in the same time of impact.
+            let reversed = ShapeCast::new(*target, -cast.direction);
+            let mut collector = ReversedShapeCastCollector {
+                collector,
+                direction: reversed.direction,
+            };
+            cast_shape(self, &reversed, &cast.shape, settings, &mut collector);
+        }
+    }
+}
+
+/// Reverses the contacts before passing them on, reversing does not change the early out
+/// fraction.
+struct ReversedCollideShapeCollector<'a>(&'a mut dyn CollisionCollector<CollideShapeResult>);
+
+impl CollisionCollector<CollideShapeResult> for ReversedCollideShapeCollector<'_> {
+    #[inline]
+    fn add_hit(&mut self, hit: &CollideShapeResult) {
+        self.0.add_hit(&hit.reversed());
+    }
+
+    #[inline]
+    fn early_out_fraction(&self) -> f32 {
+        self.0.early_out_fraction()
+    }
+}
+
+/// Reverses the hits of a cast in `direction` before passing them on.
+struct ReversedShapeCastCollector<'a> {
+    collector: &'a mut dyn CollisionCollector<ShapeCastResult>,
+    direction: Vec3,
+}
+
+impl CollisionCollector<ShapeCastResult> for ReversedShapeCastCollector<'_> {
+    #[inline]
+    fn add_hit(&mut self, hit: &ShapeCastResult) {
+        self.collector.add_hit(&hit.reversed(self.direction));
+    }
+
+    #[inline]
+    fn early_out_fraction(&self) -> f32 {
+        self.collector.early_out_fraction()
+    }
+}
+
+/// Calls `visit` for every child of a compound or decorated shape that may overlap the world
+/// space `bounds`.
+fn visit_children<'a>(
+    shape: &TransformedShape<'a>,
+    bounds: &AABox,
+    visit: &mut dyn FnMut(&TransformedShape<'a>),
+) {
+    let inner = shape.shape;
+    if let Some(scaled) = inner.downcast_ref::<ScaledShape>() {
+        let mut child = *shape;
+        child.shape = &**scaled.inner_shape();
+        child.scale = shape.scale * scaled.scale();
+        visit(&child);
+    } else if let Some(rotated) = inner.downcast_ref::<RotatedTranslatedShape>() {
+        visit(&shape.child(&**rotated.inner_shape(), Vec3::ZERO, rotated.rotation()));
+    } else if let Some(offset) = inner.downcast_ref::<OffsetCenterOfMassShape>() {
+        visit(&shape.child(&**offset.inner_shape(), -offset.offset(), Quat::IDENTITY));
+    } else {
+        let local_bounds = shape.to_local_bounds(bounds);
+        let mut visit_sub_shape = |sub_shapes: &'a [CompoundSubShape], index: u32| {
+            let sub_shape = &sub_shapes[index as usize];
+            let mut child = shape.child(&*sub_shape.shape, sub_shape.position, sub_shape.rotation);
+            child.sub_shape_id_creator = shape
+                .sub_shape_id_creator
+                .push_id(index, sub_shape_id_bits_for(sub_shapes.len() as u32));
+            visit(&child);
+        };
+        if let Some(compound) = inner.downcast_ref::<StaticCompoundShape>() {
+            compound.collide_sub_shapes(&local_bounds, |index| {
+                visit_sub_shape(compound.sub_shapes(), index)
+            });
+        } else if let Some(compound) = inner.downcast_ref::<MutableCompoundShape>() {
+            compound.collide_sub_shapes(&local_bounds, |index| {
+                visit_sub_shape(compound.sub_shapes(), index)
+            });
+        }
+    }
+}
+
+fn collide_decomposed_vs_shape(
+    dispatch: &CollisionDispatch,
+    shape1: &TransformedShape,
+    shape2: &TransformedShape,
+    settings: &CollideShapeSettings,
+    collector: &mut dyn CollisionCollector<CollideShapeResult>,
+) {
+    let bounds = shape2
+        .world_bounds()
+        .expanded_by(Vec3::splat(settings.max_separation_distance));
+    visit_children(shape1, &bounds, &mut |child| {
+        if !collector.should_early_out() {
+            dispatch.collide_shape(child, shape2, settings, collector, &());
+        }
+    });
+}
+
+fn cast_decomposed_vs_shape(
+    dispatch: &CollisionDispatch,
+    cast: &ShapeCast,
+    target: &TransformedShape,
+    settings: &ShapeCastSettings,
+    collector: &mut dyn CollisionCollector<ShapeCastResult>,
+) {
+    // A child hits the target when its start overlaps the target swept in reverse.
+    let mut bounds = target.world_bounds();
+    bounds.encapsulate_box(&bounds.translated(-cast.direction));
+    visit_children(&cast.shape, &bounds, &mut |child| {
+        if !collector.should_early_out() {
+            dispatch.cast_shape(&cast.with_shape(*child), target, settings, collector, &());
+        }
+    });
+}
+
+/// Fills the faces of `result` when requested by `settings`.
+fn collect_faces(
+    shape1: &TransformedShape,
+    shape2: &TransformedShape,
+    settings: &CollideShapeSettings,
+    result: &mut CollideShapeResult,
+) {
+    if settings.collect_faces {
+        shape1.get_supporting_face(result.penetration_axis, &mut result.face1);
+        shape2.get_supporting_face(-result.penetration_axis, &mut result.face2);
+    }
+}
+
+fn collide_sphere_vs_sphere(
+    _dispatch: &CollisionDispatch,
+    shape1: &TransformedShape,
+    shape2: &TransformedShape,
+    settings: &CollideShapeSettings,
+    collector: &mut dyn CollisionCollector<CollideShapeResult>,
+) {
+    let (Some(sphere1), Some(sphere2)) = (
+        shape1.shape.downcast_ref::<SphereShape>(),
+        shape2.shape.downcast_ref::<SphereShape>(),
+    ) else {
+        return;
+    };
+    let radius1 = sphere1.radius() * shape1.scale.x.abs();
+    let radius2 = sphere2.radius() * shape2.scale.x.abs();
+    let delta = shape2.position - shape1.position;
+    let distance_squared = delta.length_squared();
+    let max_distance = radius1 + radius2 + settings.max_separation_distance;
+    if distance_squared > max_distance * max_distance {
+        return;
+    }
+
+    let distance = distance_squared.sqrt();
+    let penetration_axis = if distance > 0.0 {
+        delta / distance
+    } else {
+        Vec3::Y
+    };
+    collector.add_hit(&CollideShapeResult {
+        contact_point1: shape1.position + penetration_axis * radius1,
+        contact_point2: shape2.position - penetration_axis * radius2,
+        penetration_axis,
+        penetration_depth: radius1 + radius2 - distance,
+        sub_shape_id1: shape1.sub_shape_id(),
+        sub_shape_id2: shape2.sub_shape_id(),
+        ..Default::default()
+    });
+}
+
+fn collide_convex_vs_convex(
+    _dispatch: &CollisionDispatch,
+    shape1: &TransformedShape,
+    shape2: &TransformedShape,
+    settings: &CollideShapeSettings,
+    collector: &mut dyn CollisionCollector<CollideShapeResult>,
+) {
+    let (Some(convex1), Some(convex2)) = (shape1.shape.as_convex(), shape2.shape.as_convex())
+    else {
+        return;
+    };
+
+    // Work relative to the first shape to keep precision far away from the origin.
+    let origin = shape1.position;
+    let translation2 = shape2.position - origin;
+    let exclude1 = convex1.get_support_function(SupportMode::ExcludeConvexRadius, shape1.scale);
+    let include1 = convex1.get_support_function(SupportMode::IncludeConvexRadius, shape1.scale);
+    let exclude2 = convex2.get_support_function(SupportMode::ExcludeConvexRadius, shape2.scale);
+    let include2 = convex2.get_support_function(SupportMode::IncludeConvexRadius, shape2.scale);
+    let Some(depth) = EpaPenetrationDepth::new().penetration_depth(
+        &TransformedConvexObject::new(&*exclude1, shape1.rotation, Vec3::ZERO),
+        &TransformedConvexObject::new(&*exclude2, shape2.rotation, translation2),
+        &TransformedConvexObject::new(&*include1, shape1.rotation, Vec3::ZERO),
+        &TransformedConvexObject::new(&*include2, shape2.rotation, translation2),
+        settings.max_separation_distance,
+        settings.penetration_tolerance,
+    ) else {
+        return;
+    };
+
+    let mut result = CollideShapeResult {
+        contact_point1: origin + depth.point_a,
+        contact_point2: origin + depth.point_b,
+        penetration_axis: depth.penetration_axis,
+        penetration_depth: depth.penetration_depth,
+        sub_shape_id1: shape1.sub_shape_id(),
+        sub_shape_id2: shape2.sub_shape_id(),
+        ..Default::default()
+    };
+    collect_faces(shape1, shape2, settings, &mut result);
+    collector.add_hit(&result);
+}
+
+/// Returns the active edges of a triangle of a mesh or height field, in the order in which its
+/// vertices are passed to the collision functions. The edges of height fields are all active.
+fn triangle_active_edges(shape: &TransformedShape, sub_shape_id: SubShapeID, mirrored: bool) -> u8 {
+    let Some(mesh) = shape.shape.downcast_ref::<MeshShape>() else {
+        return 0b111;
+    };
+    let active_edges = mesh.active_edges(sub_shape_id);
+    if mirrored {
+        // Swapping the last two vertices swaps the first and last edge.
+        (active_edges & 0b010) | ((active_edges & 0b001) << 2) | ((active_edges & 0b100) >> 2)
+    } else {
+        active_edges
+    }
+}
+
+/// Collides a convex shape against the triangles of a mesh or height field.
+fn collide_convex_vs_triangles(
+    _dispatch: &CollisionDispatch,
+    shape1: &TransformedShape,
+    shape2: &TransformedShape,
+    settings: &CollideShapeSettings,
+    collector: &mut dyn CollisionCollector<CollideShapeResult>,
+) {
+    let Some(convex1) = shape1.shape.as_convex() else {
+        return;
+    };
+    let bounds = shape2.to_local_bounds(
+        &shape1
+            .world_bounds()
+            .expanded_by(Vec3::splat(settings.max_separation_distance)),
+    );
+
+    let origin = shape1.position;
+    let exclude1 = convex1.get_support_function(SupportMode::ExcludeConvexRadius, shape1.scale);
+    let include1 = convex1.get_support_function(SupportMode::IncludeConvexRadius, shape1.scale);
+    let exclude1 = TransformedConvexObject::new(&*exclude1, shape1.rotation, Vec3::ZERO);
+    let include1 = TransformedConvexObject::new(&*include1, shape1.rotation, Vec3::ZERO);
+    // Mirroring the shape turns the triangles inside out.
+    let mirrored = shape2.scale.x * shape2.scale.y * shape2.scale.z < 0.0;
+    let mut epa = EpaPenetrationDepth::new();
+
+    let mut collide_triangle = |sub_shape_id: SubShapeID, triangle: [Vec3; 3]| {
+        if collector.should_early_out() {
+            return;
+        }
+        let [v0, mut v1, mut v2] = triangle.map(|vertex| shape2.to_world(vertex) - origin);
+        if mirrored {
+            core::mem::swap(&mut v1, &mut v2);
+        }
+        let triangle = TriangleConvexSupport::new(v0, v1, v2);
+        let Some(depth) = epa.penetration_depth(
+            &exclude1,
+            &triangle,
+            &include1,
+            &triangle,
+            settings.max_separation_distance,
+            settings.penetration_tolerance,
+        ) else {
+            return;
+        };
+
+        let mut penetration_axis = depth.penetration_axis;
+        if settings.active_edge_mode == ActiveEdgeMode::CollideOnlyWithActive {
+            penetration_axis = fix_normal(
+                [v0, v1, v2],
+                (v2 - v0).cross(v1 - v0),
+                triangle_active_edges(shape2, sub_shape_id, mirrored),
+                depth.point_b,
+                penetration_axis,
+                settings.active_edge_movement_direction,
+            )
+            .normalize();
+        }
+        let mut result = CollideShapeResult {
+            contact_point1: origin + depth.point_a,
+            contact_point2: origin + depth.point_b,
+            penetration_axis,
+            penetration_depth: depth.penetration_depth,
+            sub_shape_id1: shape1.sub_shape_id(),
+            sub_shape_id2: shape2.sub_shape_id_creator.push_sub_shape_id(sub_shape_id),
+            ..Default::default()
+        };
+        if settings.collect_faces {
+            shape1.get_supporting_face(result.penetration_axis, &mut result.face1);
+            result
+                .face2
+                .extend_from_slice(&[v0 + origin, v1 + origin, v2 + origin]);
+        }
+        collector.add_hit(&result);
+    };
+
+    if let Some(mesh) = shape2.shape.downcast_ref::<MeshShape>() {
+        mesh.collide_triangles(&bounds, &mut collide_triangle);
+    } else if let Some(height_field) = shape2.shape.downcast_ref::<HeightFieldShape>() {
+        height_field.collide_triangles(&bounds, &mut collide_triangle);
+    }
+}
+
+/// Collides a convex shape against a signed distance field, which only supports uniform
+/// scales.
+fn collide_convex_vs_sdf(
+    _dispatch: &CollisionDispatch,
+    shape1: &TransformedShape,
+    shape2: &TransformedShape,
+    settings: &CollideShapeSettings,
+    collector: &mut dyn CollisionCollector<CollideShapeResult>,
+) {
+    let (Some(convex1), Some(sdf)) = (
+        shape1.shape.as_convex(),
+        shape2.shape.downcast_ref::<SdfShape>(),
+    ) else {
+        return;
+    };
+
+    // Place the convex shape in the space of the field.
+    let scale = shape2.scale.x.abs();
+    let inverse_rotation = shape2.rotation.conjugate();
+    let rotation = inverse_rotation * shape1.rotation;
+    let translation = inverse_rotation * (shape1.position - shape2.position) / scale;
+    let scale1 = shape1.scale / scale;
+    let support = convex1.get_support_function(SupportMode::IncludeConvexRadius, scale1);
+    let support = TransformedConvexObject::new(&*support, rotation, translation);
+    let bounds = convex1
+        .local_bounds()
+        .scaled(scale1)
+        .transformed(rotation, translation);
+
+    let max_separation_distance = settings.max_separation_distance / scale;
+    sdf.collide_support(&support, &bounds, max_separation_distance, &mut |contact| {
+        let to_world = |point: Vec3| shape2.position + shape2.rotation * (point * scale);
+        let mut result = CollideShapeResult {
+            contact_point1: to_world(contact.point_a),
+            contact_point2: to_world(contact.point_b),
+            penetration_axis: -(shape2.rotation * contact.normal),
+            penetration_depth: -contact.distance * scale,
+            sub_shape_id1: shape1.sub_shape_id(),
+            sub_shape_id2: shape2.sub_shape_id(),
+            ..Default::default()
+        };
+        if settings.collect_faces {
+            shape1.get_supporting_face(result.penetration_axis, &mut result.face1);
+        }
+        collector.add_hit(&result);
+    });
+}
+
+/// Returns the fraction that a cast needs to beat to be of interest to `collector`. Hits at
+/// the start of the cast are always of interest, the collector orders them by penetration.
+#[inline]
+fn max_cast_fraction(collector: &dyn CollisionCollector<ShapeCastResult>) -> f32 {
+    collector.early_out_fraction().max(f32::MIN_POSITIVE)
+}
+
+/// Casts `a` along `direction` against `b`, both are given without their convex radius and
+/// relative to the start of the cast. Shapes that start in collision hit at fraction zero,
+/// their deepest points are found with EPA when requested by `settings`.
+fn cast_convex(
+    a: &dyn Support,
+    b: &dyn Support,
+    direction: Vec3,
+    settings: &ShapeCastSettings,
+    max_fraction: f32,
+) -> Option<(f32, PenetrationDepth)> {
+    let tolerance = settings.collision_tolerance;
+    let cast = GjkClosestPoint::new().cast_shape(a, b, direction, tolerance, max_fraction)?;
+
+    // Without a separating axis the shapes overlap by more than their convex radius.
+    let radius = a.convex_radius() + b.convex_radius();
+    let invalid_axis = cast.separating_axis.length_squared() <= tolerance * tolerance;
+    if settings.return_deepest_point && cast.fraction == 0.0 && (radius == 0.0 || invalid_axis) {
+        let depth = EpaPenetrationDepth::new().penetration_depth(
+            a,
+            b,
+            &AddConvexRadius::new(a, a.convex_radius()),
+            &AddConvexRadius::new(b, b.convex_radius()),
+            tolerance,
+            settings.penetration_tolerance,
+        )?;
+        return Some((0.0, depth));
+    }
+
+    let penetration_axis = if invalid_axis {
+        direction.normalize_or_zero()
+    } else {
+        cast.separating_axis.normalize()
+    };
+    let penetration_depth = (cast.point_a - cast.point_b).dot(penetration_axis);
+    Some((
+        cast.fraction,
+        PenetrationDepth {
+            point_a: cast.point_a,
+            point_b: cast.point_b,
+            penetration_axis,
+            penetration_depth,
+        },
+    ))
+}
+
+/// Builds the hit of `cast` against `target`, `depth` is relative to the start of the cast.
+fn shape_cast_result(
+    cast: &ShapeCast,
+    target: &TransformedShape,
+    settings: &ShapeCastSettings,
+    fraction: f32,
+    depth: &PenetrationDepth,
+) -> ShapeCastResult {
+    let origin = cast.shape.position;
+    let mut contact = CollideShapeResult {
+        contact_point1: origin + depth.point_a,
+        contact_point2: origin + depth.point_b,
+        penetration_axis: depth.penetration_axis,
+        penetration_depth: depth.penetration_depth,
+        sub_shape_id1: cast.shape.sub_shape_id(),
+        sub_shape_id2: target.sub_shape_id(),
+        ..Default::default()
+    };
+    if settings.collect_faces {
+        let mut shape1 = cast.shape;
+        shape1.position += cast.direction * fraction;
+        shape1.get_supporting_face(contact.penetration_axis, &mut contact.face1);
+        target.get_supporting_face(-contact.penetration_axis, &mut contact.face2);
+    }
+    ShapeCastResult { fraction, contact }
+}
+
+fn cast_convex_vs_convex(
+    _dispatch: &CollisionDispatch,
+    cast: &ShapeCast,
+    target: &TransformedShape,
+    settings: &ShapeCastSettings,
+    collector: &mut dyn CollisionCollector<ShapeCastResult>,
+) {
+    let shape1 = &cast.shape;
+    let (Some(convex1), Some(convex2)) = (shape1.shape.as_convex(), target.shape.as_convex())
+    else {
+        return;
+    };
+
+    let translation2 = target.position - shape1.position;
+    let support1 = convex1.get_support_function(SupportMode::ExcludeConvexRadius, shape1.scale);
+    let support2 = convex2.get_support_function(SupportMode::ExcludeConvexRadius, target.scale);
+    let Some((fraction, depth)) = cast_convex(
+        &TransformedConvexObject::new(&*support1, shape1.rotation, Vec3::ZERO),
+        &TransformedConvexObject::new(&*support2, target.rotation, translation2),
+        cast.direction,
+        settings,
+        max_cast_fraction(collector),
+    ) else {
+        return;
+    };
+
+    // Shapes that start in collision and separate along the cast are hit from the inside.
+    if settings.back_face_mode_convex == BackFaceMode::IgnoreBackFaces
+        && depth.penetration_axis.dot(cast.direction) <= 0.0
+    {
+        return;
+    }
+    collector.add_hit(&shape_cast_result(cast, target, settings, fraction, &depth));
+}
+
+/// Casts a convex shape against the triangles of a mesh or height field.
+fn cast_convex_vs_triangles(
+    _dispatch: &CollisionDispatch,
+    cast: &ShapeCast,
+    target: &TransformedShape,
+    settings: &ShapeCastSettings,
+    collector: &mut dyn CollisionCollector<ShapeCastResult>,
+) {
+    let shape1 = &cast.shape;
+    let Some(convex1) = shape1.shape.as_convex() else {
+        return;
+    };
+    let bounds = target.to_local_bounds(&cast.swept_bounds());
+
+    let origin = shape1.position;
+    let support1 = convex1.get_support_function(SupportMode::ExcludeConvexRadius, shape1.scale);
+    let support1 = TransformedConvexObject::new(&*support1, shape1.rotation, Vec3::ZERO);
+    // Mirroring the shape turns the triangles inside out.
+    let mirrored = target.scale.x * target.scale.y * target.scale.z < 0.0;
+
+    let mut cast_triangle = |sub_shape_id: SubShapeID, triangle: [Vec3; 3]| {
+        if collector.should_early_out() {
+            return;
+        }
+        let [v0, mut v1, mut v2] = triangle.map(|vertex| target.to_world(vertex) - origin);
+        if mirrored {
+            core::mem::swap(&mut v1, &mut v2);
+        }
+        if settings.back_face_mode_triangles == BackFaceMode::IgnoreBackFaces
+            && (v1 - v0).cross(v2 - v0).dot(cast.direction) > 0.0
+        {
+            return;
+        }
+
+        let Some((fraction, mut depth)) = cast_convex(
+            &support1,
+            &TriangleConvexSupport::new(v0, v1, v2),
+            cast.direction,
+            settings,
+            max_cast_fraction(collector),
+        ) else {
+            return;
+        };
+        if settings.active_edge_mode == ActiveEdgeMode::CollideOnlyWithActive {
+            depth.penetration_axis = fix_normal(
+                [v0, v1, v2],
+                (v2 - v0).cross(v1 - v0),
+                triangle_active_edges(target, sub_shape_id, mirrored),
+                depth.point_b,
+                depth.penetration_axis,
+                cast.direction,
+            )
+            .normalize();
+        }
+        let mut result = shape_cast_result(cast, target, settings, fraction, &depth);
+        result.contact.sub_shape_id2 = target.sub_shape_id_creator.push_sub_shape_id(sub_shape_id);
+        if settings.collect_faces {
+            result
+                .contact
+                .face2
+                .extend_from_slice(&[v0 + origin, v1 + origin, v2 + origin]);
+        }
+        collector.add_hit(&result);
+    };
+
+    if let Some(mesh) = target.shape.downcast_ref::<MeshShape>() {
+        mesh.collide_triangles(&bounds, &mut cast_triangle);
+    } else if let Some(height_field) = target.shape.downcast_ref::<HeightFieldShape>() {
+        height_field.collide_triangles(&bounds, &mut cast_triangle);
+    }
+}
+
+/// Casts a convex shape against a signed distance field by conservative advancement: the
+/// shape is repeatedly moved along the cast by the distance of its deepest point to the
+/// surface, which cannot skip over the surface, until it touches.
+fn cast_convex_vs_sdf(
+    _dispatch: &CollisionDispatch,
+    cast: &ShapeCast,
+    target: &TransformedShape,
+    settings: &ShapeCastSettings,
+    collector: &mut dyn CollisionCollector<ShapeCastResult>,
+) {
+    let shape1 = &cast.shape;
+    let (Some(convex1), Some(sdf)) = (
+        shape1.shape.as_convex(),
+        target.shape.downcast_ref::<SdfShape>(),
+    ) else {
+        return;
+    };
+
+    // Cast the convex shape in the space of the field.
+    let scale = target.scale.x.abs();
+    let inverse_rotation = target.rotation.conjugate();
+    let rotation = inverse_rotation * shape1.rotation;
+    let start = inverse_rotation * (shape1.position - target.position) / scale;
+    let direction = inverse_rotation * cast.direction / scale;
+    let scale1 = shape1.scale / scale;
+    let support = convex1.get_support_function(SupportMode::IncludeConvexRadius, scale1);
+    let bounds = convex1.local_bounds().scaled(scale1);
+    let length = direction.length();
+    let tolerance = settings.collision_tolerance / scale;
+    let max_fraction = max_cast_fraction(collector);
+
+    let mut fraction = 0.0;
+    for _ in 0..MAX_SDF_CAST_STEPS {
+        let translation = start + direction * fraction;
+        let support = TransformedConvexObject::new(&*support, rotation, translation);
+        let bounds = bounds.transformed(rotation, translation);
+        let reach = length * (max_fraction - fraction) + tolerance;
+        let mut deepest: Option<SdfContact> = None;
+        sdf.collide_support(&support, &bounds, reach, &mut |contact| {
+            if deepest.is_none_or(|deepest| contact.distance < deepest.distance) {
+                deepest = Some(*contact);
+            }
+        });
+        let Some(contact) = deepest else {
+            return;
+        };
+
+        if contact.distance <= tolerance {
+            let to_world = |point: Vec3| target.position + target.rotation * (point * scale);
+            let mut result = ShapeCastResult {
+                fraction,
+                contact: CollideShapeResult {
+                    contact_point1: to_world(contact.point_a),
+                    contact_point2: to_world(contact.point_b),
+                    penetration_axis: -(target.rotation * contact.normal),
+                    penetration_depth: -contact.distance * scale,
+                    sub_shape_id1: shape1.sub_shape_id(),
+                    sub_shape_id2: target.sub_shape_id(),
+                    ..Default::default()
+                },
+            };
+            if settings.collect_faces {
+                let mut shape1 = *shape1;
+                shape1.position += cast.direction * fraction;
+                shape1.get_supporting_face(
+                    result.contact.penetration_axis,
+                    &mut result.contact.face1,
+                );
+            }
+            collector.add_hit(&result);
+            return;
+        }
+
+        fraction += contact.distance / length;
+        if fraction > max_fraction {
+            return;
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use alloc::vec::Vec;
+
+use crate::{
+    collision::{manifold_between_two_faces, prune_contact_points},
+    math::Vec3,
+    shape::SubShapeID,
+};
+
+/// A contact point of a [`ContactManifold`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ContactPoint {
+    /// The point on the first shape, relative to the base offset of the manifold.
+    pub position1: Vec3,
+    /// The point on the second shape, relative to the base offset of the manifold.
+    pub position2: Vec3,
+    /// The distance the shapes overlap at this point along the normal, negative when they are
+    /// separated.
+    pub penetration_depth: f32,
+}
+
+/// The contact points between two shapes, which share a single normal.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ContactManifold {
+    /// The world space position that the contact points are relative to, usually the center of
+    /// mass of the first body. This keeps the points precise far away from the origin.
+    pub base_offset: Vec3,
+    /// The world space contact normal, pointing from the first shape into the second.
+    pub normal: Vec3,
+    pub sub_shape_id1: SubShapeID,
+    pub sub_shape_id2: SubShapeID,
+    pub points: Vec<ContactPoint>,
+}
+
+impl ContactManifold {
+    #[inline]
+    pub fn new(base_offset: Vec3, normal: Vec3) -> Self {
+        Self {
+            base_offset,
+            normal,
+            ..Default::default()
+        }
+    }
+
+    /// Builds a manifold from a contact found by GJK or EPA and the supporting faces of the
+    /// shapes, see [`manifold_between_two_faces`]. All inputs are in world space. The manifold
+    /// is pruned to at most [`MAX_CONTACT_POINTS`](super::MAX_CONTACT_POINTS) points.
+    pub fn from_faces(
+        base_offset: Vec3,
+        contact_point1: Vec3,
+        contact_point2: Vec3,
+        penetration_axis: Vec3,
+        max_contact_distance: f32,
+        face1: &[Vec3],
+        face2: &[Vec3],
+    ) -> Self {
+        let mut points = Vec::new();
+        manifold_between_two_faces(
+            contact_point1 - base_offset,
+            contact_point2 - base_offset,
+            penetration_axis,
+            max_contact_distance,
+            &face1.iter().map(|&v| v - base_offset).collect::<Vec<_>>(),
+            &face2.iter().map(|&v| v - base_offset).collect::<Vec<_>>(),
+            &mut points,
+        );
+        let normal = penetration_axis.normalize_or_zero();
+        prune_contact_points(normal, &mut points);
+
+        let mut manifold = Self::new(base_offset, normal);
+        manifold.points = points
+            .into_iter()
+            .map(|(position1, position2)| ContactPoint {
+                position1,
+                position2,
+                penetration_depth: (position1 - position2).dot(normal),
+            })
+            .collect();
+        manifold
+    }
+
+    /// Returns the deepest penetration of all points, or negative infinity when there are no
+    /// points.
+    #[inline]
+    pub fn penetration_depth(&self) -> f32 {
+        self.points
+            .iter()
+            .map(|point| point.penetration_depth)
+            .fold(f32::NEG_INFINITY, f32::max)
+    }
+
+    /// Returns the world space position of the point on the first shape of contact `index`.
+    #[inline]
+    pub fn world_position1(&self, index: usize) -> Vec3 {
+        self.base_offset + self.points[index].position1
+    }
+
+    /// Returns the world space position of the point on the second shape of contact `index`.
+    #[inline]
+    pub fn world_position2(&self, index: usize) -> Vec3 {
+        self.base_offset + self.points[index].position2
+    }
+}
@@ -0,0 +1,59 @@
+use alloc::sync::Arc;
+use core::fmt;
+
+/// Decides whether two bodies with the same group filter can collide, based on their
+/// [`CollisionGroup`]s.
+pub trait GroupFilter: fmt::Debug + Send + Sync {
+    fn can_collide(&self, group1: &CollisionGroup, group2: &CollisionGroup) -> bool;
+}
+
+/// The group of a body, used to stop bodies that are part of the same object from colliding,
+/// such as the limbs of a ragdoll.
+#[derive(Clone, Debug)]
+pub struct CollisionGroup {
+    /// The filter that decides whether bodies in this group collide, bodies without a filter
+    /// always collide.
+    pub group_filter: Option<Arc<dyn GroupFilter>>,
+    /// The object the body is part of.
+    pub group_id: u32,
+    /// The part of the object, such as a limb of a ragdoll.
+    pub sub_group_id: u32,
+}
+
+impl CollisionGroup {
+    /// The ID of a body that is not part of any group.
+    pub const INVALID_GROUP: u32 = u32::MAX;
+
+    /// The ID of a body that is not part of any sub group.
+    pub const INVALID_SUB_GROUP: u32 = u32::MAX;
+
+    #[inline]
+    pub fn new(group_filter: Arc<dyn GroupFilter>, group_id: u32, sub_group_id: u32) -> Self {
+        Self {
+            group_filter: Some(group_filter),
+            group_id,
+            sub_group_id,
+        }
+    }
+
+    /// Returns whether bodies in both groups can collide. The filter of this group is used, or
+    /// the filter of `other` if this group has none.
+    #[inline]
+    pub fn can_collide(&self, other: &Self) -> bool {
+        match self.group_filter.as_ref().or(other.group_filter.as_ref()) {
+            Some(filter) => filter.can_collide(self, other),
+            None => true,
+        }
+    }
+}
+
+impl Default for CollisionGroup {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            group_filter: None,
+            group_id: Self::INVALID_GROUP,
+            sub_group_id: Self::INVALID_SUB_GROUP,
+        }
+    }
+}
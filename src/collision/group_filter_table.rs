@@ -0,0 +1,80 @@
+use alloc::{vec, vec::Vec};
+
+use crate::collision::{CollisionGroup, GroupFilter};
+
+/// A [`GroupFilter`] that stores whether each pair of sub groups collides in a bit table.
+///
+/// Bodies in different groups always collide, bodies in the same sub group never collide.
+/// Bodies in different sub groups of the same group collide unless it has been disabled, for
+/// example between the connected limbs of a ragdoll.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GroupFilterTable {
+    sub_group_count: u32,
+    /// One bit for every pair of different sub groups.
+    bits: Vec<u32>,
+}
+
+impl GroupFilterTable {
+    /// Creates a table for the sub groups `0..sub_group_count`, all of which collide.
+    pub fn new(sub_group_count: u32) -> Self {
+        let pair_count = sub_group_count as usize * (sub_group_count as usize).saturating_sub(1);
+        Self {
+            sub_group_count,
+            bits: vec![u32::MAX; (pair_count / 2).div_ceil(32)],
+        }
+    }
+
+    #[inline]
+    pub fn sub_group_count(&self) -> u32 {
+        self.sub_group_count
+    }
+
+    /// Makes two different sub groups collide with each other.
+    pub fn enable_collision(&mut self, sub_group1: u32, sub_group2: u32) {
+        let bit = self.bit(sub_group1, sub_group2);
+        self.bits[bit / 32] |= 1 << (bit % 32);
+    }
+
+    /// Makes two different sub groups no longer collide with each other.
+    pub fn disable_collision(&mut self, sub_group1: u32, sub_group2: u32) {
+        let bit = self.bit(sub_group1, sub_group2);
+        self.bits[bit / 32] &= !(1 << (bit % 32));
+    }
+
+    /// Returns whether two sub groups of the same group collide.
+    #[inline]
+    pub fn is_collision_enabled(&self, sub_group1: u32, sub_group2: u32) -> bool {
+        if sub_group1 == sub_group2 {
+            return false;
+        }
+        let bit = self.bit(sub_group1, sub_group2);
+        self.bits[bit / 32] & (1 << (bit % 32)) != 0
+    }
+
+    /// Returns the index of the bit of a pair in the lower triangle of the table.
+    #[inline]
+    fn bit(&self, sub_group1: u32, sub_group2: u32) -> usize {
+        assert!(sub_group1 != sub_group2);
+        assert!(sub_group1 < self.sub_group_count && sub_group2 < self.sub_group_count);
+        let (low, high) = (
+            sub_group1.min(sub_group2) as usize,
+            sub_group1.max(sub_group2) as usize,
+        );
+        high * (high - 1) / 2 + low
+    }
+}
+
+impl GroupFilter for GroupFilterTable {
+    fn can_collide(&self, group1: &CollisionGroup, group2: &CollisionGroup) -> bool {
+        if group1.group_id != group2.group_id {
+            return true;
+        }
+        if group1.sub_group_id >= self.sub_group_count
+            || group2.sub_group_id >= self.sub_group_count
+        {
+            // Bodies without a known sub group are not filtered.
+            return true;
+        }
+        self.is_collision_enabled(group1.sub_group_id, group2.sub_group_id)
+    }
+}
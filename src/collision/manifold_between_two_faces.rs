@@ -0,0 +1,147 @@
+use alloc::vec::Vec;
+
+use crate::{
+    geometry::{clip_poly_vs_edge, clip_poly_vs_plane, clip_poly_vs_poly},
+    math::Vec3,
+};
+
+/// The maximum number of contact points that [`prune_contact_points`] keeps.
+pub const MAX_CONTACT_POINTS: usize = 4;
+
+/// Distances and penetration depths are clamped to this squared value when pruning, so that
+/// points at the center of mass or without penetration can still be selected.
+const MIN_DISTANCE_SQUARED: f32 = 1.0e-6;
+
+/// Builds the contact points between the supporting faces of two shapes.
+///
+/// `contact_point1` and `contact_point2` are the deepest points on the shapes as found by GJK or
+/// EPA and `penetration_axis` points from the first shape into the second, it does not need to
+/// be normalized. `face1` is the face of the first shape that is most aligned with
+/// `penetration_axis` and `face2` the face of the second shape that is most aligned with the
+/// opposite direction, both wound counter clockwise seen from outside of their shape.
+///
+/// The second face is clipped against the first and the clipped vertices are projected back onto
+/// the plane of the first face. Points that are separated by more than `max_contact_distance`
+/// are discarded. When no points remain, or either face has less than two vertices, the contact
+/// points themselves are used. Each contact is appended to `points` as the point on the first
+/// shape followed by the point on the second.
+pub fn manifold_between_two_faces(
+    contact_point1: Vec3,
+    contact_point2: Vec3,
+    penetration_axis: Vec3,
+    max_contact_distance: f32,
+    face1: &[Vec3],
+    face2: &[Vec3],
+    points: &mut Vec<(Vec3, Vec3)>,
+) {
+    let count = points.len();
+    if face1.len() >= 2 && face2.len() >= 2 {
+        let plane_origin = face1[0];
+        let first_edge = face1[1] - plane_origin;
+        let plane_normal = if face1.len() >= 3 {
+            first_edge.cross(face1[2] - face1[1])
+        } else {
+            // An edge, use the plane through it that is most aligned with the penetration axis.
+            first_edge.cross(penetration_axis).cross(first_edge)
+        };
+
+        // The projection is undefined when the penetration axis lies in the plane.
+        let axis_dot_normal = penetration_axis.dot(plane_normal);
+        if axis_dot_normal != 0.0 {
+            let mut clipped = Vec::new();
+            if face1.len() >= 3 {
+                clip_poly_vs_poly(face2, face1, penetration_axis, &mut clipped);
+            } else if face2.len() >= 3 {
+                clip_poly_vs_edge(face2, face1[0], face1[1], penetration_axis, &mut clipped);
+            } else {
+                // Two edges, limit the second edge to the extent of the first.
+                let mut partial = Vec::new();
+                clip_poly_vs_plane(face2, face1[0], first_edge, &mut partial);
+                clip_poly_vs_plane(&partial, face1[1], -first_edge, &mut clipped);
+            }
+
+            let axis_length = penetration_axis.length();
+            for point2 in clipped {
+                // The distance along the penetration axis to the plane of the first face, this is
+                // negative when the point lies behind it.
+                let distance = (point2 - plane_origin).dot(plane_normal) / axis_dot_normal;
+                if distance * axis_length < max_contact_distance {
+                    points.push((point2 - penetration_axis * distance, point2));
+                }
+            }
+        }
+    }
+
+    if points.len() == count {
+        points.push((contact_point1, contact_point2));
+    }
+}
+
+/// Reduces the contact points to at most [`MAX_CONTACT_POINTS`] that span the largest area.
+///
+/// The points are pairs of a point on the first and a point on the second shape, relative to the
+/// center of mass of the first body, and `penetration_axis` must be normalized. The first point
+/// kept is the one with the largest product of distance to
+/// the center of mass and penetration, as it has the most influence on the rotation of the body.
+/// The second point is the one furthest from the first and the last two are the furthest on
+/// either side of the line through the first two. The points that are kept are in order around
+/// the resulting polygon.
+pub fn prune_contact_points(penetration_axis: Vec3, points: &mut Vec<(Vec3, Vec3)>) {
+    if points.len() <= MAX_CONTACT_POINTS {
+        return;
+    }
+
+    // Project the points onto the plane through the center of mass.
+    let projected: Vec<Vec3> = points
+        .iter()
+        .map(|&(point, _)| point - penetration_axis * point.dot(penetration_axis))
+        .collect();
+    let penetration_squared: Vec<f32> = points
+        .iter()
+        .map(|&(point1, point2)| (point2 - point1).length_squared().max(MIN_DISTANCE_SQUARED))
+        .collect();
+    let best = |score: &dyn Fn(usize) -> f32| {
+        (0..projected.len())
+            .map(|i| (i, score(i)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| i)
+            .unwrap()
+    };
+
+    let first =
+        best(&|i| projected[i].length_squared().max(MIN_DISTANCE_SQUARED) * penetration_squared[i]);
+    let second = best(&|i| {
+        if i == first {
+            f32::MIN
+        } else {
+            (projected[i] - projected[first])
+                .length_squared()
+                .max(MIN_DISTANCE_SQUARED)
+                * penetration_squared[i]
+        }
+    });
+
+    // Find the points furthest away on both sides of the line through the first two points.
+    let perpendicular = (projected[second] - projected[first]).cross(penetration_axis);
+    let (mut third, mut fourth) = (None, None);
+    let (mut min, mut max) = (0.0, 0.0);
+    for (i, &point) in projected.iter().enumerate() {
+        if i == first || i == second {
+            continue;
+        }
+        let side = perpendicular.dot(point - projected[first]);
+        if side < min {
+            min = side;
+            third = Some(i);
+        } else if side > max {
+            max = side;
+            fourth = Some(i);
+        }
+    }
+
+    *points = [Some(first), third, Some(second), fourth]
+        .into_iter()
+        .flatten()
+        .map(|i| points[i])
+        .collect();
+}
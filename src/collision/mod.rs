@@ -0,0 +1,51 @@
+pub(crate) mod active_edge_mode;
+pub use active_edge_mode::ActiveEdgeMode;
+
+pub(crate) mod active_edges;
+pub use active_edges::{fix_normal, is_edge_active};
+
+pub(crate) mod back_face_mode;
+pub use back_face_mode::BackFaceMode;
+
+pub(crate) mod collide_shape;
+pub use collide_shape::{CollideShapeResult, CollideShapeSettings};
+
+pub(crate) mod collision_collector;
+pub use collision_collector::{
+    AllHitCollector, AnyHitCollector, ClosestHitCollector, ClosestHitPerBodyCollector,
+    CollidePointResult, CollisionCollector, CollisionResult,
+};
+
+pub(crate) mod collision_dispatch;
+pub use collision_dispatch::{CastShapeFn, CollideShapeFn, CollisionDispatch};
+
+pub(crate) mod contact_manifold;
+pub use contact_manifold::{ContactManifold, ContactPoint};
+
+pub(crate) mod group_filter;
+pub use group_filter::{CollisionGroup, GroupFilter};
+
+pub(crate) mod group_filter_table;
+pub use group_filter_table::GroupFilterTable;
+
+pub(crate) mod manifold_between_two_faces;
+pub use manifold_between_two_faces::{
+    manifold_between_two_faces, prune_contact_points, MAX_CONTACT_POINTS,
+};
+
+pub(crate) mod object_layer;
+pub use object_layer::{
+    DefaultObjectLayerFilter, ObjectLayer, ObjectLayerFilter, ObjectLayerPairFilter,
+};
+
+pub(crate) mod object_layer_pair_filter_table;
+pub use object_layer_pair_filter_table::ObjectLayerPairFilterTable;
+
+pub(crate) mod ray_cast;
+pub use ray_cast::{RayCast, RayCastResult};
+
+pub(crate) mod shape_cast;
+pub use shape_cast::{ShapeCast, ShapeCastResult, ShapeCastSettings};
+
+pub(crate) mod transformed_shape;
+pub use transformed_shape::TransformedShape;
@@ -0,0 +1,46 @@
+/// The layer of a body, which determines what it collides with.
+///
+/// Object layers are mapped onto a smaller number of [`BroadPhaseLayer`]s, see
+/// [`BroadPhaseLayerInterface`].
+///
+/// [`BroadPhaseLayer`]: crate::broad_phase::BroadPhaseLayer
+/// [`BroadPhaseLayerInterface`]: crate::broad_phase::BroadPhaseLayerInterface
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ObjectLayer(pub u16);
+
+/// Decides whether bodies in two object layers can collide.
+pub trait ObjectLayerPairFilter: Send + Sync {
+    fn should_collide(&self, layer1: ObjectLayer, layer2: ObjectLayer) -> bool;
+}
+
+/// Decides which object layers a query collides with, the unit type accepts all layers.
+pub trait ObjectLayerFilter {
+    #[inline]
+    fn should_collide(&self, _layer: ObjectLayer) -> bool {
+        true
+    }
+}
+
+impl ObjectLayerFilter for () {}
+
+/// Accepts the object layers that collide with `layer` according to an
+/// [`ObjectLayerPairFilter`].
+#[derive(Clone, Copy)]
+pub struct DefaultObjectLayerFilter<'a> {
+    pub filter: &'a dyn ObjectLayerPairFilter,
+    pub layer: ObjectLayer,
+}
+
+impl<'a> DefaultObjectLayerFilter<'a> {
+    #[inline]
+    pub fn new(filter: &'a dyn ObjectLayerPairFilter, layer: ObjectLayer) -> Self {
+        Self { filter, layer }
+    }
+}
+
+impl ObjectLayerFilter for DefaultObjectLayerFilter<'_> {
+    #[inline]
+    fn should_collide(&self, layer: ObjectLayer) -> bool {
+        self.filter.should_collide(self.layer, layer)
+    }
+}
@@ -0,0 +1,55 @@
+use alloc::{vec, vec::Vec};
+
+use crate::collision::{ObjectLayer, ObjectLayerPairFilter};
+
+/// An [`ObjectLayerPairFilter`] that stores whether each pair of layers collides in a bit
+/// table. No layers collide initially.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObjectLayerPairFilterTable {
+    layer_count: u16,
+    bits: Vec<u32>,
+}
+
+impl ObjectLayerPairFilterTable {
+    /// Creates a table for the layers `0..layer_count`.
+    pub fn new(layer_count: u16) -> Self {
+        let bit_count = layer_count as usize * layer_count as usize;
+        Self {
+            layer_count,
+            bits: vec![0; bit_count.div_ceil(32)],
+        }
+    }
+
+    #[inline]
+    pub fn layer_count(&self) -> u16 {
+        self.layer_count
+    }
+
+    /// Makes both layers collide with each other.
+    pub fn enable_collision(&mut self, layer1: ObjectLayer, layer2: ObjectLayer) {
+        for bit in [self.bit(layer1, layer2), self.bit(layer2, layer1)] {
+            self.bits[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+
+    /// Makes both layers no longer collide with each other.
+    pub fn disable_collision(&mut self, layer1: ObjectLayer, layer2: ObjectLayer) {
+        for bit in [self.bit(layer1, layer2), self.bit(layer2, layer1)] {
+            self.bits[bit / 32] &= !(1 << (bit % 32));
+        }
+    }
+
+    #[inline]
+    fn bit(&self, layer1: ObjectLayer, layer2: ObjectLayer) -> usize {
+        assert!(layer1.0 < self.layer_count && layer2.0 < self.layer_count);
+        layer1.0 as usize * self.layer_count as usize + layer2.0 as usize
+    }
+}
+
+impl ObjectLayerPairFilter for ObjectLayerPairFilterTable {
+    #[inline]
+    fn should_collide(&self, layer1: ObjectLayer, layer2: ObjectLayer) -> bool {
+        let bit = self.bit(layer1, layer2);
+        self.bits[bit / 32] & (1 << (bit % 32)) != 0
+    }
+}
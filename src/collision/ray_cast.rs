@@ -0,0 +1,67 @@
+use crate::{
+    math::{Quat, Vec3},
+    shape::SubShapeID,
+};
+
+/// A ray segment starting at `origin` and ending at `origin + direction`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RayCast {
+    pub origin: Vec3,
+    /// The direction and length of the ray.
+    pub direction: Vec3,
+}
+
+impl RayCast {
+    #[inline]
+    pub const fn new(origin: Vec3, direction: Vec3) -> Self {
+        Self { origin, direction }
+    }
+
+    /// Returns the point at `fraction` along the ray.
+    #[inline]
+    pub fn point_on_ray(&self, fraction: f32) -> Vec3 {
+        self.origin + self.direction * fraction
+    }
+
+    /// Returns the ray moved by `translation`.
+    #[inline]
+    pub fn translated(&self, translation: Vec3) -> Self {
+        Self::new(self.origin + translation, self.direction)
+    }
+
+    /// Returns the ray rotated by `rotation` and then moved by `translation`.
+    #[inline]
+    pub fn transformed(&self, rotation: Quat, translation: Vec3) -> Self {
+        Self::new(
+            rotation * self.origin + translation,
+            rotation * self.direction,
+        )
+    }
+}
+
+/// The closest hit of a ray cast.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayCastResult {
+    /// The hit fraction of the ray, a hit only counts when it is closer than the current value.
+    pub fraction: f32,
+    /// The sub shape that was hit.
+    pub sub_shape_id: SubShapeID,
+}
+
+impl RayCastResult {
+    /// Returns whether a hit has been recorded.
+    #[inline]
+    pub fn has_hit(&self) -> bool {
+        self.fraction <= 1.0
+    }
+}
+
+impl Default for RayCastResult {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            fraction: 1.0 + f32::EPSILON,
+            sub_shape_id: SubShapeID::EMPTY,
+        }
+    }
+}
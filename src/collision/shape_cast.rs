@@ -0,0 +1,99 @@
+use crate::{
+    collision::{ActiveEdgeMode, BackFaceMode, CollideShapeResult, TransformedShape},
+    geometry::AABox,
+    math::Vec3,
+};
+
+/// A shape that is swept from its position along `direction`.
+#[derive(Clone, Copy, Debug)]
+pub struct ShapeCast<'a> {
+    /// The shape at the start of the cast.
+    pub shape: TransformedShape<'a>,
+    /// The direction and length of the cast.
+    pub direction: Vec3,
+}
+
+impl<'a> ShapeCast<'a> {
+    #[inline]
+    pub fn new(shape: TransformedShape<'a>, direction: Vec3) -> Self {
+        Self { shape, direction }
+    }
+
+    /// Returns the world space bounds of the shape over the whole cast.
+    pub fn swept_bounds(&self) -> AABox {
+        let mut bounds = self.shape.world_bounds();
+        bounds.encapsulate_box(&bounds.translated(self.direction));
+        bounds
+    }
+
+    /// Returns the cast for the shape `shape`, which is a part of the cast shape.
+    #[inline]
+    pub(crate) fn with_shape(&self, shape: TransformedShape<'a>) -> Self {
+        Self::new(shape, self.direction)
+    }
+}
+
+/// Settings for casting a shape.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShapeCastSettings {
+    /// How triangles that are hit from the back are treated.
+    pub back_face_mode_triangles: BackFaceMode,
+    /// How convex shapes that are hit from the inside are treated. This happens when the cast
+    /// starts in collision and the shapes separate along the cast direction.
+    pub back_face_mode_convex: BackFaceMode,
+    /// Whether the deepest points should be found for shapes that start in collision. This
+    /// requires running EPA, otherwise the contact points are only approximate and the normal
+    /// may be the cast direction.
+    pub return_deepest_point: bool,
+    /// How hits on the inactive edges of triangles are treated.
+    pub active_edge_mode: ActiveEdgeMode,
+    /// Whether the supporting faces of the shapes at the time of impact should be collected.
+    pub collect_faces: bool,
+    /// The distance at which the shapes are considered to be touching.
+    pub collision_tolerance: f32,
+    /// The tolerance used to find the penetration depth of shapes that start in collision.
+    pub penetration_tolerance: f32,
+}
+
+impl Default for ShapeCastSettings {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            back_face_mode_triangles: BackFaceMode::IgnoreBackFaces,
+            back_face_mode_convex: BackFaceMode::IgnoreBackFaces,
+            return_deepest_point: false,
+            active_edge_mode: ActiveEdgeMode::CollideOnlyWithActive,
+            collect_faces: false,
+            collision_tolerance: 1.0e-4,
+            penetration_tolerance: 1.0e-4,
+        }
+    }
+}
+
+/// A hit of a shape cast, the contact is in world space with the cast shape moved to the time
+/// of impact.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ShapeCastResult {
+    /// The fraction of the cast direction at which the shapes first touch, zero when they
+    /// start in collision.
+    pub fraction: f32,
+    pub contact: CollideShapeResult,
+}
+
+impl ShapeCastResult {
+    /// Returns the result as if the target had been cast against the cast shape in the
+    /// opposite direction. `direction` is the direction of the cast that produced this result.
+    pub fn reversed(&self, direction: Vec3) -> Self {
+        let mut contact = self.contact.reversed();
+        let offset = -direction * self.fraction;
+        contact.contact_point1 += offset;
+        contact.contact_point2 += offset;
+        for vertex in contact.face1.iter_mut().chain(contact.face2.iter_mut()) {
+            *vertex += offset;
+        }
+        Self {
+            fraction: self.fraction,
+            contact,
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use alloc::vec::Vec;
+
+use crate::{
+    body::BodyID,
+    collision::{CollidePointResult, CollisionCollector, RayCast, RayCastResult},
+    geometry::AABox,
+    math::{Quat, Vec3},
+    shape::{scale_helpers, Shape, SubShapeID, SubShapeIDCreator},
+};
+
+/// A shape that is placed in the world for a collision query.
+#[derive(Clone, Copy, Debug)]
+pub struct TransformedShape<'a> {
+    pub shape: &'a dyn Shape,
+    /// The world space position of the center of mass of the shape.
+    pub position: Vec3,
+    pub rotation: Quat,
+    /// The scale of the shape, which is applied in the space of the shape before rotating it.
+    pub scale: Vec3,
+    /// Identifies the shape within the shape that is being queried, the sub shapes of the
+    /// shape add their own levels to it.
+    pub sub_shape_id_creator: SubShapeIDCreator,
+    /// The body that the shape belongs to, which is checked against the [`BodyFilter`] of a
+    /// query, or [`BodyID::INVALID`] for a shape without a body.
+    ///
+    /// [`BodyFilter`]: crate::body::BodyFilter
+    pub body_id: BodyID,
+}
+
+impl<'a> TransformedShape<'a> {
+    #[inline]
+    pub fn new(shape: &'a dyn Shape, position: Vec3, rotation: Quat) -> Self {
+        Self {
+            shape,
+            position,
+            rotation,
+            scale: Vec3::ONE,
+            sub_shape_id_creator: SubShapeIDCreator::new(),
+            body_id: BodyID::INVALID,
+        }
+    }
+
+    #[inline]
+    pub fn with_body_id(mut self, body_id: BodyID) -> Self {
+        self.body_id = body_id;
+        self
+    }
+
+    #[inline]
+    pub fn with_scale(mut self, scale: Vec3) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Returns the identifier of the shape itself.
+    #[inline]
+    pub fn sub_shape_id(&self) -> SubShapeID {
+        self.sub_shape_id_creator.id()
+    }
+
+    /// Returns the world space bounding box of the shape.
+    #[inline]
+    pub fn world_bounds(&self) -> AABox {
+        self.shape
+            .world_bounds(self.rotation, self.position, self.scale)
+    }
+
+    /// Transforms `point` from the space of the shape to world space.
+    #[inline]
+    pub fn to_world(&self, point: Vec3) -> Vec3 {
+        self.position + self.rotation * (point * self.scale)
+    }
+
+    /// Transforms `point` from world space to the space of the shape.
+    #[inline]
+    pub fn to_local(&self, point: Vec3) -> Vec3 {
+        self.rotation.conjugate() * (point - self.position) / self.scale
+    }
+
+    /// Returns the world space `bounds` as bounds in the space of the shape.
+    pub fn to_local_bounds(&self, bounds: &AABox) -> AABox {
+        let inverse_rotation = self.rotation.conjugate();
+        bounds
+            .transformed(inverse_rotation, -(inverse_rotation * self.position))
+            .scaled(Vec3::ONE / self.scale)
+    }
+
+    /// Casts the world space `ray` against the shape and adds the hits to `collector`, see
+    /// [`Shape::cast_ray_with_collector`].
+    pub fn cast_ray(&self, ray: &RayCast, collector: &mut dyn CollisionCollector<RayCastResult>) {
+        // Scaling the ray keeps the fractions along it the same.
+        let inverse_rotation = self.rotation.conjugate();
+        let local_ray = RayCast::new(
+            self.to_local(ray.origin),
+            inverse_rotation * ray.direction / self.scale,
+        );
+        self.shape
+            .cast_ray_with_collector(&local_ray, self.sub_shape_id_creator, collector);
+    }
+
+    /// Adds the sub shapes that contain the world space `point` to `collector`.
+    #[inline]
+    pub fn collide_point(
+        &self,
+        point: Vec3,
+        collector: &mut dyn CollisionCollector<CollidePointResult>,
+    ) {
+        self.shape.collide_point_with_collector(
+            self.to_local(point),
+            self.sub_shape_id_creator,
+            collector,
+        );
+    }
+
+    /// Fills `vertices` with the world space face of the shape that is most aligned with the
+    /// world space `direction`, see [`ConvexShape::get_supporting_face`]. Shapes that are not
+    /// convex have no supporting face.
+    ///
+    /// [`ConvexShape::get_supporting_face`]: crate::shape::ConvexShape::get_supporting_face
+    pub fn get_supporting_face(&self, direction: Vec3, vertices: &mut Vec<Vec3>) {
+        vertices.clear();
+        if let Some(convex) = self.shape.as_convex() {
+            convex.get_supporting_face(self.rotation.conjugate() * direction, self.scale, vertices);
+            for vertex in vertices.iter_mut() {
+                *vertex = self.position + self.rotation * *vertex;
+            }
+        }
+    }
+
+    /// Returns a child of the shape, `position` and `rotation` place the center of mass of
+    /// `shape` in the space of this shape.
+    pub(crate) fn child(&self, shape: &'a dyn Shape, position: Vec3, rotation: Quat) -> Self {
+        Self {
+            shape,
+            position: self.to_world(position),
+            rotation: self.rotation * rotation,
+            scale: scale_helpers::rotate_scale(rotation, self.scale).unwrap_or(self.scale),
+            sub_shape_id_creator: self.sub_shape_id_creator,
+            body_id: self.body_id,
+        }
+    }
+}
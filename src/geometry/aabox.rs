@@ -0,0 +1,192 @@
+use crate::math::{Mat33, Quat, Vec3};
+
+/// An axis aligned box.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AABox {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl AABox {
+    /// An invalid box that can be used as the starting point when encapsulating points.
+    pub const INVALID: Self = Self::new(Vec3::MAX, Vec3::MIN);
+
+    /// Creates a new box from its minimum and maximum corners.
+    #[inline]
+    pub const fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min, max }
+    }
+
+    /// Creates a box from its center and half extents.
+    #[inline]
+    pub fn from_center_and_half_extent(center: Vec3, half_extent: Vec3) -> Self {
+        Self::new(center - half_extent, center + half_extent)
+    }
+
+    /// Creates the smallest box that contains all `points`.
+    #[inline]
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Self {
+        let mut result = Self::INVALID;
+        for point in points {
+            result.encapsulate(point);
+        }
+        result
+    }
+
+    /// Returns whether min is less than or equal to max on all axes.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.min.x <= self.max.x && self.min.y <= self.max.y && self.min.z <= self.max.z
+    }
+
+    #[inline]
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    #[inline]
+    pub fn extent(&self) -> Vec3 {
+        (self.max - self.min) * 0.5
+    }
+
+    #[inline]
+    pub fn size(&self) -> Vec3 {
+        self.max - self.min
+    }
+
+    #[inline]
+    pub fn volume(&self) -> f32 {
+        let size = self.size();
+        size.x * size.y * size.z
+    }
+
+    #[inline]
+    pub fn surface_area(&self) -> f32 {
+        let size = self.size();
+        2.0 * (size.x * size.y + size.x * size.z + size.y * size.z)
+    }
+
+    /// Grows the box to contain `point`.
+    #[inline]
+    pub fn encapsulate(&mut self, point: Vec3) {
+        self.min = self.min.min(point);
+        self.max = self.max.max(point);
+    }
+
+    /// Grows the box to contain `other`.
+    #[inline]
+    pub fn encapsulate_box(&mut self, other: &Self) {
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    /// Returns the intersection of both boxes, which is invalid if they do not overlap.
+    #[inline]
+    pub fn intersect(&self, other: &Self) -> Self {
+        Self::new(self.min.max(other.min), self.max.min(other.max))
+    }
+
+    /// Returns the box grown by `radius` on all sides.
+    #[inline]
+    pub fn expanded_by(&self, radius: Vec3) -> Self {
+        Self::new(self.min - radius, self.max + radius)
+    }
+
+    /// Returns the box moved by `translation`.
+    #[inline]
+    pub fn translated(&self, translation: Vec3) -> Self {
+        Self::new(self.min + translation, self.max + translation)
+    }
+
+    /// Returns the box scaled by a possibly negative `scale`.
+    #[inline]
+    pub fn scaled(&self, scale: Vec3) -> Self {
+        let a = self.min * scale;
+        let b = self.max * scale;
+        Self::new(a.min(b), a.max(b))
+    }
+
+    /// Returns the box that contains this box after it has been transformed by `rotation` and
+    /// then `translation`.
+    pub fn transformed(&self, rotation: Quat, translation: Vec3) -> Self {
+        self.transformed_by_matrix(&Mat33::from_quat(rotation), translation)
+    }
+
+    /// Returns the box that contains this box after it has been transformed by `matrix` and then
+    /// `translation`.
+    pub fn transformed_by_matrix(&self, matrix: &Mat33, translation: Vec3) -> Self {
+        // Arvo's method, transform the center and sum the absolute contributions of each axis.
+        let center = matrix.mul_vec3(self.center()) + translation;
+        let extent = self.extent();
+        let half_extent = matrix.x_axis.abs() * extent.x
+            + matrix.y_axis.abs() * extent.y
+            + matrix.z_axis.abs() * extent.z;
+        Self::from_center_and_half_extent(center, half_extent)
+    }
+
+    #[inline]
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        point.x >= self.min.x
+            && point.y >= self.min.y
+            && point.z >= self.min.z
+            && point.x <= self.max.x
+            && point.y <= self.max.y
+            && point.z <= self.max.z
+    }
+
+    #[inline]
+    pub fn contains(&self, other: &Self) -> bool {
+        self.contains_point(other.min) && self.contains_point(other.max)
+    }
+
+    #[inline]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.min.x <= other.max.x
+            && self.min.y <= other.max.y
+            && self.min.z <= other.max.z
+            && self.max.x >= other.min.x
+            && self.max.y >= other.min.y
+            && self.max.z >= other.min.z
+    }
+
+    /// Returns the point in the box closest to `point`.
+    #[inline]
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        point.clamp(self.min, self.max)
+    }
+
+    /// Returns the squared distance from `point` to the box, which is zero inside the box.
+    #[inline]
+    pub fn distance_squared(&self, point: Vec3) -> f32 {
+        self.closest_point(point).distance_squared(point)
+    }
+
+    /// Returns the support point of the box in `direction`.
+    #[inline]
+    pub fn support(&self, direction: Vec3) -> Vec3 {
+        Vec3::new(
+            if direction.x < 0.0 {
+                self.min.x
+            } else {
+                self.max.x
+            },
+            if direction.y < 0.0 {
+                self.min.y
+            } else {
+                self.max.y
+            },
+            if direction.z < 0.0 {
+                self.min.z
+            } else {
+                self.max.z
+            },
+        )
+    }
+}
+
+impl Default for AABox {
+    #[inline]
+    fn default() -> Self {
+        Self::INVALID
+    }
+}
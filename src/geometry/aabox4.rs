@@ -0,0 +1,84 @@
+use crate::{
+    geometry::AABox,
+    math::{Vec3, Vec4},
+};
+
+/// Four axis aligned boxes stored as a structure of arrays so they can be tested at once.
+#[derive(Clone, Copy, Debug)]
+pub struct AABox4 {
+    pub min_x: Vec4,
+    pub min_y: Vec4,
+    pub min_z: Vec4,
+    pub max_x: Vec4,
+    pub max_y: Vec4,
+    pub max_z: Vec4,
+}
+
+impl AABox4 {
+    /// Four invalid boxes, these never overlap anything.
+    pub const INVALID: Self = Self {
+        min_x: Vec4::splat(f32::MAX),
+        min_y: Vec4::splat(f32::MAX),
+        min_z: Vec4::splat(f32::MAX),
+        max_x: Vec4::splat(f32::MIN),
+        max_y: Vec4::splat(f32::MIN),
+        max_z: Vec4::splat(f32::MIN),
+    };
+
+    /// Returns the box in lane `index`.
+    #[inline]
+    pub fn get(&self, index: usize) -> AABox {
+        AABox::new(
+            Vec3::new(self.min_x[index], self.min_y[index], self.min_z[index]),
+            Vec3::new(self.max_x[index], self.max_y[index], self.max_z[index]),
+        )
+    }
+
+    /// Stores `bounds` in lane `index`.
+    #[inline]
+    pub fn set(&mut self, index: usize, bounds: &AABox) {
+        self.min_x[index] = bounds.min.x;
+        self.min_y[index] = bounds.min.y;
+        self.min_z[index] = bounds.min.z;
+        self.max_x[index] = bounds.max.x;
+        self.max_y[index] = bounds.max.y;
+        self.max_z[index] = bounds.max.z;
+    }
+
+    /// Returns for each lane whether its box overlaps `bounds`.
+    #[inline]
+    pub fn overlaps(&self, bounds: &AABox) -> [bool; 4] {
+        // The boxes overlap when the intersection is not empty on every axis.
+        let size_x =
+            self.max_x.min(Vec4::splat(bounds.max.x)) - self.min_x.max(Vec4::splat(bounds.min.x));
+        let size_y =
+            self.max_y.min(Vec4::splat(bounds.max.y)) - self.min_y.max(Vec4::splat(bounds.min.y));
+        let size_z =
+            self.max_z.min(Vec4::splat(bounds.max.z)) - self.min_z.max(Vec4::splat(bounds.min.z));
+        let size = size_x.min(size_y).min(size_z);
+        [size.x >= 0.0, size.y >= 0.0, size.z >= 0.0, size.w >= 0.0]
+    }
+
+    /// Returns for each lane whether its box overlaps the sphere at `center` with `radius`.
+    #[inline]
+    pub fn overlaps_sphere(&self, center: Vec3, radius: f32) -> [bool; 4] {
+        // Invalid boxes clamp to a point that is infinitely far away.
+        let distance = |min: Vec4, max: Vec4, center: f32| {
+            let center = Vec4::splat(center);
+            center.clamp(min, max) - center
+        };
+        let dx = distance(self.min_x, self.max_x, center.x);
+        let dy = distance(self.min_y, self.max_y, center.y);
+        let dz = distance(self.min_z, self.max_z, center.z);
+        let distance_squared = dx * dx + dy * dy + dz * dz;
+        let radius_squared = radius * radius;
+        [0, 1, 2, 3].map(|lane| distance_squared[lane] <= radius_squared)
+    }
+}
+
+impl Default for AABox4 {
+    #[inline]
+    fn default() -> Self {
+        Self::INVALID
+    }
+}
@@ -0,0 +1,149 @@
+use alloc::vec::Vec;
+
+use crate::math::Vec3;
+
+/// Clips `polygon` against the plane through `plane_origin` with `plane_normal` using the
+/// Sutherland-Hodgman algorithm and writes the part in front of the plane to `clipped`. The
+/// normal does not need to be normalized. A polygon of two vertices is clipped as a line
+/// segment.
+pub fn clip_poly_vs_plane(
+    polygon: &[Vec3],
+    plane_origin: Vec3,
+    plane_normal: Vec3,
+    clipped: &mut Vec<Vec3>,
+) {
+    clipped.clear();
+    if polygon.len() < 2 {
+        return;
+    }
+    let distance = |point: Vec3| (point - plane_origin).dot(plane_normal);
+
+    if let [a, b] = *polygon {
+        let (distance_a, distance_b) = (distance(a), distance(b));
+        match (distance_a > 0.0, distance_b > 0.0) {
+            (true, true) => clipped.extend_from_slice(polygon),
+            (false, false) => {}
+            (a_inside, _) => {
+                let crossing = a + (b - a) * (distance_a / (distance_a - distance_b));
+                if a_inside {
+                    clipped.extend_from_slice(&[a, crossing]);
+                } else {
+                    clipped.extend_from_slice(&[crossing, b]);
+                }
+            }
+        }
+        return;
+    }
+
+    let mut previous = polygon[polygon.len() - 1];
+    let mut previous_distance = distance(previous);
+    let mut previous_inside = previous_distance > 0.0;
+    for &vertex in polygon {
+        let vertex_distance = distance(vertex);
+        let mut inside = vertex_distance > 0.0;
+        if inside != previous_inside {
+            let edge = vertex - previous;
+            let denominator = edge.dot(plane_normal);
+            if denominator != 0.0 {
+                clipped.push(previous - edge * (previous_distance / denominator));
+            } else {
+                // The edge lies in the plane, treat the vertex as being on the same side as the
+                // previous vertex.
+                inside = previous_inside;
+            }
+        }
+        if inside {
+            clipped.push(vertex);
+        }
+        previous = vertex;
+        previous_distance = vertex_distance;
+        previous_inside = inside;
+    }
+}
+
+/// Clips `polygon` against the edges of `clipping_polygon`, which must be convex and wound
+/// counter clockwise around `clipping_normal`, and writes the result to `clipped`. The
+/// clipping is done in the projection along `clipping_normal`, so the polygons do not need to
+/// lie in the same plane.
+pub fn clip_poly_vs_poly(
+    polygon: &[Vec3],
+    clipping_polygon: &[Vec3],
+    clipping_normal: Vec3,
+    clipped: &mut Vec<Vec3>,
+) {
+    clipped.clear();
+    clipped.extend_from_slice(polygon);
+    let mut source = Vec::with_capacity(polygon.len() + clipping_polygon.len());
+    for (i, &edge_start) in clipping_polygon.iter().enumerate() {
+        let edge_end = clipping_polygon[(i + 1) % clipping_polygon.len()];
+        // Points inwards for a counter clockwise polygon.
+        let edge_normal = clipping_normal.cross(edge_end - edge_start);
+        core::mem::swap(&mut source, clipped);
+        clip_poly_vs_plane(&source, edge_start, edge_normal, clipped);
+        if clipped.len() < 2 {
+            clipped.clear();
+            return;
+        }
+    }
+}
+
+/// Clips `polygon` against the edge from `edge_start` to `edge_end` and writes the result to
+/// `clipped`. The polygon is cut by the plane through the edge that contains
+/// `clipping_normal`, the crossings are then limited to the extent of the edge. This results in
+/// at most two points.
+pub fn clip_poly_vs_edge(
+    polygon: &[Vec3],
+    edge_start: Vec3,
+    edge_end: Vec3,
+    clipping_normal: Vec3,
+    clipped: &mut Vec<Vec3>,
+) {
+    clipped.clear();
+    if polygon.len() < 3 {
+        return;
+    }
+    let edge = edge_end - edge_start;
+    let edge_normal = clipping_normal.cross(edge);
+
+    // Project the edge onto the plane of the polygon.
+    let polygon_normal = (polygon[2] - polygon[0]).cross(polygon[1] - polygon[0]);
+    let polygon_normal_length_squared = polygon_normal.length_squared();
+    if polygon_normal_length_squared == 0.0 {
+        return;
+    }
+    let project = |point: Vec3| {
+        point
+            + polygon_normal
+                * (polygon_normal.dot(polygon[0] - point) / polygon_normal_length_squared)
+    };
+    let (start, end) = (project(edge_start), project(edge_end));
+    let projected_edge = end - start;
+    let projected_edge_length_squared = projected_edge.length_squared();
+
+    let distance = |point: Vec3| (point - edge_start).dot(edge_normal);
+    let mut previous = polygon[polygon.len() - 1];
+    let mut previous_distance = distance(previous);
+    for &vertex in polygon {
+        let vertex_distance = distance(vertex);
+        if (vertex_distance > 0.0) != (previous_distance > 0.0) {
+            let polygon_edge = vertex - previous;
+            let denominator = polygon_edge.dot(edge_normal);
+            let crossing = if denominator != 0.0 {
+                previous - polygon_edge * (previous_distance / denominator)
+            } else {
+                previous
+            };
+            // Keep the crossing within the extent of the edge.
+            let projection = (crossing - start).dot(projected_edge);
+            clipped.push(if projection < 0.0 {
+                start
+            } else if projection > projected_edge_length_squared {
+                end
+            } else {
+                crossing
+            });
+        }
+        previous = vertex;
+        previous_distance = vertex_distance;
+    }
+}
@@ -0,0 +1,199 @@
+use crate::math::Vec3;
+
+/// The closest point of a simplex to the origin.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClosestPoint {
+    pub point: Vec3,
+    /// The barycentric weight of each simplex vertex, unused vertices have weight zero.
+    pub weights: [f32; 4],
+    /// Bit mask of the simplex vertices that are required to describe the closest point.
+    pub set: u32,
+}
+
+impl ClosestPoint {
+    #[inline]
+    fn vertex(point: Vec3, index: usize) -> Self {
+        let mut weights = [0.0; 4];
+        weights[index] = 1.0;
+        Self {
+            point,
+            weights,
+            set: 1 << index,
+        }
+    }
+
+    /// Remaps the vertex indices of the result, used when evaluating sub simplices.
+    #[inline]
+    fn remap(self, indices: [usize; 3]) -> Self {
+        let mut weights = [0.0; 4];
+        let mut set = 0;
+        for (i, &index) in indices.iter().enumerate() {
+            weights[index] = self.weights[i];
+            if self.set & (1 << i) != 0 {
+                set |= 1 << index;
+            }
+        }
+        Self {
+            point: self.point,
+            weights,
+            set,
+        }
+    }
+}
+
+const DEGENERATE_EPSILON: f32 = 1.0e-12;
+
+/// Returns the closest point to the origin on the line segment `a`, `b`.
+pub fn closest_point_on_line(a: Vec3, b: Vec3) -> ClosestPoint {
+    let ab = b - a;
+    let length_squared = ab.length_squared();
+    if length_squared <= DEGENERATE_EPSILON {
+        return if a.length_squared() <= b.length_squared() {
+            ClosestPoint::vertex(a, 0)
+        } else {
+            ClosestPoint::vertex(b, 1)
+        };
+    }
+    let t = -a.dot(ab) / length_squared;
+    if t <= 0.0 {
+        ClosestPoint::vertex(a, 0)
+    } else if t >= 1.0 {
+        ClosestPoint::vertex(b, 1)
+    } else {
+        ClosestPoint {
+            point: a + ab * t,
+            weights: [1.0 - t, t, 0.0, 0.0],
+            set: 0b11,
+        }
+    }
+}
+
+/// Returns the closest point to the origin on the triangle `a`, `b`, `c`.
+pub fn closest_point_on_triangle(a: Vec3, b: Vec3, c: Vec3) -> ClosestPoint {
+    let ab = b - a;
+    let ac = c - a;
+    if ab.cross(ac).length_squared() <= DEGENERATE_EPSILON {
+        // The triangle is degenerate, pick the closest of its edges.
+        let candidates = [
+            closest_point_on_line(a, b).remap([0, 1, 3]),
+            closest_point_on_line(a, c).remap([0, 2, 3]),
+            closest_point_on_line(b, c).remap([1, 2, 3]),
+        ];
+        return candidates
+            .into_iter()
+            .min_by(|x, y| {
+                x.point
+                    .length_squared()
+                    .total_cmp(&y.point.length_squared())
+            })
+            .unwrap();
+    }
+
+    let d1 = -ab.dot(a);
+    let d2 = -ac.dot(a);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return ClosestPoint::vertex(a, 0);
+    }
+
+    let d3 = -ab.dot(b);
+    let d4 = -ac.dot(b);
+    if d3 >= 0.0 && d4 <= d3 {
+        return ClosestPoint::vertex(b, 1);
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        let v = d1 / (d1 - d3);
+        return ClosestPoint {
+            point: a + ab * v,
+            weights: [1.0 - v, v, 0.0, 0.0],
+            set: 0b011,
+        };
+    }
+
+    let d5 = -ab.dot(c);
+    let d6 = -ac.dot(c);
+    if d6 >= 0.0 && d5 <= d6 {
+        return ClosestPoint::vertex(c, 2);
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        let w = d2 / (d2 - d6);
+        return ClosestPoint {
+            point: a + ac * w,
+            weights: [1.0 - w, 0.0, w, 0.0],
+            set: 0b101,
+        };
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return ClosestPoint {
+            point: b + (c - b) * w,
+            weights: [0.0, 1.0 - w, w, 0.0],
+            set: 0b110,
+        };
+    }
+
+    let denom = 1.0 / (va + vb + vc);
+    let v = vb * denom;
+    let w = vc * denom;
+    ClosestPoint {
+        point: a + ab * v + ac * w,
+        weights: [1.0 - v - w, v, w, 0.0],
+        set: 0b111,
+    }
+}
+
+/// Returns the closest point to the origin on (or inside) the tetrahedron `a`, `b`, `c`, `d`.
+pub fn closest_point_on_tetrahedron(a: Vec3, b: Vec3, c: Vec3, d: Vec3) -> ClosestPoint {
+    let volume = (b - a).cross(c - a).dot(d - a);
+    let degenerate = volume.abs() <= DEGENERATE_EPSILON;
+
+    // Test every face that has the origin on its outside, the opposite vertex is used to
+    // determine which side is the outside.
+    let faces = [
+        ([a, b, c], d, [0, 1, 2]),
+        ([a, c, d], b, [0, 2, 3]),
+        ([a, d, b], c, [0, 3, 1]),
+        ([b, d, c], a, [1, 3, 2]),
+    ];
+    let mut best: Option<ClosestPoint> = None;
+    for ([p, q, r], opposite, indices) in faces {
+        let normal = (q - p).cross(r - p);
+        let origin_side = -p.dot(normal);
+        let opposite_side = (opposite - p).dot(normal);
+        if degenerate || origin_side * opposite_side < 0.0 {
+            let candidate = closest_point_on_triangle(p, q, r).remap(indices);
+            if best.is_none_or(|b| candidate.point.length_squared() < b.point.length_squared()) {
+                best = Some(candidate);
+            }
+        }
+    }
+
+    best.unwrap_or_else(|| {
+        // The origin is inside the tetrahedron.
+        let inv_volume = 1.0 / volume;
+        let wb = (-a).cross(c - a).dot(d - a) * inv_volume;
+        let wc = (b - a).cross(-a).dot(d - a) * inv_volume;
+        let wd = (b - a).cross(c - a).dot(-a) * inv_volume;
+        ClosestPoint {
+            point: Vec3::ZERO,
+            weights: [1.0 - wb - wc - wd, wb, wc, wd],
+            set: 0b1111,
+        }
+    })
+}
+
+/// Returns the closest point to the origin on the simplex made up of the first 1 to 4 points.
+pub fn closest_point_on_simplex(points: &[Vec3]) -> ClosestPoint {
+    match *points {
+        [a] => ClosestPoint::vertex(a, 0),
+        [a, b] => closest_point_on_line(a, b),
+        [a, b, c] => closest_point_on_triangle(a, b, c),
+        [a, b, c, d] => closest_point_on_tetrahedron(a, b, c, d),
+        _ => panic!("simplex must contain between 1 and 4 points"),
+    }
+}
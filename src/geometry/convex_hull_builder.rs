@@ -0,0 +1,602 @@
+use alloc::{
+    collections::{BinaryHeap, VecDeque},
+    vec,
+    vec::Vec,
+};
+use core::{cmp::Ordering, fmt};
+
+use crate::{geometry::Plane, math::Vec3};
+
+/// Errors that can occur while building a convex hull.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConvexHullError {
+    /// Fewer than four points were provided.
+    TooFewPoints,
+    /// All points are coincident within the tolerance.
+    Degenerate,
+    /// All points lie on a single line within the tolerance.
+    Collinear,
+    /// All points lie on a single plane within the tolerance.
+    Coplanar,
+}
+
+#[cfg(not(spirv))]
+impl fmt::Display for ConvexHullError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFewPoints => write!(f, "too few points to build a convex hull"),
+            Self::Degenerate => write!(f, "all points are coincident"),
+            Self::Collinear => write!(f, "all points are collinear"),
+            Self::Coplanar => write!(f, "all points are coplanar"),
+        }
+    }
+}
+
+/// A polygonal face of a [`ConvexHull`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConvexHullFace {
+    /// Indices into [`ConvexHull::vertices`], counter clockwise when seen from the outside.
+    pub vertices: Vec<u32>,
+}
+
+/// The output of the [`ConvexHullBuilder`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConvexHull {
+    /// The vertices of the hull, only points that are part of a face are kept.
+    pub vertices: Vec<Vec3>,
+    /// The faces of the hull, coplanar triangles are merged into a single face.
+    pub faces: Vec<ConvexHullFace>,
+    /// The outward facing plane of every face in [`ConvexHull::faces`].
+    pub planes: Vec<Plane>,
+}
+
+impl ConvexHull {
+    /// Returns the number of unique edges of the hull.
+    pub fn edge_count(&self) -> usize {
+        self.faces.iter().map(|f| f.vertices.len()).sum::<usize>() / 2
+    }
+
+    /// Returns the largest signed distance from `point` to any of the face planes, this is
+    /// negative when the point is inside the hull.
+    pub fn signed_distance(&self, point: Vec3) -> f32 {
+        self.planes
+            .iter()
+            .map(|p| p.signed_distance(point))
+            .fold(f32::MIN, f32::max)
+    }
+
+    /// Returns whether `point` lies inside the hull or within `tolerance` of its surface.
+    pub fn contains_point(&self, point: Vec3, tolerance: f32) -> bool {
+        self.signed_distance(point) <= tolerance
+    }
+}
+
+const INVALID: usize = usize::MAX;
+
+#[derive(Clone, Debug)]
+struct Triangle {
+    vertices: [usize; 3],
+    /// The triangle on the other side of the edge `vertices[i] -> vertices[(i + 1) % 3]`.
+    neighbours: [usize; 3],
+    plane: Plane,
+    area: f32,
+    conflicts: Vec<usize>,
+    furthest: usize,
+    furthest_distance: f32,
+    removed: bool,
+}
+
+impl Triangle {
+    fn new(positions: &[Vec3], vertices: [usize; 3]) -> Self {
+        let [a, b, c] = vertices.map(|i| positions[i]);
+        let normal = (b - a).cross(c - a);
+        let area = normal.length();
+        Self {
+            vertices,
+            neighbours: [INVALID; 3],
+            plane: Plane::from_point_and_normal(a, normal / area),
+            area: 0.5 * area,
+            conflicts: Vec::new(),
+            furthest: INVALID,
+            furthest_distance: 0.0,
+            removed: false,
+        }
+    }
+
+    fn add_conflict(&mut self, index: usize, distance: f32) {
+        if distance > self.furthest_distance {
+            self.furthest = index;
+            self.furthest_distance = distance;
+        }
+        self.conflicts.push(index);
+    }
+}
+
+struct HorizonEdge {
+    start: usize,
+    end: usize,
+    outside: usize,
+    outside_edge: usize,
+}
+
+/// The furthest point outside of a triangle when it was pushed on the heap, the entry is
+/// stale once the triangle is removed or its furthest point changes.
+#[derive(Clone, Copy, Debug)]
+struct Candidate {
+    distance: f32,
+    face: usize,
+    eye: usize,
+}
+
+impl Candidate {
+    #[inline]
+    fn of(triangles: &[Triangle], face: usize) -> Option<Self> {
+        let triangle = &triangles[face];
+        (triangle.furthest != INVALID).then_some(Self {
+            distance: triangle.furthest_distance,
+            face,
+            eye: triangle.furthest,
+        })
+    }
+
+    #[inline]
+    fn is_current(&self, triangles: &[Triangle]) -> bool {
+        let triangle = &triangles[self.face];
+        !triangle.removed && triangle.furthest == self.eye
+    }
+}
+
+impl PartialEq for Candidate {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.face.cmp(&other.face))
+    }
+}
+
+/// The buffers that are reused while points are added to the hull.
+#[derive(Default)]
+struct Scratch {
+    /// The removed triangles whose slots are taken by new triangles.
+    free: Vec<usize>,
+    /// The triangles with points outside of them, the furthest point first.
+    pending: BinaryHeap<Candidate>,
+    is_visible: Vec<bool>,
+    visible: Vec<usize>,
+    queue: VecDeque<usize>,
+    horizon: Vec<HorizonEdge>,
+    new_triangles: Vec<usize>,
+    orphans: Vec<usize>,
+}
+
+/// Builds convex hulls from point clouds using the QuickHull algorithm.
+///
+/// Points that lie within the tolerance of the hull are discarded, after which coplanar
+/// triangles are merged into polygons and vertices that no longer describe a corner (which
+/// would otherwise result in degenerate edges) are removed.
+#[derive(Clone, Copy, Debug)]
+pub struct ConvexHullBuilder<'a> {
+    positions: &'a [Vec3],
+    max_vertices: usize,
+}
+
+impl<'a> ConvexHullBuilder<'a> {
+    /// Creates a new builder for the given point cloud.
+    pub fn new(positions: &'a [Vec3]) -> Self {
+        Self {
+            positions,
+            max_vertices: usize::MAX,
+        }
+    }
+
+    /// Limits the number of vertices the hull will be built from, the hull is approximated
+    /// once this limit is reached.
+    pub fn with_max_vertices(mut self, max_vertices: usize) -> Self {
+        self.max_vertices = max_vertices.max(4);
+        self
+    }
+
+    /// Builds the hull, treating points within `tolerance` of a face as lying on that face.
+    pub fn build(&self, tolerance: f32) -> Result<ConvexHull, ConvexHullError> {
+        let positions = self.positions;
+        if positions.len() < 4 {
+            return Err(ConvexHullError::TooFewPoints);
+        }
+
+        let mut triangles = self.initial_simplex(tolerance)?;
+        let mut vertex_count = 4;
+        let mut scratch = Scratch::default();
+        let points: Vec<usize> = (0..positions.len()).collect();
+        self.assign_conflicts(&mut triangles, &[0, 1, 2, 3], &points, tolerance);
+        scratch
+            .pending
+            .extend((0..4).filter_map(|face| Candidate::of(&triangles, face)));
+
+        while vertex_count < self.max_vertices {
+            let Some(candidate) = scratch.pending.pop() else {
+                break;
+            };
+            if !candidate.is_current(&triangles) {
+                continue;
+            }
+            let (face, eye) = (candidate.face, candidate.eye);
+            if self.add_point(&mut triangles, &mut scratch, face, eye, tolerance) {
+                vertex_count += 1;
+            } else {
+                // The horizon could not be resolved, drop the point to guarantee progress.
+                let triangle = &mut triangles[face];
+                triangle.conflicts.retain(|&i| i != eye);
+                let plane = triangle.plane;
+                (triangle.furthest, triangle.furthest_distance) = triangle
+                    .conflicts
+                    .iter()
+                    .map(|&i| (i, plane.signed_distance(positions[i])))
+                    .fold(
+                        (INVALID, 0.0),
+                        |best, c| if c.1 > best.1 { c } else { best },
+                    );
+                scratch.pending.extend(Candidate::of(&triangles, face));
+            }
+        }
+
+        Ok(self.finalize(&triangles, tolerance))
+    }
+
+    fn initial_simplex(&self, tolerance: f32) -> Result<Vec<Triangle>, ConvexHullError> {
+        let positions = self.positions;
+
+        // Start with the two extreme points that are furthest apart.
+        let mut extremes = [0; 6];
+        for (i, p) in positions.iter().enumerate() {
+            for axis in 0..3 {
+                if p[axis] < positions[extremes[axis * 2]][axis] {
+                    extremes[axis * 2] = i;
+                }
+                if p[axis] > positions[extremes[axis * 2 + 1]][axis] {
+                    extremes[axis * 2 + 1] = i;
+                }
+            }
+        }
+        let (mut a, mut b, mut best) = (0, 0, -1.0);
+        for &i in &extremes {
+            for &j in &extremes {
+                let distance = positions[i].distance_squared(positions[j]);
+                if distance > best {
+                    (a, b, best) = (i, j, distance);
+                }
+            }
+        }
+        if best <= tolerance * tolerance {
+            return Err(ConvexHullError::Degenerate);
+        }
+
+        // Then the point furthest away from the line through them.
+        let direction = (positions[b] - positions[a]).normalize();
+        let (c, distance) = Self::furthest(positions, |p| {
+            let delta = p - positions[a];
+            (delta - direction * delta.dot(direction)).length()
+        });
+        if distance <= tolerance {
+            return Err(ConvexHullError::Collinear);
+        }
+
+        // And finally the point furthest away from the plane through all three.
+        let plane = Plane::from_points_ccw(positions[a], positions[b], positions[c]);
+        let (d, distance) = Self::furthest(positions, |p| plane.signed_distance(p).abs());
+        if distance <= tolerance {
+            return Err(ConvexHullError::Coplanar);
+        }
+        let (b, c) = if plane.signed_distance(positions[d]) > 0.0 {
+            (c, b)
+        } else {
+            (b, c)
+        };
+
+        let mut triangles = vec![
+            Triangle::new(positions, [a, b, c]),
+            Triangle::new(positions, [a, d, b]),
+            Triangle::new(positions, [b, d, c]),
+            Triangle::new(positions, [c, d, a]),
+        ];
+        for i in 0..4 {
+            for edge in 0..3 {
+                let start = triangles[i].vertices[edge];
+                let end = triangles[i].vertices[(edge + 1) % 3];
+                triangles[i].neighbours[edge] = (0..4)
+                    .find(|&j| {
+                        let v = triangles[j].vertices;
+                        (0..3).any(|k| v[k] == end && v[(k + 1) % 3] == start)
+                    })
+                    .unwrap_or(INVALID);
+            }
+        }
+        Ok(triangles)
+    }
+
+    fn furthest(positions: &[Vec3], distance: impl Fn(Vec3) -> f32) -> (usize, f32) {
+        positions
+            .iter()
+            .map(|&p| distance(p))
+            .enumerate()
+            .fold(
+                (0, f32::MIN),
+                |best, (i, d)| if d > best.1 { (i, d) } else { best },
+            )
+    }
+
+    /// Moves every point in `points` to the conflict list of the triangle of `candidates` it
+    /// lies furthest in front of, points that are not outside the hull are discarded.
+    fn assign_conflicts(
+        &self,
+        triangles: &mut [Triangle],
+        candidates: &[usize],
+        points: &[usize],
+        tolerance: f32,
+    ) {
+        for &index in points {
+            let point = self.positions[index];
+            let mut best = (INVALID, tolerance);
+            for &i in candidates {
+                let distance = triangles[i].plane.signed_distance(point);
+                if distance > best.1 {
+                    best = (i, distance);
+                }
+            }
+            if best.0 != INVALID {
+                triangles[best.0].add_conflict(index, best.1);
+            }
+        }
+    }
+
+    /// Adds `eye` to the hull, returns false when the horizon was not a single closed loop.
+    fn add_point(
+        &self,
+        triangles: &mut Vec<Triangle>,
+        scratch: &mut Scratch,
+        face: usize,
+        eye: usize,
+        tolerance: f32,
+    ) -> bool {
+        let point = self.positions[eye];
+        let Scratch {
+            free,
+            pending,
+            is_visible,
+            visible,
+            queue,
+            horizon,
+            new_triangles,
+            orphans,
+        } = scratch;
+
+        // Flood fill all triangles that can see the point.
+        is_visible.resize(triangles.len(), false);
+        visible.clear();
+        visible.push(face);
+        is_visible[face] = true;
+        queue.push_back(face);
+        while let Some(current) = queue.pop_front() {
+            for neighbour in triangles[current].neighbours {
+                if !is_visible[neighbour] && triangles[neighbour].plane.signed_distance(point) > 0.0
+                {
+                    is_visible[neighbour] = true;
+                    visible.push(neighbour);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        // Collect the edges between visible and invisible triangles and order them in a loop.
+        horizon.clear();
+        for &i in visible.iter() {
+            let triangle = &triangles[i];
+            for edge in 0..3 {
+                let outside = triangle.neighbours[edge];
+                if !is_visible[outside] {
+                    let start = triangle.vertices[edge];
+                    let end = triangle.vertices[(edge + 1) % 3];
+                    let outside_edge = (0..3)
+                        .find(|&k| triangles[outside].vertices[k] == end)
+                        .unwrap_or(INVALID);
+                    horizon.push(HorizonEdge {
+                        start,
+                        end,
+                        outside,
+                        outside_edge,
+                    });
+                }
+            }
+        }
+        for &i in visible.iter() {
+            is_visible[i] = false;
+        }
+        for i in 1..horizon.len() {
+            let end = horizon[i - 1].end;
+            let Some(next) = (i..horizon.len()).find(|&j| horizon[j].start == end) else {
+                return false;
+            };
+            horizon.swap(i, next);
+        }
+        if horizon.is_empty() || horizon[horizon.len() - 1].end != horizon[0].start {
+            return false;
+        }
+
+        // Replace the visible triangles with a fan connecting the horizon to the point, in the
+        // slots of triangles that were removed before.
+        new_triangles.clear();
+        for edge in horizon.iter() {
+            let triangle = Triangle::new(self.positions, [edge.start, edge.end, eye]);
+            let index = match free.pop() {
+                Some(index) => {
+                    triangles[index] = triangle;
+                    index
+                }
+                None => {
+                    triangles.push(triangle);
+                    triangles.len() - 1
+                }
+            };
+            new_triangles.push(index);
+        }
+        let count = horizon.len();
+        for (i, edge) in horizon.iter().enumerate() {
+            triangles[new_triangles[i]].neighbours = [
+                edge.outside,
+                new_triangles[(i + 1) % count],
+                new_triangles[(i + count - 1) % count],
+            ];
+            triangles[edge.outside].neighbours[edge.outside_edge] = new_triangles[i];
+        }
+
+        orphans.clear();
+        for &i in visible.iter() {
+            let triangle = &mut triangles[i];
+            triangle.removed = true;
+            orphans.extend(triangle.conflicts.drain(..).filter(|&p| p != eye));
+        }
+        free.extend_from_slice(visible);
+        self.assign_conflicts(triangles, new_triangles, orphans, tolerance);
+        pending.extend(
+            new_triangles
+                .iter()
+                .filter_map(|&i| Candidate::of(triangles, i)),
+        );
+        true
+    }
+
+    fn finalize(&self, triangles: &[Triangle], tolerance: f32) -> ConvexHull {
+        let positions = self.positions;
+
+        // Grow faces from the largest triangles outwards, merging neighbours that are coplanar
+        // with the seed triangle within the tolerance.
+        let mut order: Vec<usize> = (0..triangles.len())
+            .filter(|&i| !triangles[i].removed)
+            .collect();
+        order.sort_by(|&a, &b| triangles[b].area.total_cmp(&triangles[a].area));
+
+        let mut group = vec![INVALID; triangles.len()];
+        let mut polygons: Vec<Vec<usize>> = Vec::new();
+        for &seed in &order {
+            if group[seed] != INVALID {
+                continue;
+            }
+            let id = polygons.len();
+            let plane = triangles[seed].plane;
+            let mut members = vec![seed];
+            group[seed] = id;
+            let mut next = 0;
+            while next < members.len() {
+                for neighbour in triangles[members[next]].neighbours {
+                    let candidate = &triangles[neighbour];
+                    if group[neighbour] == INVALID
+                        && candidate.plane.normal.dot(plane.normal) > 0.0
+                        && candidate
+                            .vertices
+                            .iter()
+                            .all(|&v| plane.signed_distance(positions[v]).abs() <= tolerance)
+                    {
+                        group[neighbour] = id;
+                        members.push(neighbour);
+                    }
+                }
+                next += 1;
+            }
+
+            match Self::boundary_loop(triangles, &group, &members, id) {
+                Some(polygon) => polygons.push(polygon),
+                None => {
+                    // Not a simple polygon, keep the individual triangles instead.
+                    for (i, &member) in members.iter().enumerate() {
+                        group[member] = id + i;
+                        polygons.push(triangles[member].vertices.to_vec());
+                    }
+                }
+            }
+        }
+
+        // Vertices shared by fewer than three faces lie on a straight edge between two faces.
+        let mut face_count = vec![0u32; positions.len()];
+        for polygon in &polygons {
+            for &v in polygon {
+                face_count[v] += 1;
+            }
+        }
+        for polygon in &mut polygons {
+            polygon.retain(|&v| face_count[v] >= 3);
+        }
+        polygons.retain(|polygon| polygon.len() >= 3);
+
+        let mut remap = vec![INVALID; positions.len()];
+        let mut hull = ConvexHull::default();
+        for polygon in &polygons {
+            let mut face = ConvexHullFace::default();
+            let mut normal = Vec3::ZERO;
+            for (i, &v) in polygon.iter().enumerate() {
+                if remap[v] == INVALID {
+                    remap[v] = hull.vertices.len();
+                    hull.vertices.push(positions[v]);
+                }
+                face.vertices.push(remap[v] as u32);
+                let next = positions[polygon[(i + 1) % polygon.len()]];
+                normal +=
+                    (positions[v] - positions[polygon[0]]).cross(next - positions[polygon[0]]);
+            }
+            let normal = normal.normalize();
+            let constant = polygon
+                .iter()
+                .map(|&v| -normal.dot(positions[v]))
+                .fold(f32::MAX, f32::min);
+            hull.faces.push(face);
+            hull.planes.push(Plane::new(normal, constant));
+        }
+        hull
+    }
+
+    /// Walks the outer edges of a group of triangles, returns `None` if they do not form a
+    /// single loop.
+    fn boundary_loop(
+        triangles: &[Triangle],
+        group: &[usize],
+        members: &[usize],
+        id: usize,
+    ) -> Option<Vec<usize>> {
+        let mut edges = Vec::new();
+        for &member in members {
+            let triangle = &triangles[member];
+            for edge in 0..3 {
+                if group[triangle.neighbours[edge]] != id {
+                    edges.push((triangle.vertices[edge], triangle.vertices[(edge + 1) % 3]));
+                }
+            }
+        }
+
+        let mut polygon = Vec::with_capacity(edges.len());
+        let (start, mut current) = edges[0];
+        polygon.push(start);
+        while current != start {
+            if polygon.len() >= edges.len() {
+                return None;
+            }
+            polygon.push(current);
+            current = edges.iter().find(|e| e.0 == current)?.1;
+        }
+        (polygon.len() == edges.len()).then_some(polygon)
+    }
+}
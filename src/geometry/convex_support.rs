@@ -0,0 +1,136 @@
+use alloc::boxed::Box;
+
+use crate::math::{Quat, Vec3};
+
+/// A convex object that can be queried for its support point in any direction.
+pub trait Support {
+    /// Returns the point of the object that lies furthest along `direction`, the direction does
+    /// not need to be normalized.
+    fn support(&self, direction: Vec3) -> Vec3;
+
+    /// Returns the radius that is to be added to the support points to get the actual object.
+    #[inline]
+    fn convex_radius(&self) -> f32 {
+        0.0
+    }
+}
+
+impl<T: Support + ?Sized> Support for &T {
+    #[inline]
+    fn support(&self, direction: Vec3) -> Vec3 {
+        (**self).support(direction)
+    }
+
+    #[inline]
+    fn convex_radius(&self) -> f32 {
+        (**self).convex_radius()
+    }
+}
+
+impl<T: Support + ?Sized> Support for Box<T> {
+    #[inline]
+    fn support(&self, direction: Vec3) -> Vec3 {
+        (**self).support(direction)
+    }
+
+    #[inline]
+    fn convex_radius(&self) -> f32 {
+        (**self).convex_radius()
+    }
+}
+
+/// A single point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PointConvexSupport {
+    pub point: Vec3,
+}
+
+impl Support for PointConvexSupport {
+    #[inline]
+    fn support(&self, _direction: Vec3) -> Vec3 {
+        self.point
+    }
+}
+
+/// A convex object that is rotated and then translated.
+#[derive(Clone, Copy, Debug)]
+pub struct TransformedConvexObject<T> {
+    pub object: T,
+    pub rotation: Quat,
+    pub translation: Vec3,
+}
+
+impl<T> TransformedConvexObject<T> {
+    #[inline]
+    pub fn new(object: T, rotation: Quat, translation: Vec3) -> Self {
+        Self {
+            object,
+            rotation,
+            translation,
+        }
+    }
+}
+
+impl<T: Support> Support for TransformedConvexObject<T> {
+    #[inline]
+    fn support(&self, direction: Vec3) -> Vec3 {
+        let local = self.object.support(self.rotation.conjugate() * direction);
+        self.translation + self.rotation * local
+    }
+
+    #[inline]
+    fn convex_radius(&self) -> f32 {
+        self.object.convex_radius()
+    }
+}
+
+/// A convex object that is expanded by a radius, the result no longer has a convex radius.
+#[derive(Clone, Copy, Debug)]
+pub struct AddConvexRadius<T> {
+    pub object: T,
+    pub radius: f32,
+}
+
+impl<T> AddConvexRadius<T> {
+    #[inline]
+    pub fn new(object: T, radius: f32) -> Self {
+        Self { object, radius }
+    }
+}
+
+impl<T: Support> Support for AddConvexRadius<T> {
+    #[inline]
+    fn support(&self, direction: Vec3) -> Vec3 {
+        self.object.support(direction) + direction.normalize_or_zero() * self.radius
+    }
+}
+
+/// A triangle, which is the convex hull of its three vertices.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TriangleConvexSupport {
+    pub vertices: [Vec3; 3],
+}
+
+impl TriangleConvexSupport {
+    #[inline]
+    pub fn new(v0: Vec3, v1: Vec3, v2: Vec3) -> Self {
+        Self {
+            vertices: [v0, v1, v2],
+        }
+    }
+}
+
+impl Support for TriangleConvexSupport {
+    #[inline]
+    fn support(&self, direction: Vec3) -> Vec3 {
+        let [v0, v1, v2] = self.vertices;
+        let (d0, d1, d2) = (v0.dot(direction), v1.dot(direction), v2.dot(direction));
+        if d0 >= d1 && d0 >= d2 {
+            v0
+        } else if d1 >= d2 {
+            v1
+        } else {
+            v2
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use alloc::vec::Vec;
+
+use crate::{
+    geometry::{closest_point_on_triangle, GjkClosestPoint, Support},
+    math::Vec3,
+};
+
+#[cfg(all(no_std, libm, not(test)))]
+use crate::math::libm::F32Ext;
+
+const MAX_ITERATIONS: usize = 128;
+
+/// Faces with a squared normal length below this are too thin to have a reliable normal.
+const MIN_NORMAL_LENGTH_SQUARED: f32 = 1.0e-12;
+
+/// The deepest points between two convex objects as found by [`EpaPenetrationDepth`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PenetrationDepth {
+    /// The deepest point of the first object.
+    pub point_a: Vec3,
+    /// The deepest point of the second object.
+    pub point_b: Vec3,
+    /// The normalized direction in which the second object has to move to resolve the
+    /// penetration, this points from the first object into the second.
+    pub penetration_axis: Vec3,
+    /// The distance the objects overlap along the axis, negative when they are separated.
+    pub penetration_depth: f32,
+}
+
+/// A vertex of the polytope, which is a point on `A - B` and the support points it was made of.
+#[derive(Clone, Copy, Debug)]
+struct Vertex {
+    y: Vec3,
+    p: Vec3,
+    q: Vec3,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Face {
+    indices: [usize; 3],
+    /// The outward normal of the face.
+    normal: Vec3,
+    /// The distance from the origin to the plane of the face.
+    distance: f32,
+}
+
+/// Finds the penetration depth of two convex objects.
+///
+/// Objects are first tested with [`GjkClosestPoint`] after shrinking them by their convex
+/// radius. When only the convex radii overlap this directly gives the deepest points, which is
+/// the common case for resting contacts. Otherwise the Expanding Polytope Algorithm is run on
+/// the full objects, as described in "Proximity Queries and Penetration Depth Computation on 3D
+/// Game Objects" by Gino van den Bergen.
+#[derive(Clone, Debug, Default)]
+pub struct EpaPenetrationDepth {
+    gjk: GjkClosestPoint,
+    vertices: Vec<Vertex>,
+    faces: Vec<Face>,
+    edges: Vec<(usize, usize)>,
+}
+
+impl EpaPenetrationDepth {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the deepest points between `a` and `b`, or `None` when they are separated by
+    /// more than `max_separation_distance`.
+    ///
+    /// `a_exclude` and `b_exclude` are the objects without their convex radius, which they
+    /// report through [`Support::convex_radius`]. `a_include` and `b_include` are the same
+    /// objects including their convex radius.
+    pub fn penetration_depth<A, B, C, D>(
+        &mut self,
+        a_exclude: &A,
+        b_exclude: &B,
+        a_include: &C,
+        b_include: &D,
+        max_separation_distance: f32,
+        tolerance: f32,
+    ) -> Option<PenetrationDepth>
+    where
+        A: Support + ?Sized,
+        B: Support + ?Sized,
+        C: Support + ?Sized,
+        D: Support + ?Sized,
+    {
+        let radius_a = a_exclude.convex_radius();
+        let radius_b = b_exclude.convex_radius();
+        let max_distance = radius_a + radius_b + max_separation_distance;
+        let mut v = Vec3::ZERO;
+        let result = self.gjk.closest_points(
+            a_exclude,
+            b_exclude,
+            tolerance,
+            max_distance * max_distance,
+            &mut v,
+        )?;
+
+        if result.distance_squared > tolerance * tolerance {
+            let distance = result.distance_squared.sqrt();
+            if distance > max_distance {
+                return None;
+            }
+            let penetration_axis = (result.point_b - result.point_a) / distance;
+            return Some(PenetrationDepth {
+                point_a: result.point_a + penetration_axis * radius_a,
+                point_b: result.point_b - penetration_axis * radius_b,
+                penetration_axis,
+                penetration_depth: radius_a + radius_b - distance,
+            });
+        }
+
+        self.expand_polytope(a_include, b_include, tolerance)
+    }
+
+    /// Runs the Expanding Polytope Algorithm on two intersecting objects.
+    fn expand_polytope<A, B>(&mut self, a: &A, b: &B, tolerance: f32) -> Option<PenetrationDepth>
+    where
+        A: Support + ?Sized,
+        B: Support + ?Sized,
+    {
+        let support = |direction: Vec3| {
+            let p = a.support(direction);
+            let q = b.support(-direction);
+            Vertex { y: p - q, p, q }
+        };
+
+        // Start from the simplex that GJK ends with, it surrounds (or touches) the origin.
+        let mut v = Vec3::ZERO;
+        self.gjk.intersects(a, b, tolerance, &mut v);
+        let (y, p, q, count) = self.gjk.simplex();
+        self.vertices.clear();
+        self.vertices.extend((0..count).map(|i| Vertex {
+            y: y[i],
+            p: p[i],
+            q: q[i],
+        }));
+        if self.vertices.is_empty() {
+            self.vertices.push(support(Vec3::X));
+        }
+        if !self.complete_tetrahedron(&support, tolerance) {
+            return None;
+        }
+
+        self.faces.clear();
+        for indices in [[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]] {
+            let face = self.face(indices)?;
+            if face.distance < -tolerance {
+                // The origin lies outside of the tetrahedron, the objects barely touch.
+                return None;
+            }
+            self.faces.push(face);
+        }
+
+        let mut closest = self.closest_face();
+        for _ in 0..MAX_ITERATIONS {
+            let w = support(closest.normal);
+            if w.y.dot(closest.normal) - closest.distance <= tolerance {
+                break;
+            }
+
+            // Remove the faces that can see the new point and patch the hole with faces
+            // connecting the new point to the edges of the hole.
+            let index = self.vertices.len();
+            self.vertices.push(w);
+            self.edges.clear();
+            let (vertices, edges) = (&self.vertices, &mut self.edges);
+            self.faces.retain(|face| {
+                if face.normal.dot(w.y - vertices[face.indices[0]].y) <= 0.0 {
+                    return true;
+                }
+                for i in 0..3 {
+                    let edge = (face.indices[i], face.indices[(i + 1) % 3]);
+                    match edges.iter().position(|&(a, b)| (b, a) == edge) {
+                        Some(shared) => {
+                            edges.swap_remove(shared);
+                        }
+                        None => edges.push(edge),
+                    }
+                }
+                false
+            });
+            for i in 0..self.edges.len() {
+                let (start, end) = self.edges[i];
+                if let Some(face) = self.face([start, end, index]) {
+                    self.faces.push(face);
+                }
+            }
+            if self.faces.is_empty() {
+                break;
+            }
+            closest = self.closest_face();
+        }
+
+        let [i0, i1, i2] = closest.indices.map(|i| self.vertices[i]);
+        let weights = closest_point_on_triangle(i0.y, i1.y, i2.y).weights;
+        let point_a = i0.p * weights[0] + i1.p * weights[1] + i2.p * weights[2];
+        let point_b = i0.q * weights[0] + i1.q * weights[1] + i2.q * weights[2];
+        Some(PenetrationDepth {
+            point_a,
+            point_b,
+            penetration_axis: closest.normal,
+            penetration_depth: closest.distance.max(0.0),
+        })
+    }
+
+    /// Adds points to the simplex until it is a tetrahedron, returns false when `A - B` is
+    /// flat.
+    fn complete_tetrahedron(&mut self, support: &impl Fn(Vec3) -> Vertex, tolerance: f32) -> bool {
+        while self.vertices.len() < 4 {
+            let y: Vec<Vec3> = self.vertices.iter().map(|vertex| vertex.y).collect();
+            let normal = if y.len() == 3 {
+                (y[1] - y[0]).cross(y[2] - y[0])
+            } else {
+                Vec3::ZERO
+            };
+            let mut directions = [Vec3::X, -Vec3::X, Vec3::Y, -Vec3::Y, Vec3::Z, -Vec3::Z];
+            let directions = match y.len() {
+                1 => &directions[..],
+                2 => {
+                    let edge = y[1] - y[0];
+                    let axis = if edge.x.abs() < edge.y.abs() {
+                        Vec3::X
+                    } else {
+                        Vec3::Y
+                    };
+                    let u = edge.cross(axis);
+                    let v = edge.cross(u);
+                    directions = [u, -u, v, -v, u + v, -u - v];
+                    &directions[..]
+                }
+                _ => {
+                    directions[..2].copy_from_slice(&[normal, -normal]);
+                    &directions[..2]
+                }
+            };
+            // How far a point lies from the current simplex.
+            let distance = |point: Vec3| match y.len() {
+                1 => (point - y[0]).length(),
+                2 => (point - y[0]).cross(y[1] - y[0]).length() / (y[1] - y[0]).length(),
+                _ => (point - y[0]).dot(normal).abs() / normal.length(),
+            };
+
+            let best = directions
+                .iter()
+                .map(|&direction| support(direction))
+                .map(|vertex| (distance(vertex.y), vertex))
+                .max_by(|(a, _), (b, _)| a.total_cmp(b));
+            match best {
+                Some((distance, vertex)) if distance > tolerance => self.vertices.push(vertex),
+                _ => return false,
+            }
+        }
+
+        // Wind the faces so their normals point outwards.
+        let [y0, y1, y2, y3] = [0, 1, 2, 3].map(|i| self.vertices[i].y);
+        if (y1 - y0).cross(y2 - y0).dot(y3 - y0) > 0.0 {
+            self.vertices.swap(1, 2);
+        }
+        true
+    }
+
+    fn face(&self, indices: [usize; 3]) -> Option<Face> {
+        let [y0, y1, y2] = indices.map(|i| self.vertices[i].y);
+        let normal = (y1 - y0).cross(y2 - y0);
+        let length_squared = normal.length_squared();
+        if length_squared <= MIN_NORMAL_LENGTH_SQUARED {
+            return None;
+        }
+        let normal = normal / length_squared.sqrt();
+        Some(Face {
+            indices,
+            normal,
+            distance: normal.dot(y0),
+        })
+    }
+
+    fn closest_face(&self) -> Face {
+        *self
+            .faces
+            .iter()
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+            .expect("polytope has no faces")
+    }
+}
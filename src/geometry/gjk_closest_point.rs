@@ -0,0 +1,331 @@
+#[cfg(all(no_std, libm, not(test)))]
+use crate::math::libm::F32Ext;
+use crate::{
+    geometry::{closest_point_on_simplex, Support},
+    math::Vec3,
+};
+
+const MAX_ITERATIONS: usize = 64;
+
+/// The closest points between two convex objects as found by [`GjkClosestPoint`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GjkClosestPoints {
+    /// The squared distance between the objects, zero (or less than the tolerance squared)
+    /// when they intersect.
+    pub distance_squared: f32,
+    /// The closest point on the first object.
+    pub point_a: Vec3,
+    /// The closest point on the second object.
+    pub point_b: Vec3,
+}
+
+/// The first contact of a shape cast as found by [`GjkClosestPoint::cast_shape`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GjkShapeCast {
+    /// The fraction of the cast direction at which the objects first touch.
+    pub fraction: f32,
+    /// The contact point on the first object, after it has been moved to the time of impact.
+    pub point_a: Vec3,
+    /// The contact point on the second object.
+    pub point_b: Vec3,
+    /// The axis from the first object towards the second at the time of impact, its length is
+    /// the distance between the objects without their convex radius. This is near zero when
+    /// the objects without their convex radius start out intersecting.
+    pub separating_axis: Vec3,
+}
+
+/// An implementation of the Gilbert-Johnson-Keerthi algorithm, based on the formulation in
+/// "Collision Detection in Interactive 3D Environments" by Gino van den Bergen.
+///
+/// The simplex is kept between queries so that it can be handed to a penetration depth
+/// algorithm when the objects intersect.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GjkClosestPoint {
+    /// Points on the Minkowski difference `A - B` (or `x - A` for casts).
+    y: [Vec3; 4],
+    /// Support points on `A`.
+    p: [Vec3; 4],
+    /// Support points on `B`.
+    q: [Vec3; 4],
+    /// Barycentric weights of the closest point.
+    weights: [f32; 4],
+    count: usize,
+}
+
+impl GjkClosestPoint {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the points of the last simplex as `(y, p, q)`, where `y = p - q`.
+    pub fn simplex(&self) -> ([Vec3; 4], [Vec3; 4], [Vec3; 4], usize) {
+        (self.y, self.p, self.q, self.count)
+    }
+
+    /// Returns whether `a` and `b` are within `tolerance` of each other. `v` is used as initial
+    /// separating axis and receives the last axis that was tested.
+    pub fn intersects<A, B>(&mut self, a: &A, b: &B, tolerance: f32, v: &mut Vec3) -> bool
+    where
+        A: Support + ?Sized,
+        B: Support + ?Sized,
+    {
+        self.closest_points(a, b, tolerance, 0.0, v)
+            .is_some_and(|result| result.distance_squared <= tolerance * tolerance)
+    }
+
+    /// Finds the closest points between `a` and `b`. Returns `None` when the objects are
+    /// further than `max_distance_squared` apart. `v` is used as initial guess for the closest
+    /// point on `A - B` and receives the final closest point (`point_a - point_b`).
+    pub fn closest_points<A, B>(
+        &mut self,
+        a: &A,
+        b: &B,
+        tolerance: f32,
+        max_distance_squared: f32,
+        v: &mut Vec3,
+    ) -> Option<GjkClosestPoints>
+    where
+        A: Support + ?Sized,
+        B: Support + ?Sized,
+    {
+        let tolerance_squared = tolerance * tolerance;
+        if v.length_squared() <= f32::MIN_POSITIVE {
+            *v = Vec3::X;
+        }
+        let mut v_length_squared = v.length_squared();
+        let mut previous_length_squared = f32::MAX;
+        self.count = 0;
+
+        for _ in 0..MAX_ITERATIONS {
+            let p = a.support(-*v);
+            let q = b.support(*v);
+            let w = p - q;
+
+            // The distance along `v` is a lower bound on the distance between the objects.
+            let dot = v.dot(w);
+            if dot > 0.0 && dot * dot > v_length_squared * max_distance_squared {
+                return None;
+            }
+
+            // When `w` is no closer than the current point we've converged.
+            if self.count > 0 && v_length_squared - dot <= 1.0e-6 * v_length_squared {
+                break;
+            }
+
+            if !self.add_point(w, p, q) {
+                break;
+            }
+            let closest = closest_point_on_simplex(&self.y[..self.count]);
+            self.reduce(closest.set, closest.weights);
+            *v = closest.point;
+            v_length_squared = closest.point.length_squared();
+
+            if closest.set == 0b1111 || v_length_squared <= tolerance_squared {
+                // The origin lies inside the Minkowski difference.
+                break;
+            }
+            if previous_length_squared - v_length_squared <= f32::EPSILON * previous_length_squared
+            {
+                break;
+            }
+            previous_length_squared = v_length_squared;
+        }
+
+        let (point_a, point_b) = self.closest_points_on_objects();
+        Some(GjkClosestPoints {
+            distance_squared: v_length_squared,
+            point_a,
+            point_b,
+        })
+    }
+
+    /// Casts a ray from `origin` along `direction` against the convex object `a`. On a hit
+    /// the fraction of `direction` is returned, this is zero when `origin` lies inside `a`.
+    /// Only hits closer than `max_fraction` are reported.
+    pub fn cast_ray<A>(
+        &mut self,
+        origin: Vec3,
+        direction: Vec3,
+        tolerance: f32,
+        a: &A,
+        max_fraction: f32,
+    ) -> Option<f32>
+    where
+        A: Support + ?Sized,
+    {
+        let tolerance_squared = tolerance * tolerance;
+        let mut lambda = 0.0;
+        let mut x = origin;
+        let mut v = x - a.support(Vec3::X);
+        self.count = 0;
+
+        for _ in 0..MAX_ITERATIONS {
+            let p = a.support(v);
+            let w = x - p;
+
+            let vw = v.dot(w);
+            if vw > 0.0 {
+                // The ray start lies on the positive side of the support plane, advance it.
+                let vr = v.dot(direction);
+                if vr >= 0.0 {
+                    return None;
+                }
+                lambda -= vw / vr;
+                if lambda >= max_fraction {
+                    return None;
+                }
+                x = origin + direction * lambda;
+                for i in 0..self.count {
+                    self.y[i] = x - self.p[i];
+                }
+            }
+
+            if !self.add_point(x - p, p, Vec3::ZERO) {
+                break;
+            }
+            let closest = closest_point_on_simplex(&self.y[..self.count]);
+            self.reduce(closest.set, closest.weights);
+            v = closest.point;
+
+            if closest.set == 0b1111 || v.length_squared() <= tolerance_squared {
+                return Some(lambda);
+            }
+        }
+
+        (v.length_squared() <= tolerance_squared * 4.0).then_some(lambda)
+    }
+
+    /// Sweeps `a` along `direction` against `b` using conservative advancement, as described
+    /// in "Ray Casting against General Convex Objects with Application to Continuous Collision
+    /// Detection" by Gino van den Bergen. Only hits closer than `max_fraction` are reported,
+    /// objects that start out intersecting hit at fraction zero.
+    ///
+    /// The objects are given without their convex radius, which they report through
+    /// [`Support::convex_radius`]. They touch when they are within `tolerance` of each other.
+    pub fn cast_shape<A, B>(
+        &mut self,
+        a: &A,
+        b: &B,
+        direction: Vec3,
+        tolerance: f32,
+        max_fraction: f32,
+    ) -> Option<GjkShapeCast>
+    where
+        A: Support + ?Sized,
+        B: Support + ?Sized,
+    {
+        let radius_a = a.convex_radius();
+        let radius_b = b.convex_radius();
+        let radius = radius_a + radius_b;
+        let tolerance_squared = tolerance * tolerance;
+
+        // This is a ray cast from the origin along `direction` against `B - A`, where `x` is
+        // the position of the ray and `v` points from the closest point of `B - A` to `x`.
+        let mut lambda = 0.0;
+        let mut x = Vec3::ZERO;
+        let mut v = a.support(Vec3::X) - b.support(-Vec3::X);
+        let mut previous_length_squared = f32::MAX;
+        self.count = 0;
+
+        for _ in 0..MAX_ITERATIONS {
+            let p = a.support(-v);
+            let q = b.support(v);
+            let mut w = x - (q - p);
+
+            let vw = v.dot(w) - radius * v.length();
+            if vw > 0.0 {
+                // The objects are separated along `v`, advance them until the convex radii
+                // touch along this axis.
+                let vr = v.dot(direction);
+                if vr >= 0.0 {
+                    return None;
+                }
+                let previous_lambda = lambda;
+                lambda -= vw / vr;
+                if lambda >= max_fraction {
+                    return None;
+                }
+                if lambda == previous_lambda && self.count > 0 {
+                    break;
+                }
+                x = direction * lambda;
+                w = x - (q - p);
+                for i in 0..self.count {
+                    self.y[i] = x - (self.q[i] - self.p[i]);
+                }
+                previous_length_squared = f32::MAX;
+            } else if self.count > 0 {
+                // When `w` is no closer than the current point the distance has converged.
+                let v_length_squared = v.length_squared();
+                if v_length_squared - v.dot(w) <= tolerance * v_length_squared.sqrt() {
+                    break;
+                }
+            }
+
+            if !self.add_point(w, p, q) {
+                break;
+            }
+            let closest = closest_point_on_simplex(&self.y[..self.count]);
+            self.reduce(closest.set, closest.weights);
+            v = closest.point;
+            let v_length_squared = v.length_squared();
+
+            if closest.set == 0b1111 || v_length_squared <= tolerance_squared {
+                // The objects without their convex radius intersect.
+                break;
+            }
+            if previous_length_squared - v_length_squared <= f32::EPSILON * previous_length_squared
+            {
+                break;
+            }
+            previous_length_squared = v_length_squared;
+        }
+
+        // The last advance may have moved `x` after `v` was found.
+        let (point_a, point_b) = self.closest_points_on_objects();
+        let (point_a, separating_axis) = (point_a + x, point_b - point_a - x);
+        let normal = separating_axis.normalize_or_zero();
+        Some(GjkShapeCast {
+            fraction: lambda,
+            point_a: point_a + normal * radius_a,
+            point_b: point_b - normal * radius_b,
+            separating_axis,
+        })
+    }
+
+    fn add_point(&mut self, y: Vec3, p: Vec3, q: Vec3) -> bool {
+        if self.count >= 4 || self.y[..self.count].contains(&y) {
+            return false;
+        }
+        self.y[self.count] = y;
+        self.p[self.count] = p;
+        self.q[self.count] = q;
+        self.count += 1;
+        true
+    }
+
+    /// Removes the points that are not in `set` from the simplex.
+    fn reduce(&mut self, set: u32, weights: [f32; 4]) {
+        let mut count = 0;
+        for (i, &weight) in weights.iter().enumerate().take(self.count) {
+            if set & (1 << i) != 0 {
+                self.y[count] = self.y[i];
+                self.p[count] = self.p[i];
+                self.q[count] = self.q[i];
+                self.weights[count] = weight;
+                count += 1;
+            }
+        }
+        self.count = count;
+    }
+
+    fn closest_points_on_objects(&self) -> (Vec3, Vec3) {
+        let mut point_a = Vec3::ZERO;
+        let mut point_b = Vec3::ZERO;
+        for i in 0..self.count {
+            point_a += self.p[i] * self.weights[i];
+            point_b += self.q[i] * self.weights[i];
+        }
+        (point_a, point_b)
+    }
+}
@@ -0,0 +1,25 @@
+/// A triangle that refers to its vertices by index, wound counter clockwise when seen from the
+/// front.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IndexedTriangle {
+    pub indices: [u32; 3],
+    /// The material of the triangle, its meaning is up to the user.
+    pub material_index: u32,
+}
+
+impl IndexedTriangle {
+    #[inline]
+    pub const fn new(i0: u32, i1: u32, i2: u32, material_index: u32) -> Self {
+        Self {
+            indices: [i0, i1, i2],
+            material_index,
+        }
+    }
+
+    /// Returns whether two or more indices refer to the same vertex.
+    #[inline]
+    pub fn is_degenerate(&self) -> bool {
+        let [a, b, c] = self.indices;
+        a == b || b == c || c == a
+    }
+}
@@ -0,0 +1,49 @@
+pub(crate) mod aabox;
+pub use aabox::AABox;
+
+pub(crate) mod aabox4;
+pub use aabox4::AABox4;
+
+pub(crate) mod clip_poly;
+pub use clip_poly::{clip_poly_vs_edge, clip_poly_vs_plane, clip_poly_vs_poly};
+
+pub(crate) mod closest_point;
+pub use closest_point::{
+    closest_point_on_line, closest_point_on_simplex, closest_point_on_tetrahedron,
+    closest_point_on_triangle, ClosestPoint,
+};
+
+pub(crate) mod convex_hull_builder;
+pub use convex_hull_builder::{ConvexHull, ConvexHullBuilder, ConvexHullError, ConvexHullFace};
+
+pub(crate) mod convex_support;
+pub use convex_support::{
+    AddConvexRadius, PointConvexSupport, Support, TransformedConvexObject, TriangleConvexSupport,
+};
+
+pub(crate) mod epa_penetration_depth;
+pub use epa_penetration_depth::{EpaPenetrationDepth, PenetrationDepth};
+
+pub(crate) mod gjk_closest_point;
+pub use gjk_closest_point::{GjkClosestPoint, GjkClosestPoints, GjkShapeCast};
+
+pub(crate) mod indexed_triangle;
+pub use indexed_triangle::IndexedTriangle;
+
+pub(crate) mod plane;
+pub use plane::Plane;
+
+pub(crate) mod ray_aabox;
+pub use ray_aabox::{ray_aabox, ray_aabox4, RayInvDirection};
+
+pub(crate) mod ray_capsule;
+pub use ray_capsule::ray_capsule;
+
+pub(crate) mod ray_cylinder;
+pub use ray_cylinder::ray_cylinder;
+
+pub(crate) mod ray_sphere;
+pub use ray_sphere::ray_sphere;
+
+pub(crate) mod ray_triangle;
+pub use ray_triangle::ray_triangle;
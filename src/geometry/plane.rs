@@ -0,0 +1,52 @@
+use crate::math::Vec3;
+
+/// A plane defined by `normal.dot(point) + constant == 0`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Plane {
+    pub normal: Vec3,
+    pub constant: f32,
+}
+
+impl Plane {
+    /// Creates a new plane from a normal and a constant.
+    #[inline]
+    pub const fn new(normal: Vec3, constant: f32) -> Self {
+        Self { normal, constant }
+    }
+
+    /// Creates a new plane from a normal and a point on the plane.
+    #[inline]
+    pub fn from_point_and_normal(point: Vec3, normal: Vec3) -> Self {
+        Self::new(normal, -normal.dot(point))
+    }
+
+    /// Creates a new plane through three points, front facing when they wind counter clockwise.
+    #[inline]
+    pub fn from_points_ccw(a: Vec3, b: Vec3, c: Vec3) -> Self {
+        Self::from_point_and_normal(a, (b - a).cross(c - a).normalize())
+    }
+
+    /// Returns the signed distance from `point` to the plane, positive in front of the plane.
+    #[inline]
+    pub fn signed_distance(&self, point: Vec3) -> f32 {
+        self.normal.dot(point) + self.constant
+    }
+
+    /// Returns a point on the plane closest to the origin.
+    #[inline]
+    pub fn point_on_plane(&self) -> Vec3 {
+        self.normal * -self.constant
+    }
+
+    /// Returns the plane offset along its normal by `distance`.
+    #[inline]
+    pub fn offset(&self, distance: f32) -> Self {
+        Self::new(self.normal, self.constant - distance)
+    }
+
+    /// Projects `point` onto the plane.
+    #[inline]
+    pub fn project_point(&self, point: Vec3) -> Vec3 {
+        point - self.normal * self.signed_distance(point)
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{
+    geometry::AABox4,
+    math::{Vec3, Vec4},
+};
+
+/// Returns the fraction of `direction` at which the ray starting at `origin` enters the box,
+/// zero if it starts inside or `f32::MAX` when it misses.
+pub fn ray_aabox(origin: Vec3, direction: Vec3, min: Vec3, max: Vec3) -> f32 {
+    let mut near = 0.0f32;
+    let mut far = f32::MAX;
+    for axis in 0..3 {
+        if direction[axis].abs() <= f32::MIN_POSITIVE {
+            // The ray is parallel to the slab, it can only hit when it starts inside of it.
+            if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                return f32::MAX;
+            }
+            continue;
+        }
+        let inv_direction = 1.0 / direction[axis];
+        let t1 = (min[axis] - origin[axis]) * inv_direction;
+        let t2 = (max[axis] - origin[axis]) * inv_direction;
+        near = near.max(t1.min(t2));
+        far = far.min(t1.max(t2));
+        if near > far {
+            return f32::MAX;
+        }
+    }
+    near
+}
+
+/// The reciprocal of a ray direction, precomputed to test the ray against many boxes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayInvDirection {
+    pub inv_direction: Vec3,
+    /// Whether the ray is parallel to each axis, the inverse direction is undefined there.
+    pub is_parallel: [bool; 3],
+}
+
+impl RayInvDirection {
+    #[inline]
+    pub fn new(direction: Vec3) -> Self {
+        let mut inv_direction = Vec3::ONE;
+        let mut is_parallel = [false; 3];
+        for axis in 0..3 {
+            if direction[axis].abs() <= f32::MIN_POSITIVE {
+                is_parallel[axis] = true;
+            } else {
+                inv_direction[axis] = 1.0 / direction[axis];
+            }
+        }
+        Self {
+            inv_direction,
+            is_parallel,
+        }
+    }
+}
+
+/// Tests a ray against four boxes at once, returns the fraction at which the ray enters each
+/// box, zero if it starts inside or `f32::MAX` when it misses.
+pub fn ray_aabox4(origin: Vec3, inv_direction: &RayInvDirection, boxes: &AABox4) -> Vec4 {
+    let mut near = Vec4::ZERO;
+    let mut far = Vec4::splat(f32::MAX);
+    let mut missed = [false; 4];
+    let slabs = [
+        (boxes.min_x, boxes.max_x),
+        (boxes.min_y, boxes.max_y),
+        (boxes.min_z, boxes.max_z),
+    ];
+    for (axis, (min, max)) in slabs.into_iter().enumerate() {
+        if inv_direction.is_parallel[axis] {
+            // The ray is parallel to the slab, it can only hit when it starts inside of it.
+            for lane in 0..4 {
+                missed[lane] |= origin[axis] < min[lane] || origin[axis] > max[lane];
+            }
+            continue;
+        }
+        let t1 = (min - origin[axis]) * inv_direction.inv_direction[axis];
+        let t2 = (max - origin[axis]) * inv_direction.inv_direction[axis];
+        near = near.max(t1.min(t2));
+        far = far.min(t1.max(t2));
+    }
+
+    let mut result = near;
+    for lane in 0..4 {
+        if missed[lane] || near[lane] > far[lane] {
+            result[lane] = f32::MAX;
+        }
+    }
+    result
+}
@@ -0,0 +1,32 @@
+#[cfg(all(no_std, libm, not(test)))]
+use crate::math::libm::F32Ext;
+use crate::{geometry::ray_sphere, math::Vec3};
+
+/// Returns the fraction of `direction` at which the ray starting at `origin` enters a capsule
+/// around the Y axis, zero if it starts inside or `f32::MAX` when it misses.
+pub fn ray_capsule(origin: Vec3, direction: Vec3, half_height: f32, radius: f32) -> f32 {
+    let radius_squared = radius * radius;
+    let clamped = Vec3::new(0.0, origin.y.clamp(-half_height, half_height), 0.0);
+    if origin.distance_squared(clamped) <= radius_squared {
+        return 0.0;
+    }
+
+    // Test against the infinite cylinder first, only accepting hits on the cylindrical part.
+    let mut fraction = f32::MAX;
+    let a = direction.x * direction.x + direction.z * direction.z;
+    if a > f32::MIN_POSITIVE {
+        let b = 2.0 * (origin.x * direction.x + origin.z * direction.z);
+        let c = origin.x * origin.x + origin.z * origin.z - radius_squared;
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant >= 0.0 {
+            let t = (-b - discriminant.sqrt()) / (2.0 * a);
+            if t >= 0.0 && (origin.y + direction.y * t).abs() <= half_height {
+                fraction = t;
+            }
+        }
+    }
+
+    let top = Vec3::new(0.0, half_height, 0.0);
+    fraction = fraction.min(ray_sphere(origin, direction, top, radius));
+    fraction.min(ray_sphere(origin, direction, -top, radius))
+}
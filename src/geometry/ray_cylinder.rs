@@ -0,0 +1,40 @@
+#[cfg(all(no_std, libm, not(test)))]
+use crate::math::libm::F32Ext;
+use crate::math::Vec3;
+
+/// Returns the fraction of `direction` at which the ray starting at `origin` enters a cylinder
+/// around the Y axis, zero if it starts inside or `f32::MAX` when it misses.
+pub fn ray_cylinder(origin: Vec3, direction: Vec3, half_height: f32, radius: f32) -> f32 {
+    let radius_squared = radius * radius;
+    let origin_radius_squared = origin.x * origin.x + origin.z * origin.z;
+    if origin.y.abs() <= half_height && origin_radius_squared <= radius_squared {
+        return 0.0;
+    }
+
+    let mut fraction = f32::MAX;
+    let a = direction.x * direction.x + direction.z * direction.z;
+    if a > f32::MIN_POSITIVE {
+        let b = 2.0 * (origin.x * direction.x + origin.z * direction.z);
+        let c = origin_radius_squared - radius_squared;
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant >= 0.0 {
+            let t = (-b - discriminant.sqrt()) / (2.0 * a);
+            if t >= 0.0 && (origin.y + direction.y * t).abs() <= half_height {
+                fraction = t;
+            }
+        }
+    }
+
+    if direction.y.abs() > f32::MIN_POSITIVE {
+        for cap in [-half_height, half_height] {
+            let t = (cap - origin.y) / direction.y;
+            if t >= 0.0 && t < fraction {
+                let point = origin + direction * t;
+                if point.x * point.x + point.z * point.z <= radius_squared {
+                    fraction = t;
+                }
+            }
+        }
+    }
+    fraction
+}
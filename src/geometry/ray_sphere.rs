@@ -0,0 +1,27 @@
+#[cfg(all(no_std, libm, not(test)))]
+use crate::math::libm::F32Ext;
+use crate::math::Vec3;
+
+/// Returns the fraction of `direction` at which the ray starting at `origin` enters the sphere,
+/// zero if it starts inside or `f32::MAX` when it misses.
+pub fn ray_sphere(origin: Vec3, direction: Vec3, center: Vec3, radius: f32) -> f32 {
+    let center_origin = origin - center;
+    let c = center_origin.length_squared() - radius * radius;
+    if c <= 0.0 {
+        return 0.0;
+    }
+
+    let a = direction.length_squared();
+    let b = 2.0 * direction.dot(center_origin);
+    let discriminant = b * b - 4.0 * a * c;
+    if a <= f32::MIN_POSITIVE || discriminant < 0.0 {
+        return f32::MAX;
+    }
+
+    let fraction = (-b - discriminant.sqrt()) / (2.0 * a);
+    if fraction < 0.0 {
+        f32::MAX
+    } else {
+        fraction
+    }
+}
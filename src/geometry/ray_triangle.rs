@@ -0,0 +1,32 @@
+use crate::math::Vec3;
+
+/// Returns the fraction of `direction` at which the ray starting at `origin` hits the triangle
+/// `v0`, `v1`, `v2` from either side, or `f32::MAX` when it misses.
+pub fn ray_triangle(origin: Vec3, direction: Vec3, v0: Vec3, v1: Vec3, v2: Vec3) -> f32 {
+    // Möller-Trumbore intersection.
+    let e1 = v1 - v0;
+    let e2 = v2 - v0;
+    let p = direction.cross(e2);
+    let determinant = e1.dot(p);
+    if determinant.abs() <= f32::EPSILON * e1.length_squared().max(e2.length_squared()) {
+        return f32::MAX;
+    }
+    let inv_determinant = 1.0 / determinant;
+
+    let s = origin - v0;
+    let u = s.dot(p) * inv_determinant;
+    if !(0.0..=1.0).contains(&u) {
+        return f32::MAX;
+    }
+    let q = s.cross(e1);
+    let v = direction.dot(q) * inv_determinant;
+    if v < 0.0 || u + v > 1.0 {
+        return f32::MAX;
+    }
+    let fraction = e2.dot(q) * inv_determinant;
+    if fraction < 0.0 {
+        f32::MAX
+    } else {
+        fraction
+    }
+}
@@ -2,4 +2,12 @@
 #![cfg_attr(feature = "nightly", feature(stdsimd))]
 #![cfg_attr(target_arch = "spirv", feature(repr_simd))]
 
+extern crate alloc;
+
+pub mod body;
+pub mod broad_phase;
+pub mod collision;
+pub mod geometry;
 pub mod math;
+pub mod physics;
+pub mod shape;
@@ -0,0 +1,304 @@
+#[cfg(not(spirv))]
+use core::fmt;
+use core::ops::*;
+
+use crate::math::{quat::Quat, vec3::Vec3};
+
+/// Creates a 3x3 matrix from three column vectors.
+#[inline(always)]
+pub const fn mat33(x_axis: Vec3, y_axis: Vec3, z_axis: Vec3) -> Mat33 {
+    Mat33::from_cols(x_axis, y_axis, z_axis)
+}
+
+/// A 3x3 column major matrix. SIMD vector types are used for storage of the columns.
+#[derive(Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct Mat33 {
+    pub x_axis: Vec3,
+    pub y_axis: Vec3,
+    pub z_axis: Vec3,
+}
+
+impl Mat33 {
+    /// All zeroes.
+    pub const ZERO: Self = Self::from_cols(Vec3::ZERO, Vec3::ZERO, Vec3::ZERO);
+
+    /// The identity matrix.
+    pub const IDENTITY: Self = Self::from_cols(Vec3::X, Vec3::Y, Vec3::Z);
+
+    /// Creates a new matrix from three column vectors.
+    #[inline(always)]
+    pub const fn from_cols(x_axis: Vec3, y_axis: Vec3, z_axis: Vec3) -> Self {
+        Self {
+            x_axis,
+            y_axis,
+            z_axis,
+        }
+    }
+
+    /// Creates a new matrix with `diagonal` on its diagonal and zeroes elsewhere.
+    #[inline]
+    pub fn from_diagonal(diagonal: Vec3) -> Self {
+        Self::from_cols(
+            Vec3::new(diagonal.x, 0.0, 0.0),
+            Vec3::new(0.0, diagonal.y, 0.0),
+            Vec3::new(0.0, 0.0, diagonal.z),
+        )
+    }
+
+    /// Creates a rotation matrix from a normalized quaternion.
+    #[inline]
+    pub fn from_quat(q: Quat) -> Self {
+        let (x2, y2, z2) = (q.x + q.x, q.y + q.y, q.z + q.z);
+        let (xx, xy, xz) = (q.x * x2, q.x * y2, q.x * z2);
+        let (yy, yz, zz) = (q.y * y2, q.y * z2, q.z * z2);
+        let (wx, wy, wz) = (q.w * x2, q.w * y2, q.w * z2);
+        Self::from_cols(
+            Vec3::new(1.0 - (yy + zz), xy + wz, xz - wy),
+            Vec3::new(xy - wz, 1.0 - (xx + zz), yz + wx),
+            Vec3::new(xz + wy, yz - wx, 1.0 - (xx + yy)),
+        )
+    }
+
+    /// Creates the matrix that performs `v.cross(rhs)` when multiplied with `rhs`.
+    #[inline]
+    pub fn from_cross_product(v: Vec3) -> Self {
+        Self::from_cols(
+            Vec3::new(0.0, v.z, -v.y),
+            Vec3::new(-v.z, 0.0, v.x),
+            Vec3::new(v.y, -v.x, 0.0),
+        )
+    }
+
+    /// Creates the outer product `a * b^T`.
+    #[inline]
+    pub fn from_outer_product(a: Vec3, b: Vec3) -> Self {
+        Self::from_cols(a * b.x, a * b.y, a * b.z)
+    }
+
+    /// Returns the column at `index`.
+    #[inline]
+    pub fn col(&self, index: usize) -> Vec3 {
+        match index {
+            0 => self.x_axis,
+            1 => self.y_axis,
+            2 => self.z_axis,
+            _ => panic!("index out of bounds"),
+        }
+    }
+
+    /// Returns a mutable reference to the column at `index`.
+    #[inline]
+    pub fn col_mut(&mut self, index: usize) -> &mut Vec3 {
+        match index {
+            0 => &mut self.x_axis,
+            1 => &mut self.y_axis,
+            2 => &mut self.z_axis,
+            _ => panic!("index out of bounds"),
+        }
+    }
+
+    /// Returns the row at `index`.
+    #[inline]
+    pub fn row(&self, index: usize) -> Vec3 {
+        Vec3::new(self.x_axis[index], self.y_axis[index], self.z_axis[index])
+    }
+
+    /// Returns the diagonal of the matrix.
+    #[inline]
+    pub fn diagonal(&self) -> Vec3 {
+        Vec3::new(self.x_axis.x, self.y_axis.y, self.z_axis.z)
+    }
+
+    #[inline]
+    pub fn transpose(&self) -> Self {
+        Self::from_cols(self.row(0), self.row(1), self.row(2))
+    }
+
+    #[inline]
+    pub fn determinant(&self) -> f32 {
+        self.z_axis.dot(self.x_axis.cross(self.y_axis))
+    }
+
+    /// Returns the inverse of the matrix, the result is non-finite if it is not invertible.
+    #[inline]
+    pub fn inverse(&self) -> Self {
+        let tmp0 = self.y_axis.cross(self.z_axis);
+        let tmp1 = self.z_axis.cross(self.x_axis);
+        let tmp2 = self.x_axis.cross(self.y_axis);
+        let inv_det = self.z_axis.dot(tmp2).recip();
+        Self::from_cols(tmp0, tmp1, tmp2).transpose() * inv_det
+    }
+
+    /// Returns the inverse of the matrix, or `None` when the determinant is (close to) zero.
+    #[inline]
+    pub fn try_inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        (det.abs() > f32::MIN_POSITIVE && det.is_finite()).then(|| self.inverse())
+    }
+
+    #[inline]
+    pub fn mul_vec3(&self, rhs: Vec3) -> Vec3 {
+        self.x_axis * rhs.x + self.y_axis * rhs.y + self.z_axis * rhs.z
+    }
+
+    #[inline]
+    pub fn mul_mat33(&self, rhs: &Self) -> Self {
+        Self::from_cols(
+            self.mul_vec3(rhs.x_axis),
+            self.mul_vec3(rhs.y_axis),
+            self.mul_vec3(rhs.z_axis),
+        )
+    }
+
+    /// Returns whether all elements of `self` and `rhs` differ by no more than `max_abs_diff`.
+    #[inline]
+    pub fn abs_diff_eq(&self, rhs: &Self, max_abs_diff: f32) -> bool {
+        self.x_axis.abs_diff_eq(rhs.x_axis, max_abs_diff)
+            && self.y_axis.abs_diff_eq(rhs.y_axis, max_abs_diff)
+            && self.z_axis.abs_diff_eq(rhs.z_axis, max_abs_diff)
+    }
+
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.x_axis.is_finite() && self.y_axis.is_finite() && self.z_axis.is_finite()
+    }
+}
+
+impl Default for Mat33 {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Add<Mat33> for Mat33 {
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self::from_cols(
+            self.x_axis + rhs.x_axis,
+            self.y_axis + rhs.y_axis,
+            self.z_axis + rhs.z_axis,
+        )
+    }
+}
+
+impl AddAssign<Mat33> for Mat33 {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Mat33> for Mat33 {
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_cols(
+            self.x_axis - rhs.x_axis,
+            self.y_axis - rhs.y_axis,
+            self.z_axis - rhs.z_axis,
+        )
+    }
+}
+
+impl SubAssign<Mat33> for Mat33 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Mat33 {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self {
+        Self::from_cols(-self.x_axis, -self.y_axis, -self.z_axis)
+    }
+}
+
+impl Mul<Mat33> for Mat33 {
+    type Output = Self;
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        self.mul_mat33(&rhs)
+    }
+}
+
+impl MulAssign<Mat33> for Mat33 {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.mul_mat33(&rhs);
+    }
+}
+
+impl Mul<Vec3> for Mat33 {
+    type Output = Vec3;
+    #[inline]
+    fn mul(self, rhs: Vec3) -> Vec3 {
+        self.mul_vec3(rhs)
+    }
+}
+
+impl Mul<f32> for Mat33 {
+    type Output = Self;
+    #[inline]
+    fn mul(self, rhs: f32) -> Self {
+        Self::from_cols(self.x_axis * rhs, self.y_axis * rhs, self.z_axis * rhs)
+    }
+}
+
+impl MulAssign<f32> for Mat33 {
+    #[inline]
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = *self * rhs;
+    }
+}
+
+impl Mul<Mat33> for f32 {
+    type Output = Mat33;
+    #[inline]
+    fn mul(self, rhs: Mat33) -> Mat33 {
+        rhs * self
+    }
+}
+
+impl From<Quat> for Mat33 {
+    #[inline]
+    fn from(q: Quat) -> Self {
+        Self::from_quat(q)
+    }
+}
+
+impl From<[[f32; 3]; 3]> for Mat33 {
+    #[inline]
+    fn from(m: [[f32; 3]; 3]) -> Self {
+        Self::from_cols(m[0].into(), m[1].into(), m[2].into())
+    }
+}
+
+impl From<Mat33> for [[f32; 3]; 3] {
+    #[inline]
+    fn from(m: Mat33) -> Self {
+        [m.x_axis.into(), m.y_axis.into(), m.z_axis.into()]
+    }
+}
+
+#[cfg(not(spirv))]
+impl fmt::Display for Mat33 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}, {}]", self.x_axis, self.y_axis, self.z_axis)
+    }
+}
+
+#[cfg(not(spirv))]
+impl fmt::Debug for Mat33 {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct(stringify!(Mat33))
+            .field("x_axis", &self.x_axis)
+            .field("y_axis", &self.y_axis)
+            .field("z_axis", &self.z_axis)
+            .finish()
+    }
+}
@@ -1,6 +1,7 @@
+#[allow(dead_code)]
 pub(crate) mod deref;
 
-#[cfg(all(no_simd, no_std, libm))]
+#[cfg(all(no_std, libm))]
 pub(crate) mod libm;
 
 pub(crate) mod float2;
@@ -12,12 +13,18 @@ pub use float3::{float3, Float3};
 pub(crate) mod float4;
 pub use float4::{float4, Float4};
 
+pub(crate) mod mat33;
+pub use mat33::{mat33, Mat33};
+
 pub(crate) mod quat;
 pub use quat::{quat, Quat};
 
 #[cfg(any(not(no_simd), not(any(arm_neon, x86_sse, wasm_simd128))))]
 pub(crate) mod simd;
 
+pub(crate) mod uvec3;
+pub use uvec3::{uvec3, UVec3};
+
 pub(crate) mod vec3;
 pub use vec3::{vec3, Vec3};
 
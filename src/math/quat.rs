@@ -2,8 +2,10 @@
 use core::fmt;
 use core::{f32, ops::*};
 
+#[cfg(all(no_std, libm, not(test)))]
+use crate::math::libm::F32Ext;
 use crate::math::simd::*;
-use crate::math::vec4::Vec4;
+use crate::math::{mat33::Mat33, vec3::Vec3, vec4::Vec4};
 
 /// Creates a 4-dimensional vector.
 #[inline(always)]
@@ -43,6 +45,198 @@ impl Quat {
     pub const fn from_vec4(v: Vec4) -> Self {
         Self(v)
     }
+
+    /// Creates a quaternion for a normalized rotation `axis` and `angle` (in radians).
+    #[inline]
+    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Self {
+        let (s, c) = (angle * 0.5).sin_cos();
+        let v = axis * s;
+        Self::from_xyzw(v.x, v.y, v.z, c)
+    }
+
+    /// Creates a quaternion from the `angle` (in radians) around the x axis.
+    #[inline]
+    pub fn from_rotation_x(angle: f32) -> Self {
+        let (s, c) = (angle * 0.5).sin_cos();
+        Self::from_xyzw(s, 0.0, 0.0, c)
+    }
+
+    /// Creates a quaternion from the `angle` (in radians) around the y axis.
+    #[inline]
+    pub fn from_rotation_y(angle: f32) -> Self {
+        let (s, c) = (angle * 0.5).sin_cos();
+        Self::from_xyzw(0.0, s, 0.0, c)
+    }
+
+    /// Creates a quaternion from the `angle` (in radians) around the z axis.
+    #[inline]
+    pub fn from_rotation_z(angle: f32) -> Self {
+        let (s, c) = (angle * 0.5).sin_cos();
+        Self::from_xyzw(0.0, 0.0, s, c)
+    }
+
+    /// Creates the shortest rotation that rotates the normalized vector `from` onto `to`.
+    pub fn from_rotation_arc(from: Vec3, to: Vec3) -> Self {
+        let dot = from.dot(to);
+        if dot < -1.0 + 1.0e-6 {
+            // Vectors point in opposite directions, rotate half a turn around any perpendicular.
+            let axis = if from.x.abs() > from.y.abs() {
+                Vec3::new(-from.z, 0.0, from.x)
+            } else {
+                Vec3::new(0.0, from.z, -from.y)
+            };
+            return Self::from_axis_angle(axis.normalize(), f32::consts::PI);
+        }
+        let v = from.cross(to);
+        Self::from_xyzw(v.x, v.y, v.z, 1.0 + dot).normalize()
+    }
+
+    /// Creates a quaternion from a rotation matrix.
+    pub fn from_mat33(m: &Mat33) -> Self {
+        let (m00, m01, m02) = (m.x_axis.x, m.y_axis.x, m.z_axis.x);
+        let (m10, m11, m12) = (m.x_axis.y, m.y_axis.y, m.z_axis.y);
+        let (m20, m21, m22) = (m.x_axis.z, m.y_axis.z, m.z_axis.z);
+        let trace = m00 + m11 + m22;
+        // Divide by the largest component to stay accurate for every rotation.
+        if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self::from_xyzw((m21 - m12) / s, (m02 - m20) / s, (m10 - m01) / s, 0.25 * s)
+        } else if m00 > m11 && m00 > m22 {
+            let s = (1.0 + m00 - m11 - m22).sqrt() * 2.0;
+            Self::from_xyzw(0.25 * s, (m01 + m10) / s, (m02 + m20) / s, (m21 - m12) / s)
+        } else if m11 > m22 {
+            let s = (1.0 + m11 - m00 - m22).sqrt() * 2.0;
+            Self::from_xyzw((m01 + m10) / s, 0.25 * s, (m12 + m21) / s, (m02 - m20) / s)
+        } else {
+            let s = (1.0 + m22 - m00 - m11).sqrt() * 2.0;
+            Self::from_xyzw((m02 + m20) / s, (m12 + m21) / s, 0.25 * s, (m10 - m01) / s)
+        }
+    }
+
+    /// Returns the vector part of the quaternion.
+    #[inline]
+    pub fn xyz(self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    #[inline]
+    pub fn dot(self, rhs: Self) -> f32 {
+        self.0.dot(rhs.0)
+    }
+
+    #[inline]
+    pub fn length(self) -> f32 {
+        self.length_squared().sqrt()
+    }
+
+    #[inline]
+    pub fn length_squared(self) -> f32 {
+        self.dot(self)
+    }
+
+    #[inline]
+    pub fn normalize(self) -> Self {
+        Self(self.0 / self.length())
+    }
+
+    /// Returns whether `self` is of length 1.0 within `1e-4`.
+    #[inline]
+    pub fn is_normalized(self) -> bool {
+        (self.length_squared() - 1.0).abs() <= 2e-4
+    }
+
+    /// Returns the conjugate, which is the inverse rotation for a normalized quaternion.
+    #[inline]
+    pub fn conjugate(self) -> Self {
+        Self::from_xyzw(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// Returns the inverse of a normalized quaternion.
+    #[inline]
+    pub fn inverse(self) -> Self {
+        self.conjugate()
+    }
+
+    /// Returns the normalized rotation axis and angle (in radians) of a normalized quaternion.
+    pub fn to_axis_angle(self) -> (Vec3, f32) {
+        let q = if self.w < 0.0 {
+            Self(-1.0 * self.0)
+        } else {
+            self
+        };
+        let len = q.xyz().length();
+        if len < 1.0e-6 {
+            return (Vec3::X, 0.0);
+        }
+        (q.xyz() / len, 2.0 * len.atan2(q.w))
+    }
+
+    /// Rotates `v` by this (normalized) quaternion.
+    #[inline]
+    pub fn mul_vec3(self, v: Vec3) -> Vec3 {
+        let q = self.xyz();
+        let t = 2.0 * q.cross(v);
+        v + self.w * t + q.cross(t)
+    }
+
+    /// Returns the Hamilton product of `self` and `rhs`, which first applies `rhs`.
+    #[inline]
+    pub fn mul_quat(self, rhs: Self) -> Self {
+        let (a, b) = (self, rhs);
+        Self::from_xyzw(
+            a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+            a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x,
+            a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w,
+            a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z,
+        )
+    }
+
+    /// Returns whether both quaternions represent the same rotation within `max_abs_diff`.
+    #[inline]
+    pub fn abs_diff_eq(self, rhs: Self, max_abs_diff: f32) -> bool {
+        let diff = |a: Vec4, b: Vec4| {
+            let d = a - b;
+            d.x.abs().max(d.y.abs()).max(d.z.abs()).max(d.w.abs())
+        };
+        diff(self.0, rhs.0) <= max_abs_diff || diff(self.0, -1.0 * rhs.0) <= max_abs_diff
+    }
+}
+
+impl Default for Quat {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl PartialEq for Quat {
+    #[inline]
+    fn eq(&self, rhs: &Self) -> bool {
+        self.x == rhs.x && self.y == rhs.y && self.z == rhs.z && self.w == rhs.w
+    }
+}
+
+impl Mul<Quat> for Quat {
+    type Output = Self;
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        self.mul_quat(rhs)
+    }
+}
+
+impl MulAssign<Quat> for Quat {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.mul_quat(rhs);
+    }
+}
+
+impl Mul<Vec3> for Quat {
+    type Output = Vec3;
+    #[inline]
+    fn mul(self, rhs: Vec3) -> Vec3 {
+        self.mul_vec3(rhs)
+    }
 }
 
 #[cfg(not(spirv))]
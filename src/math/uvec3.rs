@@ -0,0 +1,216 @@
+#[cfg(not(spirv))]
+use core::fmt;
+use core::ops::{Add, Div, Index, IndexMut, Mul, Rem, Sub};
+
+#[cfg(all(no_std, libm, not(test)))]
+use crate::math::libm::F32Ext;
+use crate::math::vec3::Vec3;
+
+/// Creates a 3-dimensional vector of unsigned integers.
+#[inline(always)]
+pub const fn uvec3(x: u32, y: u32, z: u32) -> UVec3 {
+    UVec3::new(x, y, z)
+}
+
+/// A 3-dimensional vector of unsigned integers, for example to address the cells of a grid.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(target_arch = "spirv", repr(simd))]
+#[cfg_attr(not(target_arch = "spirv"), repr(C))]
+pub struct UVec3 {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+impl UVec3 {
+    /// All zeroes.
+    pub const ZERO: Self = Self::splat(0);
+
+    /// All ones.
+    pub const ONE: Self = Self::splat(1);
+
+    #[inline(always)]
+    pub const fn new(x: u32, y: u32, z: u32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Creates a vector with all elements set to `v`.
+    #[inline]
+    pub const fn splat(v: u32) -> Self {
+        Self::new(v, v, v)
+    }
+
+    #[inline]
+    pub const fn from_array(a: [u32; 3]) -> Self {
+        Self::new(a[0], a[1], a[2])
+    }
+
+    #[inline]
+    pub const fn to_array(&self) -> [u32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// Rounds every element of `v` down, negative elements become zero.
+    #[inline]
+    pub fn floor(v: Vec3) -> Self {
+        Self::new(v.x.floor() as u32, v.y.floor() as u32, v.z.floor() as u32)
+    }
+
+    /// Rounds every element of `v` up, negative elements become zero.
+    #[inline]
+    pub fn ceil(v: Vec3) -> Self {
+        Self::new(v.x.ceil() as u32, v.y.ceil() as u32, v.z.ceil() as u32)
+    }
+
+    #[inline]
+    pub fn as_vec3(self) -> Vec3 {
+        Vec3::new(self.x as f32, self.y as f32, self.z as f32)
+    }
+
+    #[inline]
+    pub fn min(self, rhs: Self) -> Self {
+        Self::new(self.x.min(rhs.x), self.y.min(rhs.y), self.z.min(rhs.z))
+    }
+
+    #[inline]
+    pub fn max(self, rhs: Self) -> Self {
+        Self::new(self.x.max(rhs.x), self.y.max(rhs.y), self.z.max(rhs.z))
+    }
+
+    #[inline]
+    pub fn min_element(self) -> u32 {
+        self.x.min(self.y).min(self.z)
+    }
+
+    /// Returns whether every element is smaller than the same element of `rhs`.
+    #[inline]
+    pub fn all_lt(self, rhs: Self) -> bool {
+        self.x < rhs.x && self.y < rhs.y && self.z < rhs.z
+    }
+
+    /// Returns the product of the elements, which does not overflow for the sizes of grids.
+    #[inline]
+    pub fn element_product(self) -> u64 {
+        self.x as u64 * self.y as u64 * self.z as u64
+    }
+
+    /// Returns the index of the element `self` of a grid of `size` elements, where X varies
+    /// fastest and Z slowest.
+    #[inline]
+    pub fn flatten(self, size: Self) -> usize {
+        (self.z as usize * size.y as usize + self.y as usize) * size.x as usize + self.x as usize
+    }
+}
+
+impl Index<usize> for UVec3 {
+    type Output = u32;
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("index out of bounds"),
+        }
+    }
+}
+
+impl IndexMut<usize> for UVec3 {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("index out of bounds"),
+        }
+    }
+}
+
+impl Add<UVec3> for UVec3 {
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Add<u32> for UVec3 {
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: u32) -> Self {
+        self + Self::splat(rhs)
+    }
+}
+
+impl Sub<UVec3> for UVec3 {
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Sub<u32> for UVec3 {
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: u32) -> Self {
+        self - Self::splat(rhs)
+    }
+}
+
+impl Mul<u32> for UVec3 {
+    type Output = Self;
+    #[inline]
+    fn mul(self, rhs: u32) -> Self {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Div<u32> for UVec3 {
+    type Output = Self;
+    #[inline]
+    fn div(self, rhs: u32) -> Self {
+        Self::new(self.x / rhs, self.y / rhs, self.z / rhs)
+    }
+}
+
+impl Rem<u32> for UVec3 {
+    type Output = Self;
+    #[inline]
+    fn rem(self, rhs: u32) -> Self {
+        Self::new(self.x % rhs, self.y % rhs, self.z % rhs)
+    }
+}
+
+#[cfg(not(spirv))]
+impl fmt::Display for UVec3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}, {}]", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(not(spirv))]
+impl fmt::Debug for UVec3 {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple(stringify!(UVec3))
+            .field(&self.x)
+            .field(&self.y)
+            .field(&self.z)
+            .finish()
+    }
+}
+
+impl From<[u32; 3]> for UVec3 {
+    #[inline]
+    fn from(a: [u32; 3]) -> Self {
+        Self::from_array(a)
+    }
+}
+
+impl From<UVec3> for [u32; 3] {
+    #[inline]
+    fn from(v: UVec3) -> Self {
+        v.to_array()
+    }
+}
@@ -2,7 +2,7 @@
 use core::fmt;
 use core::{f32, ops::*};
 
-#[cfg(all(no_std, no_simd, libm))]
+#[cfg(all(no_std, libm, not(test)))]
 use crate::math::libm::F32Ext;
 use crate::math::simd::*;
 use crate::math::{float3::Float3, vec4::Vec4};
@@ -184,6 +184,88 @@ impl Vec3 {
         return Vec4::splat((self.x * rhs.x) + (self.y * rhs.y) + (self.z * rhs.z));
     }
 
+    #[inline]
+    pub fn length(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    #[inline]
+    pub fn length_squared(self) -> f32 {
+        self.dot(self)
+    }
+
+    #[inline]
+    pub fn length_recip(self) -> f32 {
+        self.length().recip()
+    }
+
+    #[inline]
+    pub fn distance(self, rhs: Self) -> f32 {
+        (self - rhs).length()
+    }
+
+    #[inline]
+    pub fn distance_squared(self, rhs: Self) -> f32 {
+        (self - rhs).length_squared()
+    }
+
+    /// Returns `self` normalized to length 1.0. The result is non-finite if `self` is zero
+    /// length.
+    #[inline]
+    pub fn normalize(self) -> Self {
+        self * self.length_recip()
+    }
+
+    /// Returns `self` normalized to length 1.0, or zero if `self` is (close to) zero length.
+    #[inline]
+    pub fn normalize_or_zero(self) -> Self {
+        let recip = self.length_recip();
+        if recip.is_finite() && recip > 0.0 {
+            self * recip
+        } else {
+            Self::ZERO
+        }
+    }
+
+    /// Returns a vector of length 1.0 that is perpendicular to `self`, which must not be zero
+    /// length.
+    #[inline]
+    pub fn normalized_perpendicular(self) -> Self {
+        if self.x.abs() > self.y.abs() {
+            Self::new(self.z, 0.0, -self.x).normalize()
+        } else {
+            Self::new(0.0, self.z, -self.y).normalize()
+        }
+    }
+
+    /// Returns whether `self` is of length 1.0 within `1e-4`.
+    #[inline]
+    pub fn is_normalized(self) -> bool {
+        (self.length_squared() - 1.0).abs() <= 2e-4
+    }
+
+    #[inline]
+    pub fn is_finite(self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    #[inline]
+    pub fn is_nan(self) -> bool {
+        self.x.is_nan() || self.y.is_nan() || self.z.is_nan()
+    }
+
+    /// Returns whether all elements of `self` and `rhs` differ by no more than `max_abs_diff`.
+    #[inline]
+    pub fn abs_diff_eq(self, rhs: Self, max_abs_diff: f32) -> bool {
+        (self - rhs).abs().max_element() <= max_abs_diff
+    }
+
+    /// Performs a linear interpolation between `self` and `rhs` based on `s`.
+    #[inline]
+    pub fn lerp(self, rhs: Self, s: f32) -> Self {
+        self + ((rhs - self) * s)
+    }
+
     #[inline]
     pub fn cross(self, rhs: Self) -> Self {
         #[cfg(x86_sse)]
@@ -295,6 +377,28 @@ impl Default for Vec3 {
     }
 }
 
+impl PartialEq for Vec3 {
+    #[inline]
+    fn eq(&self, rhs: &Self) -> bool {
+        self.x == rhs.x && self.y == rhs.y && self.z == rhs.z
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self {
+        #[cfg(x86_sse)]
+        return Self(unsafe { _mm_xor_ps(_mm_set1_ps(-0.0), self.0) });
+        #[cfg(arm_neon)]
+        return Self(unsafe { vnegq_f32(self.0) });
+        #[cfg(wasm_simd128)]
+        return Self(f32x4_neg(self.0));
+        #[cfg(not(any(x86_sse, arm_neon, wasm_simd128)))]
+        return Self::new(-self.x, -self.y, -self.z);
+    }
+}
+
 impl Div<Vec3> for Vec3 {
     type Output = Self;
     #[inline]
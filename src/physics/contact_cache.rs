@@ -0,0 +1,192 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{body::BodyID, math::Vec3, shape::SubShapeID};
+
+/// A free slot in the hash table of a [`ContactCache`].
+const EMPTY: u32 = u32::MAX;
+
+/// Identifies the contact between two sub shapes of two bodies across steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SubShapePair {
+    pub body_id1: BodyID,
+    pub sub_shape_id1: SubShapeID,
+    pub body_id2: BodyID,
+    pub sub_shape_id2: SubShapeID,
+}
+
+impl SubShapePair {
+    /// Hashes the identifiers with FNV-1a, followed by a final mix so that the low bits, which
+    /// select the slot, depend on all identifiers.
+    #[inline]
+    fn hash(&self) -> u64 {
+        let values = [
+            self.body_id1.value(),
+            self.sub_shape_id1.value(),
+            self.body_id2.value(),
+            self.sub_shape_id2.value(),
+        ];
+        let mut hash = 0xcbf2_9ce4_8422_2325u64;
+        for value in values {
+            hash = (hash ^ value as u64).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        hash ^ (hash >> 32)
+    }
+}
+
+/// A contact point that was solved during the last step.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CachedContactPoint {
+    /// The point on the first body relative to its center of mass, in the space of the body.
+    pub local_position1: Vec3,
+    /// The point on the second body relative to its center of mass, in the space of the body.
+    pub local_position2: Vec3,
+    /// The impulse applied along the normal.
+    pub normal_lambda: f32,
+    /// The impulse applied along the first friction axis.
+    pub tangent1_lambda: f32,
+    /// The impulse applied along the second friction axis.
+    pub tangent2_lambda: f32,
+}
+
+impl CachedContactPoint {
+    /// Returns the first of `points` whose positions on both bodies are within `max_distance`
+    /// of `local_position1` and `local_position2`.
+    pub fn find_nearby(
+        points: &[Self],
+        local_position1: Vec3,
+        local_position2: Vec3,
+        max_distance: f32,
+    ) -> Option<&Self> {
+        let max_distance_squared = max_distance * max_distance;
+        points.iter().find(|point| {
+            point.local_position1.distance_squared(local_position1) <= max_distance_squared
+                && point.local_position2.distance_squared(local_position2) <= max_distance_squared
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct CachedManifold {
+    pair: SubShapePair,
+    first_point: u32,
+    point_count: u32,
+}
+
+/// Remembers the contacts of a step, so that the next step can continue from the impulses of
+/// the contact points that persist.
+///
+/// Manifolds are found through a hash table with open addressing and linear probing. All
+/// storage is allocated for the maximum number of manifolds and points when the cache is
+/// created and kept when it is cleared, so that a step does not allocate. Manifolds that do not
+/// fit are not cached.
+#[derive(Clone, Debug)]
+pub struct ContactCache {
+    /// Indices into `manifolds`, the length is a power of two and at least twice the maximum
+    /// number of manifolds so that probing always ends at a free slot.
+    table: Vec<u32>,
+    manifolds: Vec<CachedManifold>,
+    points: Vec<CachedContactPoint>,
+    max_manifolds: usize,
+    max_points: usize,
+}
+
+impl ContactCache {
+    pub fn new(max_manifolds: usize, max_points: usize) -> Self {
+        assert!(max_manifolds < EMPTY as usize && max_points <= u32::MAX as usize);
+        Self {
+            table: vec![EMPTY; (max_manifolds * 2).next_power_of_two()],
+            manifolds: Vec::with_capacity(max_manifolds),
+            points: Vec::with_capacity(max_points),
+            max_manifolds,
+            max_points,
+        }
+    }
+
+    #[inline]
+    pub fn max_manifolds(&self) -> usize {
+        self.max_manifolds
+    }
+
+    #[inline]
+    pub fn max_points(&self) -> usize {
+        self.max_points
+    }
+
+    #[inline]
+    pub fn manifold_count(&self) -> usize {
+        self.manifolds.len()
+    }
+
+    #[inline]
+    pub fn point_count(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Removes all manifolds, keeping the storage.
+    pub fn clear(&mut self) {
+        if !self.manifolds.is_empty() {
+            self.table.fill(EMPTY);
+            self.manifolds.clear();
+            self.points.clear();
+        }
+    }
+
+    /// Returns the cached points of the contact between the sub shapes of `pair`.
+    pub fn find(&self, pair: &SubShapePair) -> Option<&[CachedContactPoint]> {
+        match self.probe(pair) {
+            Ok(index) => Some(self.manifold_points(&self.manifolds[index])),
+            Err(_) => None,
+        }
+    }
+
+    /// Adds the points of the contact between the sub shapes of `pair`. Returns `false`, and
+    /// leaves the cache unchanged, when `pair` is already cached or the cache is full.
+    pub fn insert(
+        &mut self,
+        pair: SubShapePair,
+        points: impl ExactSizeIterator<Item = CachedContactPoint>,
+    ) -> bool {
+        if self.manifolds.len() >= self.max_manifolds
+            || self.points.len() + points.len() > self.max_points
+        {
+            return false;
+        }
+        let Err(slot) = self.probe(&pair) else {
+            return false;
+        };
+        self.table[slot] = self.manifolds.len() as u32;
+        self.manifolds.push(CachedManifold {
+            pair,
+            first_point: self.points.len() as u32,
+            point_count: points.len() as u32,
+        });
+        self.points.extend(points);
+        true
+    }
+
+    /// Returns all cached contacts in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (&SubShapePair, &[CachedContactPoint])> {
+        self.manifolds
+            .iter()
+            .map(|manifold| (&manifold.pair, self.manifold_points(manifold)))
+    }
+
+    #[inline]
+    fn manifold_points(&self, manifold: &CachedManifold) -> &[CachedContactPoint] {
+        let first_point = manifold.first_point as usize;
+        &self.points[first_point..first_point + manifold.point_count as usize]
+    }
+
+    /// Returns the index of the manifold of `pair`, or the free slot where it would go.
+    fn probe(&self, pair: &SubShapePair) -> Result<usize, usize> {
+        let mask = self.table.len() - 1;
+        let mut slot = pair.hash() as usize & mask;
+        loop {
+            match self.table[slot] {
+                EMPTY => return Err(slot),
+                index if self.manifolds[index as usize].pair == *pair => return Ok(index as usize),
+                _ => slot = (slot + 1) & mask,
+            }
+        }
+    }
+}
@@ -0,0 +1,565 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+#[cfg(all(no_std, libm, not(test)))]
+use crate::math::libm::F32Ext;
+use crate::{
+    body::{Body, BodyManager},
+    math::Vec3,
+    physics::{
+        CachedContactPoint, Contact, ContactCache, ContactListener, ContactSettings,
+        PhysicsSettings, SubShapePair,
+    },
+    shape::SubShapeID,
+};
+
+/// Combines a material property of the two bodies of a contact, such as their friction.
+pub type CombineFunction = fn(&Body, SubShapeID, &Body, SubShapeID) -> f32;
+
+/// Combines the friction of two bodies as their geometric mean, so that a frictionless body
+/// slides over everything.
+pub fn default_combine_friction(
+    body1: &Body,
+    _sub_shape_id1: SubShapeID,
+    body2: &Body,
+    _sub_shape_id2: SubShapeID,
+) -> f32 {
+    (body1.friction * body2.friction).sqrt()
+}
+
+/// Combines the restitution of two bodies as their maximum, so that a bouncy body bounces off
+/// everything.
+pub fn default_combine_restitution(
+    body1: &Body,
+    _sub_shape_id1: SubShapeID,
+    body2: &Body,
+    _sub_shape_id2: SubShapeID,
+) -> f32 {
+    body1.restitution.max(body2.restitution)
+}
+
+/// The masses of the bodies of a contact, scaled by its [`ContactSettings`].
+#[derive(Clone, Copy, Debug, Default)]
+struct ContactMasses {
+    inverse_mass1: f32,
+    inverse_inertia_scale1: f32,
+    inverse_mass2: f32,
+    inverse_inertia_scale2: f32,
+}
+
+impl ContactMasses {
+    fn new(body1: &Body, body2: &Body, settings: &ContactSettings) -> Self {
+        Self {
+            inverse_mass1: body1.inverse_mass() * settings.inverse_mass_scale1,
+            inverse_inertia_scale1: settings.inverse_inertia_scale1,
+            inverse_mass2: body2.inverse_mass() * settings.inverse_mass_scale2,
+            inverse_inertia_scale2: settings.inverse_inertia_scale2,
+        }
+    }
+}
+
+/// The part of a contact point that solves the relative velocity along a single axis.
+#[derive(Clone, Copy, Debug, Default)]
+struct AxisConstraintPart {
+    r1_cross_axis: Vec3,
+    r2_cross_axis: Vec3,
+    inverse_inertia1_r1_cross_axis: Vec3,
+    inverse_inertia2_r2_cross_axis: Vec3,
+    effective_mass: f32,
+    /// The total impulse applied along the axis.
+    lambda: f32,
+}
+
+impl AxisConstraintPart {
+    /// Creates the part for `axis` at offsets `r1` and `r2` from the centers of mass.
+    fn new(
+        axis: Vec3,
+        r1: Vec3,
+        r2: Vec3,
+        body1: &Body,
+        body2: &Body,
+        masses: &ContactMasses,
+    ) -> Self {
+        let r1_cross_axis = r1.cross(axis);
+        let r2_cross_axis = r2.cross(axis);
+        let inverse_inertia1_r1_cross_axis = body1
+            .multiply_world_space_inverse_inertia_by_vector(r1_cross_axis)
+            * masses.inverse_inertia_scale1;
+        let inverse_inertia2_r2_cross_axis = body2
+            .multiply_world_space_inverse_inertia_by_vector(r2_cross_axis)
+            * masses.inverse_inertia_scale2;
+        let inverse_effective_mass = masses.inverse_mass1
+            + masses.inverse_mass2
+            + r1_cross_axis.dot(inverse_inertia1_r1_cross_axis)
+            + r2_cross_axis.dot(inverse_inertia2_r2_cross_axis);
+        Self {
+            r1_cross_axis,
+            r2_cross_axis,
+            inverse_inertia1_r1_cross_axis,
+            inverse_inertia2_r2_cross_axis,
+            effective_mass: if inverse_effective_mass > 0.0 {
+                1.0 / inverse_effective_mass
+            } else {
+                0.0
+            },
+            lambda: 0.0,
+        }
+    }
+
+    /// Returns the velocity of the contact point on the second body relative to the first
+    /// along `axis`.
+    #[inline]
+    fn relative_velocity(&self, axis: Vec3, body1: &Body, body2: &Body) -> f32 {
+        axis.dot(body2.linear_velocity() - body1.linear_velocity())
+            + self.r2_cross_axis.dot(body2.angular_velocity())
+            - self.r1_cross_axis.dot(body1.angular_velocity())
+    }
+
+    /// Applies an impulse of `lambda` along `axis` to the second body and the opposite impulse
+    /// to the first.
+    #[inline]
+    fn apply_impulse(
+        &self,
+        axis: Vec3,
+        lambda: f32,
+        masses: &ContactMasses,
+        body1: &mut Body,
+        body2: &mut Body,
+    ) {
+        body1.add_velocity_step(
+            axis * (-lambda * masses.inverse_mass1),
+            self.inverse_inertia1_r1_cross_axis * -lambda,
+        );
+        body2.add_velocity_step(
+            axis * (lambda * masses.inverse_mass2),
+            self.inverse_inertia2_r2_cross_axis * lambda,
+        );
+    }
+
+    /// Applies the difference between `lambda` and the total impulse, and stores `lambda` as
+    /// the new total.
+    #[inline]
+    fn set_lambda(
+        &mut self,
+        axis: Vec3,
+        lambda: f32,
+        masses: &ContactMasses,
+        body1: &mut Body,
+        body2: &mut Body,
+    ) {
+        self.apply_impulse(axis, lambda - self.lambda, masses, body1, body2);
+        self.lambda = lambda;
+    }
+
+    /// Moves the bodies apart along `axis` by a position impulse of `lambda`.
+    #[inline]
+    fn apply_position_impulse(
+        &self,
+        axis: Vec3,
+        lambda: f32,
+        masses: &ContactMasses,
+        body1: &mut Body,
+        body2: &mut Body,
+    ) {
+        body1.add_position_step(
+            axis * (-lambda * masses.inverse_mass1),
+            self.inverse_inertia1_r1_cross_axis * -lambda,
+        );
+        body2.add_position_step(
+            axis * (lambda * masses.inverse_mass2),
+            self.inverse_inertia2_r2_cross_axis * lambda,
+        );
+    }
+}
+
+/// A contact point with a normal and two friction axes.
+#[derive(Clone, Copy, Debug)]
+struct ContactConstraintPoint {
+    /// The point on the first body relative to its center of mass, in the space of the body.
+    local_position1: Vec3,
+    /// The point on the second body relative to its center of mass, in the space of the body.
+    local_position2: Vec3,
+    normal: AxisConstraintPart,
+    tangent1: AxisConstraintPart,
+    tangent2: AxisConstraintPart,
+    /// The relative normal velocity the solver drives towards. It is negative for points
+    /// that are still separated, which may approach until they touch, and positive for
+    /// points that bounce.
+    normal_target_velocity: f32,
+    /// The relative velocities along the friction axes that friction drives towards, from the
+    /// relative surface velocity of the contact.
+    tangent1_target_velocity: f32,
+    tangent2_target_velocity: f32,
+}
+
+/// The constraint built from the [`ContactManifold`](crate::collision::ContactManifold) of a
+/// [`Contact`].
+#[derive(Clone, Debug)]
+struct ContactConstraint {
+    pair: SubShapePair,
+    normal: Vec3,
+    tangent1: Vec3,
+    tangent2: Vec3,
+    friction: f32,
+    masses: ContactMasses,
+    /// The range of the points in the points of the manager, empty for a sensor.
+    points: Range<usize>,
+}
+
+/// Solves the contacts between bodies with sequential impulses.
+///
+/// Every contact point gets a constraint along the normal, which only pushes, and two friction
+/// constraints that are limited by the normal impulse times the combined friction. The
+/// impulses of every point are remembered for the next step in a [`ContactCache`], and applied
+/// up front to converge faster when the point is found again at nearly the same place on both
+/// bodies. After the velocities have been
+/// solved and integrated, the remaining penetration is removed by moving the bodies directly
+/// with non-linear Gauss-Seidel iterations.
+#[derive(Debug)]
+pub struct ContactConstraintManager {
+    combine_friction: CombineFunction,
+    combine_restitution: CombineFunction,
+    constraints: Vec<ContactConstraint>,
+    points: Vec<ContactConstraintPoint>,
+    /// The contacts of the last step and the contacts of the current step, the roles are
+    /// swapped at the end of every step.
+    caches: [ContactCache; 2],
+    /// The index of the cache with the contacts of the last step.
+    cache_index: usize,
+}
+
+impl ContactConstraintManager {
+    pub fn new() -> Self {
+        let settings = PhysicsSettings::default();
+        Self {
+            combine_friction: default_combine_friction,
+            combine_restitution: default_combine_restitution,
+            constraints: Vec::new(),
+            points: Vec::new(),
+            caches: [new_cache(&settings), new_cache(&settings)],
+            cache_index: 0,
+        }
+    }
+
+    /// Replaces the function that combines the friction of two bodies, the default is
+    /// [`default_combine_friction`].
+    #[inline]
+    pub fn set_combine_friction(&mut self, combine_friction: CombineFunction) {
+        self.combine_friction = combine_friction;
+    }
+
+    /// Replaces the function that combines the restitution of two bodies, the default is
+    /// [`default_combine_restitution`].
+    #[inline]
+    pub fn set_combine_restitution(&mut self, combine_restitution: CombineFunction) {
+        self.combine_restitution = combine_restitution;
+    }
+
+    /// Returns the number of constraints that were built during the last step.
+    #[inline]
+    pub fn constraint_count(&self) -> usize {
+        self.constraints.len()
+    }
+
+    /// Returns the contacts that were solved during the last step.
+    #[inline]
+    pub fn contact_cache(&self) -> &ContactCache {
+        &self.caches[self.cache_index]
+    }
+
+    /// Builds a constraint for every contact, with the impulses of the last step for the
+    /// points that persist. `listener` is told which contacts were added and which persist,
+    /// and can change their settings. `delta_time` is the time the velocities are solved for.
+    pub(crate) fn build_constraints(
+        &mut self,
+        contacts: &[Contact],
+        body_manager: &BodyManager,
+        settings: &PhysicsSettings,
+        delta_time: f32,
+        mut listener: Option<&mut dyn ContactListener>,
+    ) {
+        if !cache_matches(&self.caches[0], settings) {
+            // Carry the contacts of the last step over, the ones that no longer fit are removed.
+            let mut caches = [new_cache(settings), new_cache(settings)];
+            for (pair, points) in self.caches[self.cache_index].iter() {
+                if !caches[self.cache_index].insert(*pair, points.iter().copied()) {
+                    if let Some(listener) = listener.as_mut() {
+                        listener.on_contact_removed(pair);
+                    }
+                }
+            }
+            self.caches = caches;
+        }
+        self.constraints.clear();
+        self.points.clear();
+        let cache = &self.caches[self.cache_index];
+        let max_distance = settings.contact_point_preserve_lambda_max_distance;
+        for contact in contacts {
+            let manifold = &contact.manifold;
+            let (Some(body1), Some(body2)) = (
+                body_manager.body(contact.body_id1),
+                body_manager.body(contact.body_id2),
+            ) else {
+                continue;
+            };
+            if manifold.points.is_empty() || manifold.normal == Vec3::ZERO {
+                continue;
+            }
+
+            let pair = SubShapePair {
+                body_id1: contact.body_id1,
+                sub_shape_id1: manifold.sub_shape_id1,
+                body_id2: contact.body_id2,
+                sub_shape_id2: manifold.sub_shape_id2,
+            };
+            let mut contact_settings = ContactSettings::new(
+                (self.combine_friction)(body1, pair.sub_shape_id1, body2, pair.sub_shape_id2),
+                (self.combine_restitution)(body1, pair.sub_shape_id1, body2, pair.sub_shape_id2),
+            );
+            let cached_points = cache.find(&pair);
+            if let Some(listener) = listener.as_mut() {
+                match cached_points {
+                    Some(_) => {
+                        listener.on_contact_persisted(body1, body2, manifold, &mut contact_settings)
+                    }
+                    None => {
+                        listener.on_contact_added(body1, body2, manifold, &mut contact_settings)
+                    }
+                }
+            }
+
+            let normal = manifold.normal;
+            let tangent1 = normal.normalized_perpendicular();
+            let tangent2 = normal.cross(tangent1);
+            let masses = ContactMasses::new(body1, body2, &contact_settings);
+            let cached_points = cached_points.unwrap_or_default();
+            let (rotation1, rotation2) = (body1.rotation().inverse(), body2.rotation().inverse());
+            let restitution = contact_settings.combined_restitution;
+
+            // A sensor only keeps its place in the cache, it has no points to solve.
+            let first_point = self.points.len();
+            let points = manifold
+                .points
+                .iter()
+                .filter(|_| !contact_settings.is_sensor);
+            self.points.extend(points.map(|point| {
+                let r1 = manifold.base_offset + point.position1 - body1.center_of_mass_position();
+                let r2 = manifold.base_offset + point.position2 - body2.center_of_mass_position();
+                let (local_position1, local_position2) = (rotation1 * r1, rotation2 * r2);
+                let cached = CachedContactPoint::find_nearby(
+                    cached_points,
+                    local_position1,
+                    local_position2,
+                    max_distance,
+                )
+                .copied()
+                .unwrap_or_default();
+                let new_part = |axis, lambda| AxisConstraintPart {
+                    lambda,
+                    ..AxisConstraintPart::new(axis, r1, r2, body1, body2, &masses)
+                };
+                let normal_part = new_part(normal, cached.normal_lambda);
+                let tangent1_part = new_part(tangent1, cached.tangent1_lambda);
+                let tangent2_part = new_part(tangent2, cached.tangent2_lambda);
+
+                // Separated points may close the gap during this step, but no further.
+                let speculative_velocity = (-point.penetration_depth).max(0.0) / delta_time;
+                let normal_velocity = normal_part.relative_velocity(normal, body1, body2);
+                let normal_target_velocity = if restitution > 0.0
+                    && normal_velocity < -settings.min_velocity_for_restitution
+                    && normal_velocity < -speculative_velocity
+                {
+                    -restitution * normal_velocity
+                } else {
+                    -speculative_velocity
+                };
+                let surface_velocity = contact_settings.relative_linear_surface_velocity
+                    + contact_settings.relative_angular_surface_velocity.cross(r1);
+
+                ContactConstraintPoint {
+                    local_position1,
+                    local_position2,
+                    normal: normal_part,
+                    tangent1: tangent1_part,
+                    tangent2: tangent2_part,
+                    normal_target_velocity,
+                    tangent1_target_velocity: -tangent1.dot(surface_velocity),
+                    tangent2_target_velocity: -tangent2.dot(surface_velocity),
+                }
+            }));
+            self.constraints.push(ContactConstraint {
+                pair,
+                normal,
+                tangent1,
+                tangent2,
+                friction: contact_settings.combined_friction,
+                masses,
+                points: first_point..self.points.len(),
+            });
+        }
+    }
+
+    /// Applies the impulses of the last step to the bodies.
+    pub(crate) fn warm_start(&self, body_manager: &mut BodyManager) {
+        for constraint in &self.constraints {
+            let pair = constraint.pair;
+            let Some((body1, body2)) = body_manager.body_pair_mut(pair.body_id1, pair.body_id2)
+            else {
+                continue;
+            };
+            let masses = &constraint.masses;
+            for point in &self.points[constraint.points.clone()] {
+                let (normal, tangent1, tangent2) =
+                    (&point.normal, &point.tangent1, &point.tangent2);
+                tangent1.apply_impulse(constraint.tangent1, tangent1.lambda, masses, body1, body2);
+                tangent2.apply_impulse(constraint.tangent2, tangent2.lambda, masses, body1, body2);
+                normal.apply_impulse(constraint.normal, normal.lambda, masses, body1, body2);
+            }
+        }
+    }
+
+    /// Runs a single iteration of the velocity solver over all constraints. Friction is solved
+    /// first, so that the normal constraints have the final say.
+    pub(crate) fn solve_velocity_constraints(&mut self, body_manager: &mut BodyManager) {
+        for constraint in &self.constraints {
+            let pair = constraint.pair;
+            let Some((body1, body2)) = body_manager.body_pair_mut(pair.body_id1, pair.body_id2)
+            else {
+                continue;
+            };
+            let masses = &constraint.masses;
+
+            let points = &mut self.points[constraint.points.clone()];
+            for point in points.iter_mut() {
+                let (tangent1, tangent2) = (&mut point.tangent1, &mut point.tangent2);
+                let velocity1 = tangent1.relative_velocity(constraint.tangent1, body1, body2);
+                let velocity2 = tangent2.relative_velocity(constraint.tangent2, body1, body2);
+                let mut lambda1 = tangent1.lambda
+                    - tangent1.effective_mass * (velocity1 - point.tangent1_target_velocity);
+                let mut lambda2 = tangent2.lambda
+                    - tangent2.effective_mass * (velocity2 - point.tangent2_target_velocity);
+                // The friction impulse is limited to a circle rather than a square.
+                let max_lambda = constraint.friction * point.normal.lambda;
+                let length_squared = lambda1 * lambda1 + lambda2 * lambda2;
+                if length_squared > max_lambda * max_lambda {
+                    let scale = max_lambda / length_squared.sqrt();
+                    lambda1 *= scale;
+                    lambda2 *= scale;
+                }
+                tangent1.set_lambda(constraint.tangent1, lambda1, masses, body1, body2);
+                tangent2.set_lambda(constraint.tangent2, lambda2, masses, body1, body2);
+            }
+
+            for point in points.iter_mut() {
+                let normal = &mut point.normal;
+                let normal_velocity = normal.relative_velocity(constraint.normal, body1, body2);
+                let lambda = (normal.lambda
+                    + normal.effective_mass * (point.normal_target_velocity - normal_velocity))
+                    .max(0.0);
+                normal.set_lambda(constraint.normal, lambda, masses, body1, body2);
+            }
+        }
+    }
+
+    /// Runs a single iteration of the position solver over all constraints, which moves the
+    /// bodies apart by a fraction of the penetration that exceeds the slop.
+    pub(crate) fn solve_position_constraints(
+        &self,
+        body_manager: &mut BodyManager,
+        settings: &PhysicsSettings,
+    ) {
+        for constraint in &self.constraints {
+            let pair = constraint.pair;
+            let Some((body1, body2)) = body_manager.body_pair_mut(pair.body_id1, pair.body_id2)
+            else {
+                continue;
+            };
+            let (normal, masses) = (constraint.normal, &constraint.masses);
+
+            for point in &self.points[constraint.points.clone()] {
+                let r1 = body1.rotation() * point.local_position1;
+                let r2 = body2.rotation() * point.local_position2;
+                let penetration =
+                    (body1.center_of_mass_position() + r1 - body2.center_of_mass_position() - r2)
+                        .dot(normal);
+                let error = penetration - settings.penetration_slop;
+                if error <= 0.0 {
+                    continue;
+                }
+                let part = AxisConstraintPart::new(normal, r1, r2, body1, body2, masses);
+                let lambda = settings.baumgarte * error * part.effective_mass;
+                part.apply_position_impulse(normal, lambda, masses, body1, body2);
+            }
+        }
+    }
+
+    /// Remembers the points of the constraints and their impulses for the next step. The
+    /// contacts of the last step between bodies that are asleep are kept, the others that were
+    /// not found again are reported to `listener` as removed.
+    pub(crate) fn update_contact_cache(
+        &mut self,
+        body_manager: &BodyManager,
+        mut listener: Option<&mut dyn ContactListener>,
+    ) {
+        let cache_index = self.cache_index ^ 1;
+        let [cache0, cache1] = &mut self.caches;
+        let (previous, cache) = match cache_index {
+            0 => (&*cache1, cache0),
+            _ => (&*cache0, cache1),
+        };
+        cache.clear();
+        for constraint in &self.constraints {
+            let points =
+                self.points[constraint.points.clone()]
+                    .iter()
+                    .map(|point| CachedContactPoint {
+                        local_position1: point.local_position1,
+                        local_position2: point.local_position2,
+                        normal_lambda: point.normal.lambda,
+                        tangent1_lambda: point.tangent1.lambda,
+                        tangent2_lambda: point.tangent2.lambda,
+                    });
+            cache.insert(constraint.pair, points);
+        }
+        let is_asleep = |body_id| {
+            body_manager
+                .body(body_id)
+                .is_some_and(|body| body.is_added() && !body.is_active())
+        };
+        for (pair, points) in previous.iter() {
+            if cache.find(pair).is_some()
+                || (is_asleep(pair.body_id1)
+                    && is_asleep(pair.body_id2)
+                    && cache.insert(*pair, points.iter().copied()))
+            {
+                continue;
+            }
+            if let Some(listener) = &mut listener {
+                listener.on_contact_removed(pair);
+            }
+        }
+        self.cache_index = cache_index;
+    }
+}
+
+impl Default for ContactConstraintManager {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[inline]
+fn new_cache(settings: &PhysicsSettings) -> ContactCache {
+    ContactCache::new(
+        settings.max_cached_manifolds as usize,
+        settings.max_cached_contact_points as usize,
+    )
+}
+
+/// Returns whether `cache` has the size that `settings` ask for.
+#[inline]
+fn cache_matches(cache: &ContactCache, settings: &PhysicsSettings) -> bool {
+    cache.max_manifolds() == settings.max_cached_manifolds as usize
+        && cache.max_points() == settings.max_cached_contact_points as usize
+}
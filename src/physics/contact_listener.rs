@@ -0,0 +1,116 @@
+use crate::{
+    body::Body,
+    collision::{CollideShapeResult, ContactManifold},
+    math::Vec3,
+    physics::SubShapePair,
+};
+
+/// Decides what happens to a contact that the narrowphase found, see
+/// [`ContactListener::on_contact_validate`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValidateResult {
+    /// Accepts this contact and all following contacts of the pair of bodies during this step,
+    /// without asking again.
+    #[default]
+    AcceptAllContactsForThisBodyPair,
+    /// Accepts this contact.
+    AcceptContact,
+    /// Rejects this contact.
+    RejectContact,
+    /// Rejects this contact and all following contacts of the pair of bodies during this step.
+    RejectAllContactsForThisBodyPair,
+}
+
+/// How a contact is solved, which a [`ContactListener`] can change when the contact is added
+/// or persists.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContactSettings {
+    /// The friction of the contact, from the combine function of the
+    /// [`ContactConstraintManager`](super::ContactConstraintManager).
+    pub combined_friction: f32,
+    /// The restitution of the contact, from the combine function of the
+    /// [`ContactConstraintManager`](super::ContactConstraintManager).
+    pub combined_restitution: f32,
+    /// Scales the inverse mass of the first body, zero makes it immovable for this contact.
+    pub inverse_mass_scale1: f32,
+    /// Scales the inverse inertia of the first body.
+    pub inverse_inertia_scale1: f32,
+    /// Scales the inverse mass of the second body, zero makes it immovable for this contact.
+    pub inverse_mass_scale2: f32,
+    /// Scales the inverse inertia of the second body.
+    pub inverse_inertia_scale2: f32,
+    /// A sensor contact is reported to the listener but does not push the bodies apart.
+    pub is_sensor: bool,
+    /// The world space velocity of the surface of the second body relative to the surface of
+    /// the first body, which friction drives the bodies towards, for example for a conveyor
+    /// belt.
+    pub relative_linear_surface_velocity: Vec3,
+    /// The world space angular velocity of the surface of the second body relative to the
+    /// surface of the first body, around the center of mass of the first body.
+    pub relative_angular_surface_velocity: Vec3,
+}
+
+impl ContactSettings {
+    /// Creates settings with the combined friction and restitution that solve the contact
+    /// normally.
+    pub fn new(combined_friction: f32, combined_restitution: f32) -> Self {
+        Self {
+            combined_friction,
+            combined_restitution,
+            inverse_mass_scale1: 1.0,
+            inverse_inertia_scale1: 1.0,
+            inverse_mass_scale2: 1.0,
+            inverse_inertia_scale2: 1.0,
+            is_sensor: false,
+            relative_linear_surface_velocity: Vec3::ZERO,
+            relative_angular_surface_velocity: Vec3::ZERO,
+        }
+    }
+}
+
+/// Receives the contacts between bodies during [`PhysicsSystem::update`].
+///
+/// The first body of a contact is always dynamic. Contacts are identified by the sub shapes in
+/// contact, a contact is added the first step it is found and persists while it is found again
+/// every following step. Contacts are remembered in the contact cache of the
+/// [`ContactConstraintManager`](super::ContactConstraintManager), contacts that do not fit are
+/// removed and added again every step.
+///
+/// [`PhysicsSystem::update`]: super::PhysicsSystem::update
+pub trait ContactListener: Send + Sync {
+    /// Called for every contact before its manifold is built. The base offset is the position
+    /// the manifold will be relative to, the collision result is in world space.
+    fn on_contact_validate(
+        &mut self,
+        _body1: &Body,
+        _body2: &Body,
+        _base_offset: Vec3,
+        _result: &CollideShapeResult,
+    ) -> ValidateResult {
+        ValidateResult::AcceptAllContactsForThisBodyPair
+    }
+
+    /// Called when two sub shapes start touching.
+    fn on_contact_added(
+        &mut self,
+        _body1: &Body,
+        _body2: &Body,
+        _manifold: &ContactManifold,
+        _settings: &mut ContactSettings,
+    ) {
+    }
+
+    /// Called every step that two sub shapes keep touching, the settings start from their
+    /// defaults every step.
+    fn on_contact_persisted(
+        &mut self,
+        _body1: &Body,
+        _body2: &Body,
+        _manifold: &ContactManifold,
+        _settings: &mut ContactSettings,
+    ) {
+    }
+
+    /// Called when two sub shapes stopped touching, the bodies may have been removed already.
+    fn on_contact_removed(&mut self, _pair: &SubShapePair) {}
+}
@@ -0,0 +1,110 @@
+use alloc::vec::Vec;
+
+/// Groups bodies that are linked by contacts into islands, which are simulated and go to sleep
+/// together.
+///
+/// Bodies are identified by their index in the list of active bodies of a step. Links are
+/// merged with a union-find, where the root of a group is always its body with the lowest
+/// index, so islands are ordered by their first body and bodies by their index within an
+/// island.
+#[derive(Clone, Debug, Default)]
+pub struct IslandBuilder {
+    /// The parent of every body in the union-find, roots point to themselves.
+    parents: Vec<u32>,
+    /// The bodies of all islands, island after island.
+    bodies: Vec<u32>,
+    /// The index into `bodies` where every island starts, followed by the number of bodies.
+    island_starts: Vec<u32>,
+}
+
+impl IslandBuilder {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes all links and islands and starts over with `body_count` unlinked bodies.
+    pub fn prepare(&mut self, body_count: usize) {
+        assert!(body_count <= u32::MAX as usize);
+        self.parents.clear();
+        self.parents.extend(0..body_count as u32);
+        self.bodies.clear();
+        self.island_starts.clear();
+    }
+
+    /// Puts the bodies at `index1` and `index2` in the same island.
+    pub fn link_bodies(&mut self, index1: usize, index2: usize) {
+        let root1 = self.find_root(index1 as u32);
+        let root2 = self.find_root(index2 as u32);
+        if root1 < root2 {
+            self.parents[root2 as usize] = root1;
+        } else {
+            self.parents[root1 as usize] = root2;
+        }
+    }
+
+    /// Builds the islands from the links, after which they can be read.
+    pub fn finalize(&mut self) {
+        let body_count = self.parents.len();
+        // Number the islands in the order of their roots and count their bodies.
+        let mut island_of_root = Vec::with_capacity(body_count);
+        let mut counts = Vec::new();
+        for index in 0..body_count as u32 {
+            let root = self.find_root(index);
+            let island = if root == index {
+                counts.push(0);
+                counts.len() as u32 - 1
+            } else {
+                island_of_root[root as usize]
+            };
+            island_of_root.push(island);
+            counts[island as usize] += 1;
+        }
+
+        self.island_starts.clear();
+        self.island_starts.push(0);
+        for count in counts {
+            let start = *self.island_starts.last().unwrap();
+            self.island_starts.push(start + count);
+        }
+        let mut next = self.island_starts.clone();
+        self.bodies.clear();
+        self.bodies.resize(body_count, 0);
+        for (index, &island) in island_of_root.iter().enumerate() {
+            let slot = &mut next[island as usize];
+            self.bodies[*slot as usize] = index as u32;
+            *slot += 1;
+        }
+    }
+
+    /// Returns the number of islands, zero before [`IslandBuilder::finalize`].
+    #[inline]
+    pub fn island_count(&self) -> usize {
+        self.island_starts.len().saturating_sub(1)
+    }
+
+    /// Returns the indices of the bodies of `island`.
+    #[inline]
+    pub fn island(&self, island: usize) -> &[u32] {
+        let start = self.island_starts[island] as usize;
+        let end = self.island_starts[island + 1] as usize;
+        &self.bodies[start..end]
+    }
+
+    /// Returns the indices of the bodies of all islands.
+    #[inline]
+    pub fn islands(&self) -> impl Iterator<Item = &[u32]> {
+        (0..self.island_count()).map(|island| self.island(island))
+    }
+
+    /// Returns the root of the group of `index`, halving the path to it on the way.
+    fn find_root(&mut self, mut index: u32) -> u32 {
+        while self.parents[index as usize] != index {
+            let parent = self.parents[index as usize];
+            let grandparent = self.parents[parent as usize];
+            self.parents[index as usize] = grandparent;
+            index = grandparent;
+        }
+        index
+    }
+}
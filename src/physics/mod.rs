@@ -0,0 +1,20 @@
+pub(crate) mod contact_cache;
+pub use contact_cache::{CachedContactPoint, ContactCache, SubShapePair};
+
+pub(crate) mod contact_constraint_manager;
+pub use contact_constraint_manager::{
+    default_combine_friction, default_combine_restitution, CombineFunction,
+    ContactConstraintManager,
+};
+
+pub(crate) mod contact_listener;
+pub use contact_listener::{ContactListener, ContactSettings, ValidateResult};
+
+pub(crate) mod island_builder;
+pub use island_builder::IslandBuilder;
+
+pub(crate) mod physics_settings;
+pub use physics_settings::PhysicsSettings;
+
+pub(crate) mod physics_system;
+pub use physics_system::{Contact, PhysicsSystem, DEFAULT_GRAVITY};
@@ -0,0 +1,63 @@
+/// Settings that control the accuracy and behavior of a [`PhysicsSystem`](super::PhysicsSystem).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhysicsSettings {
+    /// The number of iterations of the velocity solver per step.
+    pub velocity_steps: u32,
+    /// The number of iterations of the position solver per step.
+    pub position_steps: u32,
+    /// The number of times the velocities are solved and integrated for every collision step,
+    /// the contacts are only found once per collision step.
+    pub integration_sub_steps: u32,
+    /// The fraction of the penetration that the position solver resolves per iteration.
+    pub baumgarte: f32,
+    /// The penetration that is allowed to remain, which keeps resting contacts from jittering.
+    pub penetration_slop: f32,
+    /// The approach velocity below which contacts do not bounce, in m/s.
+    pub min_velocity_for_restitution: f32,
+    /// Shapes that are closer than this distance get contacts before they touch, which stops
+    /// them from moving into each other during the next step.
+    pub speculative_contact_distance: f32,
+    /// The fraction of its inner radius that a body with
+    /// [`MotionQuality::LinearCast`](crate::body::MotionQuality::LinearCast) has to move
+    /// during a step to be swept.
+    pub linear_cast_threshold: f32,
+    /// The fraction of its inner radius that a swept body may move into the body it hits,
+    /// when other contacts push it further than its contact with that body allows.
+    pub linear_cast_max_penetration: f32,
+    /// The maximum number of manifolds that are remembered between steps to warm start the
+    /// solver.
+    pub max_cached_manifolds: u32,
+    /// The maximum number of contact points that are remembered between steps.
+    pub max_cached_contact_points: u32,
+    /// How far a contact point may move on both bodies between steps to still continue from
+    /// the impulses of the last step.
+    pub contact_point_preserve_lambda_max_distance: f32,
+    /// Whether bodies can go to sleep.
+    pub allow_sleeping: bool,
+    /// The velocity below which the points of a body have to stay to go to sleep, in m/s.
+    pub point_velocity_sleep_threshold: f32,
+    /// How long a body has to stay below the sleep threshold to go to sleep, in seconds.
+    pub time_before_sleep: f32,
+}
+
+impl Default for PhysicsSettings {
+    fn default() -> Self {
+        Self {
+            velocity_steps: 10,
+            position_steps: 2,
+            integration_sub_steps: 1,
+            baumgarte: 0.2,
+            penetration_slop: 0.02,
+            min_velocity_for_restitution: 1.0,
+            speculative_contact_distance: 0.02,
+            linear_cast_threshold: 0.75,
+            linear_cast_max_penetration: 0.25,
+            max_cached_manifolds: 4096,
+            max_cached_contact_points: 16384,
+            contact_point_preserve_lambda_max_distance: 0.01,
+            allow_sleeping: true,
+            point_velocity_sleep_threshold: 0.03,
+            time_before_sleep: 0.5,
+        }
+    }
+}
@@ -0,0 +1,685 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{fmt, mem};
+
+use crate::{
+    body::{
+        Body, BodyActivationListener, BodyFilter, BodyID, BodyInterface, BodyManager,
+        IgnoreSingleBodyFilter, MotionQuality,
+    },
+    broad_phase::{BroadPhase, DefaultBroadPhaseLayerFilter, ObjectVsBroadPhaseLayerFilter},
+    collision::{
+        AllHitCollector, CollideShapeSettings, CollisionDispatch, ContactManifold, ContactPoint,
+        DefaultObjectLayerFilter, ObjectLayerPairFilter, ShapeCast, ShapeCastResult,
+        ShapeCastSettings,
+    },
+    geometry::AABox,
+    math::Vec3,
+    physics::{
+        ContactConstraintManager, ContactListener, IslandBuilder, PhysicsSettings, ValidateResult,
+    },
+};
+
+/// The default gravity, in m/s².
+pub const DEFAULT_GRAVITY: Vec3 = Vec3::new(0.0, -9.81, 0.0);
+
+/// The contact points between two bodies that were found during the last step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contact {
+    /// The first body, which is always dynamic.
+    pub body_id1: BodyID,
+    pub body_id2: BodyID,
+    /// The contact points, relative to the center of mass of the first body.
+    pub manifold: ContactManifold,
+}
+
+/// The plane that the center of mass of a swept body may not move beyond during a step.
+#[derive(Clone, Copy, Debug)]
+struct LinearCastLimit {
+    body_id: BodyID,
+    /// The normal of the contact that the sweep found.
+    normal: Vec3,
+    /// The largest distance of the center of mass along the normal.
+    max_distance: f32,
+}
+
+/// Simulates the bodies in the world.
+///
+/// Bodies are created and added through the [`BodyInterface`] returned by
+/// [`PhysicsSystem::body_interface`], after which [`PhysicsSystem::update`] moves them
+/// forward in time. The [`BroadPhase`] implementation is chosen when the system is created.
+pub struct PhysicsSystem {
+    body_manager: BodyManager,
+    broad_phase: Box<dyn BroadPhase>,
+    object_vs_broad_phase_layer_filter: Arc<dyn ObjectVsBroadPhaseLayerFilter>,
+    object_layer_pair_filter: Arc<dyn ObjectLayerPairFilter>,
+    collision_dispatch: CollisionDispatch,
+    contact_constraint_manager: ContactConstraintManager,
+    contact_listener: Option<Box<dyn ContactListener>>,
+    body_filter: Option<Box<dyn BodyFilter + Send + Sync>>,
+    island_builder: IslandBuilder,
+    settings: PhysicsSettings,
+    gravity: Vec3,
+    /// The bodies that are awake, in the order of their index.
+    active_bodies: Vec<BodyID>,
+    /// The pairs of bodies whose bounds overlap, the first body is active.
+    body_pairs: Vec<(BodyID, BodyID)>,
+    contacts: Vec<Contact>,
+    /// The pairs of bodies of the contacts, with the lower body first, in order.
+    contact_pairs: Vec<(BodyID, BodyID)>,
+    /// The limits of the swept bodies, in the order of their index.
+    linear_cast_limits: Vec<LinearCastLimit>,
+    /// Scratch buffers that are reused every step.
+    new_bodies: Vec<BodyID>,
+    body_bounds: Vec<(BodyID, AABox)>,
+    can_sleep: Vec<bool>,
+    sleeping: Vec<BodyID>,
+}
+
+impl PhysicsSystem {
+    /// Creates a system that holds at most `max_bodies` bodies. The layer filters decide which
+    /// bodies collide, the broadphase has to map object layers to the same broadphase layers
+    /// that `object_vs_broad_phase_layer_filter` was built for.
+    pub fn new(
+        max_bodies: usize,
+        broad_phase: Box<dyn BroadPhase>,
+        object_vs_broad_phase_layer_filter: Arc<dyn ObjectVsBroadPhaseLayerFilter>,
+        object_layer_pair_filter: Arc<dyn ObjectLayerPairFilter>,
+    ) -> Self {
+        Self {
+            body_manager: BodyManager::new(max_bodies),
+            broad_phase,
+            object_vs_broad_phase_layer_filter,
+            object_layer_pair_filter,
+            collision_dispatch: CollisionDispatch::new(),
+            contact_constraint_manager: ContactConstraintManager::new(),
+            contact_listener: None,
+            body_filter: None,
+            island_builder: IslandBuilder::new(),
+            settings: PhysicsSettings::default(),
+            gravity: DEFAULT_GRAVITY,
+            active_bodies: Vec::new(),
+            body_pairs: Vec::new(),
+            contacts: Vec::new(),
+            contact_pairs: Vec::new(),
+            linear_cast_limits: Vec::new(),
+            new_bodies: Vec::new(),
+            body_bounds: Vec::new(),
+            can_sleep: Vec::new(),
+            sleeping: Vec::new(),
+        }
+    }
+
+    /// Returns the interface through which bodies are created, added and changed.
+    #[inline]
+    pub fn body_interface(&mut self) -> BodyInterface<'_> {
+        BodyInterface::new(&mut self.body_manager, &mut *self.broad_phase)
+    }
+
+    #[inline]
+    pub fn body_manager(&self) -> &BodyManager {
+        &self.body_manager
+    }
+
+    /// Returns the body with `body_id`, or `None` when it has been destroyed.
+    #[inline]
+    pub fn body(&self, body_id: BodyID) -> Option<&Body> {
+        self.body_manager.body(body_id)
+    }
+
+    #[inline]
+    pub fn broad_phase(&self) -> &dyn BroadPhase {
+        &*self.broad_phase
+    }
+
+    #[inline]
+    pub fn object_vs_broad_phase_layer_filter(&self) -> &dyn ObjectVsBroadPhaseLayerFilter {
+        &*self.object_vs_broad_phase_layer_filter
+    }
+
+    #[inline]
+    pub fn object_layer_pair_filter(&self) -> &dyn ObjectLayerPairFilter {
+        &*self.object_layer_pair_filter
+    }
+
+    /// Returns the dispatch table of the narrowphase, to register shapes defined by the
+    /// application.
+    #[inline]
+    pub fn collision_dispatch_mut(&mut self) -> &mut CollisionDispatch {
+        &mut self.collision_dispatch
+    }
+
+    #[inline]
+    pub fn contact_constraint_manager(&self) -> &ContactConstraintManager {
+        &self.contact_constraint_manager
+    }
+
+    /// Returns the contact solver, to replace how the friction and restitution of bodies are
+    /// combined.
+    #[inline]
+    pub fn contact_constraint_manager_mut(&mut self) -> &mut ContactConstraintManager {
+        &mut self.contact_constraint_manager
+    }
+
+    #[inline]
+    pub fn contact_listener(&self) -> Option<&dyn ContactListener> {
+        self.contact_listener.as_deref()
+    }
+
+    /// Sets the listener that is told about the contacts between bodies.
+    #[inline]
+    pub fn set_contact_listener(&mut self, contact_listener: Option<Box<dyn ContactListener>>) {
+        self.contact_listener = contact_listener;
+    }
+
+    #[inline]
+    pub fn body_filter(&self) -> Option<&dyn BodyFilter> {
+        self.body_filter
+            .as_deref()
+            .map(|body_filter| body_filter as &dyn BodyFilter)
+    }
+
+    /// Sets the filter that decides which bodies take part in collision detection, bodies that
+    /// it rejects do not collide with anything.
+    #[inline]
+    pub fn set_body_filter(&mut self, body_filter: Option<Box<dyn BodyFilter + Send + Sync>>) {
+        self.body_filter = body_filter;
+    }
+
+    #[inline]
+    pub fn body_activation_listener(&self) -> Option<&dyn BodyActivationListener> {
+        self.body_manager.body_activation_listener()
+    }
+
+    /// Sets the listener that is told about bodies that wake up or go to sleep.
+    #[inline]
+    pub fn set_body_activation_listener(
+        &mut self,
+        activation_listener: Option<Box<dyn BodyActivationListener>>,
+    ) {
+        self.body_manager
+            .set_body_activation_listener(activation_listener);
+    }
+
+    /// Returns the islands of the last step, the indices of their bodies refer to the bodies
+    /// that were active during that step in the order of their index.
+    #[inline]
+    pub fn island_builder(&self) -> &IslandBuilder {
+        &self.island_builder
+    }
+
+    #[inline]
+    pub fn physics_settings(&self) -> &PhysicsSettings {
+        &self.settings
+    }
+
+    #[inline]
+    pub fn set_physics_settings(&mut self, settings: PhysicsSettings) {
+        self.settings = settings;
+    }
+
+    #[inline]
+    pub fn gravity(&self) -> Vec3 {
+        self.gravity
+    }
+
+    #[inline]
+    pub fn set_gravity(&mut self, gravity: Vec3) {
+        self.gravity = gravity;
+    }
+
+    /// Returns the contacts that were found during the last step.
+    #[inline]
+    pub fn contacts(&self) -> &[Contact] {
+        &self.contacts
+    }
+
+    /// Moves the simulation forward by `delta_time` seconds, split into `collision_steps`
+    /// steps. Every step applies gravity, updates the broadphase, finds the pairs of bodies
+    /// whose bounds overlap, collides them in the narrowphase, solves the contacts, integrates
+    /// the bodies, moves them in the broadphase and puts the islands that stopped moving to
+    /// sleep. Nothing happens without time to simulate or without collision steps.
+    pub fn update(&mut self, delta_time: f32, collision_steps: u32) {
+        if delta_time <= 0.0 || collision_steps == 0 {
+            return;
+        }
+        let step_delta_time = delta_time / collision_steps as f32;
+        for _ in 0..collision_steps {
+            self.step(step_delta_time);
+        }
+    }
+
+    fn step(&mut self, delta_time: f32) {
+        let sub_steps = self.settings.integration_sub_steps.max(1);
+        let sub_step_delta_time = delta_time / sub_steps as f32;
+
+        self.find_active_bodies();
+        self.apply_gravity(sub_step_delta_time);
+        self.broad_phase.update();
+        self.find_contacts(delta_time, sub_step_delta_time);
+        self.contact_constraint_manager.build_constraints(
+            &self.contacts,
+            &self.body_manager,
+            &self.settings,
+            delta_time,
+            self.contact_listener
+                .as_deref_mut()
+                .map(|listener| listener as _),
+        );
+        self.contact_constraint_manager
+            .warm_start(&mut self.body_manager);
+        for sub_step in 0..sub_steps {
+            if sub_step > 0 {
+                self.apply_gravity(sub_step_delta_time);
+            }
+            for _ in 0..self.settings.velocity_steps {
+                self.contact_constraint_manager
+                    .solve_velocity_constraints(&mut self.body_manager);
+            }
+            self.integrate(sub_step_delta_time);
+            for _ in 0..self.settings.position_steps {
+                self.contact_constraint_manager
+                    .solve_position_constraints(&mut self.body_manager, &self.settings);
+            }
+        }
+        self.contact_constraint_manager.update_contact_cache(
+            &self.body_manager,
+            self.contact_listener
+                .as_deref_mut()
+                .map(|listener| listener as _),
+        );
+        self.update_broad_phase();
+        self.update_sleep_state(delta_time);
+    }
+
+    fn find_active_bodies(&mut self) {
+        self.active_bodies.clear();
+        self.active_bodies.extend(
+            self.body_manager
+                .bodies()
+                .filter(|body| body.is_active())
+                .map(Body::id),
+        );
+    }
+
+    /// Applies gravity, the accumulated forces and damping to the active bodies.
+    fn apply_gravity(&mut self, delta_time: f32) {
+        for &body_id in &self.active_bodies {
+            let body = self.body_manager.body_mut(body_id).unwrap();
+            body.apply_forces_gravity_and_damping(self.gravity, delta_time);
+        }
+    }
+
+    /// Finds the contacts of the active bodies, including the contacts that swept bodies will
+    /// make during the step. Sleeping dynamic bodies that they touch are woken up, with gravity
+    /// applied for the first sub step, and their contacts are found during the same step.
+    fn find_contacts(&mut self, delta_time: f32, sub_step_delta_time: f32) {
+        self.body_pairs.clear();
+        self.contacts.clear();
+        self.contact_pairs.clear();
+        self.linear_cast_limits.clear();
+        let mut new_bodies = mem::take(&mut self.new_bodies);
+        new_bodies.clear();
+        new_bodies.extend_from_slice(&self.active_bodies);
+        while !new_bodies.is_empty() {
+            let first_pair = self.body_pairs.len();
+            let first_contact = self.contacts.len();
+            self.find_body_pairs(&new_bodies);
+            self.collide_body_pairs(first_pair, delta_time);
+            let new_contacts = &self.contacts[first_contact..];
+            self.contact_pairs.extend(
+                new_contacts
+                    .iter()
+                    .map(|contact| ordered_pair(contact.body_id1, contact.body_id2)),
+            );
+            self.contact_pairs.sort_unstable();
+            self.contact_pairs.dedup();
+            self.find_linear_cast_contacts(&new_bodies, delta_time);
+
+            new_bodies.clear();
+            for contact in &self.contacts[first_contact..] {
+                for body_id in [contact.body_id1, contact.body_id2] {
+                    let body = self.body_manager.body(body_id).unwrap();
+                    if body.is_dynamic() && !body.is_active() {
+                        new_bodies.push(body_id);
+                    }
+                }
+            }
+            new_bodies.sort_unstable();
+            new_bodies.dedup();
+            self.body_manager.activate_bodies(&new_bodies);
+            for &body_id in &new_bodies {
+                let body = self.body_manager.body_mut(body_id).unwrap();
+                body.apply_forces_gravity_and_damping(self.gravity, sub_step_delta_time);
+            }
+            self.active_bodies.extend_from_slice(&new_bodies);
+            self.active_bodies.sort_unstable();
+        }
+        self.linear_cast_limits
+            .sort_unstable_by_key(|limit| limit.body_id);
+        self.new_bodies = new_bodies;
+    }
+
+    /// Finds the pairs of `new_bodies` and the bodies whose bounds overlap, except for the
+    /// pairs with bodies that were active before, which have been found already.
+    fn find_body_pairs(&mut self, new_bodies: &[BodyID]) {
+        let active_bodies = &self.active_bodies;
+        let body_filter = self
+            .body_filter
+            .as_deref()
+            .map_or(&() as &dyn BodyFilter, |filter| filter);
+        let body_pairs = &mut self.body_pairs;
+        self.broad_phase.find_colliding_pairs(
+            new_bodies,
+            self.settings.speculative_contact_distance,
+            &*self.object_vs_broad_phase_layer_filter,
+            &*self.object_layer_pair_filter,
+            body_filter,
+            &mut |body_id1, body_id2| {
+                if active_bodies.binary_search(&body_id2).is_err()
+                    || new_bodies.binary_search(&body_id2).is_ok()
+                {
+                    body_pairs.push((body_id1, body_id2));
+                }
+            },
+        );
+    }
+
+    /// Collides the shapes of the pairs of bodies from `first_pair` on and builds a manifold
+    /// for every contact that the contact listener accepts.
+    fn collide_body_pairs(&mut self, first_pair: usize, delta_time: f32) {
+        let mut settings = CollideShapeSettings {
+            max_separation_distance: self.settings.speculative_contact_distance,
+            collect_faces: true,
+            ..Default::default()
+        };
+        for &(body_id1, body_id2) in &self.body_pairs[first_pair..] {
+            let (Some(body1), Some(body2)) = (
+                self.body_manager.body(body_id1),
+                self.body_manager.body(body_id2),
+            ) else {
+                continue;
+            };
+            // Bodies that are not dynamic do not respond to contacts.
+            let (body1, body2) = match (body1.is_dynamic(), body2.is_dynamic()) {
+                (true, _) => (body1, body2),
+                (false, true) => (body2, body1),
+                (false, false) => continue,
+            };
+            if !body1.collision_group.can_collide(&body2.collision_group) {
+                continue;
+            }
+
+            settings.active_edge_movement_direction =
+                (body1.linear_velocity() - body2.linear_velocity()) * delta_time;
+            let mut collector = AllHitCollector::new();
+            // The body filter was applied when the pairs were found.
+            self.collision_dispatch.collide_shape(
+                &body1.transformed_shape(),
+                &body2.transformed_shape(),
+                &settings,
+                &mut collector,
+                &(),
+            );
+            let mut validate = self.contact_listener.is_some();
+            for hit in collector.hits {
+                if let (true, Some(listener)) = (validate, &mut self.contact_listener) {
+                    let base_offset = body1.center_of_mass_position();
+                    match listener.on_contact_validate(body1, body2, base_offset, &hit) {
+                        ValidateResult::AcceptAllContactsForThisBodyPair => validate = false,
+                        ValidateResult::AcceptContact => {}
+                        ValidateResult::RejectContact => continue,
+                        ValidateResult::RejectAllContactsForThisBodyPair => break,
+                    }
+                }
+                let mut manifold = ContactManifold::from_faces(
+                    body1.center_of_mass_position(),
+                    hit.contact_point1,
+                    hit.contact_point2,
+                    hit.penetration_axis,
+                    settings.max_separation_distance,
+                    &hit.face1,
+                    &hit.face2,
+                );
+                if manifold.points.is_empty() {
+                    manifold.points.push(ContactPoint {
+                        position1: hit.contact_point1 - manifold.base_offset,
+                        position2: hit.contact_point2 - manifold.base_offset,
+                        penetration_depth: hit.penetration_depth,
+                    });
+                }
+                manifold.sub_shape_id1 = hit.sub_shape_id1;
+                manifold.sub_shape_id2 = hit.sub_shape_id2;
+                self.contacts.push(Contact {
+                    body_id1: body1.id(),
+                    body_id2: body2.id(),
+                    manifold,
+                });
+            }
+        }
+    }
+
+    /// Sweeps the dynamic bodies of `bodies` with [`MotionQuality::LinearCast`] that move far
+    /// enough along their displacement during the step, and adds a contact at the first body
+    /// they hit. The contact is solved like any other, and limits the motion of the body to
+    /// just inside the body it hit.
+    fn find_linear_cast_contacts(&mut self, bodies: &[BodyID], delta_time: f32) {
+        for &body_id in bodies {
+            let body = self.body_manager.body(body_id).unwrap();
+            let displacement = body.linear_velocity() * delta_time;
+            let threshold = self.settings.linear_cast_threshold * body.shape().inner_radius();
+            if !body.is_dynamic()
+                || body.motion_quality() != MotionQuality::LinearCast
+                || displacement.length_squared() <= threshold * threshold
+            {
+                continue;
+            }
+            let Some((body_id2, hit)) = self.cast_body(body_id, displacement) else {
+                continue;
+            };
+
+            // The point on the swept body is moved back to where the body is now.
+            let body = self.body_manager.body(body_id).unwrap();
+            let base_offset = body.center_of_mass_position();
+            let normal = hit.contact.penetration_axis.normalize();
+            let offset = displacement * hit.fraction;
+            let mut manifold = ContactManifold::new(base_offset, normal);
+            manifold.sub_shape_id1 = hit.contact.sub_shape_id1;
+            manifold.sub_shape_id2 = hit.contact.sub_shape_id2;
+            manifold.points.push(ContactPoint {
+                position1: hit.contact.contact_point1 - offset - base_offset,
+                position2: hit.contact.contact_point2 - base_offset,
+                penetration_depth: hit.contact.penetration_depth - normal.dot(offset),
+            });
+            self.contacts.push(Contact {
+                body_id1: body_id,
+                body_id2,
+                manifold,
+            });
+            let pair = ordered_pair(body_id, body_id2);
+            if let Err(index) = self.contact_pairs.binary_search(&pair) {
+                self.contact_pairs.insert(index, pair);
+            }
+
+            let max_penetration =
+                self.settings.linear_cast_max_penetration * body.shape().inner_radius();
+            self.linear_cast_limits.push(LinearCastLimit {
+                body_id,
+                normal,
+                max_distance: normal.dot(base_offset + offset) + max_penetration,
+            });
+        }
+    }
+
+    /// Moves the active bodies by their velocities. Swept bodies stop just inside the body
+    /// they hit.
+    fn integrate(&mut self, delta_time: f32) {
+        for &body_id in &self.active_bodies {
+            let body = self.body_manager.body_mut(body_id).unwrap();
+            body.integrate(delta_time);
+            let limits = &self.linear_cast_limits;
+            let Ok(index) = limits.binary_search_by_key(&body_id, |limit| limit.body_id) else {
+                continue;
+            };
+            let limit = &limits[index];
+            let excess = limit.normal.dot(body.center_of_mass_position()) - limit.max_distance;
+            if excess > 0.0 {
+                body.add_position_step(-limit.normal * excess, Vec3::ZERO);
+            }
+        }
+    }
+
+    /// Sweeps the shape of a body along `displacement` and returns the first hit that moves
+    /// into another body, with that body, that the body filter and the contact listener
+    /// accept. Bodies that the body has a contact with already are skipped.
+    fn cast_body(
+        &mut self,
+        body_id: BodyID,
+        displacement: Vec3,
+    ) -> Option<(BodyID, ShapeCastResult)> {
+        let body_filter = self.body_filter().unwrap_or(&());
+        if !body_filter.should_collide(body_id) {
+            return None;
+        }
+        let body = self.body_manager.body(body_id).unwrap();
+        let cast = ShapeCast::new(body.transformed_shape(), displacement);
+        let mut candidates = AllHitCollector::new();
+        self.broad_phase.collide_aabox(
+            &cast.swept_bounds(),
+            &mut candidates,
+            &DefaultBroadPhaseLayerFilter::new(
+                &*self.object_vs_broad_phase_layer_filter,
+                body.object_layer(),
+            ),
+            &DefaultObjectLayerFilter::new(&*self.object_layer_pair_filter, body.object_layer()),
+            &(body_filter, IgnoreSingleBodyFilter(body_id)),
+        );
+
+        let settings = ShapeCastSettings::default();
+        let mut hits = Vec::new();
+        for body_id2 in candidates.hits {
+            let Some(body2) = self.body_manager.body(body_id2) else {
+                continue;
+            };
+            if !body.collision_group.can_collide(&body2.collision_group)
+                || self
+                    .contact_pairs
+                    .binary_search(&ordered_pair(body_id, body_id2))
+                    .is_ok()
+            {
+                continue;
+            }
+            let mut collector = AllHitCollector::new();
+            // The body filter was applied when the candidates were found.
+            self.collision_dispatch.cast_shape(
+                &cast,
+                &body2.transformed_shape(),
+                &settings,
+                &mut collector,
+                &(),
+            );
+            hits.extend(
+                collector
+                    .hits
+                    .into_iter()
+                    .filter(|hit| hit.contact.penetration_axis.dot(displacement) > 0.0)
+                    .map(|hit| (body_id2, hit)),
+            );
+        }
+        hits.sort_by(|(_, hit1), (_, hit2)| hit1.fraction.total_cmp(&hit2.fraction));
+
+        let Some(listener) = &mut self.contact_listener else {
+            return hits.into_iter().next();
+        };
+        let base_offset = body.center_of_mass_position();
+        hits.into_iter().find(|(body_id2, hit)| {
+            let body2 = self.body_manager.body(*body_id2).unwrap();
+            matches!(
+                listener.on_contact_validate(body, body2, base_offset, &hit.contact),
+                ValidateResult::AcceptAllContactsForThisBodyPair | ValidateResult::AcceptContact
+            )
+        })
+    }
+
+    /// Updates the bounds of the active bodies and passes them to the broadphase.
+    fn update_broad_phase(&mut self) {
+        for &body_id in &self.active_bodies {
+            self.body_manager.body_mut(body_id).unwrap().update_bounds();
+        }
+        self.body_bounds.clear();
+        self.body_bounds
+            .extend(self.active_bodies.iter().map(|&body_id| {
+                (
+                    body_id,
+                    self.body_manager.body(body_id).unwrap().world_bounds(),
+                )
+            }));
+        self.broad_phase
+            .notify_bodies_aabb_changed(&self.body_bounds);
+    }
+
+    /// Builds the islands of the active bodies, which are linked by their contacts with other
+    /// dynamic bodies, and puts the islands whose bodies all moved slowly for long enough to
+    /// sleep.
+    fn update_sleep_state(&mut self, delta_time: f32) {
+        self.can_sleep.clear();
+        self.can_sleep
+            .extend(self.active_bodies.iter().map(|&body_id| {
+                self.body_manager
+                    .body_mut(body_id)
+                    .unwrap()
+                    .update_sleep_state(
+                        self.settings.point_velocity_sleep_threshold,
+                        self.settings.time_before_sleep,
+                        delta_time,
+                    )
+            }));
+
+        self.island_builder.prepare(self.active_bodies.len());
+        for contact in &self.contacts {
+            if !self
+                .body_manager
+                .body(contact.body_id2)
+                .unwrap()
+                .is_dynamic()
+            {
+                continue;
+            }
+            let index1 = self.active_bodies.binary_search(&contact.body_id1);
+            let index2 = self.active_bodies.binary_search(&contact.body_id2);
+            if let (Ok(index1), Ok(index2)) = (index1, index2) {
+                self.island_builder.link_bodies(index1, index2);
+            }
+        }
+        self.island_builder.finalize();
+
+        if !self.settings.allow_sleeping {
+            return;
+        }
+        let can_sleep = &self.can_sleep;
+        self.sleeping.clear();
+        self.sleeping.extend(
+            self.island_builder
+                .islands()
+                .filter(|island| island.iter().all(|&index| can_sleep[index as usize]))
+                .flatten()
+                .map(|&index| self.active_bodies[index as usize]),
+        );
+        self.body_manager.deactivate_bodies(&self.sleeping);
+    }
+}
+
+impl fmt::Debug for PhysicsSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PhysicsSystem")
+            .field("body_manager", &self.body_manager)
+            .field("settings", &self.settings)
+            .field("gravity", &self.gravity)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns the pair of bodies with the lower body first.
+#[inline]
+fn ordered_pair(body_id1: BodyID, body_id2: BodyID) -> (BodyID, BodyID) {
+    (body_id1.min(body_id2), body_id1.max(body_id2))
+}
@@ -0,0 +1,199 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{
+    body::MassProperties,
+    collision::{RayCast, RayCastResult},
+    geometry::{ray_aabox, AABox, Support},
+    math::Vec3,
+    shape::{
+        ConvexShape, Shape, ShapeError, ShapeSubType, SubShapeID, SupportMode,
+        DEFAULT_CONVEX_RADIUS, DEFAULT_DENSITY,
+    },
+};
+
+/// A box centered around the origin, the corners can be rounded by a convex radius.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoxShape {
+    half_extent: Vec3,
+    convex_radius: f32,
+    density: f32,
+}
+
+impl BoxShape {
+    /// Creates a box with the given `half_extent`, the convex radius defaults to
+    /// [`DEFAULT_CONVEX_RADIUS`] or the smallest half extent, whichever is smaller.
+    pub fn new(half_extent: Vec3) -> Result<Self, ShapeError> {
+        Self::with_convex_radius(
+            half_extent,
+            DEFAULT_CONVEX_RADIUS.min(half_extent.min_element()),
+        )
+    }
+
+    /// Creates a box with the given `half_extent` and `convex_radius`.
+    pub fn with_convex_radius(half_extent: Vec3, convex_radius: f32) -> Result<Self, ShapeError> {
+        if !(half_extent.min_element() > 0.0 && half_extent.is_finite()) {
+            return Err(ShapeError::InvalidHalfExtent);
+        }
+        if !(convex_radius >= 0.0 && convex_radius <= half_extent.min_element()) {
+            return Err(ShapeError::InvalidConvexRadius);
+        }
+        Ok(Self {
+            half_extent,
+            convex_radius,
+            density: DEFAULT_DENSITY,
+        })
+    }
+
+    /// Sets the density of the shape in kg / m^3.
+    pub fn with_density(mut self, density: f32) -> Result<Self, ShapeError> {
+        self.density = super::validate_density(density)?;
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn half_extent(&self) -> Vec3 {
+        self.half_extent
+    }
+
+    #[inline]
+    pub fn convex_radius(&self) -> f32 {
+        self.convex_radius
+    }
+}
+
+struct BoxSupport {
+    half_extent: Vec3,
+    convex_radius: f32,
+}
+
+impl Support for BoxSupport {
+    #[inline]
+    fn support(&self, direction: Vec3) -> Vec3 {
+        AABox::new(-self.half_extent, self.half_extent).support(direction)
+    }
+
+    #[inline]
+    fn convex_radius(&self) -> f32 {
+        self.convex_radius
+    }
+}
+
+impl Shape for BoxShape {
+    fn sub_type(&self) -> ShapeSubType {
+        ShapeSubType::Box
+    }
+
+    fn local_bounds(&self) -> AABox {
+        AABox::new(-self.half_extent, self.half_extent)
+    }
+
+    fn inner_radius(&self) -> f32 {
+        self.half_extent.min_element()
+    }
+
+    fn volume(&self) -> f32 {
+        let size = self.half_extent * 2.0;
+        size.x * size.y * size.z
+    }
+
+    fn mass_properties(&self) -> MassProperties {
+        let mut mass_properties = MassProperties::default();
+        mass_properties.set_mass_and_inertia_of_solid_box(2.0 * self.half_extent, self.density);
+        mass_properties
+    }
+
+    fn surface_normal(&self, _sub_shape_id: SubShapeID, local_position: Vec3) -> Vec3 {
+        // Pick the axis on which the point is closest to the surface.
+        let distance = (local_position.abs() - self.half_extent).abs();
+        let axis = if distance.x <= distance.y && distance.x <= distance.z {
+            0
+        } else if distance.y <= distance.z {
+            1
+        } else {
+            2
+        };
+        let mut normal = Vec3::ZERO;
+        normal[axis] = if local_position[axis] < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        normal
+    }
+
+    fn cast_ray(&self, ray: &RayCast, hit: &mut RayCastResult) -> bool {
+        let fraction = ray_aabox(
+            ray.origin,
+            ray.direction,
+            -self.half_extent,
+            self.half_extent,
+        );
+        if fraction < hit.fraction {
+            hit.fraction = fraction;
+            hit.sub_shape_id = SubShapeID::EMPTY;
+            return true;
+        }
+        false
+    }
+
+    fn collide_point(&self, point: Vec3) -> bool {
+        self.local_bounds().contains_point(point)
+    }
+
+    fn as_convex(&self) -> Option<&dyn ConvexShape> {
+        Some(self)
+    }
+}
+
+impl ConvexShape for BoxShape {
+    fn density(&self) -> f32 {
+        self.density
+    }
+
+    fn get_support_function(&self, mode: SupportMode, scale: Vec3) -> Box<dyn Support + '_> {
+        let half_extent = self.half_extent * scale.abs();
+        match mode {
+            SupportMode::IncludeConvexRadius | SupportMode::Default => Box::new(BoxSupport {
+                half_extent,
+                convex_radius: 0.0,
+            }),
+            SupportMode::ExcludeConvexRadius => {
+                let convex_radius = self.convex_radius * scale.abs().min_element();
+                Box::new(BoxSupport {
+                    half_extent: half_extent - convex_radius,
+                    convex_radius,
+                })
+            }
+        }
+    }
+
+    fn get_supporting_face(&self, direction: Vec3, scale: Vec3, vertices: &mut Vec<Vec3>) {
+        // The box is symmetric, so mirroring it does not change its faces.
+        let half_extent = self.half_extent * scale.abs();
+        let abs_direction = direction.abs();
+        let axis = if abs_direction.x >= abs_direction.y && abs_direction.x >= abs_direction.z {
+            0
+        } else if abs_direction.y >= abs_direction.z {
+            1
+        } else {
+            2
+        };
+
+        // Walk around the face counter clockwise, the axes u, v and the face axis form a right
+        // handed frame.
+        let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+        let mut corners = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)];
+        let sign = 1.0f32.copysign(direction[axis]);
+        if sign < 0.0 {
+            corners.reverse();
+        }
+        vertices.clear();
+        vertices.extend(corners.iter().map(|&(cu, cv)| {
+            let mut vertex = Vec3::ZERO;
+            vertex[axis] = sign * half_extent[axis];
+            vertex[u] = cu * half_extent[u];
+            vertex[v] = cv * half_extent[v];
+            vertex
+        }));
+    }
+}
@@ -0,0 +1,217 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::f32::consts::PI;
+
+use crate::{
+    body::MassProperties,
+    collision::{RayCast, RayCastResult},
+    geometry::{ray_capsule, AABox, Support},
+    math::{Mat33, Vec3},
+    shape::{
+        scale_helpers, ConvexShape, Shape, ShapeError, ShapeSubType, SubShapeID, SupportMode,
+        DEFAULT_DENSITY,
+    },
+};
+
+/// The side of the capsule is used as its supporting face when the projections of its ends on
+/// the direction differ by less than this fraction of the length of the direction.
+const CAPSULE_PROJECTION_SLOP: f32 = 0.02;
+
+/// A capsule centered around the origin with its cylindrical part along the Y axis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CapsuleShape {
+    half_height_of_cylinder: f32,
+    radius: f32,
+    density: f32,
+}
+
+impl CapsuleShape {
+    /// Creates a capsule, the total height is `2 * (half_height_of_cylinder + radius)`.
+    pub fn new(half_height_of_cylinder: f32, radius: f32) -> Result<Self, ShapeError> {
+        if !(half_height_of_cylinder >= 0.0 && half_height_of_cylinder.is_finite()) {
+            return Err(ShapeError::InvalidHeight);
+        }
+        if !(radius > 0.0 && radius.is_finite()) {
+            return Err(ShapeError::InvalidRadius);
+        }
+        Ok(Self {
+            half_height_of_cylinder,
+            radius,
+            density: DEFAULT_DENSITY,
+        })
+    }
+
+    /// Sets the density of the shape in kg / m^3.
+    pub fn with_density(mut self, density: f32) -> Result<Self, ShapeError> {
+        self.density = super::validate_density(density)?;
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn half_height_of_cylinder(&self) -> f32 {
+        self.half_height_of_cylinder
+    }
+
+    #[inline]
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+}
+
+/// The line segment of a capsule, the radius is reported as convex radius.
+struct CapsuleNoConvex {
+    half_height: f32,
+    radius: f32,
+}
+
+impl Support for CapsuleNoConvex {
+    #[inline]
+    fn support(&self, direction: Vec3) -> Vec3 {
+        Vec3::new(0.0, self.half_height.copysign(direction.y), 0.0)
+    }
+
+    #[inline]
+    fn convex_radius(&self) -> f32 {
+        self.radius
+    }
+}
+
+/// A capsule that includes its radius in the support points.
+struct CapsuleWithConvex {
+    half_height: f32,
+    radius: f32,
+}
+
+impl Support for CapsuleWithConvex {
+    #[inline]
+    fn support(&self, direction: Vec3) -> Vec3 {
+        let y = self.half_height.copysign(direction.y);
+        Vec3::new(0.0, y, 0.0) + direction.normalize_or_zero() * self.radius
+    }
+}
+
+impl Shape for CapsuleShape {
+    fn sub_type(&self) -> ShapeSubType {
+        ShapeSubType::Capsule
+    }
+
+    fn local_bounds(&self) -> AABox {
+        let extent = Vec3::new(
+            self.radius,
+            self.half_height_of_cylinder + self.radius,
+            self.radius,
+        );
+        AABox::new(-extent, extent)
+    }
+
+    fn inner_radius(&self) -> f32 {
+        self.radius
+    }
+
+    fn volume(&self) -> f32 {
+        let r = self.radius;
+        PI * r * r * (2.0 * self.half_height_of_cylinder + 4.0 / 3.0 * r)
+    }
+
+    fn mass_properties(&self) -> MassProperties {
+        let (h, r) = (self.half_height_of_cylinder, self.radius);
+        let r2 = r * r;
+        let cylinder_mass = PI * r2 * 2.0 * h * self.density;
+        let sphere_mass = 4.0 / 3.0 * PI * r2 * r * self.density;
+
+        // The cylinder plus two hemispheres moved to the ends of the cylinder using the
+        // parallel axis theorem, see: https://www.gamedev.net/tutorials/programming/math-and-physics/capsule-inertia-tensor-r3856/
+        let inertia_y = cylinder_mass * r2 * 0.5 + sphere_mass * r2 * 0.4;
+        let inertia_x = cylinder_mass * (r2 * 0.25 + h * h / 3.0)
+            + sphere_mass * (r2 * 0.4 + h * h + 0.75 * h * r);
+        MassProperties::new(
+            cylinder_mass + sphere_mass,
+            Mat33::from_diagonal(Vec3::new(inertia_x, inertia_y, inertia_x)),
+        )
+    }
+
+    fn is_valid_scale(&self, scale: Vec3) -> bool {
+        scale_helpers::is_valid_scale(scale) && scale_helpers::is_uniform_scale(scale)
+    }
+
+    fn surface_normal(&self, _sub_shape_id: SubShapeID, local_position: Vec3) -> Vec3 {
+        let y = local_position
+            .y
+            .clamp(-self.half_height_of_cylinder, self.half_height_of_cylinder);
+        let normal = (local_position - Vec3::new(0.0, y, 0.0)).normalize_or_zero();
+        if normal == Vec3::ZERO {
+            Vec3::Y
+        } else {
+            normal
+        }
+    }
+
+    fn cast_ray(&self, ray: &RayCast, hit: &mut RayCastResult) -> bool {
+        let fraction = ray_capsule(
+            ray.origin,
+            ray.direction,
+            self.half_height_of_cylinder,
+            self.radius,
+        );
+        if fraction < hit.fraction {
+            hit.fraction = fraction;
+            hit.sub_shape_id = SubShapeID::EMPTY;
+            return true;
+        }
+        false
+    }
+
+    fn collide_point(&self, point: Vec3) -> bool {
+        let y = point
+            .y
+            .clamp(-self.half_height_of_cylinder, self.half_height_of_cylinder);
+        point.distance_squared(Vec3::new(0.0, y, 0.0)) <= self.radius * self.radius
+    }
+
+    fn as_convex(&self) -> Option<&dyn ConvexShape> {
+        Some(self)
+    }
+}
+
+impl ConvexShape for CapsuleShape {
+    fn density(&self) -> f32 {
+        self.density
+    }
+
+    fn get_support_function(&self, mode: SupportMode, scale: Vec3) -> Box<dyn Support + '_> {
+        let scale = scale.x.abs();
+        let half_height = self.half_height_of_cylinder * scale;
+        let radius = self.radius * scale;
+        match mode {
+            SupportMode::IncludeConvexRadius => Box::new(CapsuleWithConvex {
+                half_height,
+                radius,
+            }),
+            SupportMode::ExcludeConvexRadius | SupportMode::Default => Box::new(CapsuleNoConvex {
+                half_height,
+                radius,
+            }),
+        }
+    }
+
+    fn get_supporting_face(&self, direction: Vec3, scale: Vec3, vertices: &mut Vec<Vec3>) {
+        vertices.clear();
+        let scale = scale.x.abs();
+        let half_height = self.half_height_of_cylinder * scale;
+        let radius = self.radius * scale;
+
+        // Only the line along the side of the cylinder is flat, return it when both ends of
+        // the line are (almost) equally far along the direction.
+        let horizontal = Vec3::new(direction.x, 0.0, direction.z);
+        let length = horizontal.length();
+        if length == 0.0 || half_height == 0.0 {
+            return;
+        }
+        let side = horizontal * (radius / length);
+        let top = side + Vec3::new(0.0, half_height, 0.0);
+        let bottom = side - Vec3::new(0.0, half_height, 0.0);
+        if (top - bottom).dot(direction).abs() < CAPSULE_PROJECTION_SLOP * direction.length() {
+            vertices.push(top);
+            vertices.push(bottom);
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use alloc::sync::Arc;
+
+use crate::{
+    body::MassProperties,
+    collision::{CollidePointResult, CollisionCollector, RayCast, RayCastResult},
+    geometry::AABox,
+    math::{Quat, Vec3},
+    shape::{
+        scale_helpers, sub_shape_id_bits_for, Shape, ShapeError, SubShapeID, SubShapeIDCreator,
+    },
+};
+
+/// A child of a compound shape.
+#[derive(Clone, Debug)]
+pub struct CompoundSubShape {
+    pub shape: Arc<dyn Shape>,
+    /// The position of the child.
+    ///
+    /// When a compound is created this is the position of the origin of the child. Once part
+    /// of a compound it is the position of the center of mass of the child relative to the
+    /// center of mass of the compound.
+    pub position: Vec3,
+    pub rotation: Quat,
+    /// A value that is not used by the compound, the application can use it to identify the
+    /// child.
+    pub user_data: u32,
+}
+
+impl CompoundSubShape {
+    #[inline]
+    pub fn new(shape: Arc<dyn Shape>, position: Vec3, rotation: Quat) -> Self {
+        Self {
+            shape,
+            position,
+            rotation,
+            user_data: 0,
+        }
+    }
+
+    #[inline]
+    pub fn with_user_data(mut self, user_data: u32) -> Self {
+        self.user_data = user_data;
+        self
+    }
+
+    /// Returns the bounds of the child in the space of the compound.
+    #[inline]
+    pub fn bounds(&self) -> AABox {
+        self.shape
+            .world_bounds(self.rotation, self.position, Vec3::ONE)
+    }
+
+    /// Transforms `point` from the space of the compound to the space of the child.
+    #[inline]
+    pub fn to_local(&self, point: Vec3) -> Vec3 {
+        self.rotation.conjugate() * (point - self.position)
+    }
+
+    /// Casts `ray`, which is in the space of the compound, against the child. The sub shape ID
+    /// of a hit is appended to `creator`.
+    pub(crate) fn cast_ray(
+        &self,
+        creator: SubShapeIDCreator,
+        ray: &RayCast,
+        hit: &mut RayCastResult,
+    ) -> bool {
+        let local_ray = ray
+            .translated(-self.position)
+            .transformed(self.rotation.conjugate(), Vec3::ZERO);
+        let mut local_hit = RayCastResult {
+            fraction: hit.fraction,
+            sub_shape_id: SubShapeID::EMPTY,
+        };
+        if !self.shape.cast_ray(&local_ray, &mut local_hit) {
+            return false;
+        }
+        hit.fraction = local_hit.fraction;
+        hit.sub_shape_id = creator.push_sub_shape_id(local_hit.sub_shape_id);
+        true
+    }
+
+    /// Casts `ray`, which is in the space of the compound, against the child and adds the
+    /// hits to `collector`, see [`Shape::cast_ray_with_collector`].
+    #[inline]
+    pub(crate) fn cast_ray_with_collector(
+        &self,
+        creator: SubShapeIDCreator,
+        ray: &RayCast,
+        collector: &mut dyn CollisionCollector<RayCastResult>,
+    ) {
+        let local_ray = ray
+            .translated(-self.position)
+            .transformed(self.rotation.conjugate(), Vec3::ZERO);
+        self.shape
+            .cast_ray_with_collector(&local_ray, creator, collector);
+    }
+
+    #[inline]
+    pub(crate) fn collide_point(&self, point: Vec3) -> bool {
+        self.shape.collide_point(self.to_local(point))
+    }
+
+    #[inline]
+    pub(crate) fn collide_point_with_collector(
+        &self,
+        creator: SubShapeIDCreator,
+        point: Vec3,
+        collector: &mut dyn CollisionCollector<CollidePointResult>,
+    ) {
+        self.shape
+            .collide_point_with_collector(self.to_local(point), creator, collector);
+    }
+
+    #[inline]
+    pub(crate) fn surface_normal(&self, sub_shape_id: SubShapeID, position: Vec3) -> Vec3 {
+        self.rotation
+            * self
+                .shape
+                .surface_normal(sub_shape_id, self.to_local(position))
+    }
+}
+
+/// Moves the children of a new compound from the space of its origin to the space of its
+/// center of mass, which is returned.
+pub(crate) fn center_sub_shapes(sub_shapes: &mut [CompoundSubShape]) -> Vec3 {
+    for sub_shape in sub_shapes.iter_mut() {
+        sub_shape.position += sub_shape.rotation * sub_shape.shape.center_of_mass();
+    }
+    let center_of_mass = center_of_mass(sub_shapes);
+    for sub_shape in sub_shapes.iter_mut() {
+        sub_shape.position -= center_of_mass;
+    }
+    center_of_mass
+}
+
+/// Returns the mass weighted average of the centers of mass of the children, or zero when the
+/// children have no mass.
+pub(crate) fn center_of_mass(sub_shapes: &[CompoundSubShape]) -> Vec3 {
+    let mut center = Vec3::ZERO;
+    let mut mass = 0.0;
+    for sub_shape in sub_shapes {
+        let child_mass = sub_shape.shape.mass_properties().mass;
+        center += sub_shape.position * child_mass;
+        mass += child_mass;
+    }
+    if mass > 0.0 {
+        center / mass
+    } else {
+        Vec3::ZERO
+    }
+}
+
+/// Returns the number of bits needed to identify a child and its sub shapes.
+pub(crate) fn sub_shape_id_bits(sub_shapes: &[CompoundSubShape]) -> Result<u32, ShapeError> {
+    let child_bits = sub_shapes
+        .iter()
+        .map(|sub_shape| sub_shape.shape.sub_shape_id_bits())
+        .max()
+        .unwrap_or(0);
+    let bits = sub_shape_id_bits_for(sub_shapes.len() as u32) + child_bits;
+    if bits > SubShapeID::MAX_BITS {
+        return Err(ShapeError::SubShapeIDOverflow);
+    }
+    Ok(bits)
+}
+
+/// Returns whether every child can be scaled by `scale` in the space of the compound.
+pub(crate) fn is_valid_scale(sub_shapes: &[CompoundSubShape], scale: Vec3) -> bool {
+    scale_helpers::is_valid_scale(scale)
+        && sub_shapes.iter().all(|sub_shape| {
+            scale_helpers::rotate_scale(sub_shape.rotation, scale)
+                .is_some_and(|child_scale| sub_shape.shape.is_valid_scale(child_scale))
+        })
+}
+
+/// Returns the bounds of all children.
+pub(crate) fn local_bounds(sub_shapes: &[CompoundSubShape]) -> AABox {
+    let mut bounds = AABox::INVALID;
+    for sub_shape in sub_shapes {
+        bounds.encapsulate_box(&sub_shape.bounds());
+    }
+    bounds
+}
+
+pub(crate) fn inner_radius(sub_shapes: &[CompoundSubShape]) -> f32 {
+    sub_shapes
+        .iter()
+        .map(|sub_shape| sub_shape.shape.inner_radius())
+        .reduce(f32::min)
+        .unwrap_or(0.0)
+}
+
+pub(crate) fn volume(sub_shapes: &[CompoundSubShape]) -> f32 {
+    sub_shapes
+        .iter()
+        .map(|sub_shape| sub_shape.shape.volume())
+        .sum()
+}
+
+/// Combines the mass properties of the children around the center of mass of the compound.
+pub(crate) fn mass_properties(sub_shapes: &[CompoundSubShape]) -> MassProperties {
+    let mut result = MassProperties::default();
+    for sub_shape in sub_shapes {
+        // Rotate the inertia into the space of the compound and move it away from the center
+        // of mass.
+        let mut child = sub_shape.shape.mass_properties();
+        child.rotate(sub_shape.rotation);
+        child.translate(sub_shape.position);
+        result.mass += child.mass;
+        result.inertia += child.inertia;
+    }
+    result
+}
@@ -0,0 +1,341 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{
+    body::MassProperties,
+    collision::{RayCast, RayCastResult},
+    geometry::{AABox, ConvexHullBuilder, ConvexHullFace, Plane, Support},
+    math::{Mat33, Vec3},
+    shape::{
+        ConvexShape, Shape, ShapeError, ShapeSubType, SubShapeID, SupportMode,
+        DEFAULT_CONVEX_RADIUS, DEFAULT_DENSITY,
+    },
+};
+
+/// The maximum number of points a [`ConvexHullShape`] can consist of.
+pub const MAX_POINTS_IN_HULL: usize = 256;
+
+/// The tolerance used to build the hull, points closer than this to the hull are discarded.
+const HULL_TOLERANCE: f32 = 1.0e-3;
+
+/// A convex hull around a point cloud, the corners can be rounded by a convex radius.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvexHullShape {
+    /// The vertices of the hull relative to the center of mass.
+    points: Vec<Vec3>,
+    /// The direction to move each point in to shrink the hull by a convex radius of one.
+    convex_offsets: Vec<Vec3>,
+    faces: Vec<ConvexHullFace>,
+    /// The outward facing plane of each face relative to the center of mass.
+    planes: Vec<Plane>,
+    center_of_mass: Vec3,
+    local_bounds: AABox,
+    inner_radius: f32,
+    volume: f32,
+    /// The inertia for a density of one.
+    unit_inertia: Mat33,
+    convex_radius: f32,
+    density: f32,
+}
+
+impl ConvexHullShape {
+    /// Creates the convex hull of `points`, the convex radius defaults to
+    /// [`DEFAULT_CONVEX_RADIUS`] or the inner radius, whichever is smaller.
+    pub fn new(points: &[Vec3]) -> Result<Self, ShapeError> {
+        let mut shape = Self::with_convex_radius(points, 0.0)?;
+        shape.convex_radius = DEFAULT_CONVEX_RADIUS.min(shape.inner_radius);
+        Ok(shape)
+    }
+
+    /// Creates the convex hull of `points` with the given `convex_radius`.
+    ///
+    /// The resulting hull may consist of at most [`MAX_POINTS_IN_HULL`] points, use
+    /// [`ConvexHullBuilder::with_max_vertices`] to simplify larger point clouds first.
+    pub fn with_convex_radius(points: &[Vec3], convex_radius: f32) -> Result<Self, ShapeError> {
+        let hull = ConvexHullBuilder::new(points)
+            .build(HULL_TOLERANCE)
+            .map_err(ShapeError::ConvexHull)?;
+        if hull.vertices.len() > MAX_POINTS_IN_HULL {
+            return Err(ShapeError::TooManyPoints);
+        }
+
+        // Decompose the hull into tetrahedra that share the first vertex and accumulate their
+        // volume, first moment and covariance.
+        let reference = hull.vertices[0];
+        let mut volume = 0.0;
+        let mut moment = Vec3::ZERO;
+        let mut covariance = Mat33::ZERO;
+        let canonical =
+            Mat33::from([[2.0, 1.0, 1.0], [1.0, 2.0, 1.0], [1.0, 1.0, 2.0]]) * (1.0 / 120.0);
+        for face in &hull.faces {
+            let a = hull.vertices[face.vertices[0] as usize] - reference;
+            for edge in face.vertices[1..].windows(2) {
+                let b = hull.vertices[edge[0] as usize] - reference;
+                let c = hull.vertices[edge[1] as usize] - reference;
+                let determinant = a.dot(b.cross(c));
+                volume += determinant / 6.0;
+                moment += (a + b + c) * (determinant / 24.0);
+                let matrix = Mat33::from_cols(a, b, c);
+                covariance += matrix * canonical * matrix.transpose() * determinant;
+            }
+        }
+        let offset = moment / volume;
+        let center_of_mass = reference + offset;
+        covariance -= Mat33::from_outer_product(offset, offset) * volume;
+        let trace = covariance.x_axis.x + covariance.y_axis.y + covariance.z_axis.z;
+        let unit_inertia = Mat33::from_diagonal(Vec3::splat(trace)) - covariance;
+
+        let points: Vec<Vec3> = hull.vertices.iter().map(|&p| p - center_of_mass).collect();
+        let planes: Vec<Plane> = hull
+            .planes
+            .iter()
+            .map(|p| Plane::new(p.normal, p.constant + p.normal.dot(center_of_mass)))
+            .collect();
+        let inner_radius = planes
+            .iter()
+            .map(|p| -p.constant)
+            .fold(f32::MAX, f32::min)
+            .max(0.0);
+        if !(convex_radius >= 0.0 && convex_radius <= inner_radius) {
+            return Err(ShapeError::InvalidConvexRadius);
+        }
+
+        let convex_offsets = (0..points.len())
+            .map(|index| Self::convex_offset(&hull.faces, &planes, index as u32))
+            .collect();
+
+        Ok(Self {
+            local_bounds: AABox::from_points(points.iter().copied()),
+            points,
+            convex_offsets,
+            faces: hull.faces,
+            planes,
+            center_of_mass,
+            inner_radius,
+            volume,
+            unit_inertia,
+            convex_radius,
+            density: DEFAULT_DENSITY,
+        })
+    }
+
+    /// Sets the density of the shape in kg / m^3.
+    pub fn with_density(mut self, density: f32) -> Result<Self, ShapeError> {
+        self.density = super::validate_density(density)?;
+        Ok(self)
+    }
+
+    /// Returns the vertices of the hull relative to the center of mass.
+    #[inline]
+    pub fn points(&self) -> &[Vec3] {
+        &self.points
+    }
+
+    /// Returns the faces of the hull, these index into [`ConvexHullShape::points`].
+    #[inline]
+    pub fn faces(&self) -> &[ConvexHullFace] {
+        &self.faces
+    }
+
+    /// Returns the plane of every face relative to the center of mass.
+    #[inline]
+    pub fn planes(&self) -> &[Plane] {
+        &self.planes
+    }
+
+    #[inline]
+    pub fn convex_radius(&self) -> f32 {
+        self.convex_radius
+    }
+
+    /// Computes the offset that moves `index` inward by one unit from the planes around it.
+    fn convex_offset(faces: &[ConvexHullFace], planes: &[Plane], index: u32) -> Vec3 {
+        let normals: Vec<Vec3> = faces
+            .iter()
+            .zip(planes)
+            .filter(|(face, _)| face.vertices.contains(&index))
+            .map(|(_, plane)| plane.normal)
+            .collect();
+
+        // Pick the three most independent planes and find the point that lies one unit behind
+        // all of them.
+        let n0 = normals[0];
+        let n1 = normals
+            .iter()
+            .copied()
+            .min_by(|a, b| n0.dot(*a).abs().total_cmp(&n0.dot(*b).abs()))
+            .unwrap();
+        let cross = n0.cross(n1);
+        let n2 = normals
+            .iter()
+            .copied()
+            .max_by(|a, b| cross.dot(*a).abs().total_cmp(&cross.dot(*b).abs()))
+            .unwrap();
+        if cross.dot(n2).abs() > 1.0e-3 {
+            if let Some(inverse) = Mat33::from_cols(n0, n1, n2).transpose().try_inverse() {
+                return inverse * -Vec3::ONE;
+            }
+        }
+
+        -normals
+            .iter()
+            .fold(Vec3::ZERO, |sum, &normal| sum + normal)
+            .normalize_or_zero()
+    }
+}
+
+struct HullSupport<'a> {
+    points: &'a [Vec3],
+    convex_offsets: &'a [Vec3],
+    scale: Vec3,
+    convex_radius: f32,
+    /// The convex radius the offsets are multiplied with before scaling.
+    unscaled_convex_radius: f32,
+}
+
+impl Support for HullSupport<'_> {
+    #[inline]
+    fn support(&self, direction: Vec3) -> Vec3 {
+        // (scale * p) . d == p . (scale * d)
+        let local_direction = direction * self.scale;
+        let mut best = 0;
+        let mut best_dot = f32::MIN;
+        for (i, point) in self.points.iter().enumerate() {
+            let dot = point.dot(local_direction);
+            if dot > best_dot {
+                best = i;
+                best_dot = dot;
+            }
+        }
+        (self.points[best] + self.convex_offsets[best] * self.unscaled_convex_radius) * self.scale
+    }
+
+    #[inline]
+    fn convex_radius(&self) -> f32 {
+        self.convex_radius
+    }
+}
+
+impl Shape for ConvexHullShape {
+    fn sub_type(&self) -> ShapeSubType {
+        ShapeSubType::ConvexHull
+    }
+
+    fn center_of_mass(&self) -> Vec3 {
+        self.center_of_mass
+    }
+
+    fn local_bounds(&self) -> AABox {
+        self.local_bounds
+    }
+
+    fn inner_radius(&self) -> f32 {
+        self.inner_radius
+    }
+
+    fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    fn mass_properties(&self) -> MassProperties {
+        MassProperties::new(self.volume * self.density, self.unit_inertia * self.density)
+    }
+
+    fn surface_normal(&self, _sub_shape_id: SubShapeID, local_position: Vec3) -> Vec3 {
+        self.planes
+            .iter()
+            .max_by(|a, b| {
+                a.signed_distance(local_position)
+                    .total_cmp(&b.signed_distance(local_position))
+            })
+            .unwrap()
+            .normal
+    }
+
+    fn cast_ray(&self, ray: &RayCast, hit: &mut RayCastResult) -> bool {
+        // Clip the ray against all planes, the entry point is where it passes the last plane.
+        let mut min_fraction = 0.0;
+        let mut max_fraction = hit.fraction;
+        for plane in &self.planes {
+            let distance = plane.signed_distance(ray.origin);
+            let denominator = plane.normal.dot(ray.direction);
+            if denominator == 0.0 {
+                if distance > 0.0 {
+                    return false;
+                }
+                continue;
+            }
+            let fraction = -distance / denominator;
+            if denominator < 0.0 {
+                min_fraction = f32::max(min_fraction, fraction);
+            } else {
+                max_fraction = f32::min(max_fraction, fraction);
+            }
+            if min_fraction > max_fraction {
+                return false;
+            }
+        }
+        if min_fraction < hit.fraction {
+            hit.fraction = min_fraction;
+            hit.sub_shape_id = SubShapeID::EMPTY;
+            return true;
+        }
+        false
+    }
+
+    fn collide_point(&self, point: Vec3) -> bool {
+        self.planes.iter().all(|p| p.signed_distance(point) <= 0.0)
+    }
+
+    fn as_convex(&self) -> Option<&dyn ConvexShape> {
+        Some(self)
+    }
+}
+
+impl ConvexShape for ConvexHullShape {
+    fn density(&self) -> f32 {
+        self.density
+    }
+
+    fn get_support_function(&self, mode: SupportMode, scale: Vec3) -> Box<dyn Support + '_> {
+        let (unscaled_convex_radius, convex_radius) = match mode {
+            SupportMode::IncludeConvexRadius | SupportMode::Default => (0.0, 0.0),
+            SupportMode::ExcludeConvexRadius => (
+                self.convex_radius,
+                self.convex_radius * scale.abs().min_element(),
+            ),
+        };
+        Box::new(HullSupport {
+            points: &self.points,
+            convex_offsets: &self.convex_offsets,
+            scale,
+            convex_radius,
+            unscaled_convex_radius,
+        })
+    }
+
+    fn get_supporting_face(&self, direction: Vec3, scale: Vec3, vertices: &mut Vec<Vec3>) {
+        // Normals transform with the inverse of the scale.
+        let inverse_scale = 1.0 / scale;
+        let (face, _) = self
+            .faces
+            .iter()
+            .zip(&self.planes)
+            .max_by(|(_, a), (_, b)| {
+                let a = (a.normal * inverse_scale).normalize().dot(direction);
+                let b = (b.normal * inverse_scale).normalize().dot(direction);
+                a.total_cmp(&b)
+            })
+            .unwrap();
+
+        vertices.clear();
+        vertices.extend(
+            face.vertices
+                .iter()
+                .map(|&i| self.points[i as usize] * scale),
+        );
+        if scale.x * scale.y * scale.z < 0.0 {
+            // Mirroring flips the winding order.
+            vertices.reverse();
+        }
+    }
+}
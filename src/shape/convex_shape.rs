@@ -0,0 +1,120 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{
+    collision::{RayCast, RayCastResult},
+    geometry::{
+        AABox, GjkClosestPoint, GjkClosestPoints, PointConvexSupport, Support,
+        TransformedConvexObject, TriangleConvexSupport,
+    },
+    math::{Quat, Vec3},
+    shape::{Shape, SubShapeID},
+};
+
+/// The convex radius that shapes use when none is specified.
+pub const DEFAULT_CONVEX_RADIUS: f32 = 0.05;
+
+/// The density (in kg / m^3) that shapes use when none is specified.
+pub const DEFAULT_DENSITY: f32 = 1000.0;
+
+/// The tolerance used by GJK based queries.
+pub(crate) const GJK_TOLERANCE: f32 = 1.0e-4;
+
+/// How the convex radius should be treated by a support function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SupportMode {
+    /// The support function returns the shape shrunk by its convex radius, which is reported
+    /// separately through [`Support::convex_radius`].
+    ExcludeConvexRadius,
+    /// The support function returns the full shape and reports no convex radius.
+    IncludeConvexRadius,
+    /// The support function may pick whatever is most efficient for the shape.
+    #[default]
+    Default,
+}
+
+/// A shape that is convex and can therefore be described by a support function.
+pub trait ConvexShape: Shape {
+    /// Returns the density of the shape in kg / m^3.
+    fn density(&self) -> f32;
+
+    /// Returns the support function of the shape after scaling it by `scale`.
+    fn get_support_function(&self, mode: SupportMode, scale: Vec3) -> Box<dyn Support + '_>;
+
+    /// Fills `vertices` with the face of the shape (after scaling it by `scale`) that is most
+    /// aligned with `direction`, wound counter clockwise. Shapes without flat faces leave
+    /// `vertices` empty.
+    fn get_supporting_face(&self, _direction: Vec3, _scale: Vec3, vertices: &mut Vec<Vec3>) {
+        vertices.clear();
+    }
+}
+
+/// Casts a ray against a convex shape using GJK.
+pub(crate) fn cast_ray_convex(
+    shape: &dyn ConvexShape,
+    ray: &RayCast,
+    hit: &mut RayCastResult,
+) -> bool {
+    let support = shape.get_support_function(SupportMode::IncludeConvexRadius, Vec3::ONE);
+    match GjkClosestPoint::new().cast_ray(
+        ray.origin,
+        ray.direction,
+        GJK_TOLERANCE,
+        &*support,
+        hit.fraction,
+    ) {
+        Some(fraction) => {
+            hit.fraction = fraction;
+            hit.sub_shape_id = SubShapeID::EMPTY;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Tests whether `point` lies inside a convex shape using GJK.
+pub(crate) fn collide_point_convex(shape: &dyn ConvexShape, point: Vec3) -> bool {
+    let support = shape.get_support_function(SupportMode::IncludeConvexRadius, Vec3::ONE);
+    let mut v = point;
+    GjkClosestPoint::new().intersects(
+        &*support,
+        &PointConvexSupport { point },
+        GJK_TOLERANCE,
+        &mut v,
+    )
+}
+
+/// Collides a convex shape against the triangles of a mesh like shape. `collide_triangles` is
+/// called with the bounds of the shape and must visit every triangle that may overlap them,
+/// `callback` receives the closest points for each triangle within `max_separation_distance`.
+pub(crate) fn collide_convex_vs_triangles(
+    shape: &dyn ConvexShape,
+    rotation: Quat,
+    translation: Vec3,
+    max_separation_distance: f32,
+    collide_triangles: impl FnOnce(&AABox, &mut dyn FnMut(SubShapeID, [Vec3; 3])),
+    callback: &mut dyn FnMut(SubShapeID, &GjkClosestPoints),
+) {
+    let bounds = shape
+        .local_bounds()
+        .transformed(rotation, translation)
+        .expanded_by(Vec3::splat(max_separation_distance));
+    let support = shape.get_support_function(SupportMode::IncludeConvexRadius, Vec3::ONE);
+    let support = TransformedConvexObject::new(&*support, rotation, translation);
+    let max_distance_squared = max_separation_distance * max_separation_distance;
+
+    collide_triangles(&bounds, &mut |sub_shape_id, [v0, v1, v2]| {
+        let triangle = TriangleConvexSupport::new(v0, v1, v2);
+        let mut v = translation - (v0 + v1 + v2) / 3.0;
+        if let Some(result) = GjkClosestPoint::new().closest_points(
+            &support,
+            &triangle,
+            GJK_TOLERANCE,
+            max_distance_squared,
+            &mut v,
+        ) {
+            if result.distance_squared <= max_distance_squared {
+                callback(sub_shape_id, &result);
+            }
+        }
+    });
+}
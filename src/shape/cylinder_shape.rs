@@ -0,0 +1,230 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::f32::consts::{FRAC_1_SQRT_2, PI};
+
+#[cfg(all(no_std, libm, not(test)))]
+use crate::math::libm::F32Ext;
+use crate::{
+    body::MassProperties,
+    collision::{RayCast, RayCastResult},
+    geometry::{ray_cylinder, AABox, Support},
+    math::{Mat33, Vec3},
+    shape::{
+        scale_helpers, ConvexShape, Shape, ShapeError, ShapeSubType, SubShapeID, SupportMode,
+        DEFAULT_CONVEX_RADIUS, DEFAULT_DENSITY,
+    },
+};
+
+/// The unit circle approximated by 8 points on XZ, in the order of increasing angle from X to
+/// Z.
+const CAP_VERTICES: [(f32, f32); 8] = [
+    (1.0, 0.0),
+    (FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    (0.0, 1.0),
+    (-FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    (-1.0, 0.0),
+    (-FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+    (0.0, -1.0),
+    (FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+];
+
+/// A cylinder centered around the origin with its axis along Y, the edges can be rounded by a
+/// convex radius.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CylinderShape {
+    half_height: f32,
+    radius: f32,
+    convex_radius: f32,
+    density: f32,
+}
+
+impl CylinderShape {
+    /// Creates a cylinder, the convex radius defaults to [`DEFAULT_CONVEX_RADIUS`] or the
+    /// smallest dimension, whichever is smaller.
+    pub fn new(half_height: f32, radius: f32) -> Result<Self, ShapeError> {
+        let convex_radius = DEFAULT_CONVEX_RADIUS.min(half_height).min(radius);
+        Self::with_convex_radius(half_height, radius, convex_radius)
+    }
+
+    /// Creates a cylinder with the given `convex_radius`.
+    pub fn with_convex_radius(
+        half_height: f32,
+        radius: f32,
+        convex_radius: f32,
+    ) -> Result<Self, ShapeError> {
+        if !(half_height > 0.0 && half_height.is_finite()) {
+            return Err(ShapeError::InvalidHeight);
+        }
+        if !(radius > 0.0 && radius.is_finite()) {
+            return Err(ShapeError::InvalidRadius);
+        }
+        if !(convex_radius >= 0.0 && convex_radius <= half_height.min(radius)) {
+            return Err(ShapeError::InvalidConvexRadius);
+        }
+        Ok(Self {
+            half_height,
+            radius,
+            convex_radius,
+            density: DEFAULT_DENSITY,
+        })
+    }
+
+    /// Sets the density of the shape in kg / m^3.
+    pub fn with_density(mut self, density: f32) -> Result<Self, ShapeError> {
+        self.density = super::validate_density(density)?;
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn half_height(&self) -> f32 {
+        self.half_height
+    }
+
+    #[inline]
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    #[inline]
+    pub fn convex_radius(&self) -> f32 {
+        self.convex_radius
+    }
+}
+
+struct CylinderSupport {
+    half_height: f32,
+    radius: f32,
+    convex_radius: f32,
+}
+
+impl Support for CylinderSupport {
+    #[inline]
+    fn support(&self, direction: Vec3) -> Vec3 {
+        let length = (direction.x * direction.x + direction.z * direction.z).sqrt();
+        let y = self.half_height.copysign(direction.y);
+        if length > 0.0 {
+            let scale = self.radius / length;
+            Vec3::new(direction.x * scale, y, direction.z * scale)
+        } else {
+            Vec3::new(0.0, y, 0.0)
+        }
+    }
+
+    #[inline]
+    fn convex_radius(&self) -> f32 {
+        self.convex_radius
+    }
+}
+
+impl Shape for CylinderShape {
+    fn sub_type(&self) -> ShapeSubType {
+        ShapeSubType::Cylinder
+    }
+
+    fn local_bounds(&self) -> AABox {
+        let extent = Vec3::new(self.radius, self.half_height, self.radius);
+        AABox::new(-extent, extent)
+    }
+
+    fn inner_radius(&self) -> f32 {
+        self.half_height.min(self.radius)
+    }
+
+    fn volume(&self) -> f32 {
+        PI * self.radius * self.radius * 2.0 * self.half_height
+    }
+
+    fn mass_properties(&self) -> MassProperties {
+        let mass = self.volume() * self.density;
+        let r2 = self.radius * self.radius;
+        let height = 2.0 * self.half_height;
+        let inertia_x = mass * (3.0 * r2 + height * height) / 12.0;
+        let inertia_y = mass * r2 * 0.5;
+        MassProperties::new(
+            mass,
+            Mat33::from_diagonal(Vec3::new(inertia_x, inertia_y, inertia_x)),
+        )
+    }
+
+    fn is_valid_scale(&self, scale: Vec3) -> bool {
+        scale_helpers::is_valid_scale(scale) && scale_helpers::is_uniform_scale_xz(scale)
+    }
+
+    fn surface_normal(&self, _sub_shape_id: SubShapeID, local_position: Vec3) -> Vec3 {
+        let radial = Vec3::new(local_position.x, 0.0, local_position.z);
+        let radial_length = radial.length();
+        if local_position.y.abs() - self.half_height > radial_length - self.radius
+            || radial_length == 0.0
+        {
+            Vec3::new(0.0, 1.0f32.copysign(local_position.y), 0.0)
+        } else {
+            radial / radial_length
+        }
+    }
+
+    fn cast_ray(&self, ray: &RayCast, hit: &mut RayCastResult) -> bool {
+        let fraction = ray_cylinder(ray.origin, ray.direction, self.half_height, self.radius);
+        if fraction < hit.fraction {
+            hit.fraction = fraction;
+            hit.sub_shape_id = SubShapeID::EMPTY;
+            return true;
+        }
+        false
+    }
+
+    fn collide_point(&self, point: Vec3) -> bool {
+        point.y.abs() <= self.half_height
+            && point.x * point.x + point.z * point.z <= self.radius * self.radius
+    }
+
+    fn as_convex(&self) -> Option<&dyn ConvexShape> {
+        Some(self)
+    }
+}
+
+impl ConvexShape for CylinderShape {
+    fn density(&self) -> f32 {
+        self.density
+    }
+
+    fn get_support_function(&self, mode: SupportMode, scale: Vec3) -> Box<dyn Support + '_> {
+        let half_height = self.half_height * scale.y.abs();
+        let radius = self.radius * scale.x.abs();
+        match mode {
+            SupportMode::IncludeConvexRadius | SupportMode::Default => Box::new(CylinderSupport {
+                half_height,
+                radius,
+                convex_radius: 0.0,
+            }),
+            SupportMode::ExcludeConvexRadius => {
+                let convex_radius = self.convex_radius * scale.x.abs().min(scale.y.abs());
+                Box::new(CylinderSupport {
+                    half_height: half_height - convex_radius,
+                    radius: radius - convex_radius,
+                    convex_radius,
+                })
+            }
+        }
+    }
+
+    fn get_supporting_face(&self, direction: Vec3, scale: Vec3, vertices: &mut Vec<Vec3>) {
+        let half_height = self.half_height * scale.y.abs();
+        let radius = self.radius * scale.x.abs();
+        vertices.clear();
+
+        let radial = (direction.x * direction.x + direction.z * direction.z).sqrt();
+        if radial * half_height > radius * direction.y.abs() {
+            // The side is the most aligned, return the line along it.
+            let side = Vec3::new(direction.x, 0.0, direction.z) * (radius / radial);
+            vertices.push(side + Vec3::new(0.0, half_height, 0.0));
+            vertices.push(side - Vec3::new(0.0, half_height, 0.0));
+        } else {
+            // Approximate the cap by a polygon, wound counter clockwise seen from outside.
+            let sign = 1.0f32.copysign(direction.y);
+            vertices.extend(
+                CAP_VERTICES
+                    .iter()
+                    .map(|&(x, z)| Vec3::new(x * radius, sign * half_height, -sign * z * radius)),
+            );
+        }
+    }
+}
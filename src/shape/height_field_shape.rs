@@ -0,0 +1,862 @@
+use alloc::{vec, vec::Vec};
+
+#[cfg(all(no_std, libm, not(test)))]
+use crate::math::libm::F32Ext;
+use crate::{
+    body::MassProperties,
+    collision::{CollisionCollector, RayCast, RayCastResult},
+    geometry::{ray_aabox4, ray_triangle, AABox, AABox4, GjkClosestPoints, RayInvDirection},
+    math::{Quat, Vec3},
+    shape::{
+        collide_convex_vs_triangles,
+        stream::{StreamIn, StreamOut},
+        sub_shape_id_bits_for, ConvexShape, Shape, ShapeError, ShapeSubType, SubShapeID,
+        SubShapeIDCreator,
+    },
+};
+
+/// A sample with this height is a hole, the triangles that use it are removed.
+pub const NO_COLLISION_VALUE: f32 = f32::MAX;
+
+/// The maximum number of samples along each side of a [`HeightFieldShape`].
+pub const MAX_SAMPLE_COUNT: u32 = 1 << 15;
+
+/// The largest height range coordinate.
+const RANGE_MAX: u16 = u16::MAX;
+
+/// A range that contains no samples.
+const EMPTY_RANGE: [u16; 2] = [RANGE_MAX, 0];
+
+/// Identifies the binary format written by [`HeightFieldShape::save`].
+const HEIGHT_FIELD_MAGIC: [u8; 4] = *b"PHFD";
+const HEIGHT_FIELD_VERSION: u32 = 1;
+
+/// The settings to create a [`HeightFieldShape`] from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HeightFieldSettings<'a> {
+    /// The heights of `sample_count * sample_count` samples in row major order, holes are
+    /// marked with [`NO_COLLISION_VALUE`].
+    pub samples: &'a [f32],
+    /// The number of samples along each side, between 2 and [`MAX_SAMPLE_COUNT`].
+    pub sample_count: u32,
+    /// The local position of the first sample.
+    pub offset: Vec3,
+    /// The distance between samples on X and Z, and the scale applied to the heights on Y. A
+    /// sample `(x, y)` is located at `offset + scale * (x, height, y)`.
+    pub scale: Vec3,
+    /// The number of cells along each side of a block that shares its quantization range,
+    /// must be 2, 4 or 8.
+    pub block_size: u32,
+    /// The number of bits that a sample is quantized to within its block, between 2 and 8.
+    pub bits_per_sample: u32,
+    /// The material index of every cell in row major order, or empty to use zero for all
+    /// cells.
+    pub material_indices: &'a [u8],
+}
+
+impl<'a> HeightFieldSettings<'a> {
+    /// Creates settings with an offset of zero, unit scale, blocks of 2 by 2 cells and 8 bits
+    /// per sample.
+    pub fn new(samples: &'a [f32], sample_count: u32) -> Self {
+        Self {
+            samples,
+            sample_count,
+            offset: Vec3::ZERO,
+            scale: Vec3::ONE,
+            block_size: 2,
+            bits_per_sample: 8,
+            material_indices: &[],
+        }
+    }
+}
+
+/// A regular grid of height samples, typically used for terrain.
+///
+/// Samples are quantized relative to the height range of the block of cells they belong to. A
+/// hierarchy of conservative height ranges is kept on top of the blocks so that queries only
+/// visit the cells that they can touch. Each cell is made up of two triangles, the sub shape
+/// ID of a triangle encodes `2 * cell + triangle` with `cell = y * (sample_count - 1) + x`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeightFieldShape {
+    offset: Vec3,
+    scale: Vec3,
+    sample_count: u32,
+    block_size: u32,
+    bits_per_sample: u32,
+    blocks_per_side: u32,
+    /// Heights are quantized to 16 bits relative to `height_min` to describe ranges.
+    height_min: f32,
+    height_step: f32,
+    /// The range that the samples owned by each block are quantized in.
+    block_ranges: Vec<[u16; 2]>,
+    /// The sample codes in row major order, packed into `bits_per_sample` bits each starting
+    /// from the lowest bit of each byte.
+    samples: Vec<u8>,
+    material_indices: Vec<u8>,
+    /// Conservative height ranges of the cells covered by each block, followed by levels
+    /// that each combine 2 by 2 entries of the level below until a single entry remains.
+    range_levels: Vec<Vec<[u16; 2]>>,
+}
+
+impl HeightFieldShape {
+    pub fn new(settings: &HeightFieldSettings) -> Result<Self, ShapeError> {
+        let sample_count = settings.sample_count;
+        if !(2..=MAX_SAMPLE_COUNT).contains(&sample_count)
+            || settings.samples.len() != (sample_count * sample_count) as usize
+        {
+            return Err(ShapeError::InvalidSampleCount);
+        }
+        if !matches!(settings.block_size, 2 | 4 | 8) {
+            return Err(ShapeError::InvalidBlockSize);
+        }
+        if !(2..=8).contains(&settings.bits_per_sample) {
+            return Err(ShapeError::InvalidBitsPerSample);
+        }
+        if !(settings.scale.min_element() > 0.0
+            && settings.scale.is_finite()
+            && settings.offset.is_finite())
+        {
+            return Err(ShapeError::InvalidScale);
+        }
+        let cell_count = ((sample_count - 1) * (sample_count - 1)) as usize;
+        if !settings.material_indices.is_empty() && settings.material_indices.len() != cell_count {
+            return Err(ShapeError::InvalidSampleCount);
+        }
+
+        let (mut min, mut max) = (f32::MAX, f32::MIN);
+        for &sample in settings.samples {
+            if sample != NO_COLLISION_VALUE {
+                if !sample.is_finite() {
+                    return Err(ShapeError::InvalidHeight);
+                }
+                min = min.min(sample);
+                max = max.max(sample);
+            }
+        }
+        if min > max {
+            (min, max) = (0.0, 0.0);
+        }
+
+        let mut shape = Self::with_layout(
+            settings.offset,
+            settings.scale,
+            sample_count,
+            settings.block_size,
+            settings.bits_per_sample,
+            min,
+            (max - min) / RANGE_MAX as f32,
+        );
+        shape.material_indices = settings.material_indices.to_vec();
+        let last_block = shape.blocks_per_side - 1;
+        for by in 0..=last_block {
+            for bx in 0..=last_block {
+                shape.quantize_block(bx, by, |x, y| {
+                    settings.samples[(y * sample_count + x) as usize]
+                });
+            }
+        }
+        shape.update_ranges(0, 0, last_block, last_block);
+        Ok(shape)
+    }
+
+    /// Creates an empty height field with the given layout.
+    fn with_layout(
+        offset: Vec3,
+        scale: Vec3,
+        sample_count: u32,
+        block_size: u32,
+        bits_per_sample: u32,
+        height_min: f32,
+        height_step: f32,
+    ) -> Self {
+        let blocks_per_side = (sample_count - 1).div_ceil(block_size);
+        let mut range_levels = Vec::new();
+        let mut side = blocks_per_side;
+        loop {
+            range_levels.push(vec![EMPTY_RANGE; (side * side) as usize]);
+            if side == 1 {
+                break;
+            }
+            side = side.div_ceil(2);
+        }
+        Self {
+            offset,
+            scale,
+            sample_count,
+            block_size,
+            bits_per_sample,
+            blocks_per_side,
+            height_min,
+            height_step,
+            block_ranges: vec![[0, 0]; (blocks_per_side * blocks_per_side) as usize],
+            samples: vec![0; Self::packed_len(sample_count, bits_per_sample)],
+            material_indices: Vec::new(),
+            range_levels,
+        }
+    }
+
+    #[inline]
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+
+    #[inline]
+    pub fn block_size(&self) -> u32 {
+        self.block_size
+    }
+
+    #[inline]
+    pub fn bits_per_sample(&self) -> u32 {
+        self.bits_per_sample
+    }
+
+    /// Returns the height of sample `(x, y)` after quantization, or [`NO_COLLISION_VALUE`]
+    /// when the sample is a hole.
+    #[inline]
+    pub fn get_height(&self, x: u32, y: u32) -> f32 {
+        assert!(x < self.sample_count && y < self.sample_count);
+        let code = self.code(x, y);
+        if code == self.hole_code() {
+            return NO_COLLISION_VALUE;
+        }
+        let (low, high) = self.block_heights(self.owner(x), self.owner(y));
+        low + code as f32 * (high - low) / (self.hole_code() - 1) as f32
+    }
+
+    /// Returns whether sample `(x, y)` is a hole.
+    #[inline]
+    pub fn is_no_collision(&self, x: u32, y: u32) -> bool {
+        self.get_height(x, y) == NO_COLLISION_VALUE
+    }
+
+    /// Returns the local position of sample `(x, y)`, the height is meaningless for holes.
+    #[inline]
+    pub fn get_position(&self, x: u32, y: u32) -> Vec3 {
+        self.offset + self.scale * Vec3::new(x as f32, self.get_height(x, y), y as f32)
+    }
+
+    /// Replaces the heights of the `size_x` by `size_y` samples starting at `(start_x,
+    /// start_y)`, `heights` is in row major order. Heights are clamped to the height range
+    /// the height field was created with.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the region does not lie inside of the height field or when `heights` does
+    /// not contain `size_x * size_y` samples.
+    pub fn set_heights(
+        &mut self,
+        start_x: u32,
+        start_y: u32,
+        size_x: u32,
+        size_y: u32,
+        heights: &[f32],
+    ) {
+        assert!(size_x > 0 && size_y > 0);
+        assert!(start_x + size_x <= self.sample_count && start_y + size_y <= self.sample_count);
+        assert_eq!(heights.len(), (size_x * size_y) as usize);
+
+        let (end_x, end_y) = (start_x + size_x - 1, start_y + size_y - 1);
+        let (min, max) = (
+            self.height_min,
+            self.height_min + RANGE_MAX as f32 * self.height_step,
+        );
+        for by in self.owner(start_y)..=self.owner(end_y) {
+            for bx in self.owner(start_x)..=self.owner(end_x) {
+                // Gather the current heights of the block first, as requantizing the block
+                // changes how they are decoded.
+                let (x0, x1) = self.owned_samples(bx);
+                let (y0, y1) = self.owned_samples(by);
+                let width = x1 - x0 + 1;
+                let mut block = Vec::with_capacity((width * (y1 - y0 + 1)) as usize);
+                for y in y0..=y1 {
+                    for x in x0..=x1 {
+                        let height =
+                            if (start_x..=end_x).contains(&x) && (start_y..=end_y).contains(&y) {
+                                heights[((y - start_y) * size_x + x - start_x) as usize]
+                            } else {
+                                self.get_height(x, y)
+                            };
+                        block.push(if height == NO_COLLISION_VALUE {
+                            height
+                        } else {
+                            height.clamp(min, max)
+                        });
+                    }
+                }
+                self.quantize_block(bx, by, |x, y| block[((y - y0) * width + x - x0) as usize]);
+            }
+        }
+
+        // Blocks also cover the first sample of the next block.
+        self.update_ranges(
+            self.owner(start_x.saturating_sub(1)),
+            self.owner(start_y.saturating_sub(1)),
+            self.owner(end_x),
+            self.owner(end_y),
+        );
+    }
+
+    /// Calls `callback` for every triangle whose bounds overlap `bounds`.
+    pub fn collide_triangles(
+        &self,
+        bounds: &AABox,
+        mut callback: impl FnMut(SubShapeID, [Vec3; 3]),
+    ) {
+        let top = self.range_levels.len() - 1;
+        if !self.node_bounds(top, 0, 0).overlaps(bounds) {
+            return;
+        }
+        let mut stack = vec![(top, 0, 0)];
+        while let Some((level, x, y)) = stack.pop() {
+            if level == 0 {
+                self.visit_block_triangles(x, y, |sub_shape_id, triangle| {
+                    if AABox::from_points(triangle).overlaps(bounds) {
+                        callback(sub_shape_id, triangle);
+                    }
+                });
+                continue;
+            }
+            let (children, boxes) = self.children(level, x, y);
+            let overlaps = boxes.overlaps(bounds);
+            for (child, overlaps) in children.into_iter().zip(overlaps) {
+                if let (Some((x, y)), true) = (child, overlaps) {
+                    stack.push((level - 1, x, y));
+                }
+            }
+        }
+    }
+
+    /// Collides a convex shape against the height field, see [`MeshShape::collide_convex`].
+    ///
+    /// [`MeshShape::collide_convex`]: super::MeshShape::collide_convex
+    pub fn collide_convex(
+        &self,
+        shape: &dyn ConvexShape,
+        rotation: Quat,
+        translation: Vec3,
+        max_separation_distance: f32,
+        mut callback: impl FnMut(SubShapeID, &GjkClosestPoints),
+    ) {
+        collide_convex_vs_triangles(
+            shape,
+            rotation,
+            translation,
+            max_separation_distance,
+            |bounds, visit| self.collide_triangles(bounds, visit),
+            &mut callback,
+        );
+    }
+
+    /// Returns the vertices of the triangle identified by `sub_shape_id`.
+    pub fn triangle(&self, sub_shape_id: SubShapeID) -> [Vec3; 3] {
+        let cells_per_side = self.sample_count - 1;
+        let index = self.triangle_index(sub_shape_id);
+        let cell = index / 2;
+        let (x, y) = (cell % cells_per_side, cell / cells_per_side);
+        let [p00, p10, p01, p11] =
+            [(x, y), (x + 1, y), (x, y + 1), (x + 1, y + 1)].map(|(x, y)| self.get_position(x, y));
+        if index.is_multiple_of(2) {
+            [p00, p01, p11]
+        } else {
+            [p00, p11, p10]
+        }
+    }
+
+    /// Saves the height field in a binary format that can be loaded with
+    /// [`HeightFieldShape::restore`]. Saving a restored height field produces the exact same
+    /// bytes.
+    pub fn save(&self, data: &mut Vec<u8>) {
+        let mut stream = StreamOut::new(data);
+        stream.write_bytes(&HEIGHT_FIELD_MAGIC);
+        stream.write_u32(HEIGHT_FIELD_VERSION);
+        stream.write_vec3(self.offset);
+        stream.write_vec3(self.scale);
+        stream.write_u32(self.sample_count);
+        stream.write_u32(self.block_size);
+        stream.write_u32(self.bits_per_sample);
+        stream.write_f32(self.height_min);
+        stream.write_f32(self.height_step);
+        for &[low, high] in &self.block_ranges {
+            stream.write_u16(low);
+            stream.write_u16(high);
+        }
+        stream.write_bytes(&self.samples);
+        stream.write_len(self.material_indices.len());
+        stream.write_bytes(&self.material_indices);
+    }
+
+    /// Loads a height field that was saved with [`HeightFieldShape::save`].
+    pub fn restore(data: &[u8]) -> Result<Self, ShapeError> {
+        let mut stream = StreamIn::new(data);
+        if stream.read_bytes(HEIGHT_FIELD_MAGIC.len())? != HEIGHT_FIELD_MAGIC
+            || stream.read_u32()? != HEIGHT_FIELD_VERSION
+        {
+            return Err(ShapeError::InvalidData);
+        }
+        let offset = stream.read_vec3()?;
+        let scale = stream.read_vec3()?;
+        let sample_count = stream.read_u32()?;
+        let block_size = stream.read_u32()?;
+        let bits_per_sample = stream.read_u32()?;
+        let height_min = stream.read_f32()?;
+        let height_step = stream.read_f32()?;
+        let valid = (2..=MAX_SAMPLE_COUNT).contains(&sample_count)
+            && matches!(block_size, 2 | 4 | 8)
+            && (2..=8).contains(&bits_per_sample)
+            && scale.min_element() > 0.0
+            && scale.is_finite()
+            && offset.is_finite()
+            && height_min.is_finite()
+            && height_step.is_finite()
+            && height_step >= 0.0;
+        if !valid {
+            return Err(ShapeError::InvalidData);
+        }
+
+        let mut shape = Self::with_layout(
+            offset,
+            scale,
+            sample_count,
+            block_size,
+            bits_per_sample,
+            height_min,
+            height_step,
+        );
+        for range in &mut shape.block_ranges {
+            *range = [stream.read_u16()?, stream.read_u16()?];
+            if range[0] > range[1] {
+                return Err(ShapeError::InvalidData);
+            }
+        }
+        let samples = stream.read_bytes(shape.samples.len())?;
+        let padding_shift = sample_count * sample_count % 8 * bits_per_sample % 8;
+        if padding_shift != 0 && samples[samples.len() - 1] >> padding_shift != 0 {
+            return Err(ShapeError::InvalidData);
+        }
+        shape.samples.copy_from_slice(samples);
+        let material_count = stream.read_len(1)?;
+        let cell_count = ((sample_count - 1) * (sample_count - 1)) as usize;
+        if material_count != 0 && material_count != cell_count {
+            return Err(ShapeError::InvalidData);
+        }
+        shape.material_indices = stream.read_bytes(material_count)?.to_vec();
+        stream.finish()?;
+
+        let last_block = shape.blocks_per_side - 1;
+        shape.update_ranges(0, 0, last_block, last_block);
+        Ok(shape)
+    }
+
+    #[inline]
+    fn sub_shape_id(&self, index: u32) -> SubShapeID {
+        SubShapeIDCreator::new()
+            .push_id(index, self.sub_shape_id_bits())
+            .id()
+    }
+
+    #[inline]
+    fn triangle_index(&self, sub_shape_id: SubShapeID) -> u32 {
+        sub_shape_id.pop_id(self.sub_shape_id_bits()).0
+    }
+
+    /// Returns the number of bytes needed to pack all sample codes.
+    #[inline]
+    fn packed_len(sample_count: u32, bits_per_sample: u32) -> usize {
+        (sample_count as u64 * sample_count as u64 * bits_per_sample as u64).div_ceil(8) as usize
+    }
+
+    /// Returns the bit offset of the code of sample `(x, y)`.
+    #[inline]
+    fn code_bit(&self, x: u32, y: u32) -> usize {
+        ((y as u64 * self.sample_count as u64 + x as u64) * self.bits_per_sample as u64) as usize
+    }
+
+    /// Returns the code of sample `(x, y)`, which may straddle two bytes.
+    #[inline]
+    fn code(&self, x: u32, y: u32) -> u8 {
+        let bit = self.code_bit(x, y);
+        let (byte, shift) = (bit / 8, bit % 8);
+        let mut bits = self.samples[byte] as u16;
+        if shift + self.bits_per_sample as usize > 8 {
+            bits |= (self.samples[byte + 1] as u16) << 8;
+        }
+        (bits >> shift) as u8 & self.hole_code()
+    }
+
+    /// Replaces the code of sample `(x, y)`.
+    #[inline]
+    fn set_code(&mut self, x: u32, y: u32, code: u8) {
+        let bit = self.code_bit(x, y);
+        let (byte, shift) = (bit / 8, bit % 8);
+        let mask = (self.hole_code() as u16) << shift;
+        let bits = (code as u16) << shift;
+        self.samples[byte] = (self.samples[byte] & !mask as u8) | bits as u8;
+        if shift + self.bits_per_sample as usize > 8 {
+            let next = &mut self.samples[byte + 1];
+            *next = (*next & !(mask >> 8) as u8) | (bits >> 8) as u8;
+        }
+    }
+
+    /// The sample code that marks a hole, lower codes are heights.
+    #[inline]
+    fn hole_code(&self) -> u8 {
+        ((1u32 << self.bits_per_sample) - 1) as u8
+    }
+
+    /// Returns the block that sample or cell coordinate `v` belongs to.
+    #[inline]
+    fn owner(&self, v: u32) -> u32 {
+        (v / self.block_size).min(self.blocks_per_side - 1)
+    }
+
+    /// Returns the first and last sample coordinate owned by block coordinate `b`, the last
+    /// block also owns the samples on the far edge.
+    #[inline]
+    fn owned_samples(&self, b: u32) -> (u32, u32) {
+        let start = b * self.block_size;
+        if b == self.blocks_per_side - 1 {
+            (start, self.sample_count - 1)
+        } else {
+            (start, start + self.block_size - 1)
+        }
+    }
+
+    /// Returns the first and one past the last cell coordinate covered by node `n` of `level`.
+    #[inline]
+    fn node_cells(&self, level: usize, n: u32) -> (u32, u32) {
+        let cells_per_side = self.sample_count - 1;
+        let start = ((n << level) * self.block_size).min(cells_per_side);
+        let end = (((n + 1) << level) * self.block_size).min(cells_per_side);
+        (start, end)
+    }
+
+    #[inline]
+    fn range_height(&self, value: u16) -> f32 {
+        self.height_min + value as f32 * self.height_step
+    }
+
+    /// Returns the lowest and highest height of the samples owned by a block.
+    #[inline]
+    fn block_heights(&self, bx: u32, by: u32) -> (f32, f32) {
+        let [low, high] = self.block_ranges[(by * self.blocks_per_side + bx) as usize];
+        (self.range_height(low), self.range_height(high))
+    }
+
+    /// Quantizes `height` so that the range height is never larger than `height`.
+    fn quantize_floor(&self, height: f32) -> u16 {
+        if self.height_step == 0.0 {
+            return 0;
+        }
+        let relative = (height - self.height_min) / self.height_step;
+        let mut result = relative.floor().clamp(0.0, RANGE_MAX as f32) as u16;
+        while result > 0 && self.range_height(result) > height {
+            result -= 1;
+        }
+        result
+    }
+
+    /// Quantizes `height` so that the range height is never smaller than `height`.
+    fn quantize_ceil(&self, height: f32) -> u16 {
+        if self.height_step == 0.0 {
+            return 0;
+        }
+        let relative = (height - self.height_min) / self.height_step;
+        let mut result = relative.ceil().clamp(0.0, RANGE_MAX as f32) as u16;
+        while result < RANGE_MAX && self.range_height(result) < height {
+            result += 1;
+        }
+        result
+    }
+
+    /// Quantizes the samples owned by block `(bx, by)`, `height` returns the height of a
+    /// sample.
+    fn quantize_block(&mut self, bx: u32, by: u32, height: impl Fn(u32, u32) -> f32) {
+        let (x0, x1) = self.owned_samples(bx);
+        let (y0, y1) = self.owned_samples(by);
+        let (mut min, mut max) = (f32::MAX, f32::MIN);
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                let h = height(x, y);
+                if h != NO_COLLISION_VALUE {
+                    min = min.min(h);
+                    max = max.max(h);
+                }
+            }
+        }
+        let range = if min <= max {
+            [self.quantize_floor(min), self.quantize_ceil(max)]
+        } else {
+            [0, 0]
+        };
+        self.block_ranges[(by * self.blocks_per_side + bx) as usize] = range;
+
+        let (low, high) = self.block_heights(bx, by);
+        let hole = self.hole_code();
+        let levels = (hole - 1) as f32;
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                let h = height(x, y);
+                let code = if h == NO_COLLISION_VALUE {
+                    hole
+                } else if high > low {
+                    ((h - low) / (high - low) * levels)
+                        .round()
+                        .clamp(0.0, levels) as u8
+                } else {
+                    0
+                };
+                self.set_code(x, y, code);
+            }
+        }
+    }
+
+    /// Recomputes the height ranges of blocks `(bx0, by0)` up to and including `(bx1, by1)`
+    /// and of all nodes above them.
+    fn update_ranges(&mut self, bx0: u32, by0: u32, bx1: u32, by1: u32) {
+        for by in by0..=by1 {
+            for bx in bx0..=bx1 {
+                let (x0, x1) = self.node_cells(0, bx);
+                let (y0, y1) = self.node_cells(0, by);
+                let (mut min, mut max) = (f32::MAX, f32::MIN);
+                for y in y0..=y1 {
+                    for x in x0..=x1 {
+                        let h = self.get_height(x, y);
+                        if h != NO_COLLISION_VALUE {
+                            min = min.min(h);
+                            max = max.max(h);
+                        }
+                    }
+                }
+                self.range_levels[0][(by * self.blocks_per_side + bx) as usize] = if min <= max {
+                    [self.quantize_floor(min), self.quantize_ceil(max)]
+                } else {
+                    EMPTY_RANGE
+                };
+            }
+        }
+
+        let mut side = self.blocks_per_side;
+        for level in 1..self.range_levels.len() {
+            let parent_side = side.div_ceil(2);
+            for y in (by0 >> level)..=(by1 >> level) {
+                for x in (bx0 >> level)..=(bx1 >> level) {
+                    let mut range = EMPTY_RANGE;
+                    for (cx, cy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                        let (cx, cy) = (2 * x + cx, 2 * y + cy);
+                        if cx < side && cy < side {
+                            let [low, high] =
+                                self.range_levels[level - 1][(cy * side + cx) as usize];
+                            range = [range[0].min(low), range[1].max(high)];
+                        }
+                    }
+                    self.range_levels[level][(y * parent_side + x) as usize] = range;
+                }
+            }
+            side = parent_side;
+        }
+    }
+
+    /// Returns the bounds of node `(x, y)` of `level`, which are invalid when it has no
+    /// triangles.
+    fn node_bounds(&self, level: usize, x: u32, y: u32) -> AABox {
+        let side = self.blocks_per_side.div_ceil(1 << level);
+        let [low, high] = self.range_levels[level][(y * side + x) as usize];
+        if low > high {
+            return AABox::INVALID;
+        }
+        let (x0, x1) = self.node_cells(level, x);
+        let (y0, y1) = self.node_cells(level, y);
+        AABox::new(
+            self.offset + self.scale * Vec3::new(x0 as f32, self.range_height(low), y0 as f32),
+            self.offset + self.scale * Vec3::new(x1 as f32, self.range_height(high), y1 as f32),
+        )
+    }
+
+    /// Returns the (up to) four children of node `(x, y)` of `level` and their bounds.
+    fn children(&self, level: usize, x: u32, y: u32) -> ([Option<(u32, u32)>; 4], AABox4) {
+        let side = self.blocks_per_side.div_ceil(1 << (level - 1));
+        let mut children = [None; 4];
+        let mut boxes = AABox4::INVALID;
+        for (i, (cx, cy)) in [(0, 0), (1, 0), (0, 1), (1, 1)].into_iter().enumerate() {
+            let (cx, cy) = (2 * x + cx, 2 * y + cy);
+            if cx < side && cy < side {
+                let bounds = self.node_bounds(level - 1, cx, cy);
+                if bounds.is_valid() {
+                    children[i] = Some((cx, cy));
+                    boxes.set(i, &bounds);
+                }
+            }
+        }
+        (children, boxes)
+    }
+
+    /// Calls `visit` for the triangles of all cells in block `(bx, by)`, skipping triangles
+    /// that use a hole.
+    fn visit_block_triangles(
+        &self,
+        bx: u32,
+        by: u32,
+        mut visit: impl FnMut(SubShapeID, [Vec3; 3]),
+    ) {
+        let cells_per_side = self.sample_count - 1;
+        let (x0, x1) = self.node_cells(0, bx);
+        let (y0, y1) = self.node_cells(0, by);
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let corners = [(x, y), (x + 1, y), (x, y + 1), (x + 1, y + 1)];
+                let holes = corners.map(|(x, y)| self.is_no_collision(x, y));
+                let [p00, p10, p01, p11] = corners.map(|(x, y)| self.get_position(x, y));
+                let cell = y * cells_per_side + x;
+                if !(holes[0] || holes[2] || holes[3]) {
+                    visit(self.sub_shape_id(2 * cell), [p00, p01, p11]);
+                }
+                if !(holes[0] || holes[3] || holes[1]) {
+                    visit(self.sub_shape_id(2 * cell + 1), [p00, p11, p10]);
+                }
+            }
+        }
+    }
+
+    /// Walks the hierarchy front to back along `ray`, `on_hit` is called with every triangle
+    /// that is hit closer than the current maximum fraction and returns the new maximum
+    /// fraction.
+    fn walk_ray(
+        &self,
+        ray: &RayCast,
+        mut max_fraction: f32,
+        mut on_hit: impl FnMut(SubShapeID, f32) -> f32,
+    ) {
+        let top = self.range_levels.len() - 1;
+        let bounds = self.node_bounds(top, 0, 0);
+        if !bounds.is_valid() {
+            return;
+        }
+        let inv_direction = RayInvDirection::new(ray.direction);
+        let mut stack = vec![(top, 0, 0, 0.0)];
+        while let Some((level, x, y, fraction)) = stack.pop() {
+            if fraction >= max_fraction {
+                continue;
+            }
+            if level == 0 {
+                self.visit_block_triangles(x, y, |sub_shape_id, [v0, v1, v2]| {
+                    let fraction = ray_triangle(ray.origin, ray.direction, v0, v1, v2);
+                    if fraction < max_fraction {
+                        max_fraction = on_hit(sub_shape_id, fraction);
+                    }
+                });
+                continue;
+            }
+
+            let (children, boxes) = self.children(level, x, y);
+            let fractions = ray_aabox4(ray.origin, &inv_direction, &boxes);
+            let mut nodes = [(0, 0, 0, 0.0); 4];
+            let mut count = 0;
+            for (i, child) in children.into_iter().enumerate() {
+                if let Some((x, y)) = child {
+                    if fractions[i] < max_fraction {
+                        nodes[count] = (level - 1, x, y, fractions[i]);
+                        count += 1;
+                    }
+                }
+            }
+            // Push the furthest child first so that the closest child is visited first.
+            nodes[..count].sort_unstable_by(|a, b| b.3.total_cmp(&a.3));
+            stack.extend_from_slice(&nodes[..count]);
+        }
+    }
+}
+
+impl Shape for HeightFieldShape {
+    fn sub_type(&self) -> ShapeSubType {
+        ShapeSubType::HeightField
+    }
+
+    fn local_bounds(&self) -> AABox {
+        let bounds = self.node_bounds(self.range_levels.len() - 1, 0, 0);
+        if bounds.is_valid() {
+            bounds
+        } else {
+            // Only holes, fall back to the area of the height field at its lowest height.
+            let cells = (self.sample_count - 1) as f32;
+            AABox::new(
+                self.offset,
+                self.offset + self.scale * Vec3::new(cells, 0.0, cells),
+            )
+        }
+    }
+
+    fn inner_radius(&self) -> f32 {
+        0.0
+    }
+
+    fn volume(&self) -> f32 {
+        0.0
+    }
+
+    fn mass_properties(&self) -> MassProperties {
+        MassProperties::default()
+    }
+
+    fn sub_shape_id_bits(&self) -> u32 {
+        let cells_per_side = self.sample_count - 1;
+        sub_shape_id_bits_for(2 * cells_per_side * cells_per_side)
+    }
+
+    fn material_index(&self, sub_shape_id: SubShapeID) -> u32 {
+        self.material_indices
+            .get((self.triangle_index(sub_shape_id) / 2) as usize)
+            .map_or(0, |&index| index as u32)
+    }
+
+    fn surface_normal(&self, sub_shape_id: SubShapeID, _local_position: Vec3) -> Vec3 {
+        let [v0, v1, v2] = self.triangle(sub_shape_id);
+        (v1 - v0).cross(v2 - v0).normalize_or_zero()
+    }
+
+    fn cast_ray(&self, ray: &RayCast, hit: &mut RayCastResult) -> bool {
+        let mut closest = None;
+        self.walk_ray(ray, hit.fraction, |sub_shape_id, fraction| {
+            closest = Some((sub_shape_id, fraction));
+            fraction
+        });
+        match closest {
+            Some((sub_shape_id, fraction)) => {
+                hit.fraction = fraction;
+                hit.sub_shape_id = sub_shape_id;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reports every triangle that is hit.
+    fn cast_ray_with_collector(
+        &self,
+        ray: &RayCast,
+        creator: SubShapeIDCreator,
+        collector: &mut dyn CollisionCollector<RayCastResult>,
+    ) {
+        self.walk_ray(
+            ray,
+            collector.early_out_fraction(),
+            |sub_shape_id, fraction| {
+                collector.add_hit(&RayCastResult {
+                    fraction,
+                    sub_shape_id: creator.push_sub_shape_id(sub_shape_id),
+                });
+                collector.early_out_fraction()
+            },
+        );
+    }
+
+    fn collide_point(&self, point: Vec3) -> bool {
+        // The height field is solid below its surface, within its bounds.
+        let bounds = self.local_bounds();
+        if !bounds.contains_point(point) {
+            return false;
+        }
+        let direction = Vec3::new(0.0, bounds.max.y - point.y + 1.0, 0.0);
+        let mut hit = RayCastResult::default();
+        self.cast_ray(&RayCast::new(point, direction), &mut hit)
+    }
+}
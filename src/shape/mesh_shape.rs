@@ -0,0 +1,676 @@
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+#[cfg(all(no_std, libm, not(test)))]
+use crate::math::libm::F32Ext;
+
+use crate::{
+    body::MassProperties,
+    collision::{is_edge_active, CollisionCollector, RayCast, RayCastResult},
+    geometry::{
+        ray_aabox4, ray_triangle, AABox, AABox4, GjkClosestPoints, IndexedTriangle, RayInvDirection,
+    },
+    math::{Quat, Vec3, Vec4},
+    shape::{
+        collide_convex_vs_triangles,
+        stream::{StreamIn, StreamOut},
+        sub_shape_id_bits_for, ConvexShape, Shape, ShapeError, ShapeSubType, SubShapeID,
+        SubShapeIDCreator,
+    },
+};
+
+/// The cosine of 5 degrees. Edges between triangles whose normals are closer than this are
+/// inactive by default.
+pub const DEFAULT_ACTIVE_EDGE_COS_THRESHOLD_ANGLE: f32 = 0.996_194_7;
+
+/// The maximum number of triangles stored in a leaf of the tree.
+const MAX_TRIANGLES_PER_LEAF: usize = 8;
+
+/// Marks a child of a node as a leaf, the remaining bits hold the index of its first triangle
+/// (shifted by [`LEAF_COUNT_BITS`]) and the number of triangles.
+const LEAF_BIT: u32 = 1 << 31;
+const LEAF_COUNT_BITS: u32 = 4;
+const LEAF_COUNT_MASK: u32 = (1 << LEAF_COUNT_BITS) - 1;
+
+/// Marks an unused child of a node.
+const EMPTY_CHILD: u32 = u32::MAX;
+
+/// The largest quantized coordinate.
+const QUANTIZED_MAX: u16 = u16::MAX;
+
+/// Identifies the binary format written by [`MeshShape::save`].
+const MESH_MAGIC: [u8; 4] = *b"PMSH";
+const MESH_VERSION: u32 = 2;
+
+/// A node of the tree with four children. The bounds of the children are stored as a
+/// structure of arrays, quantized relative to the bounds of the mesh.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Node {
+    min: [[u16; 4]; 3],
+    max: [[u16; 4]; 3],
+    children: [u32; 4],
+}
+
+impl Default for Node {
+    fn default() -> Self {
+        Self {
+            min: [[QUANTIZED_MAX; 4]; 3],
+            max: [[0; 4]; 3],
+            children: [EMPTY_CHILD; 4],
+        }
+    }
+}
+
+/// A triangle mesh, typically used for static level geometry.
+///
+/// The triangles are stored in an axis aligned bounding box tree with four children per node,
+/// the bounds of which are quantized to 16 bits per coordinate. Meshes have no volume and can
+/// therefore only be used by static bodies.
+///
+/// Every edge is marked as active or inactive when the mesh is created. Objects that slide
+/// over a flat part of the mesh would otherwise catch on the edges between its triangles, see
+/// [`fix_normal`](crate::collision::fix_normal).
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeshShape {
+    vertices: Vec<Vec3>,
+    /// The triangles in the order in which they are referenced by the leaves of the tree.
+    triangles: Vec<[u32; 3]>,
+    material_indices: Vec<u32>,
+    /// The active edges of every triangle, see [`MeshShape::active_edges`].
+    active_edges: Vec<u8>,
+    /// The nodes of the tree, the root is the first node.
+    nodes: Vec<Node>,
+    bounds: AABox,
+    /// The size of a single quantization step on each axis.
+    quantization_step: Vec3,
+}
+
+impl MeshShape {
+    /// Creates a mesh from indexed triangles, degenerate triangles are discarded. Edges are
+    /// active when the angle between their triangles is larger than 5 degrees.
+    #[inline]
+    pub fn new(vertices: &[Vec3], triangles: &[IndexedTriangle]) -> Result<Self, ShapeError> {
+        Self::with_active_edge_threshold(
+            vertices,
+            triangles,
+            DEFAULT_ACTIVE_EDGE_COS_THRESHOLD_ANGLE,
+        )
+    }
+
+    /// Creates a mesh from indexed triangles, see [`MeshShape::new`]. Convex edges are active
+    /// when the cosine of the angle between their triangles is below `cos_threshold_angle`,
+    /// see [`is_edge_active`].
+    pub fn with_active_edge_threshold(
+        vertices: &[Vec3],
+        triangles: &[IndexedTriangle],
+        cos_threshold_angle: f32,
+    ) -> Result<Self, ShapeError> {
+        let mut kept = Vec::with_capacity(triangles.len());
+        for triangle in triangles {
+            if triangle
+                .indices
+                .iter()
+                .any(|&i| i as usize >= vertices.len())
+            {
+                return Err(ShapeError::InvalidTriangleIndex);
+            }
+            let [a, b, c] = triangle.indices.map(|i| vertices[i as usize]);
+            if !triangle.is_degenerate() && (b - a).cross(c - a).length_squared() > 0.0 {
+                kept.push(*triangle);
+            }
+        }
+        if kept.is_empty() {
+            return Err(ShapeError::EmptyMesh);
+        }
+        if kept.len() >= (LEAF_BIT >> LEAF_COUNT_BITS) as usize {
+            return Err(ShapeError::TooManyTriangles);
+        }
+
+        let bounds = AABox::from_points(
+            kept.iter()
+                .flat_map(|t| t.indices.map(|i| vertices[i as usize])),
+        );
+        let mut builder = TreeBuilder {
+            vertices,
+            triangles: &kept,
+            order: (0..kept.len() as u32).collect(),
+            centroids: kept
+                .iter()
+                .map(|t| {
+                    t.indices
+                        .iter()
+                        .fold(Vec3::ZERO, |sum, &i| sum + vertices[i as usize])
+                        / 3.0
+                })
+                .collect(),
+            nodes: Vec::new(),
+            bounds,
+            quantization_step: Self::quantization_step(&bounds),
+        };
+        builder.build_root();
+        let active_edges = Self::find_active_edges(vertices, &kept, cos_threshold_angle);
+
+        Ok(Self {
+            vertices: vertices.to_vec(),
+            triangles: builder
+                .order
+                .iter()
+                .map(|&i| kept[i as usize].indices)
+                .collect(),
+            material_indices: builder
+                .order
+                .iter()
+                .map(|&i| kept[i as usize].material_index)
+                .collect(),
+            active_edges: builder
+                .order
+                .iter()
+                .map(|&i| active_edges[i as usize])
+                .collect(),
+            nodes: builder.nodes,
+            bounds,
+            quantization_step: builder.quantization_step,
+        })
+    }
+
+    #[inline]
+    pub fn vertices(&self) -> &[Vec3] {
+        &self.vertices
+    }
+
+    #[inline]
+    pub fn triangle_count(&self) -> usize {
+        self.triangles.len()
+    }
+
+    /// Returns the vertices of the triangle identified by `sub_shape_id`.
+    #[inline]
+    pub fn triangle(&self, sub_shape_id: SubShapeID) -> [Vec3; 3] {
+        self.triangles[self.triangle_index(sub_shape_id)].map(|i| self.vertices[i as usize])
+    }
+
+    /// Returns the active edges of the triangle identified by `sub_shape_id`. Bit `i` is set
+    /// when the edge from vertex `i` to vertex `i + 1` is active.
+    #[inline]
+    pub fn active_edges(&self, sub_shape_id: SubShapeID) -> u8 {
+        self.active_edges[self.triangle_index(sub_shape_id)]
+    }
+
+    /// Calls `callback` for every triangle whose bounds overlap `bounds`.
+    pub fn collide_triangles(
+        &self,
+        bounds: &AABox,
+        mut callback: impl FnMut(SubShapeID, [Vec3; 3]),
+    ) {
+        let mut stack = vec![0];
+        while let Some(code) = stack.pop() {
+            if code & LEAF_BIT != 0 {
+                for index in Self::leaf_range(code) {
+                    let triangle = self.triangles[index].map(|i| self.vertices[i as usize]);
+                    if AABox::from_points(triangle).overlaps(bounds) {
+                        callback(self.sub_shape_id(index), triangle);
+                    }
+                }
+                continue;
+            }
+            let node = &self.nodes[code as usize];
+            let overlaps = self.decode_bounds(node).overlaps(bounds);
+            for (&child, overlaps) in node.children.iter().zip(overlaps) {
+                if child != EMPTY_CHILD && overlaps {
+                    stack.push(child);
+                }
+            }
+        }
+    }
+
+    /// Collides a convex shape against the mesh, `rotation` and `translation` place the center
+    /// of mass of `shape` in the space of the mesh. `callback` is called with the closest points
+    /// for every triangle that is within `max_separation_distance` of the shape, these are in
+    /// the space of the mesh.
+    pub fn collide_convex(
+        &self,
+        shape: &dyn ConvexShape,
+        rotation: Quat,
+        translation: Vec3,
+        max_separation_distance: f32,
+        mut callback: impl FnMut(SubShapeID, &GjkClosestPoints),
+    ) {
+        collide_convex_vs_triangles(
+            shape,
+            rotation,
+            translation,
+            max_separation_distance,
+            |bounds, visit| self.collide_triangles(bounds, visit),
+            &mut callback,
+        );
+    }
+
+    /// Saves the mesh, including its tree, in a binary format that can be loaded with
+    /// [`MeshShape::restore`].
+    pub fn save(&self, data: &mut Vec<u8>) {
+        let mut stream = StreamOut::new(data);
+        stream.write_bytes(&MESH_MAGIC);
+        stream.write_u32(MESH_VERSION);
+        stream.write_vec3(self.bounds.min);
+        stream.write_vec3(self.bounds.max);
+        stream.write_len(self.vertices.len());
+        for &vertex in &self.vertices {
+            stream.write_vec3(vertex);
+        }
+        stream.write_len(self.triangles.len());
+        for (triangle, &material_index) in self.triangles.iter().zip(&self.material_indices) {
+            for &index in triangle {
+                stream.write_u32(index);
+            }
+            stream.write_u32(material_index);
+        }
+        stream.write_bytes(&self.active_edges);
+        stream.write_len(self.nodes.len());
+        for node in &self.nodes {
+            for value in node.min.iter().chain(&node.max).flatten() {
+                stream.write_u16(*value);
+            }
+            for &child in &node.children {
+                stream.write_u32(child);
+            }
+        }
+    }
+
+    /// Loads a mesh that was saved with [`MeshShape::save`].
+    pub fn restore(data: &[u8]) -> Result<Self, ShapeError> {
+        let mut stream = StreamIn::new(data);
+        if stream.read_bytes(MESH_MAGIC.len())? != MESH_MAGIC || stream.read_u32()? != MESH_VERSION
+        {
+            return Err(ShapeError::InvalidData);
+        }
+        let bounds = AABox::new(stream.read_vec3()?, stream.read_vec3()?);
+
+        let vertex_count = stream.read_len(12)?;
+        let vertices = (0..vertex_count)
+            .map(|_| stream.read_vec3())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let triangle_count = stream.read_len(16)?;
+        let mut triangles = Vec::with_capacity(triangle_count);
+        let mut material_indices = Vec::with_capacity(triangle_count);
+        for _ in 0..triangle_count {
+            let triangle = [stream.read_u32()?, stream.read_u32()?, stream.read_u32()?];
+            if triangle.iter().any(|&i| i as usize >= vertex_count) {
+                return Err(ShapeError::InvalidData);
+            }
+            triangles.push(triangle);
+            material_indices.push(stream.read_u32()?);
+        }
+        let active_edges = stream.read_bytes(triangle_count)?.to_vec();
+        if active_edges.iter().any(|&edges| edges > 0b111) {
+            return Err(ShapeError::InvalidData);
+        }
+
+        let node_count = stream.read_len(64)?;
+        let mut nodes = Vec::with_capacity(node_count);
+        for _ in 0..node_count {
+            let mut node = Node::default();
+            for value in node.min.iter_mut().chain(&mut node.max).flatten() {
+                *value = stream.read_u16()?;
+            }
+            for child in &mut node.children {
+                *child = stream.read_u32()?;
+                let valid = if *child == EMPTY_CHILD {
+                    true
+                } else if *child & LEAF_BIT != 0 {
+                    Self::leaf_range(*child).end <= triangle_count
+                } else {
+                    // Children are always stored after their parent, which rules out cycles.
+                    (*child as usize) > nodes.len() && (*child as usize) < node_count
+                };
+                if !valid {
+                    return Err(ShapeError::InvalidData);
+                }
+            }
+            nodes.push(node);
+        }
+        stream.finish()?;
+        if nodes.is_empty() || triangles.is_empty() || !bounds.is_valid() {
+            return Err(ShapeError::InvalidData);
+        }
+
+        Ok(Self {
+            vertices,
+            triangles,
+            material_indices,
+            active_edges,
+            nodes,
+            bounds,
+            quantization_step: Self::quantization_step(&bounds),
+        })
+    }
+
+    /// Returns the active edges of every triangle. Edges are matched by their vertex indices,
+    /// edges that are not shared by exactly two triangles are always active.
+    fn find_active_edges(
+        vertices: &[Vec3],
+        triangles: &[IndexedTriangle],
+        cos_threshold_angle: f32,
+    ) -> Vec<u8> {
+        let mut edges = BTreeMap::<(u32, u32), Vec<(usize, usize)>>::new();
+        for (index, triangle) in triangles.iter().enumerate() {
+            for edge in 0..3 {
+                let (a, b) = (triangle.indices[edge], triangle.indices[(edge + 1) % 3]);
+                edges
+                    .entry((a.min(b), a.max(b)))
+                    .or_default()
+                    .push((index, edge));
+            }
+        }
+
+        let vertex = |triangle: usize, i: usize| vertices[triangles[triangle].indices[i] as usize];
+        let normal = |triangle: usize| {
+            let [a, b, c] = [0, 1, 2].map(|i| vertex(triangle, i));
+            (b - a).cross(c - a).normalize()
+        };
+        let mut active_edges = vec![0; triangles.len()];
+        for shared in edges.values() {
+            let active = match shared[..] {
+                [(triangle1, edge), (triangle2, _)] => is_edge_active(
+                    normal(triangle1),
+                    normal(triangle2),
+                    vertex(triangle1, (edge + 1) % 3) - vertex(triangle1, edge),
+                    cos_threshold_angle,
+                ),
+                _ => true,
+            };
+            if active {
+                for &(triangle, edge) in shared {
+                    active_edges[triangle] |= 1 << edge;
+                }
+            }
+        }
+        active_edges
+    }
+
+    fn quantization_step(bounds: &AABox) -> Vec3 {
+        (bounds.size() / QUANTIZED_MAX as f32).max(Vec3::splat(f32::MIN_POSITIVE))
+    }
+
+    #[inline]
+    fn sub_shape_id(&self, index: usize) -> SubShapeID {
+        SubShapeIDCreator::new()
+            .push_id(index as u32, self.sub_shape_id_bits())
+            .id()
+    }
+
+    #[inline]
+    fn triangle_index(&self, sub_shape_id: SubShapeID) -> usize {
+        sub_shape_id.pop_id(self.sub_shape_id_bits()).0 as usize
+    }
+
+    #[inline]
+    fn leaf_range(code: u32) -> core::ops::Range<usize> {
+        let start = ((code & !LEAF_BIT) >> LEAF_COUNT_BITS) as usize;
+        start..start + (code & LEAF_COUNT_MASK) as usize
+    }
+
+    /// Returns the bounds of the children of `node`.
+    #[inline]
+    fn decode_bounds(&self, node: &Node) -> AABox4 {
+        let decode = |values: &[u16; 4], axis: usize| {
+            Vec4::new(
+                values[0] as f32,
+                values[1] as f32,
+                values[2] as f32,
+                values[3] as f32,
+            ) * self.quantization_step[axis]
+                + self.bounds.min[axis]
+        };
+        AABox4 {
+            min_x: decode(&node.min[0], 0),
+            min_y: decode(&node.min[1], 1),
+            min_z: decode(&node.min[2], 2),
+            max_x: decode(&node.max[0], 0),
+            max_y: decode(&node.max[1], 1),
+            max_z: decode(&node.max[2], 2),
+        }
+    }
+
+    /// Walks the tree front to back along `ray`, `on_hit` is called with every triangle that
+    /// is hit closer than the current maximum fraction and returns the new maximum fraction.
+    fn walk_ray(
+        &self,
+        ray: &RayCast,
+        mut max_fraction: f32,
+        mut on_hit: impl FnMut(usize, f32) -> f32,
+    ) {
+        let inv_direction = RayInvDirection::new(ray.direction);
+        let mut stack = vec![(0, 0.0)];
+        while let Some((code, fraction)) = stack.pop() {
+            if fraction >= max_fraction {
+                continue;
+            }
+            if code & LEAF_BIT != 0 {
+                for index in Self::leaf_range(code) {
+                    let [v0, v1, v2] = self.triangles[index].map(|i| self.vertices[i as usize]);
+                    let fraction = ray_triangle(ray.origin, ray.direction, v0, v1, v2);
+                    if fraction < max_fraction {
+                        max_fraction = on_hit(index, fraction);
+                    }
+                }
+                continue;
+            }
+
+            let node = &self.nodes[code as usize];
+            let fractions = ray_aabox4(ray.origin, &inv_direction, &self.decode_bounds(node));
+            let mut children = [(0, 0.0); 4];
+            let mut count = 0;
+            for (i, &child) in node.children.iter().enumerate() {
+                if child != EMPTY_CHILD && fractions[i] < max_fraction {
+                    children[count] = (child, fractions[i]);
+                    count += 1;
+                }
+            }
+            // Push the furthest child first so that the closest child is visited first.
+            children[..count].sort_unstable_by(|a, b| b.1.total_cmp(&a.1));
+            stack.extend_from_slice(&children[..count]);
+        }
+    }
+}
+
+/// Builds the tree of a [`MeshShape`] by recursively splitting the triangles at the median
+/// of their centroids along the longest axis.
+struct TreeBuilder<'a> {
+    vertices: &'a [Vec3],
+    triangles: &'a [IndexedTriangle],
+    /// The triangles in tree order.
+    order: Vec<u32>,
+    centroids: Vec<Vec3>,
+    nodes: Vec<Node>,
+    bounds: AABox,
+    quantization_step: Vec3,
+}
+
+impl TreeBuilder<'_> {
+    fn build_root(&mut self) {
+        // The root is always a node, even if all triangles fit in a single leaf.
+        self.nodes.push(Node::default());
+        let count = self.order.len();
+        let partitions = self.partition(0, count);
+        self.fill_node(0, &partitions);
+    }
+
+    /// Builds the subtree for the triangles `start..end` and returns the child code for it.
+    fn build(&mut self, start: usize, end: usize) -> u32 {
+        if end - start <= MAX_TRIANGLES_PER_LEAF {
+            return LEAF_BIT | ((start as u32) << LEAF_COUNT_BITS) | (end - start) as u32;
+        }
+        let index = self.nodes.len();
+        self.nodes.push(Node::default());
+        let partitions = self.partition(start, end);
+        self.fill_node(index, &partitions);
+        index as u32
+    }
+
+    /// Splits `start..end` into at most four ranges by repeatedly halving the largest one.
+    fn partition(&mut self, start: usize, end: usize) -> Vec<(usize, usize)> {
+        let mut partitions = vec![(start, end)];
+        while partitions.len() < 4 {
+            let (index, &(start, end)) = partitions
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, (start, end))| end - start)
+                .unwrap();
+            if end - start <= MAX_TRIANGLES_PER_LEAF {
+                break;
+            }
+            let middle = self.split(start, end);
+            partitions[index] = (start, middle);
+            partitions.insert(index + 1, (middle, end));
+        }
+        partitions
+    }
+
+    /// Reorders `start..end` around the median centroid on the longest axis and returns the
+    /// index of the median.
+    fn split(&mut self, start: usize, end: usize) -> usize {
+        let centroids = &self.centroids;
+        let bounds = AABox::from_points(
+            self.order[start..end]
+                .iter()
+                .map(|&i| centroids[i as usize]),
+        );
+        let size = bounds.size();
+        let axis = if size.x >= size.y && size.x >= size.z {
+            0
+        } else if size.y >= size.z {
+            1
+        } else {
+            2
+        };
+        let middle = (start + end) / 2;
+        self.order[start..end].select_nth_unstable_by(middle - start, |&a, &b| {
+            centroids[a as usize][axis].total_cmp(&centroids[b as usize][axis])
+        });
+        middle
+    }
+
+    fn fill_node(&mut self, index: usize, partitions: &[(usize, usize)]) {
+        for (i, &(start, end)) in partitions.iter().enumerate() {
+            let bounds = AABox::from_points(self.order[start..end].iter().flat_map(|&t| {
+                self.triangles[t as usize]
+                    .indices
+                    .map(|v| self.vertices[v as usize])
+            }));
+            for axis in 0..3 {
+                self.nodes[index].min[axis][i] = self.quantize_min(bounds.min[axis], axis);
+                self.nodes[index].max[axis][i] = self.quantize_max(bounds.max[axis], axis);
+            }
+            let child = self.build(start, end);
+            self.nodes[index].children[i] = child;
+        }
+    }
+
+    #[inline]
+    fn dequantize(&self, value: u16, axis: usize) -> f32 {
+        value as f32 * self.quantization_step[axis] + self.bounds.min[axis]
+    }
+
+    /// Quantizes `value` so that the dequantized value is never larger than `value`.
+    fn quantize_min(&self, value: f32, axis: usize) -> u16 {
+        let relative = (value - self.bounds.min[axis]) / self.quantization_step[axis];
+        let mut result = relative.floor().clamp(0.0, QUANTIZED_MAX as f32) as u16;
+        while result > 0 && self.dequantize(result, axis) > value {
+            result -= 1;
+        }
+        result
+    }
+
+    /// Quantizes `value` so that the dequantized value is never smaller than `value`.
+    fn quantize_max(&self, value: f32, axis: usize) -> u16 {
+        let relative = (value - self.bounds.min[axis]) / self.quantization_step[axis];
+        let mut result = relative.ceil().clamp(0.0, QUANTIZED_MAX as f32) as u16;
+        while result < QUANTIZED_MAX && self.dequantize(result, axis) < value {
+            result += 1;
+        }
+        result
+    }
+}
+
+impl Shape for MeshShape {
+    fn sub_type(&self) -> ShapeSubType {
+        ShapeSubType::Mesh
+    }
+
+    fn local_bounds(&self) -> AABox {
+        self.bounds
+    }
+
+    fn inner_radius(&self) -> f32 {
+        0.0
+    }
+
+    fn volume(&self) -> f32 {
+        0.0
+    }
+
+    fn mass_properties(&self) -> MassProperties {
+        MassProperties::default()
+    }
+
+    fn sub_shape_id_bits(&self) -> u32 {
+        sub_shape_id_bits_for(self.triangles.len() as u32)
+    }
+
+    fn material_index(&self, sub_shape_id: SubShapeID) -> u32 {
+        self.material_indices[self.triangle_index(sub_shape_id)]
+    }
+
+    fn surface_normal(&self, sub_shape_id: SubShapeID, _local_position: Vec3) -> Vec3 {
+        let [v0, v1, v2] = self.triangle(sub_shape_id);
+        (v1 - v0).cross(v2 - v0).normalize_or_zero()
+    }
+
+    fn cast_ray(&self, ray: &RayCast, hit: &mut RayCastResult) -> bool {
+        let mut closest = None;
+        self.walk_ray(ray, hit.fraction, |index, fraction| {
+            closest = Some((index, fraction));
+            fraction
+        });
+        match closest {
+            Some((index, fraction)) => {
+                hit.fraction = fraction;
+                hit.sub_shape_id = self.sub_shape_id(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reports every triangle that is hit.
+    fn cast_ray_with_collector(
+        &self,
+        ray: &RayCast,
+        creator: SubShapeIDCreator,
+        collector: &mut dyn CollisionCollector<RayCastResult>,
+    ) {
+        self.walk_ray(ray, collector.early_out_fraction(), |index, fraction| {
+            collector.add_hit(&RayCastResult {
+                fraction,
+                sub_shape_id: creator.push_sub_shape_id(self.sub_shape_id(index)),
+            });
+            collector.early_out_fraction()
+        });
+    }
+
+    fn collide_point(&self, point: Vec3) -> bool {
+        if !self.bounds.contains_point(point) {
+            return false;
+        }
+
+        // Cast a ray upwards and count the number of crossings, which is odd inside a closed
+        // mesh.
+        let direction = Vec3::new(0.0, self.bounds.max.y - point.y + 1.0, 0.0);
+        let mut hits = 0;
+        self.walk_ray(&RayCast::new(point, direction), 1.0, |_, _| {
+            hits += 1;
+            1.0
+        });
+        hits % 2 == 1
+    }
+}
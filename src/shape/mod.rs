@@ -0,0 +1,71 @@
+pub(crate) mod box_shape;
+pub use box_shape::BoxShape;
+
+pub(crate) mod capsule_shape;
+pub use capsule_shape::CapsuleShape;
+
+pub(crate) mod compound_shape;
+pub use compound_shape::CompoundSubShape;
+
+pub(crate) mod convex_hull_shape;
+pub use convex_hull_shape::{ConvexHullShape, MAX_POINTS_IN_HULL};
+
+pub(crate) mod convex_shape;
+pub(crate) use convex_shape::{cast_ray_convex, collide_convex_vs_triangles, collide_point_convex};
+pub use convex_shape::{ConvexShape, SupportMode, DEFAULT_CONVEX_RADIUS, DEFAULT_DENSITY};
+
+pub(crate) mod cylinder_shape;
+pub use cylinder_shape::CylinderShape;
+
+pub(crate) mod height_field_shape;
+pub use height_field_shape::{
+    HeightFieldSettings, HeightFieldShape, MAX_SAMPLE_COUNT, NO_COLLISION_VALUE,
+};
+
+pub(crate) mod mesh_shape;
+pub use mesh_shape::{MeshShape, DEFAULT_ACTIVE_EDGE_COS_THRESHOLD_ANGLE};
+
+pub(crate) mod mutable_compound_shape;
+pub use mutable_compound_shape::MutableCompoundShape;
+
+pub(crate) mod offset_center_of_mass_shape;
+pub use offset_center_of_mass_shape::OffsetCenterOfMassShape;
+
+pub(crate) mod rotated_translated_shape;
+pub use rotated_translated_shape::RotatedTranslatedShape;
+
+pub(crate) mod scale_helpers;
+
+pub(crate) mod scaled_shape;
+pub use scaled_shape::ScaledShape;
+
+pub(crate) mod sdf_shape;
+pub use sdf_shape::{SdfContact, SdfSettings, SdfShape};
+
+#[allow(clippy::module_inception)]
+pub(crate) mod shape;
+pub use shape::{Shape, ShapeError, ShapeSubType, ShapeType};
+
+pub(crate) mod sphere_shape;
+pub use sphere_shape::SphereShape;
+
+pub(crate) mod static_compound_shape;
+pub use static_compound_shape::StaticCompoundShape;
+
+pub(crate) mod stream;
+
+pub(crate) mod sub_shape_id;
+pub(crate) use sub_shape_id::sub_shape_id_bits_for;
+pub use sub_shape_id::{SubShapeID, SubShapeIDCreator};
+
+pub(crate) mod tapered_capsule_shape;
+pub use tapered_capsule_shape::TaperedCapsuleShape;
+
+/// Returns `density` when it is a valid density for a shape.
+pub(crate) fn validate_density(density: f32) -> Result<f32, ShapeError> {
+    if density > 0.0 && density.is_finite() {
+        Ok(density)
+    } else {
+        Err(ShapeError::InvalidDensity)
+    }
+}
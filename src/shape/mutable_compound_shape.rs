@@ -0,0 +1,292 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::{
+    body::MassProperties,
+    collision::{CollidePointResult, CollisionCollector, RayCast, RayCastResult},
+    geometry::{ray_aabox4, AABox, AABox4, RayInvDirection},
+    math::{Quat, Vec3},
+    shape::{
+        compound_shape, sub_shape_id_bits_for, CompoundSubShape, Shape, ShapeError, ShapeSubType,
+        SubShapeID, SubShapeIDCreator,
+    },
+};
+
+/// A compound of shapes that can be added, removed and moved after it has been created.
+///
+/// The bounds of the children are stored in groups of four that are tested at once, which
+/// makes changes cheap but queries linear in the number of children. Prefer a
+/// [`StaticCompoundShape`](super::StaticCompoundShape) for compounds that do not change.
+///
+/// Modifying the compound does not move its center of mass, call
+/// [`MutableCompoundShape::adjust_center_of_mass`] to recenter it.
+#[derive(Clone, Debug)]
+pub struct MutableCompoundShape {
+    sub_shapes: Vec<CompoundSubShape>,
+    /// The bounds of the children in groups of four.
+    sub_shape_bounds: Vec<AABox4>,
+    center_of_mass: Vec3,
+    local_bounds: AABox,
+    sub_shape_id_bits: u32,
+}
+
+impl MutableCompoundShape {
+    /// Creates a compound from its children, the positions of which are the positions of their
+    /// origins. The compound may be empty.
+    pub fn new(sub_shapes: &[CompoundSubShape]) -> Result<Self, ShapeError> {
+        let sub_shape_id_bits = compound_shape::sub_shape_id_bits(sub_shapes)?;
+        let mut sub_shapes = sub_shapes.to_vec();
+        let center_of_mass = compound_shape::center_sub_shapes(&mut sub_shapes);
+        let mut shape = Self {
+            sub_shapes,
+            sub_shape_bounds: Vec::new(),
+            center_of_mass,
+            local_bounds: AABox::INVALID,
+            sub_shape_id_bits,
+        };
+        shape.update_bounds(0);
+        Ok(shape)
+    }
+
+    /// Returns the children, positioned relative to the center of mass of the compound.
+    #[inline]
+    pub fn sub_shapes(&self) -> &[CompoundSubShape] {
+        &self.sub_shapes
+    }
+
+    /// Splits `sub_shape_id` into the index of the child and the sub shape ID within the child.
+    #[inline]
+    pub fn sub_shape_index(&self, sub_shape_id: SubShapeID) -> (u32, SubShapeID) {
+        sub_shape_id.pop_id(self.index_bits())
+    }
+
+    /// Adds a child, `position` and `rotation` place the origin of `shape` relative to the
+    /// center of mass of the compound. Returns the index of the new child.
+    pub fn add_shape(
+        &mut self,
+        shape: Arc<dyn Shape>,
+        position: Vec3,
+        rotation: Quat,
+        user_data: u32,
+    ) -> Result<u32, ShapeError> {
+        let index = self.sub_shapes.len();
+        let mut sub_shape =
+            CompoundSubShape::new(shape, position, rotation).with_user_data(user_data);
+        sub_shape.position += rotation * sub_shape.shape.center_of_mass();
+        self.sub_shapes.push(sub_shape);
+        if let Err(error) = self.update_sub_shape_id_bits() {
+            self.sub_shapes.pop();
+            return Err(error);
+        }
+        self.update_bounds(index);
+        Ok(index as u32)
+    }
+
+    /// Removes the child at `index`, the children after it move down by one.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `index` is out of bounds.
+    pub fn remove_shape(&mut self, index: u32) {
+        self.sub_shapes.remove(index as usize);
+        // Removing a child never needs more bits.
+        self.update_sub_shape_id_bits().unwrap();
+        self.update_bounds(index as usize);
+    }
+
+    /// Moves the child at `index`, `position` and `rotation` place the origin of the child
+    /// relative to the center of mass of the compound.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `index` is out of bounds.
+    pub fn modify_shape(&mut self, index: u32, position: Vec3, rotation: Quat) {
+        let sub_shape = &mut self.sub_shapes[index as usize];
+        sub_shape.position = position + rotation * sub_shape.shape.center_of_mass();
+        sub_shape.rotation = rotation;
+        self.update_bounds(index as usize);
+    }
+
+    /// Moves the child at `index` and replaces its shape.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `index` is out of bounds.
+    pub fn replace_shape(
+        &mut self,
+        index: u32,
+        shape: Arc<dyn Shape>,
+        position: Vec3,
+        rotation: Quat,
+    ) -> Result<(), ShapeError> {
+        let previous = core::mem::replace(&mut self.sub_shapes[index as usize].shape, shape);
+        if let Err(error) = self.update_sub_shape_id_bits() {
+            self.sub_shapes[index as usize].shape = previous;
+            return Err(error);
+        }
+        self.modify_shape(index, position, rotation);
+        Ok(())
+    }
+
+    /// Moves the center of mass of the compound to the center of mass of its children.
+    pub fn adjust_center_of_mass(&mut self) {
+        let offset = compound_shape::center_of_mass(&self.sub_shapes);
+        if offset == Vec3::ZERO {
+            return;
+        }
+        for sub_shape in &mut self.sub_shapes {
+            sub_shape.position -= offset;
+        }
+        self.center_of_mass += offset;
+        self.update_bounds(0);
+    }
+
+    /// Calls `callback` with the index of every child whose bounds overlap `bounds`.
+    pub fn collide_sub_shapes(&self, bounds: &AABox, mut callback: impl FnMut(u32)) {
+        for (block, sub_shape_bounds) in self.sub_shape_bounds.iter().enumerate() {
+            for (i, overlaps) in sub_shape_bounds.overlaps(bounds).into_iter().enumerate() {
+                if overlaps {
+                    callback((block * 4 + i) as u32);
+                }
+            }
+        }
+    }
+
+    /// The number of bits used for the index of a child.
+    #[inline]
+    fn index_bits(&self) -> u32 {
+        sub_shape_id_bits_for(self.sub_shapes.len() as u32)
+    }
+
+    fn update_sub_shape_id_bits(&mut self) -> Result<(), ShapeError> {
+        self.sub_shape_id_bits = compound_shape::sub_shape_id_bits(&self.sub_shapes)?;
+        Ok(())
+    }
+
+    /// Recomputes the bounds of the children from `first` onwards and of the compound.
+    fn update_bounds(&mut self, first: usize) {
+        self.sub_shape_bounds
+            .resize(self.sub_shapes.len().div_ceil(4), AABox4::INVALID);
+        for index in first - first % 4..self.sub_shape_bounds.len() * 4 {
+            let bounds = self
+                .sub_shapes
+                .get(index)
+                .map_or(AABox::INVALID, CompoundSubShape::bounds);
+            self.sub_shape_bounds[index / 4].set(index % 4, &bounds);
+        }
+        self.local_bounds = compound_shape::local_bounds(&self.sub_shapes);
+    }
+}
+
+impl Shape for MutableCompoundShape {
+    fn sub_type(&self) -> ShapeSubType {
+        ShapeSubType::MutableCompound
+    }
+
+    fn center_of_mass(&self) -> Vec3 {
+        self.center_of_mass
+    }
+
+    fn local_bounds(&self) -> AABox {
+        if self.local_bounds.is_valid() {
+            self.local_bounds
+        } else {
+            AABox::new(Vec3::ZERO, Vec3::ZERO)
+        }
+    }
+
+    fn inner_radius(&self) -> f32 {
+        compound_shape::inner_radius(&self.sub_shapes)
+    }
+
+    fn volume(&self) -> f32 {
+        compound_shape::volume(&self.sub_shapes)
+    }
+
+    fn mass_properties(&self) -> MassProperties {
+        compound_shape::mass_properties(&self.sub_shapes)
+    }
+
+    fn is_valid_scale(&self, scale: Vec3) -> bool {
+        compound_shape::is_valid_scale(&self.sub_shapes, scale)
+    }
+
+    fn sub_shape_id_bits(&self) -> u32 {
+        self.sub_shape_id_bits
+    }
+
+    fn material_index(&self, sub_shape_id: SubShapeID) -> u32 {
+        let (index, remainder) = self.sub_shape_index(sub_shape_id);
+        self.sub_shapes[index as usize]
+            .shape
+            .material_index(remainder)
+    }
+
+    fn surface_normal(&self, sub_shape_id: SubShapeID, local_position: Vec3) -> Vec3 {
+        let (index, remainder) = self.sub_shape_index(sub_shape_id);
+        self.sub_shapes[index as usize].surface_normal(remainder, local_position)
+    }
+
+    fn cast_ray(&self, ray: &RayCast, hit: &mut RayCastResult) -> bool {
+        let inv_direction = RayInvDirection::new(ray.direction);
+        let index_bits = self.index_bits();
+        let mut result = false;
+        for (block, sub_shape_bounds) in self.sub_shape_bounds.iter().enumerate() {
+            let fractions = ray_aabox4(ray.origin, &inv_direction, sub_shape_bounds);
+            for i in 0..4 {
+                let index = block * 4 + i;
+                if index < self.sub_shapes.len() && fractions[i] < hit.fraction {
+                    let creator = SubShapeIDCreator::new().push_id(index as u32, index_bits);
+                    result |= self.sub_shapes[index].cast_ray(creator, ray, hit);
+                }
+            }
+        }
+        result
+    }
+
+    fn cast_ray_with_collector(
+        &self,
+        ray: &RayCast,
+        creator: SubShapeIDCreator,
+        collector: &mut dyn CollisionCollector<RayCastResult>,
+    ) {
+        let inv_direction = RayInvDirection::new(ray.direction);
+        let index_bits = self.index_bits();
+        for (block, sub_shape_bounds) in self.sub_shape_bounds.iter().enumerate() {
+            let fractions = ray_aabox4(ray.origin, &inv_direction, sub_shape_bounds);
+            for i in 0..4 {
+                let index = block * 4 + i;
+                if collector.should_early_out() {
+                    return;
+                }
+                if index < self.sub_shapes.len() && fractions[i] < collector.early_out_fraction() {
+                    let creator = creator.push_id(index as u32, index_bits);
+                    self.sub_shapes[index].cast_ray_with_collector(creator, ray, collector);
+                }
+            }
+        }
+    }
+
+    fn collide_point(&self, point: Vec3) -> bool {
+        let mut inside = false;
+        self.collide_sub_shapes(&AABox::new(point, point), |index| {
+            inside = inside || self.sub_shapes[index as usize].collide_point(point);
+        });
+        inside
+    }
+
+    fn collide_point_with_collector(
+        &self,
+        point: Vec3,
+        creator: SubShapeIDCreator,
+        collector: &mut dyn CollisionCollector<CollidePointResult>,
+    ) {
+        let index_bits = self.index_bits();
+        self.collide_sub_shapes(&AABox::new(point, point), |index| {
+            if !collector.should_early_out() {
+                let creator = creator.push_id(index, index_bits);
+                self.sub_shapes[index as usize]
+                    .collide_point_with_collector(creator, point, collector);
+            }
+        });
+    }
+}
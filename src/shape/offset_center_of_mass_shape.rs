@@ -0,0 +1,156 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+
+use crate::{
+    body::MassProperties,
+    collision::{CollidePointResult, CollisionCollector, RayCast, RayCastResult},
+    geometry::{AABox, Support, TransformedConvexObject},
+    math::{Quat, Vec3},
+    shape::{ConvexShape, Shape, ShapeSubType, SubShapeID, SubShapeIDCreator, SupportMode},
+};
+
+/// Moves the center of mass of another shape without moving the shape itself, for example to
+/// lower the center of mass of a vehicle to make it more stable.
+///
+/// The mass and inertia of the inner shape are used unchanged around the new center of mass.
+#[derive(Clone, Debug)]
+pub struct OffsetCenterOfMassShape {
+    inner_shape: Arc<dyn Shape>,
+    offset: Vec3,
+}
+
+impl OffsetCenterOfMassShape {
+    /// Moves the center of mass of `inner_shape` by `offset`.
+    pub fn new(inner_shape: Arc<dyn Shape>, offset: Vec3) -> Self {
+        Self {
+            inner_shape,
+            offset,
+        }
+    }
+
+    #[inline]
+    pub fn inner_shape(&self) -> &Arc<dyn Shape> {
+        &self.inner_shape
+    }
+
+    #[inline]
+    pub fn offset(&self) -> Vec3 {
+        self.offset
+    }
+
+    #[inline]
+    fn inner_convex(&self) -> &dyn ConvexShape {
+        self.inner_shape
+            .as_convex()
+            .expect("inner shape is not convex")
+    }
+}
+
+impl Shape for OffsetCenterOfMassShape {
+    fn sub_type(&self) -> ShapeSubType {
+        ShapeSubType::OffsetCenterOfMass
+    }
+
+    fn center_of_mass(&self) -> Vec3 {
+        self.inner_shape.center_of_mass() + self.offset
+    }
+
+    fn local_bounds(&self) -> AABox {
+        self.inner_shape.local_bounds().translated(-self.offset)
+    }
+
+    fn world_bounds(&self, rotation: Quat, translation: Vec3, scale: Vec3) -> AABox {
+        self.inner_shape.world_bounds(
+            rotation,
+            translation - rotation * (scale * self.offset),
+            scale,
+        )
+    }
+
+    fn inner_radius(&self) -> f32 {
+        self.inner_shape.inner_radius()
+    }
+
+    fn volume(&self) -> f32 {
+        self.inner_shape.volume()
+    }
+
+    fn mass_properties(&self) -> MassProperties {
+        self.inner_shape.mass_properties()
+    }
+
+    fn is_valid_scale(&self, scale: Vec3) -> bool {
+        self.inner_shape.is_valid_scale(scale)
+    }
+
+    fn sub_shape_id_bits(&self) -> u32 {
+        self.inner_shape.sub_shape_id_bits()
+    }
+
+    fn material_index(&self, sub_shape_id: SubShapeID) -> u32 {
+        self.inner_shape.material_index(sub_shape_id)
+    }
+
+    fn surface_normal(&self, sub_shape_id: SubShapeID, local_position: Vec3) -> Vec3 {
+        self.inner_shape
+            .surface_normal(sub_shape_id, local_position + self.offset)
+    }
+
+    fn cast_ray(&self, ray: &RayCast, hit: &mut RayCastResult) -> bool {
+        self.inner_shape.cast_ray(&ray.translated(self.offset), hit)
+    }
+
+    fn cast_ray_with_collector(
+        &self,
+        ray: &RayCast,
+        creator: SubShapeIDCreator,
+        collector: &mut dyn CollisionCollector<RayCastResult>,
+    ) {
+        self.inner_shape
+            .cast_ray_with_collector(&ray.translated(self.offset), creator, collector);
+    }
+
+    fn collide_point(&self, point: Vec3) -> bool {
+        self.inner_shape.collide_point(point + self.offset)
+    }
+
+    fn collide_point_with_collector(
+        &self,
+        point: Vec3,
+        creator: SubShapeIDCreator,
+        collector: &mut dyn CollisionCollector<CollidePointResult>,
+    ) {
+        self.inner_shape
+            .collide_point_with_collector(point + self.offset, creator, collector);
+    }
+
+    fn as_convex(&self) -> Option<&dyn ConvexShape> {
+        self.inner_shape
+            .as_convex()
+            .map(|_| self as &dyn ConvexShape)
+    }
+}
+
+/// Only usable when the inner shape is convex, see [`Shape::as_convex`].
+impl ConvexShape for OffsetCenterOfMassShape {
+    fn density(&self) -> f32 {
+        self.inner_convex().density()
+    }
+
+    fn get_support_function(&self, mode: SupportMode, scale: Vec3) -> Box<dyn Support + '_> {
+        let support = self.inner_convex().get_support_function(mode, scale);
+        Box::new(TransformedConvexObject::new(
+            support,
+            Quat::IDENTITY,
+            -scale * self.offset,
+        ))
+    }
+
+    fn get_supporting_face(&self, direction: Vec3, scale: Vec3, vertices: &mut Vec<Vec3>) {
+        self.inner_convex()
+            .get_supporting_face(direction, scale, vertices);
+        let offset = scale * self.offset;
+        for vertex in vertices.iter_mut() {
+            *vertex -= offset;
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+
+use crate::{
+    body::MassProperties,
+    collision::{CollidePointResult, CollisionCollector, RayCast, RayCastResult},
+    geometry::{AABox, Support, TransformedConvexObject},
+    math::{Quat, Vec3},
+    shape::{
+        scale_helpers, ConvexShape, Shape, ShapeSubType, SubShapeID, SubShapeIDCreator, SupportMode,
+    },
+};
+
+/// Rotates and translates another shape.
+///
+/// Scaling the shape non uniformly is only possible when the scale can be expressed as a scale
+/// in the space of the inner shape, see [`Shape::is_valid_scale`].
+#[derive(Clone, Debug)]
+pub struct RotatedTranslatedShape {
+    inner_shape: Arc<dyn Shape>,
+    /// The rotation of the inner shape, which is applied around its center of mass.
+    rotation: Quat,
+    center_of_mass: Vec3,
+}
+
+impl RotatedTranslatedShape {
+    /// Places the origin of `inner_shape` at `position` and rotates it by `rotation`.
+    pub fn new(inner_shape: Arc<dyn Shape>, position: Vec3, rotation: Quat) -> Self {
+        let center_of_mass = position + rotation * inner_shape.center_of_mass();
+        Self {
+            inner_shape,
+            rotation,
+            center_of_mass,
+        }
+    }
+
+    #[inline]
+    pub fn inner_shape(&self) -> &Arc<dyn Shape> {
+        &self.inner_shape
+    }
+
+    #[inline]
+    pub fn rotation(&self) -> Quat {
+        self.rotation
+    }
+
+    /// Returns the position of the origin of the inner shape.
+    #[inline]
+    pub fn position(&self) -> Vec3 {
+        self.center_of_mass - self.rotation * self.inner_shape.center_of_mass()
+    }
+
+    /// Returns `scale` in the space of the inner shape.
+    #[inline]
+    fn inner_scale(&self, scale: Vec3) -> Vec3 {
+        scale_helpers::rotate_scale(self.rotation, scale)
+            .expect("scale cannot be rotated into the inner shape")
+    }
+
+    #[inline]
+    fn inner_convex(&self) -> &dyn ConvexShape {
+        self.inner_shape
+            .as_convex()
+            .expect("inner shape is not convex")
+    }
+}
+
+impl Shape for RotatedTranslatedShape {
+    fn sub_type(&self) -> ShapeSubType {
+        ShapeSubType::RotatedTranslated
+    }
+
+    fn center_of_mass(&self) -> Vec3 {
+        self.center_of_mass
+    }
+
+    fn local_bounds(&self) -> AABox {
+        self.inner_shape
+            .world_bounds(self.rotation, Vec3::ZERO, Vec3::ONE)
+    }
+
+    fn world_bounds(&self, rotation: Quat, translation: Vec3, scale: Vec3) -> AABox {
+        match scale_helpers::rotate_scale(self.rotation, scale) {
+            Some(inner_scale) => {
+                self.inner_shape
+                    .world_bounds(rotation * self.rotation, translation, inner_scale)
+            }
+            None => self
+                .local_bounds()
+                .scaled(scale)
+                .transformed(rotation, translation),
+        }
+    }
+
+    fn inner_radius(&self) -> f32 {
+        self.inner_shape.inner_radius()
+    }
+
+    fn volume(&self) -> f32 {
+        self.inner_shape.volume()
+    }
+
+    fn mass_properties(&self) -> MassProperties {
+        let mut mass_properties = self.inner_shape.mass_properties();
+        mass_properties.rotate(self.rotation);
+        mass_properties
+    }
+
+    fn is_valid_scale(&self, scale: Vec3) -> bool {
+        scale_helpers::rotate_scale(self.rotation, scale)
+            .is_some_and(|inner_scale| self.inner_shape.is_valid_scale(inner_scale))
+    }
+
+    fn sub_shape_id_bits(&self) -> u32 {
+        self.inner_shape.sub_shape_id_bits()
+    }
+
+    fn material_index(&self, sub_shape_id: SubShapeID) -> u32 {
+        self.inner_shape.material_index(sub_shape_id)
+    }
+
+    fn surface_normal(&self, sub_shape_id: SubShapeID, local_position: Vec3) -> Vec3 {
+        let local_position = self.rotation.conjugate() * local_position;
+        self.rotation
+            * self
+                .inner_shape
+                .surface_normal(sub_shape_id, local_position)
+    }
+
+    fn cast_ray(&self, ray: &RayCast, hit: &mut RayCastResult) -> bool {
+        let local_ray = ray.transformed(self.rotation.conjugate(), Vec3::ZERO);
+        self.inner_shape.cast_ray(&local_ray, hit)
+    }
+
+    fn cast_ray_with_collector(
+        &self,
+        ray: &RayCast,
+        creator: SubShapeIDCreator,
+        collector: &mut dyn CollisionCollector<RayCastResult>,
+    ) {
+        let local_ray = ray.transformed(self.rotation.conjugate(), Vec3::ZERO);
+        self.inner_shape
+            .cast_ray_with_collector(&local_ray, creator, collector);
+    }
+
+    fn collide_point(&self, point: Vec3) -> bool {
+        self.inner_shape
+            .collide_point(self.rotation.conjugate() * point)
+    }
+
+    fn collide_point_with_collector(
+        &self,
+        point: Vec3,
+        creator: SubShapeIDCreator,
+        collector: &mut dyn CollisionCollector<CollidePointResult>,
+    ) {
+        self.inner_shape.collide_point_with_collector(
+            self.rotation.conjugate() * point,
+            creator,
+            collector,
+        );
+    }
+
+    fn as_convex(&self) -> Option<&dyn ConvexShape> {
+        self.inner_shape
+            .as_convex()
+            .map(|_| self as &dyn ConvexShape)
+    }
+}
+
+/// Only usable when the inner shape is convex, see [`Shape::as_convex`].
+impl ConvexShape for RotatedTranslatedShape {
+    fn density(&self) -> f32 {
+        self.inner_convex().density()
+    }
+
+    fn get_support_function(&self, mode: SupportMode, scale: Vec3) -> Box<dyn Support + '_> {
+        let support = self
+            .inner_convex()
+            .get_support_function(mode, self.inner_scale(scale));
+        Box::new(TransformedConvexObject::new(
+            support,
+            self.rotation,
+            Vec3::ZERO,
+        ))
+    }
+
+    fn get_supporting_face(&self, direction: Vec3, scale: Vec3, vertices: &mut Vec<Vec3>) {
+        self.inner_convex().get_supporting_face(
+            self.rotation.conjugate() * direction,
+            self.inner_scale(scale),
+            vertices,
+        );
+        for vertex in vertices.iter_mut() {
+            *vertex = self.rotation * *vertex;
+        }
+    }
+}
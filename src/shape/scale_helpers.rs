@@ -0,0 +1,43 @@
+use crate::math::{Mat33, Quat, Vec3};
+
+/// Scale components closer than this (squared) are considered equal.
+const SCALE_TOLERANCE_SQ: f32 = 1.0e-8;
+
+/// The smallest scale component that a shape can be scaled by.
+const MIN_SCALE: f32 = 1.0e-6;
+
+/// Returns whether `scale` is finite and does not collapse any axis.
+#[inline]
+pub(crate) fn is_valid_scale(scale: Vec3) -> bool {
+    scale.is_finite() && scale.abs().min_element() > MIN_SCALE
+}
+
+/// Returns whether `scale` scales all axes by the same amount, ignoring the sign.
+#[inline]
+pub(crate) fn is_uniform_scale(scale: Vec3) -> bool {
+    let abs = scale.abs();
+    (abs - Vec3::new(abs.y, abs.z, abs.x)).length_squared() <= SCALE_TOLERANCE_SQ
+}
+
+/// Returns whether `scale` scales the X and Z axes by the same amount, ignoring the sign.
+#[inline]
+pub(crate) fn is_uniform_scale_xz(scale: Vec3) -> bool {
+    let difference = scale.x.abs() - scale.z.abs();
+    difference * difference <= SCALE_TOLERANCE_SQ
+}
+
+/// Returns the scale in the space of a child that is rotated by `rotation`, or `None` when the
+/// scale becomes a shear in that space, which shapes cannot represent.
+pub(crate) fn rotate_scale(rotation: Quat, scale: Vec3) -> Option<Vec3> {
+    if scale.x == scale.y && scale.y == scale.z {
+        return Some(scale);
+    }
+    let rotation = Mat33::from_quat(rotation);
+    let scaled = rotation.transpose() * Mat33::from_diagonal(scale) * rotation;
+    let diagonal = scaled.diagonal();
+    let off_diagonal = scaled - Mat33::from_diagonal(diagonal);
+    let tolerance = 1.0e-4 * scale.abs().max_element();
+    off_diagonal
+        .abs_diff_eq(&Mat33::ZERO, tolerance)
+        .then_some(diagonal)
+}
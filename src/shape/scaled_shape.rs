@@ -0,0 +1,156 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+
+use crate::{
+    body::MassProperties,
+    collision::{CollidePointResult, CollisionCollector, RayCast, RayCastResult},
+    geometry::{AABox, Support},
+    math::{Quat, Vec3},
+    shape::{
+        ConvexShape, Shape, ShapeError, ShapeSubType, SubShapeID, SubShapeIDCreator, SupportMode,
+    },
+};
+
+/// Scales another shape, possibly non uniformly or with a negative scale to mirror it.
+///
+/// Not every shape supports every scale, spheres and capsules for example can only be scaled
+/// uniformly. See [`Shape::is_valid_scale`].
+#[derive(Clone, Debug)]
+pub struct ScaledShape {
+    inner_shape: Arc<dyn Shape>,
+    scale: Vec3,
+}
+
+impl ScaledShape {
+    pub fn new(inner_shape: Arc<dyn Shape>, scale: Vec3) -> Result<Self, ShapeError> {
+        if !inner_shape.is_valid_scale(scale) {
+            return Err(ShapeError::InvalidScale);
+        }
+        Ok(Self { inner_shape, scale })
+    }
+
+    #[inline]
+    pub fn inner_shape(&self) -> &Arc<dyn Shape> {
+        &self.inner_shape
+    }
+
+    #[inline]
+    pub fn scale(&self) -> Vec3 {
+        self.scale
+    }
+
+    #[inline]
+    fn inner_convex(&self) -> &dyn ConvexShape {
+        self.inner_shape
+            .as_convex()
+            .expect("inner shape is not convex")
+    }
+}
+
+impl Shape for ScaledShape {
+    fn sub_type(&self) -> ShapeSubType {
+        ShapeSubType::Scaled
+    }
+
+    fn center_of_mass(&self) -> Vec3 {
+        self.inner_shape.center_of_mass() * self.scale
+    }
+
+    fn local_bounds(&self) -> AABox {
+        self.inner_shape.local_bounds().scaled(self.scale)
+    }
+
+    fn world_bounds(&self, rotation: Quat, translation: Vec3, scale: Vec3) -> AABox {
+        self.inner_shape
+            .world_bounds(rotation, translation, scale * self.scale)
+    }
+
+    fn inner_radius(&self) -> f32 {
+        self.inner_shape.inner_radius() * self.scale.abs().min_element()
+    }
+
+    fn volume(&self) -> f32 {
+        self.inner_shape.volume() * (self.scale.x * self.scale.y * self.scale.z).abs()
+    }
+
+    fn mass_properties(&self) -> MassProperties {
+        let mut mass_properties = self.inner_shape.mass_properties();
+        mass_properties.scale(self.scale);
+        mass_properties
+    }
+
+    fn is_valid_scale(&self, scale: Vec3) -> bool {
+        self.inner_shape.is_valid_scale(scale * self.scale)
+    }
+
+    fn sub_shape_id_bits(&self) -> u32 {
+        self.inner_shape.sub_shape_id_bits()
+    }
+
+    fn material_index(&self, sub_shape_id: SubShapeID) -> u32 {
+        self.inner_shape.material_index(sub_shape_id)
+    }
+
+    fn surface_normal(&self, sub_shape_id: SubShapeID, local_position: Vec3) -> Vec3 {
+        // Normals transform with the inverse of the scale.
+        let normal = self
+            .inner_shape
+            .surface_normal(sub_shape_id, local_position / self.scale);
+        (normal / self.scale).normalize_or_zero()
+    }
+
+    fn cast_ray(&self, ray: &RayCast, hit: &mut RayCastResult) -> bool {
+        // Scaling the ray keeps the fractions along it the same.
+        let inv_scale = 1.0 / self.scale;
+        let local_ray = RayCast::new(ray.origin * inv_scale, ray.direction * inv_scale);
+        self.inner_shape.cast_ray(&local_ray, hit)
+    }
+
+    fn cast_ray_with_collector(
+        &self,
+        ray: &RayCast,
+        creator: SubShapeIDCreator,
+        collector: &mut dyn CollisionCollector<RayCastResult>,
+    ) {
+        let inv_scale = 1.0 / self.scale;
+        let local_ray = RayCast::new(ray.origin * inv_scale, ray.direction * inv_scale);
+        self.inner_shape
+            .cast_ray_with_collector(&local_ray, creator, collector);
+    }
+
+    fn collide_point(&self, point: Vec3) -> bool {
+        self.inner_shape.collide_point(point / self.scale)
+    }
+
+    fn collide_point_with_collector(
+        &self,
+        point: Vec3,
+        creator: SubShapeIDCreator,
+        collector: &mut dyn CollisionCollector<CollidePointResult>,
+    ) {
+        self.inner_shape
+            .collide_point_with_collector(point / self.scale, creator, collector);
+    }
+
+    fn as_convex(&self) -> Option<&dyn ConvexShape> {
+        self.inner_shape
+            .as_convex()
+            .map(|_| self as &dyn ConvexShape)
+    }
+}
+
+/// Only usable when the inner shape is convex, see [`Shape::as_convex`].
+impl ConvexShape for ScaledShape {
+    fn density(&self) -> f32 {
+        self.inner_convex().density()
+    }
+
+    fn get_support_function(&self, mode: SupportMode, scale: Vec3) -> Box<dyn Support + '_> {
+        self.inner_convex()
+            .get_support_function(mode, scale * self.scale)
+    }
+
+    fn get_supporting_face(&self, direction: Vec3, scale: Vec3, vertices: &mut Vec<Vec3>) {
+        self.inner_convex()
+            .get_supporting_face(direction, scale * self.scale, vertices);
+    }
+}
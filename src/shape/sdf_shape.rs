@@ -0,0 +1,549 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    body::MassProperties,
+    collision::{RayCast, RayCastResult},
+    geometry::{closest_point_on_triangle, ray_aabox, AABox, Support, TransformedConvexObject},
+    math::{Mat33, Quat, UVec3, Vec3, Vec4},
+    shape::{
+        scale_helpers, ConvexShape, MeshShape, Shape, ShapeError, ShapeSubType, SubShapeID,
+        SupportMode, DEFAULT_DENSITY,
+    },
+};
+
+/// The number of samples along each side of a brick.
+const BRICK_SIZE: u32 = 4;
+
+/// The maximum number of steps taken by a ray before it gives up.
+const MAX_RAY_STEPS: u32 = 256;
+
+/// The number of bisection steps used to find the surface after a ray stepped through it.
+const RAY_REFINE_STEPS: u32 = 8;
+
+/// A ray has hit the surface when it is this fraction of a cell away from it.
+const RAY_HIT_TOLERANCE: f32 = 1.0e-3;
+
+/// The number of times the support point of a convex shape is moved against the normal of the
+/// field before a contact is reported.
+const CONTACT_REFINE_STEPS: u32 = 4;
+
+/// The support point only moves when that brings it at least this fraction of a cell deeper.
+const CONTACT_REFINE_TOLERANCE: f32 = 1.0e-3;
+
+/// Contacts that are closer than this fraction of a cell to an earlier contact are dropped.
+const CONTACT_MERGE_DISTANCE: f32 = 0.1;
+
+/// The distance, in cells, up to which the baker computes exact distances.
+const BAKE_BAND: f32 = 3.0;
+
+/// The number of cells that the baker adds around the mesh.
+const BAKE_PADDING: f32 = 2.0;
+
+/// The maximum number of samples that the baker creates.
+const MAX_BAKED_SAMPLES: u64 = 1 << 24;
+
+/// The settings to create an [`SdfShape`] from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SdfSettings<'a> {
+    /// The signed distances of `sample_count.x * sample_count.y * sample_count.z` samples, X
+    /// varies fastest and Z slowest. Distances are negative inside of the shape.
+    pub distances: &'a [f32],
+    /// The number of samples along each axis, at least 2.
+    pub sample_count: UVec3,
+    /// The local position of the first sample.
+    pub offset: Vec3,
+    /// The distance between samples on all axes. A sample `(x, y, z)` is located at
+    /// `offset + cell_size * (x, y, z)`.
+    pub cell_size: f32,
+    /// Bricks of 4 by 4 by 4 samples that are all at least this far from the surface are
+    /// stored as a single value, which keeps the grid sparse. The default of infinity keeps
+    /// every sample.
+    pub narrow_band: f32,
+    pub density: f32,
+}
+
+impl<'a> SdfSettings<'a> {
+    /// Creates dense settings with an offset of zero.
+    pub fn new(distances: &'a [f32], sample_count: UVec3, cell_size: f32) -> Self {
+        Self {
+            distances,
+            sample_count,
+            offset: Vec3::ZERO,
+            cell_size,
+            narrow_band: f32::INFINITY,
+            density: DEFAULT_DENSITY,
+        }
+    }
+}
+
+/// A brick of samples, either far enough from the surface to be stored as a single value or
+/// stored in full starting at an offset into the samples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Brick {
+    Uniform(f32),
+    Dense(u32),
+}
+
+/// A contact between a convex shape and an [`SdfShape`], in the space of the field.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SdfContact {
+    /// The deepest point of the convex shape.
+    pub point_a: Vec3,
+    /// The point on the surface of the field closest to `point_a`.
+    pub point_b: Vec3,
+    /// The outward surface normal of the field at `point_b`.
+    pub normal: Vec3,
+    /// The signed distance of `point_a` to the surface, negative when penetrating.
+    pub distance: f32,
+}
+
+/// A shape described by a regular grid of signed distances, for example destructible or
+/// sculpted terrain.
+///
+/// The grid is split into bricks of 4 by 4 by 4 samples, bricks that are away from the surface
+/// can be stored as a single conservative distance. Distances are interpolated trilinearly and
+/// the normal is the gradient of the distance. Ray casts march along the ray by the distance
+/// to the surface. The field has no sub shapes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SdfShape {
+    /// The position of the first sample relative to the center of mass.
+    offset: Vec3,
+    cell_size: f32,
+    sample_count: UVec3,
+    bricks_per_side: UVec3,
+    bricks: Vec<Brick>,
+    /// The samples of the dense bricks, each brick stores `BRICK_SIZE³` samples.
+    samples: Vec<f32>,
+    center_of_mass: Vec3,
+    volume: f32,
+    /// The inertia for a density of one.
+    unit_inertia: Mat33,
+    density: f32,
+}
+
+impl SdfShape {
+    pub fn new(settings: &SdfSettings) -> Result<Self, ShapeError> {
+        let sample_count = settings.sample_count;
+        if sample_count.min_element() < 2
+            || sample_count.element_product() != settings.distances.len() as u64
+        {
+            return Err(ShapeError::InvalidSampleCount);
+        }
+        if !(settings.cell_size > 0.0 && settings.cell_size.is_finite()) {
+            return Err(ShapeError::InvalidCellSize);
+        }
+        if !settings.offset.is_finite() || settings.distances.iter().any(|d| d.is_nan()) {
+            return Err(ShapeError::InvalidData);
+        }
+        let density = super::validate_density(settings.density)?;
+
+        let bricks_per_side = (sample_count + (BRICK_SIZE - 1)) / BRICK_SIZE;
+        let mut shape = Self {
+            offset: settings.offset,
+            cell_size: settings.cell_size,
+            sample_count,
+            bricks_per_side,
+            bricks: Vec::new(),
+            samples: Vec::new(),
+            center_of_mass: Vec3::ZERO,
+            volume: 0.0,
+            unit_inertia: Mat33::ZERO,
+            density,
+        };
+        shape.build_bricks(settings.distances, settings.narrow_band);
+        shape.update_mass_properties();
+        Ok(shape)
+    }
+
+    /// Bakes a field from a closed mesh with samples `cell_size` apart. Distances are exact up
+    /// to a few cells from the surface and are clamped further away, bricks beyond that are
+    /// stored sparsely.
+    pub fn from_mesh(mesh: &MeshShape, cell_size: f32) -> Result<Self, ShapeError> {
+        if !(cell_size > 0.0 && cell_size.is_finite()) {
+            return Err(ShapeError::InvalidCellSize);
+        }
+        let bounds = mesh
+            .local_bounds()
+            .expanded_by(Vec3::splat(BAKE_PADDING * cell_size));
+        let sample_count = UVec3::ceil(bounds.size() / cell_size) + 1;
+        if sample_count.element_product() > MAX_BAKED_SAMPLES {
+            return Err(ShapeError::InvalidCellSize);
+        }
+        let position = |sample: UVec3| bounds.min + sample.as_vec3() * cell_size;
+
+        // Rasterize the unsigned distance to every triangle into the samples around it.
+        let band = BAKE_BAND * cell_size;
+        let mut distances = vec![band; sample_count.element_product() as usize];
+        mesh.collide_triangles(&bounds, |_, [v0, v1, v2]| {
+            let triangle_bounds = AABox::from_points([v0, v1, v2]).expanded_by(Vec3::splat(band));
+            let first = UVec3::ceil((triangle_bounds.min - bounds.min) / cell_size);
+            let last =
+                UVec3::floor((triangle_bounds.max - bounds.min) / cell_size).min(sample_count - 1);
+            for z in first.z..=last.z {
+                for y in first.y..=last.y {
+                    for x in first.x..=last.x {
+                        let sample = UVec3::new(x, y, z);
+                        let point = position(sample);
+                        let closest = closest_point_on_triangle(v0 - point, v1 - point, v2 - point);
+                        let distance = &mut distances[sample.flatten(sample_count)];
+                        *distance = distance.min(closest.point.length());
+                    }
+                }
+            }
+        });
+
+        // Samples inside of the mesh get a negative distance. The samples are nudged off the
+        // grid so that the parity test does not run along the edges of axis aligned meshes.
+        let nudge = Vec3::new(1.3e-4, 0.0, 0.7e-4) * cell_size;
+        for z in 0..sample_count.z {
+            for y in 0..sample_count.y {
+                for x in 0..sample_count.x {
+                    let sample = UVec3::new(x, y, z);
+                    if mesh.collide_point(position(sample) + nudge) {
+                        distances[sample.flatten(sample_count)] *= -1.0;
+                    }
+                }
+            }
+        }
+
+        Self::new(&SdfSettings {
+            distances: &distances,
+            sample_count,
+            offset: bounds.min,
+            cell_size,
+            narrow_band: band,
+            density: DEFAULT_DENSITY,
+        })
+    }
+
+    /// Returns the shape with its mass properties based on `density`.
+    #[inline]
+    pub fn with_density(mut self, density: f32) -> Result<Self, ShapeError> {
+        self.density = super::validate_density(density)?;
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn sample_count(&self) -> UVec3 {
+        self.sample_count
+    }
+
+    #[inline]
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Returns the number of bricks that store all of their samples.
+    #[inline]
+    pub fn dense_brick_count(&self) -> usize {
+        self.samples.len() / BRICK_SIZE.pow(3) as usize
+    }
+
+    /// Returns the distance stored for `sample`, samples in sparse bricks return the distance
+    /// of the brick.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the sample is out of bounds.
+    #[inline]
+    pub fn get_distance(&self, sample: UVec3) -> f32 {
+        assert!(sample.all_lt(self.sample_count));
+        let brick = (sample / BRICK_SIZE).flatten(self.bricks_per_side);
+        match self.bricks[brick] {
+            Brick::Uniform(distance) => distance,
+            Brick::Dense(start) => {
+                let offset = (sample % BRICK_SIZE).flatten(UVec3::splat(BRICK_SIZE));
+                self.samples[start as usize + offset]
+            }
+        }
+    }
+
+    /// Returns the interpolated signed distance from `point` to the surface. Outside of the
+    /// grid this is the distance to the grid added to the distance at the closest point of the
+    /// grid.
+    pub fn distance(&self, point: Vec3) -> f32 {
+        let bounds = self.local_bounds();
+        let clamped = bounds.closest_point(point);
+        self.interpolate(clamped) + (point - clamped).length()
+    }
+
+    /// Returns the gradient of the signed distance at `point`, which has a length of about one
+    /// near the surface.
+    pub fn gradient(&self, point: Vec3) -> Vec3 {
+        let step = 0.5 * self.cell_size;
+        let derivative =
+            |axis: Vec3| self.distance(point + axis * step) - self.distance(point - axis * step);
+        Vec3::new(
+            derivative(Vec3::X),
+            derivative(Vec3::Y),
+            derivative(Vec3::Z),
+        ) / (2.0 * step)
+    }
+
+    /// Collides a convex shape against the field, `rotation` and `translation` place the center
+    /// of mass of `shape` in the space of the field.
+    ///
+    /// The support points of the shape are sampled in a number of directions and then slid
+    /// against the normal of the field to find the deepest points. `callback` is called for
+    /// every distinct point that is within `max_separation_distance` of the surface.
+    pub fn collide_convex(
+        &self,
+        shape: &dyn ConvexShape,
+        rotation: Quat,
+        translation: Vec3,
+        max_separation_distance: f32,
+        mut callback: impl FnMut(&SdfContact),
+    ) {
+        let bounds = shape.local_bounds().transformed(rotation, translation);
+        let support = shape.get_support_function(SupportMode::IncludeConvexRadius, Vec3::ONE);
+        let support = TransformedConvexObject::new(&*support, rotation, translation);
+        self.collide_support(&support, &bounds, max_separation_distance, &mut callback);
+    }
+
+    /// Collides a convex object with bounds `bounds`, both in the space of the field, against
+    /// the field. See [`SdfShape::collide_convex`].
+    pub(crate) fn collide_support(
+        &self,
+        support: &dyn Support,
+        bounds: &AABox,
+        max_separation_distance: f32,
+        callback: &mut dyn FnMut(&SdfContact),
+    ) {
+        let bounds = bounds.expanded_by(Vec3::splat(max_separation_distance));
+        if !bounds.overlaps(&self.local_bounds()) {
+            return;
+        }
+
+        let refine_tolerance = CONTACT_REFINE_TOLERANCE * self.cell_size;
+        let merge_distance_squared =
+            CONTACT_MERGE_DISTANCE * CONTACT_MERGE_DISTANCE * self.cell_size * self.cell_size;
+        let mut reported: Vec<Vec3> = Vec::new();
+        for direction in Self::sample_directions() {
+            let mut point = support.support(direction);
+            for _ in 0..CONTACT_REFINE_STEPS {
+                let normal = self.gradient(point).normalize_or_zero();
+                if normal == Vec3::ZERO {
+                    break;
+                }
+                let next = support.support(-normal);
+                if next.distance_squared(point) <= merge_distance_squared
+                    || self.distance(next) > self.distance(point) - refine_tolerance
+                {
+                    break;
+                }
+                point = next;
+            }
+
+            let distance = self.distance(point);
+            if distance > max_separation_distance
+                || reported
+                    .iter()
+                    .any(|other| other.distance_squared(point) <= merge_distance_squared)
+            {
+                continue;
+            }
+            reported.push(point);
+            let normal = self.gradient(point).normalize_or_zero();
+            callback(&SdfContact {
+                point_a: point,
+                point_b: point - normal * distance,
+                normal,
+                distance,
+            });
+        }
+    }
+
+    /// The directions in which the support points of a convex shape are sampled, towards the
+    /// faces, edges and corners of a cube.
+    fn sample_directions() -> impl Iterator<Item = Vec3> {
+        (0..27)
+            .filter(|&i| i != 13)
+            .map(|i| Vec3::new((i % 3) as f32, (i / 3 % 3) as f32, (i / 9) as f32) - Vec3::ONE)
+    }
+
+    /// Trilinearly interpolates the samples at `point`, which must lie within the grid.
+    fn interpolate(&self, point: Vec3) -> f32 {
+        let grid = (point - self.offset) / self.cell_size;
+        let cell = UVec3::floor(grid).min(self.sample_count - 2);
+        let t = grid - cell.as_vec3();
+
+        // Blend the four edges along Z at once, then the remaining square.
+        let corners = |z| {
+            let corner = |x, y| self.get_distance(cell + UVec3::new(x, y, z));
+            Vec4::new(corner(0, 0), corner(1, 0), corner(0, 1), corner(1, 1))
+        };
+        let lower = corners(0);
+        let edges = lower + (corners(1) - lower) * t.z;
+        let [d00, d10, d01, d11] = edges.to_array();
+        let d0 = d00 + (d10 - d00) * t.x;
+        let d1 = d01 + (d11 - d01) * t.x;
+        d0 + (d1 - d0) * t.y
+    }
+
+    /// Splits the distances into bricks, bricks that do not come within `narrow_band` of the
+    /// surface only keep their smallest distance.
+    fn build_bricks(&mut self, distances: &[f32], narrow_band: f32) {
+        let sample_count = self.sample_count;
+        let brick_samples = |brick: UVec3| {
+            (0..BRICK_SIZE.pow(3)).map(move |i| {
+                let offset = UVec3::new(i, i / BRICK_SIZE, i / (BRICK_SIZE * BRICK_SIZE));
+                // Samples beyond the grid repeat the last sample.
+                let sample = (brick * BRICK_SIZE + offset % BRICK_SIZE).min(sample_count - 1);
+                distances[sample.flatten(sample_count)]
+            })
+        };
+
+        let bricks_per_side = self.bricks_per_side;
+        for brick_z in 0..bricks_per_side.z {
+            for brick_y in 0..bricks_per_side.y {
+                for brick_x in 0..bricks_per_side.x {
+                    let samples = brick_samples(UVec3::new(brick_x, brick_y, brick_z));
+                    let (mut min, mut max) = (f32::MAX, f32::MIN);
+                    for distance in samples.clone() {
+                        min = min.min(distance);
+                        max = max.max(distance);
+                    }
+                    let brick = if min >= narrow_band {
+                        Brick::Uniform(min)
+                    } else if max <= -narrow_band {
+                        Brick::Uniform(max)
+                    } else {
+                        let start = self.samples.len() as u32;
+                        self.samples.extend(samples);
+                        Brick::Dense(start)
+                    };
+                    self.bricks.push(brick);
+                }
+            }
+        }
+    }
+
+    /// Integrates the cells of the grid to find the volume, center of mass and inertia, then
+    /// moves the grid so that its center of mass is at the origin.
+    fn update_mass_properties(&mut self) {
+        let cell_volume = self.cell_size * self.cell_size * self.cell_size;
+        let cell_count = self.sample_count - 1;
+        let mut volume = 0.0;
+        let mut first_moment = Vec3::ZERO;
+        let mut inertia = Mat33::ZERO;
+        for z in 0..cell_count.z {
+            for y in 0..cell_count.y {
+                for x in 0..cell_count.x {
+                    let cell = UVec3::new(x, y, z);
+                    let center = self.offset + (cell.as_vec3() + 0.5) * self.cell_size;
+                    // Treat the surface as a plane through the cell to estimate how much of it
+                    // is inside.
+                    let fraction =
+                        (0.5 - self.interpolate(center) / self.cell_size).clamp(0.0, 1.0);
+                    if fraction <= 0.0 {
+                        continue;
+                    }
+                    let mass = fraction * cell_volume;
+                    volume += mass;
+                    first_moment += center * mass;
+                    inertia += (Mat33::from_diagonal(Vec3::splat(center.length_squared()))
+                        - Mat33::from_outer_product(center, center))
+                        * mass
+                        + Mat33::from_diagonal(Vec3::splat(self.cell_size * self.cell_size / 6.0))
+                            * mass;
+                }
+            }
+        }
+        if volume <= 0.0 {
+            return;
+        }
+
+        // Move the inertia from the origin to the center of mass.
+        let center_of_mass = first_moment / volume;
+        inertia -= (Mat33::from_diagonal(Vec3::splat(center_of_mass.length_squared()))
+            - Mat33::from_outer_product(center_of_mass, center_of_mass))
+            * volume;
+        self.offset -= center_of_mass;
+        self.center_of_mass = center_of_mass;
+        self.volume = volume;
+        self.unit_inertia = inertia;
+    }
+}
+
+impl Shape for SdfShape {
+    fn sub_type(&self) -> ShapeSubType {
+        ShapeSubType::Sdf
+    }
+
+    fn center_of_mass(&self) -> Vec3 {
+        self.center_of_mass
+    }
+
+    fn local_bounds(&self) -> AABox {
+        let size = (self.sample_count - 1).as_vec3() * self.cell_size;
+        AABox::new(self.offset, self.offset + size)
+    }
+
+    fn inner_radius(&self) -> f32 {
+        (-self.distance(Vec3::ZERO)).max(0.0)
+    }
+
+    fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    fn mass_properties(&self) -> MassProperties {
+        MassProperties::new(self.volume * self.density, self.unit_inertia * self.density)
+    }
+
+    fn is_valid_scale(&self, scale: Vec3) -> bool {
+        // Distances only remain distances under uniform scale.
+        scale_helpers::is_valid_scale(scale) && scale_helpers::is_uniform_scale(scale)
+    }
+
+    fn surface_normal(&self, _sub_shape_id: SubShapeID, local_position: Vec3) -> Vec3 {
+        self.gradient(local_position).normalize_or_zero()
+    }
+
+    fn cast_ray(&self, ray: &RayCast, hit: &mut RayCastResult) -> bool {
+        let bounds = self.local_bounds();
+        let length = ray.direction.length();
+        let mut fraction = ray_aabox(ray.origin, ray.direction, bounds.min, bounds.max);
+        let max_fraction = hit.fraction.min(1.0);
+        if length <= 0.0 || fraction > max_fraction {
+            return false;
+        }
+
+        let tolerance = RAY_HIT_TOLERANCE * self.cell_size;
+        let mut previous = None;
+        for _ in 0..MAX_RAY_STEPS {
+            let distance = self.distance(ray.point_on_ray(fraction));
+            if distance <= tolerance {
+                // The step went through the surface, search back for the crossing.
+                if let (true, Some(mut outside)) = (distance < 0.0, previous) {
+                    let mut inside = fraction;
+                    for _ in 0..RAY_REFINE_STEPS {
+                        let middle = 0.5 * (outside + inside);
+                        if self.distance(ray.point_on_ray(middle)) < 0.0 {
+                            inside = middle;
+                        } else {
+                            outside = middle;
+                        }
+                    }
+                    fraction = outside;
+                }
+                if fraction >= hit.fraction {
+                    return false;
+                }
+                hit.fraction = fraction;
+                hit.sub_shape_id = SubShapeID::EMPTY;
+                return true;
+            }
+            previous = Some(fraction);
+            fraction += distance.max(tolerance) / length;
+            if fraction > max_fraction {
+                return false;
+            }
+        }
+        false
+    }
+
+    fn collide_point(&self, point: Vec3) -> bool {
+        self.local_bounds().contains_point(point) && self.interpolate(point) <= 0.0
+    }
+}
@@ -0,0 +1,252 @@
+use core::{any::Any, fmt};
+
+use crate::{
+    body::MassProperties,
+    collision::{CollidePointResult, CollisionCollector, RayCast, RayCastResult},
+    geometry::AABox,
+    geometry::ConvexHullError,
+    math::{Quat, Vec3},
+    shape::{scale_helpers, ConvexShape, SubShapeID, SubShapeIDCreator},
+};
+
+/// The category a shape belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ShapeType {
+    Convex,
+    Compound,
+    Decorated,
+    Mesh,
+    HeightField,
+    Sdf,
+    User,
+}
+
+/// The concrete type of a shape.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShapeSubType {
+    Sphere,
+    Box,
+    Capsule,
+    TaperedCapsule,
+    Cylinder,
+    ConvexHull,
+    StaticCompound,
+    MutableCompound,
+    Scaled,
+    RotatedTranslated,
+    OffsetCenterOfMass,
+    Mesh,
+    HeightField,
+    Sdf,
+    /// A shape defined by the application, its collision functions must be registered with
+    /// [`CollisionDispatch`](crate::collision::CollisionDispatch).
+    User(u8),
+    /// A convex shape defined by the application, it can use the built in convex collision
+    /// functions when registered with
+    /// [`CollisionDispatch::register_convex_sub_type`](crate::collision::CollisionDispatch::register_convex_sub_type).
+    UserConvex(u8),
+}
+
+impl ShapeSubType {
+    /// Returns the category of this shape type.
+    pub const fn shape_type(self) -> ShapeType {
+        match self {
+            Self::Sphere
+            | Self::Box
+            | Self::Capsule
+            | Self::TaperedCapsule
+            | Self::Cylinder
+            | Self::ConvexHull
+            | Self::UserConvex(_) => ShapeType::Convex,
+            Self::StaticCompound | Self::MutableCompound => ShapeType::Compound,
+            Self::Scaled | Self::RotatedTranslated | Self::OffsetCenterOfMass => {
+                ShapeType::Decorated
+            }
+            Self::Mesh => ShapeType::Mesh,
+            Self::HeightField => ShapeType::HeightField,
+            Self::Sdf => ShapeType::Sdf,
+            Self::User(_) => ShapeType::User,
+        }
+    }
+}
+
+/// Errors that can occur when creating a shape.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ShapeError {
+    /// A radius was negative, zero or not finite.
+    InvalidRadius,
+    /// A height was negative or not finite.
+    InvalidHeight,
+    /// A half extent was negative, zero or not finite.
+    InvalidHalfExtent,
+    /// The convex radius was negative or larger than the shape allows.
+    InvalidConvexRadius,
+    /// The density was negative, zero or not finite.
+    InvalidDensity,
+    /// The convex hull could not be built.
+    ConvexHull(ConvexHullError),
+    /// The convex hull has more than [`MAX_POINTS_IN_HULL`](super::MAX_POINTS_IN_HULL) points.
+    TooManyPoints,
+    /// A triangle refers to a vertex that does not exist.
+    InvalidTriangleIndex,
+    /// The mesh has no triangles that are not degenerate.
+    EmptyMesh,
+    /// The mesh has more triangles than can be addressed.
+    TooManyTriangles,
+    /// The number of height field samples does not match its size.
+    InvalidSampleCount,
+    /// The height field block size is not 2, 4 or 8.
+    InvalidBlockSize,
+    /// The number of bits per height field sample is not between 2 and 8.
+    InvalidBitsPerSample,
+    /// A scale was negative, zero or not finite.
+    InvalidScale,
+    /// The cell size of a signed distance field was negative, zero, not finite or too small
+    /// for the baked grid to fit in memory.
+    InvalidCellSize,
+    /// The compound has no sub shapes.
+    EmptyCompound,
+    /// The sub shape IDs of the compound and its children need more than
+    /// [`SubShapeID::MAX_BITS`] bits.
+    SubShapeIDOverflow,
+    /// Saved shape data is corrupt or of an unsupported version.
+    InvalidData,
+}
+
+#[cfg(not(spirv))]
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRadius => write!(f, "invalid radius"),
+            Self::InvalidHeight => write!(f, "invalid height"),
+            Self::InvalidHalfExtent => write!(f, "invalid half extent"),
+            Self::InvalidConvexRadius => write!(f, "invalid convex radius"),
+            Self::InvalidDensity => write!(f, "invalid density"),
+            Self::ConvexHull(error) => write!(f, "invalid convex hull: {error}"),
+            Self::TooManyPoints => write!(f, "too many points in convex hull"),
+            Self::InvalidTriangleIndex => write!(f, "invalid triangle index"),
+            Self::EmptyMesh => write!(f, "mesh has no valid triangles"),
+            Self::TooManyTriangles => write!(f, "too many triangles in mesh"),
+            Self::InvalidSampleCount => write!(f, "invalid sample count"),
+            Self::InvalidBlockSize => write!(f, "invalid block size"),
+            Self::InvalidBitsPerSample => write!(f, "invalid bits per sample"),
+            Self::InvalidScale => write!(f, "invalid scale"),
+            Self::InvalidCellSize => write!(f, "invalid cell size"),
+            Self::EmptyCompound => write!(f, "compound has no sub shapes"),
+            Self::SubShapeIDOverflow => write!(f, "too many sub shapes to identify"),
+            Self::InvalidData => write!(f, "invalid shape data"),
+        }
+    }
+}
+
+/// The interface shared by all collision shapes.
+///
+/// All queries are performed in the local space of the shape, relative to its center of mass.
+pub trait Shape: Any + fmt::Debug + Send + Sync {
+    /// Returns the concrete type of the shape.
+    fn sub_type(&self) -> ShapeSubType;
+
+    /// Returns the category of the shape.
+    fn shape_type(&self) -> ShapeType {
+        self.sub_type().shape_type()
+    }
+
+    /// Returns the center of mass relative to the origin the shape was defined in.
+    fn center_of_mass(&self) -> Vec3 {
+        Vec3::ZERO
+    }
+
+    /// Returns the bounding box of the shape.
+    fn local_bounds(&self) -> AABox;
+
+    /// Returns the bounding box of the shape after it has been scaled, rotated and translated.
+    fn world_bounds(&self, rotation: Quat, translation: Vec3, scale: Vec3) -> AABox {
+        self.local_bounds()
+            .scaled(scale)
+            .transformed(rotation, translation)
+    }
+
+    /// Returns the radius of the largest sphere around the center of mass that fits inside of
+    /// the shape.
+    fn inner_radius(&self) -> f32;
+
+    /// Returns the volume of the shape.
+    fn volume(&self) -> f32;
+
+    /// Returns the mass and inertia of the shape.
+    fn mass_properties(&self) -> MassProperties;
+
+    /// Returns whether the shape can be scaled by `scale`. Scales are never allowed to be zero
+    /// on any axis, some shapes can only be scaled uniformly.
+    fn is_valid_scale(&self, scale: Vec3) -> bool {
+        scale_helpers::is_valid_scale(scale)
+    }
+
+    /// Returns the number of bits of a [`SubShapeID`] that are used to identify the sub shapes
+    /// of this shape and its children.
+    fn sub_shape_id_bits(&self) -> u32 {
+        0
+    }
+
+    /// Returns the material index of the sub shape.
+    fn material_index(&self, _sub_shape_id: SubShapeID) -> u32 {
+        0
+    }
+
+    /// Returns the outward facing surface normal of the sub shape at `local_position`.
+    fn surface_normal(&self, sub_shape_id: SubShapeID, local_position: Vec3) -> Vec3;
+
+    /// Casts a ray against the shape, returns true and updates `hit` when a hit is found that
+    /// is closer than `hit.fraction`. Rays starting inside the shape hit at fraction zero.
+    fn cast_ray(&self, ray: &RayCast, hit: &mut RayCastResult) -> bool;
+
+    /// Returns whether `point` lies inside the shape.
+    fn collide_point(&self, point: Vec3) -> bool;
+
+    /// Casts a ray against the shape and adds the hits to `collector`, only hits closer than
+    /// the early out fraction of the collector are reported. `creator` identifies the shape
+    /// within the shape that is being queried. By default only the closest hit is reported.
+    fn cast_ray_with_collector(
+        &self,
+        ray: &RayCast,
+        creator: SubShapeIDCreator,
+        collector: &mut dyn CollisionCollector<RayCastResult>,
+    ) {
+        let mut hit = RayCastResult {
+            fraction: collector.early_out_fraction(),
+            sub_shape_id: SubShapeID::EMPTY,
+        };
+        if self.cast_ray(ray, &mut hit) {
+            hit.sub_shape_id = creator.push_sub_shape_id(hit.sub_shape_id);
+            collector.add_hit(&hit);
+        }
+    }
+
+    /// Adds every sub shape that contains `point` to `collector`, see
+    /// [`Shape::cast_ray_with_collector`].
+    fn collide_point_with_collector(
+        &self,
+        point: Vec3,
+        creator: SubShapeIDCreator,
+        collector: &mut dyn CollisionCollector<CollidePointResult>,
+    ) {
+        if self.collide_point(point) {
+            collector.add_hit(&CollidePointResult {
+                sub_shape_id: creator.id(),
+            });
+        }
+    }
+
+    /// Returns the shape as a convex shape, if it is one.
+    fn as_convex(&self) -> Option<&dyn ConvexShape> {
+        None
+    }
+}
+
+impl dyn Shape {
+    /// Returns the shape as its concrete type `T`, if it is one.
+    #[inline]
+    pub fn downcast_ref<T: Shape>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref()
+    }
+}
@@ -0,0 +1,144 @@
+use alloc::boxed::Box;
+use core::f32::consts::PI;
+
+use crate::{
+    body::MassProperties,
+    collision::{RayCast, RayCastResult},
+    geometry::{ray_sphere, AABox, Support},
+    math::{Mat33, Vec3},
+    shape::{
+        scale_helpers, ConvexShape, Shape, ShapeError, ShapeSubType, SubShapeID, SupportMode,
+        DEFAULT_DENSITY,
+    },
+};
+
+/// A sphere centered around the origin.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SphereShape {
+    radius: f32,
+    density: f32,
+}
+
+impl SphereShape {
+    /// Creates a sphere with the given `radius`.
+    pub fn new(radius: f32) -> Result<Self, ShapeError> {
+        if !(radius > 0.0 && radius.is_finite()) {
+            return Err(ShapeError::InvalidRadius);
+        }
+        Ok(Self {
+            radius,
+            density: DEFAULT_DENSITY,
+        })
+    }
+
+    /// Sets the density of the shape in kg / m^3.
+    pub fn with_density(mut self, density: f32) -> Result<Self, ShapeError> {
+        self.density = super::validate_density(density)?;
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+}
+
+/// A sphere reduced to a point, the radius is reported as convex radius.
+struct SphereNoConvex {
+    radius: f32,
+}
+
+impl Support for SphereNoConvex {
+    #[inline]
+    fn support(&self, _direction: Vec3) -> Vec3 {
+        Vec3::ZERO
+    }
+
+    #[inline]
+    fn convex_radius(&self) -> f32 {
+        self.radius
+    }
+}
+
+/// A sphere that includes its radius in the support points.
+struct SphereWithConvex {
+    radius: f32,
+}
+
+impl Support for SphereWithConvex {
+    #[inline]
+    fn support(&self, direction: Vec3) -> Vec3 {
+        direction.normalize_or_zero() * self.radius
+    }
+}
+
+impl Shape for SphereShape {
+    fn sub_type(&self) -> ShapeSubType {
+        ShapeSubType::Sphere
+    }
+
+    fn local_bounds(&self) -> AABox {
+        AABox::from_center_and_half_extent(Vec3::ZERO, Vec3::splat(self.radius))
+    }
+
+    fn inner_radius(&self) -> f32 {
+        self.radius
+    }
+
+    fn volume(&self) -> f32 {
+        4.0 / 3.0 * PI * self.radius * self.radius * self.radius
+    }
+
+    fn mass_properties(&self) -> MassProperties {
+        let mass = self.volume() * self.density;
+        let inertia = 0.4 * mass * self.radius * self.radius;
+        MassProperties::new(mass, Mat33::from_diagonal(Vec3::splat(inertia)))
+    }
+
+    fn is_valid_scale(&self, scale: Vec3) -> bool {
+        scale_helpers::is_valid_scale(scale) && scale_helpers::is_uniform_scale(scale)
+    }
+
+    fn surface_normal(&self, _sub_shape_id: SubShapeID, local_position: Vec3) -> Vec3 {
+        let normal = local_position.normalize_or_zero();
+        if normal == Vec3::ZERO {
+            Vec3::Y
+        } else {
+            normal
+        }
+    }
+
+    fn cast_ray(&self, ray: &RayCast, hit: &mut RayCastResult) -> bool {
+        let fraction = ray_sphere(ray.origin, ray.direction, Vec3::ZERO, self.radius);
+        if fraction < hit.fraction {
+            hit.fraction = fraction;
+            hit.sub_shape_id = SubShapeID::EMPTY;
+            return true;
+        }
+        false
+    }
+
+    fn collide_point(&self, point: Vec3) -> bool {
+        point.length_squared() <= self.radius * self.radius
+    }
+
+    fn as_convex(&self) -> Option<&dyn ConvexShape> {
+        Some(self)
+    }
+}
+
+impl ConvexShape for SphereShape {
+    fn density(&self) -> f32 {
+        self.density
+    }
+
+    fn get_support_function(&self, mode: SupportMode, scale: Vec3) -> Box<dyn Support + '_> {
+        let radius = self.radius * scale.x.abs();
+        match mode {
+            SupportMode::IncludeConvexRadius => Box::new(SphereWithConvex { radius }),
+            SupportMode::ExcludeConvexRadius | SupportMode::Default => {
+                Box::new(SphereNoConvex { radius })
+            }
+        }
+    }
+}
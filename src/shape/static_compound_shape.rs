@@ -0,0 +1,317 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    body::MassProperties,
+    collision::{CollidePointResult, CollisionCollector, RayCast, RayCastResult},
+    geometry::{ray_aabox4, AABox, AABox4, RayInvDirection},
+    math::Vec3,
+    shape::{
+        compound_shape, sub_shape_id_bits_for, CompoundSubShape, Shape, ShapeError, ShapeSubType,
+        SubShapeID, SubShapeIDCreator,
+    },
+};
+
+/// Marks a child of a node as a leaf, the remaining bits hold the index of the sub shape.
+const LEAF_BIT: u32 = 1 << 31;
+
+/// Marks an unused child of a node.
+const EMPTY_CHILD: u32 = u32::MAX;
+
+/// A node of the tree with four children.
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    bounds: AABox4,
+    children: [u32; 4],
+}
+
+/// A compound of shapes that cannot be changed after it has been created.
+///
+/// The children are stored in an axis aligned bounding box tree with four children per node.
+/// The sub shape ID of a hit consists of the index of the child followed by the sub shape ID
+/// within the child.
+#[derive(Clone, Debug)]
+pub struct StaticCompoundShape {
+    sub_shapes: Vec<CompoundSubShape>,
+    /// The nodes of the tree, the root is the first node.
+    nodes: Vec<Node>,
+    center_of_mass: Vec3,
+    local_bounds: AABox,
+    inner_radius: f32,
+    volume: f32,
+    mass_properties: MassProperties,
+    sub_shape_id_bits: u32,
+}
+
+impl StaticCompoundShape {
+    /// Creates a compound from its children, the positions of which are the positions of their
+    /// origins.
+    pub fn new(sub_shapes: &[CompoundSubShape]) -> Result<Self, ShapeError> {
+        if sub_shapes.is_empty() {
+            return Err(ShapeError::EmptyCompound);
+        }
+        if sub_shapes.len() >= LEAF_BIT as usize {
+            return Err(ShapeError::SubShapeIDOverflow);
+        }
+        let sub_shape_id_bits = compound_shape::sub_shape_id_bits(sub_shapes)?;
+        let mut sub_shapes = sub_shapes.to_vec();
+        let center_of_mass = compound_shape::center_sub_shapes(&mut sub_shapes);
+
+        let bounds: Vec<AABox> = sub_shapes.iter().map(CompoundSubShape::bounds).collect();
+        let mut order: Vec<u32> = (0..sub_shapes.len() as u32).collect();
+        let mut nodes = Vec::new();
+        Self::build(&bounds, &mut order, &mut nodes);
+
+        Ok(Self {
+            nodes,
+            center_of_mass,
+            local_bounds: compound_shape::local_bounds(&sub_shapes),
+            inner_radius: compound_shape::inner_radius(&sub_shapes),
+            volume: compound_shape::volume(&sub_shapes),
+            mass_properties: compound_shape::mass_properties(&sub_shapes),
+            sub_shape_id_bits,
+            sub_shapes,
+        })
+    }
+
+    /// Returns the children, positioned relative to the center of mass of the compound.
+    #[inline]
+    pub fn sub_shapes(&self) -> &[CompoundSubShape] {
+        &self.sub_shapes
+    }
+
+    /// Splits `sub_shape_id` into the index of the child and the sub shape ID within the child.
+    #[inline]
+    pub fn sub_shape_index(&self, sub_shape_id: SubShapeID) -> (u32, SubShapeID) {
+        sub_shape_id.pop_id(self.index_bits())
+    }
+
+    /// Calls `callback` with the index of every child whose bounds overlap `bounds`.
+    pub fn collide_sub_shapes(&self, bounds: &AABox, mut callback: impl FnMut(u32)) {
+        let mut stack = vec![0];
+        while let Some(code) = stack.pop() {
+            if code & LEAF_BIT != 0 {
+                callback(code & !LEAF_BIT);
+                continue;
+            }
+            let node = &self.nodes[code as usize];
+            let overlaps = node.bounds.overlaps(bounds);
+            for (&child, overlaps) in node.children.iter().zip(overlaps) {
+                if child != EMPTY_CHILD && overlaps {
+                    stack.push(child);
+                }
+            }
+        }
+    }
+
+    /// The number of bits used for the index of a child.
+    #[inline]
+    fn index_bits(&self) -> u32 {
+        sub_shape_id_bits_for(self.sub_shapes.len() as u32)
+    }
+
+    #[inline]
+    fn creator(&self, index: u32) -> SubShapeIDCreator {
+        SubShapeIDCreator::new().push_id(index, self.index_bits())
+    }
+
+    /// Builds the subtree for the sub shapes in `order` and returns the index of its node. The
+    /// root is always a node, even if it only has a single child.
+    fn build(bounds: &[AABox], order: &mut [u32], nodes: &mut Vec<Node>) -> u32 {
+        let index = nodes.len();
+        nodes.push(Node {
+            bounds: AABox4::INVALID,
+            children: [EMPTY_CHILD; 4],
+        });
+
+        // Split into at most four groups by repeatedly halving the largest one.
+        let mut partitions = vec![(0, order.len())];
+        while partitions.len() < 4 {
+            let (i, &(start, end)) = partitions
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, (start, end))| end - start)
+                .unwrap();
+            if end - start <= 1 {
+                break;
+            }
+            let middle = Self::split(bounds, &mut order[start..end]) + start;
+            partitions[i] = (start, middle);
+            partitions.insert(i + 1, (middle, end));
+        }
+
+        for (i, &(start, end)) in partitions.iter().enumerate() {
+            let group = &mut order[start..end];
+            let mut group_bounds = AABox::INVALID;
+            for &sub_shape in group.iter() {
+                group_bounds.encapsulate_box(&bounds[sub_shape as usize]);
+            }
+            let child = if group.len() == 1 {
+                LEAF_BIT | group[0]
+            } else {
+                Self::build(bounds, group, nodes)
+            };
+            nodes[index].bounds.set(i, &group_bounds);
+            nodes[index].children[i] = child;
+        }
+        index as u32
+    }
+
+    /// Reorders `order` around the median center on the longest axis and returns the index of
+    /// the median.
+    fn split(bounds: &[AABox], order: &mut [u32]) -> usize {
+        let centers = AABox::from_points(order.iter().map(|&i| bounds[i as usize].center()));
+        let size = centers.size();
+        let axis = if size.x >= size.y && size.x >= size.z {
+            0
+        } else if size.y >= size.z {
+            1
+        } else {
+            2
+        };
+        let middle = order.len() / 2;
+        order.select_nth_unstable_by(middle, |&a, &b| {
+            let a = bounds[a as usize].center()[axis];
+            let b = bounds[b as usize].center()[axis];
+            a.total_cmp(&b)
+        });
+        middle
+    }
+}
+
+impl Shape for StaticCompoundShape {
+    fn sub_type(&self) -> ShapeSubType {
+        ShapeSubType::StaticCompound
+    }
+
+    fn center_of_mass(&self) -> Vec3 {
+        self.center_of_mass
+    }
+
+    fn local_bounds(&self) -> AABox {
+        self.local_bounds
+    }
+
+    fn inner_radius(&self) -> f32 {
+        self.inner_radius
+    }
+
+    fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    fn mass_properties(&self) -> MassProperties {
+        self.mass_properties
+    }
+
+    fn is_valid_scale(&self, scale: Vec3) -> bool {
+        compound_shape::is_valid_scale(&self.sub_shapes, scale)
+    }
+
+    fn sub_shape_id_bits(&self) -> u32 {
+        self.sub_shape_id_bits
+    }
+
+    fn material_index(&self, sub_shape_id: SubShapeID) -> u32 {
+        let (index, remainder) = self.sub_shape_index(sub_shape_id);
+        self.sub_shapes[index as usize]
+            .shape
+            .material_index(remainder)
+    }
+
+    fn surface_normal(&self, sub_shape_id: SubShapeID, local_position: Vec3) -> Vec3 {
+        let (index, remainder) = self.sub_shape_index(sub_shape_id);
+        self.sub_shapes[index as usize].surface_normal(remainder, local_position)
+    }
+
+    fn cast_ray(&self, ray: &RayCast, hit: &mut RayCastResult) -> bool {
+        // Visit the children front to back so that distant children can be skipped.
+        let inv_direction = RayInvDirection::new(ray.direction);
+        let mut result = false;
+        let mut stack = vec![(0, 0.0)];
+        while let Some((code, fraction)) = stack.pop() {
+            if fraction >= hit.fraction {
+                continue;
+            }
+            if code & LEAF_BIT != 0 {
+                let index = code & !LEAF_BIT;
+                result |= self.sub_shapes[index as usize].cast_ray(self.creator(index), ray, hit);
+                continue;
+            }
+
+            let node = &self.nodes[code as usize];
+            let fractions = ray_aabox4(ray.origin, &inv_direction, &node.bounds);
+            let mut children = [(0, 0.0); 4];
+            let mut count = 0;
+            for (i, &child) in node.children.iter().enumerate() {
+                if child != EMPTY_CHILD && fractions[i] < hit.fraction {
+                    children[count] = (child, fractions[i]);
+                    count += 1;
+                }
+            }
+            // Push the furthest child first so that the closest child is visited first.
+            children[..count].sort_unstable_by(|a, b| b.1.total_cmp(&a.1));
+            stack.extend_from_slice(&children[..count]);
+        }
+        result
+    }
+
+    fn cast_ray_with_collector(
+        &self,
+        ray: &RayCast,
+        creator: SubShapeIDCreator,
+        collector: &mut dyn CollisionCollector<RayCastResult>,
+    ) {
+        let inv_direction = RayInvDirection::new(ray.direction);
+        let mut stack = vec![(0, 0.0)];
+        while let Some((code, fraction)) = stack.pop() {
+            if collector.should_early_out() {
+                break;
+            }
+            if fraction >= collector.early_out_fraction() {
+                continue;
+            }
+            if code & LEAF_BIT != 0 {
+                let index = code & !LEAF_BIT;
+                let creator = creator.push_id(index, self.index_bits());
+                self.sub_shapes[index as usize].cast_ray_with_collector(creator, ray, collector);
+                continue;
+            }
+
+            let node = &self.nodes[code as usize];
+            let fractions = ray_aabox4(ray.origin, &inv_direction, &node.bounds);
+            let mut children = [(0, 0.0); 4];
+            let mut count = 0;
+            for (i, &child) in node.children.iter().enumerate() {
+                if child != EMPTY_CHILD && fractions[i] < collector.early_out_fraction() {
+                    children[count] = (child, fractions[i]);
+                    count += 1;
+                }
+            }
+            children[..count].sort_unstable_by(|a, b| b.1.total_cmp(&a.1));
+            stack.extend_from_slice(&children[..count]);
+        }
+    }
+
+    fn collide_point(&self, point: Vec3) -> bool {
+        let mut inside = false;
+        self.collide_sub_shapes(&AABox::new(point, point), |index| {
+            inside = inside || self.sub_shapes[index as usize].collide_point(point);
+        });
+        inside
+    }
+
+    fn collide_point_with_collector(
+        &self,
+        point: Vec3,
+        creator: SubShapeIDCreator,
+        collector: &mut dyn CollisionCollector<CollidePointResult>,
+    ) {
+        self.collide_sub_shapes(&AABox::new(point, point), |index| {
+            if !collector.should_early_out() {
+                let creator = creator.push_id(index, self.index_bits());
+                self.sub_shapes[index as usize]
+                    .collide_point_with_collector(creator, point, collector);
+            }
+        });
+    }
+}
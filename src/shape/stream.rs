@@ -0,0 +1,121 @@
+use alloc::vec::Vec;
+
+use crate::{math::Vec3, shape::ShapeError};
+
+/// Writes values in little endian byte order, used to save baked shapes.
+pub(crate) struct StreamOut<'a> {
+    data: &'a mut Vec<u8>,
+}
+
+impl<'a> StreamOut<'a> {
+    #[inline]
+    pub fn new(data: &'a mut Vec<u8>) -> Self {
+        Self { data }
+    }
+
+    #[inline]
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+    }
+
+    #[inline]
+    pub fn write_u16(&mut self, value: u16) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    #[inline]
+    pub fn write_u32(&mut self, value: u32) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    #[inline]
+    pub fn write_f32(&mut self, value: f32) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    #[inline]
+    pub fn write_vec3(&mut self, value: Vec3) {
+        self.write_f32(value.x);
+        self.write_f32(value.y);
+        self.write_f32(value.z);
+    }
+
+    /// Writes the length of a sequence, which is limited to `u32::MAX` elements.
+    #[inline]
+    pub fn write_len(&mut self, len: usize) {
+        self.write_u32(u32::try_from(len).expect("sequence too long to be saved"));
+    }
+}
+
+/// Reads values written by [`StreamOut`], every read fails with [`ShapeError::InvalidData`]
+/// when the data is exhausted.
+pub(crate) struct StreamIn<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> StreamIn<'a> {
+    #[inline]
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    #[inline]
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], ShapeError> {
+        if self.data.len() < len {
+            return Err(ShapeError::InvalidData);
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ShapeError> {
+        Ok(self.read_bytes(N)?.try_into().unwrap())
+    }
+
+    #[inline]
+    pub fn read_u16(&mut self) -> Result<u16, ShapeError> {
+        Ok(u16::from_le_bytes(self.read_array()?))
+    }
+
+    #[inline]
+    pub fn read_u32(&mut self) -> Result<u32, ShapeError> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    #[inline]
+    pub fn read_f32(&mut self) -> Result<f32, ShapeError> {
+        Ok(f32::from_le_bytes(self.read_array()?))
+    }
+
+    #[inline]
+    pub fn read_vec3(&mut self) -> Result<Vec3, ShapeError> {
+        Ok(Vec3::new(
+            self.read_f32()?,
+            self.read_f32()?,
+            self.read_f32()?,
+        ))
+    }
+
+    /// Reads the length of a sequence of elements that each take at least `element_size`
+    /// bytes, failing early when the data cannot possibly hold that many elements.
+    #[inline]
+    pub fn read_len(&mut self, element_size: usize) -> Result<usize, ShapeError> {
+        let len = self.read_u32()? as usize;
+        if len.saturating_mul(element_size) > self.data.len() {
+            return Err(ShapeError::InvalidData);
+        }
+        Ok(len)
+    }
+
+    /// Fails unless all data has been read.
+    #[inline]
+    pub fn finish(self) -> Result<(), ShapeError> {
+        if self.data.is_empty() {
+            Ok(())
+        } else {
+            Err(ShapeError::InvalidData)
+        }
+    }
+}
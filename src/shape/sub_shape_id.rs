@@ -0,0 +1,115 @@
+/// Identifies a part of a shape, such as a triangle of a mesh or a child of a compound.
+///
+/// Identifiers are hierarchical, every level of a shape consumes a number of bits starting at
+/// the least significant bit. The unused bits are set, so that a shape with no sub shapes has
+/// the [`SubShapeID::EMPTY`] identifier. Use [`SubShapeIDCreator`] to build an identifier and
+/// [`SubShapeID::pop_id`] to take it apart again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SubShapeID(u32);
+
+impl SubShapeID {
+    /// The number of bits available to identify sub shapes.
+    pub const MAX_BITS: u32 = u32::BITS;
+
+    /// The identifier of a shape that has no sub shapes.
+    pub const EMPTY: Self = Self(!0);
+
+    /// Creates an identifier from its raw value.
+    #[inline]
+    pub const fn from_raw(value: u32) -> Self {
+        Self(value)
+    }
+
+    /// Returns the raw value of the identifier.
+    #[inline]
+    pub const fn value(self) -> u32 {
+        self.0
+    }
+
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.0 == Self::EMPTY.0
+    }
+
+    /// Splits off the first `bits` bits of the identifier, returns their value and the
+    /// identifier of the remaining levels.
+    #[inline]
+    pub const fn pop_id(self, bits: u32) -> (u32, Self) {
+        let mask = ((1u64 << bits) - 1) as u32;
+        let fill = ((!0u64) << (Self::MAX_BITS - bits)) as u32;
+        let remainder = ((self.0 as u64) >> bits) as u32 | fill;
+        (self.0 & mask, Self(remainder))
+    }
+}
+
+impl Default for SubShapeID {
+    #[inline]
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+/// Builds a [`SubShapeID`] one level at a time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SubShapeIDCreator {
+    id: SubShapeID,
+    current_bit: u32,
+}
+
+impl SubShapeIDCreator {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            id: SubShapeID::EMPTY,
+            current_bit: 0,
+        }
+    }
+
+    /// Appends `value`, which takes `bits` bits, as the next level of the identifier.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the identifier runs out of bits.
+    #[inline]
+    pub const fn push_id(self, value: u32, bits: u32) -> Self {
+        assert!(self.current_bit + bits <= SubShapeID::MAX_BITS);
+        debug_assert!((value as u64) < (1u64 << bits));
+        let mask = (((1u64 << bits) - 1) << self.current_bit) as u32;
+        let value = ((value as u64) << self.current_bit) as u32;
+        Self {
+            id: SubShapeID((self.id.0 & !mask) | value),
+            current_bit: self.current_bit + bits,
+        }
+    }
+
+    /// Appends `sub_shape_id`, which was created relative to a child shape, as the remaining
+    /// levels of the identifier.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the levels of `sub_shape_id` do not fit in the bits that are left.
+    #[inline]
+    pub const fn push_sub_shape_id(self, sub_shape_id: SubShapeID) -> SubShapeID {
+        let (value, rest) = sub_shape_id.pop_id(SubShapeID::MAX_BITS - self.current_bit);
+        assert!(rest.is_empty());
+        self.push_id(value, SubShapeID::MAX_BITS - self.current_bit)
+            .id
+    }
+
+    #[inline]
+    pub const fn id(self) -> SubShapeID {
+        self.id
+    }
+
+    /// Returns the number of bits that have been written so far.
+    #[inline]
+    pub const fn num_bits_written(self) -> u32 {
+        self.current_bit
+    }
+}
+
+/// Returns the number of bits needed to identify one of `count` sub shapes.
+#[inline]
+pub(crate) const fn sub_shape_id_bits_for(count: u32) -> u32 {
+    u32::BITS - count.saturating_sub(1).leading_zeros()
+}
@@ -0,0 +1,254 @@
+use alloc::boxed::Box;
+use core::f32::consts::PI;
+
+#[cfg(all(no_std, libm, not(test)))]
+use crate::math::libm::F32Ext;
+use crate::{
+    body::MassProperties,
+    collision::{RayCast, RayCastResult},
+    geometry::{AABox, Support},
+    math::{Mat33, Vec3},
+    shape::{
+        cast_ray_convex, collide_point_convex, scale_helpers, ConvexShape, Shape, ShapeError,
+        ShapeSubType, SubShapeID, SupportMode, DEFAULT_DENSITY,
+    },
+};
+
+/// A capsule with a different radius at the top and the bottom, which is the convex hull of two
+/// spheres on the Y axis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TaperedCapsuleShape {
+    /// The Y coordinate of the top sphere relative to the center of mass.
+    top_center: f32,
+    /// The Y coordinate of the bottom sphere relative to the center of mass.
+    bottom_center: f32,
+    top_radius: f32,
+    bottom_radius: f32,
+    /// Sine of the angle between the side of the capsule and the Y axis.
+    sin_alpha: f32,
+    center_of_mass: f32,
+    volume: f32,
+    /// The inertia around the X and Y axis for a density of one.
+    unit_inertia: (f32, f32),
+    density: f32,
+}
+
+impl TaperedCapsuleShape {
+    /// Creates a tapered capsule, the sphere centers are `half_height` above and below the
+    /// origin. The spheres may not contain each other.
+    pub fn new(half_height: f32, top_radius: f32, bottom_radius: f32) -> Result<Self, ShapeError> {
+        if !(half_height > 0.0 && half_height.is_finite()) {
+            return Err(ShapeError::InvalidHeight);
+        }
+        if !(top_radius > 0.0
+            && bottom_radius > 0.0
+            && top_radius.is_finite()
+            && bottom_radius.is_finite())
+            || (top_radius - bottom_radius).abs() >= 2.0 * half_height
+        {
+            return Err(ShapeError::InvalidRadius);
+        }
+
+        let (h, rt, rb) = (half_height, top_radius, bottom_radius);
+        let sin_alpha = (rb - rt) / (2.0 * h);
+        let cos_alpha = (1.0 - sin_alpha * sin_alpha).sqrt();
+
+        // The shape is a solid of revolution made up of a spherical cap, a cone frustum and
+        // another spherical cap. The integrands are polynomials of at most degree 4, which a
+        // three point Gauss-Legendre quadrature integrates exactly.
+        let y = [-h - rb, -h + rb * sin_alpha, h + rt * sin_alpha, h + rt];
+        let radius_squared = |y: f32, segment: usize| match segment {
+            0 => rb * rb - (y + h) * (y + h),
+            1 => {
+                let t = (y - (-h + rb * sin_alpha)) / (2.0 * h * cos_alpha * cos_alpha);
+                let r = (rb + (rt - rb) * t) * cos_alpha;
+                r * r
+            }
+            _ => rt * rt - (y - h) * (y - h),
+        };
+        let integrate = |f: &dyn Fn(f32, f32) -> f32| {
+            let mut sum = 0.0;
+            for segment in 0..3 {
+                let (a, b) = (y[segment], y[segment + 1]);
+                let (mid, half) = ((a + b) * 0.5, (b - a) * 0.5);
+                for (node, weight) in GAUSS_LEGENDRE_3 {
+                    let y = mid + half * node;
+                    sum += weight * half * f(y, radius_squared(y, segment).max(0.0));
+                }
+            }
+            sum
+        };
+
+        let volume = integrate(&|_, r2| PI * r2);
+        let center_of_mass = integrate(&|y, r2| PI * r2 * y) / volume;
+        let inertia_y = integrate(&|_, r2| PI * r2 * r2 * 0.5);
+        let inertia_x = integrate(&|y, r2| PI * r2 * (r2 * 0.25 + y * y))
+            - volume * center_of_mass * center_of_mass;
+
+        Ok(Self {
+            top_center: h - center_of_mass,
+            bottom_center: -h - center_of_mass,
+            top_radius,
+            bottom_radius,
+            sin_alpha,
+            center_of_mass,
+            volume,
+            unit_inertia: (inertia_x, inertia_y),
+            density: DEFAULT_DENSITY,
+        })
+    }
+
+    /// Sets the density of the shape in kg / m^3.
+    pub fn with_density(mut self, density: f32) -> Result<Self, ShapeError> {
+        self.density = super::validate_density(density)?;
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn half_height(&self) -> f32 {
+        0.5 * (self.top_center - self.bottom_center)
+    }
+
+    #[inline]
+    pub fn top_radius(&self) -> f32 {
+        self.top_radius
+    }
+
+    #[inline]
+    pub fn bottom_radius(&self) -> f32 {
+        self.bottom_radius
+    }
+}
+
+const GAUSS_LEGENDRE_3: [(f32, f32); 3] = [
+    (-0.774_596_7, 5.0 / 9.0),
+    (0.0, 8.0 / 9.0),
+    (0.774_596_7, 5.0 / 9.0),
+];
+
+struct TaperedCapsuleSupport {
+    top_center: Vec3,
+    bottom_center: Vec3,
+    top_radius: f32,
+    bottom_radius: f32,
+    convex_radius: f32,
+}
+
+impl Support for TaperedCapsuleSupport {
+    #[inline]
+    fn support(&self, direction: Vec3) -> Vec3 {
+        let normal = direction.normalize_or_zero();
+        let top = self.top_center + normal * self.top_radius;
+        let bottom = self.bottom_center + normal * self.bottom_radius;
+        if top.dot(direction) > bottom.dot(direction) {
+            top
+        } else {
+            bottom
+        }
+    }
+
+    #[inline]
+    fn convex_radius(&self) -> f32 {
+        self.convex_radius
+    }
+}
+
+impl Shape for TaperedCapsuleShape {
+    fn sub_type(&self) -> ShapeSubType {
+        ShapeSubType::TaperedCapsule
+    }
+
+    fn center_of_mass(&self) -> Vec3 {
+        Vec3::new(0.0, self.center_of_mass, 0.0)
+    }
+
+    fn local_bounds(&self) -> AABox {
+        let radius = self.top_radius.max(self.bottom_radius);
+        AABox::new(
+            Vec3::new(-radius, self.bottom_center - self.bottom_radius, -radius),
+            Vec3::new(radius, self.top_center + self.top_radius, radius),
+        )
+    }
+
+    fn inner_radius(&self) -> f32 {
+        self.top_radius.min(self.bottom_radius)
+    }
+
+    fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    fn mass_properties(&self) -> MassProperties {
+        let (inertia_x, inertia_y) = self.unit_inertia;
+        MassProperties::new(
+            self.volume * self.density,
+            Mat33::from_diagonal(Vec3::new(inertia_x, inertia_y, inertia_x) * self.density),
+        )
+    }
+
+    fn is_valid_scale(&self, scale: Vec3) -> bool {
+        scale_helpers::is_valid_scale(scale) && scale_helpers::is_uniform_scale(scale)
+    }
+
+    fn surface_normal(&self, _sub_shape_id: SubShapeID, local_position: Vec3) -> Vec3 {
+        let radial = Vec3::new(local_position.x, 0.0, local_position.z);
+        let radial_length = radial.length();
+
+        // Points above the tangent circle of a sphere belong to that sphere.
+        let top = local_position - Vec3::new(0.0, self.top_center, 0.0);
+        if top.y >= self.sin_alpha * top.length() {
+            return top.normalize_or_zero();
+        }
+        let bottom = local_position - Vec3::new(0.0, self.bottom_center, 0.0);
+        if bottom.y <= self.sin_alpha * bottom.length() {
+            return bottom.normalize_or_zero();
+        }
+
+        let cos_alpha = (1.0 - self.sin_alpha * self.sin_alpha).sqrt();
+        let radial = if radial_length > 0.0 {
+            radial / radial_length
+        } else {
+            Vec3::X
+        };
+        radial * cos_alpha + Vec3::new(0.0, self.sin_alpha, 0.0)
+    }
+
+    fn cast_ray(&self, ray: &RayCast, hit: &mut RayCastResult) -> bool {
+        cast_ray_convex(self, ray, hit)
+    }
+
+    fn collide_point(&self, point: Vec3) -> bool {
+        collide_point_convex(self, point)
+    }
+
+    fn as_convex(&self) -> Option<&dyn ConvexShape> {
+        Some(self)
+    }
+}
+
+impl ConvexShape for TaperedCapsuleShape {
+    fn density(&self) -> f32 {
+        self.density
+    }
+
+    fn get_support_function(&self, mode: SupportMode, scale: Vec3) -> Box<dyn Support + '_> {
+        // The scale is uniform apart from its sign, a negative scale on Y flips the capsule.
+        let top_center = Vec3::new(0.0, self.top_center * scale.y, 0.0);
+        let bottom_center = Vec3::new(0.0, self.bottom_center * scale.y, 0.0);
+        let top_radius = self.top_radius * scale.x.abs();
+        let bottom_radius = self.bottom_radius * scale.x.abs();
+        let convex_radius = match mode {
+            SupportMode::IncludeConvexRadius => 0.0,
+            SupportMode::ExcludeConvexRadius | SupportMode::Default => {
+                top_radius.min(bottom_radius)
+            }
+        };
+        Box::new(TaperedCapsuleSupport {
+            top_center,
+            bottom_center,
+            top_radius: top_radius - convex_radius,
+            bottom_radius: bottom_radius - convex_radius,
+            convex_radius,
+        })
+    }
+}
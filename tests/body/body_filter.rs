@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use phys_rs::{
+    body::{BodyCreationSettings, BodyInterface, BodyManager, IgnoreSingleBodyFilter, MotionType},
+    broad_phase::{
+        BroadPhase, BroadPhaseCastResult, BroadPhaseLayerInterfaceTable, BroadPhaseQuadTree,
+    },
+    collision::{
+        AllHitCollector, CollisionDispatch, ObjectLayer, RayCast, ShapeCast, ShapeCastSettings,
+        TransformedShape,
+    },
+    math::{vec3, Quat},
+    shape::SphereShape,
+};
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_ignore_single_body() {
+    let mut body_manager = BodyManager::new(16);
+    let mut broad_phase =
+        BroadPhaseQuadTree::new(Arc::new(BroadPhaseLayerInterfaceTable::new(1, 1)));
+    let mut body_interface = BodyInterface::new(&mut body_manager, &mut broad_phase);
+    let sphere = Arc::new(SphereShape::new(1.0).unwrap());
+    let [near, far] = [0.0, 5.0].map(|x| {
+        let settings = BodyCreationSettings::new(
+            sphere.clone(),
+            vec3(x, 0.0, 0.0),
+            Quat::IDENTITY,
+            MotionType::Static,
+            ObjectLayer(0),
+        );
+        body_interface.create_and_add_body(&settings).unwrap()
+    });
+
+    // The ray passes through both bodies, the filter removes the first one.
+    let ray = RayCast::new(vec3(-5.0, 0.0, 0.0), vec3(15.0, 0.0, 0.0));
+    let mut collector = AllHitCollector::<BroadPhaseCastResult>::new();
+    broad_phase.cast_ray(
+        &ray,
+        &mut collector,
+        &(),
+        &(),
+        &IgnoreSingleBodyFilter(near),
+    );
+    let hits: Vec<_> = collector.hits.iter().map(|hit| hit.body_id).collect();
+    assert_eq!(hits, [far]);
+
+    // A shape cast against an ignored body is not performed.
+    let probe = SphereShape::new(0.5).unwrap();
+    let cast = ShapeCast::new(
+        TransformedShape::new(&probe, vec3(0.0, 5.0, 0.0), Quat::IDENTITY),
+        vec3(0.0, -10.0, 0.0),
+    );
+    let target = body_manager.body(near).unwrap().transformed_shape();
+    assert_eq!(target.body_id, near);
+    let settings = ShapeCastSettings::default();
+    let dispatch = CollisionDispatch::new();
+    let mut collector = AllHitCollector::new();
+    dispatch.cast_shape(&cast, &target, &settings, &mut collector, &());
+    assert_eq!(collector.hits.len(), 1);
+    let mut collector = AllHitCollector::new();
+    let filter = IgnoreSingleBodyFilter(near);
+    dispatch.cast_shape(&cast, &target, &settings, &mut collector, &filter);
+    assert!(collector.hits.is_empty());
+}
@@ -0,0 +1,263 @@
+use std::sync::Arc;
+
+use phys_rs::{
+    body::{
+        BodyCreationSettings, BodyError, BodyID, BodyInterface, BodyManager, MotionQuality,
+        MotionType,
+    },
+    broad_phase::{BroadPhase, BroadPhaseLayer, BroadPhaseQuadTree},
+    collision::{AllHitCollector, ObjectLayer},
+    geometry::AABox,
+    math::{vec3, Quat, Vec3},
+    shape::{BoxShape, SphereShape},
+};
+
+use crate::physics::layer_interface;
+
+fn broad_phase() -> BroadPhaseQuadTree {
+    BroadPhaseQuadTree::new(Arc::new(layer_interface()))
+}
+
+fn hits_at(broad_phase: &dyn BroadPhase, point: Vec3) -> Vec<BodyID> {
+    let mut collector = AllHitCollector::new();
+    broad_phase.collide_point(point, &mut collector, &(), &(), &());
+    collector.hits
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_add_remove_destroy() {
+    let mut body_manager = BodyManager::new(16);
+    let mut broad_phase = broad_phase();
+    let mut body_interface = BodyInterface::new(&mut body_manager, &mut broad_phase);
+    let settings = BodyCreationSettings::new(
+        Arc::new(BoxShape::new(Vec3::ONE).unwrap()),
+        Vec3::ZERO,
+        Quat::IDENTITY,
+        MotionType::Static,
+        ObjectLayer(0),
+    );
+
+    let body_id = body_interface.create_body(&settings).unwrap();
+    assert!(body_interface.is_valid(body_id));
+    assert!(!body_interface.is_added(body_id));
+    assert_eq!(
+        body_interface.remove_body(body_id),
+        Err(BodyError::NotAdded)
+    );
+    body_interface.add_body(body_id).unwrap();
+    assert_eq!(
+        body_interface.add_body(body_id),
+        Err(BodyError::AlreadyAdded)
+    );
+
+    // An added body has to be removed before it can be destroyed.
+    assert_eq!(
+        body_interface.destroy_body(body_id),
+        Err(BodyError::AlreadyAdded)
+    );
+    body_interface.remove_body(body_id).unwrap();
+    body_interface.destroy_body(body_id).unwrap();
+    assert!(!body_interface.is_valid(body_id));
+    assert_eq!(
+        body_interface.add_body(body_id),
+        Err(BodyError::InvalidBodyID)
+    );
+    assert_eq!(body_interface.position(body_id), None);
+
+    let body_id = body_interface.create_and_add_body(&settings).unwrap();
+    assert!(body_interface.is_added(body_id));
+    assert_eq!(hits_at(&broad_phase, Vec3::ZERO), [body_id]);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_set_properties() {
+    let mut body_manager = BodyManager::new(16);
+    let mut broad_phase = broad_phase();
+    let mut body_interface = BodyInterface::new(&mut body_manager, &mut broad_phase);
+    let settings = BodyCreationSettings::new(
+        Arc::new(SphereShape::new(1.0).unwrap()),
+        Vec3::ZERO,
+        Quat::IDENTITY,
+        MotionType::Dynamic,
+        ObjectLayer(0),
+    );
+    let body_id = body_interface.create_and_add_body(&settings).unwrap();
+
+    // Moving the body moves it in the broadphase.
+    let position = vec3(10.0, 0.0, 0.0);
+    body_interface.set_position(body_id, position).unwrap();
+    assert_eq!(body_interface.position(body_id), Some(position));
+    let rotation = Quat::from_rotation_z(1.0);
+    body_interface.set_rotation(body_id, rotation).unwrap();
+    assert_eq!(body_interface.rotation(body_id), Some(rotation));
+    assert_eq!(
+        body_interface.set_position(body_id, Vec3::splat(f32::INFINITY)),
+        Err(BodyError::InvalidPosition)
+    );
+
+    body_interface
+        .set_shape(body_id, Arc::new(SphereShape::new(2.0).unwrap()))
+        .unwrap();
+    let bounds = body_interface.world_bounds(body_id).unwrap();
+    assert!(bounds.contains(&AABox::from_center_and_half_extent(
+        position,
+        Vec3::splat(2.0)
+    )));
+
+    body_interface
+        .set_motion_type(body_id, MotionType::Kinematic)
+        .unwrap();
+    assert_eq!(
+        body_interface.motion_type(body_id),
+        Some(MotionType::Kinematic)
+    );
+
+    body_interface
+        .set_motion_quality(body_id, MotionQuality::LinearCast)
+        .unwrap();
+    assert_eq!(
+        body_interface.motion_quality(body_id),
+        Some(MotionQuality::LinearCast)
+    );
+
+    body_interface.set_friction(body_id, 0.5).unwrap();
+    assert_eq!(body_interface.friction(body_id), Some(0.5));
+    assert_eq!(
+        body_interface.set_friction(body_id, -1.0),
+        Err(BodyError::InvalidFriction)
+    );
+    body_interface.set_restitution(body_id, 0.25).unwrap();
+    assert_eq!(body_interface.restitution(body_id), Some(0.25));
+    body_interface.set_user_data(body_id, 42).unwrap();
+    assert_eq!(body_interface.user_data(body_id), Some(42));
+
+    // Changing the object layer moves the body to another broadphase layer.
+    body_interface
+        .set_object_layer(body_id, ObjectLayer(1))
+        .unwrap();
+    assert_eq!(body_interface.object_layer(body_id), Some(ObjectLayer(1)));
+    assert_eq!(
+        broad_phase.broad_phase_layer(body_id),
+        Some(BroadPhaseLayer(1))
+    );
+    assert_eq!(hits_at(&broad_phase, position), [body_id]);
+    assert!(hits_at(&broad_phase, Vec3::ZERO).is_empty());
+    assert_eq!(broad_phase.body_bounds(body_id), Some(bounds));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_velocities() {
+    let mut body_manager = BodyManager::new(16);
+    let mut broad_phase = broad_phase();
+    let mut body_interface = BodyInterface::new(&mut body_manager, &mut broad_phase);
+    let mut settings = BodyCreationSettings::new(
+        Arc::new(SphereShape::new(1.0).unwrap()),
+        Vec3::ZERO,
+        Quat::IDENTITY,
+        MotionType::Dynamic,
+        ObjectLayer(0),
+    );
+    settings.linear_velocity = vec3(1.0, 0.0, 0.0);
+    let body_id = body_interface.create_body(&settings).unwrap();
+    assert_eq!(body_interface.linear_velocity(body_id), Some(Vec3::X));
+
+    body_interface
+        .set_angular_velocity(body_id, vec3(0.0, 2.0, 0.0))
+        .unwrap();
+    assert_eq!(
+        body_interface.angular_velocity(body_id),
+        Some(2.0 * Vec3::Y)
+    );
+
+    // Kinematic bodies keep their velocity but are not affected by impulses.
+    body_interface
+        .set_motion_type(body_id, MotionType::Kinematic)
+        .unwrap();
+    body_interface.add_impulse(body_id, Vec3::Y).unwrap();
+    assert_eq!(body_interface.linear_velocity(body_id), Some(Vec3::X));
+
+    // Static bodies do not move at all.
+    body_interface
+        .set_motion_type(body_id, MotionType::Static)
+        .unwrap();
+    body_interface
+        .set_linear_velocity(body_id, Vec3::Y)
+        .unwrap();
+    assert_eq!(body_interface.linear_velocity(body_id), Some(Vec3::ZERO));
+
+    // An impulse away from the center of mass also spins the body.
+    body_interface
+        .set_motion_type(body_id, MotionType::Dynamic)
+        .unwrap();
+    let body = body_manager.body_mut(body_id).unwrap();
+    body.add_impulse_at_point(Vec3::Z, Vec3::X);
+    let inverse_mass = body.motion_properties().unwrap().inverse_mass();
+    assert!(body
+        .linear_velocity()
+        .abs_diff_eq(Vec3::Z * inverse_mass, 1.0e-6));
+    assert!(body.angular_velocity().y < 0.0);
+    assert!(body.point_velocity(Vec3::X).z > body.linear_velocity().z);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_activation() {
+    let mut body_manager = BodyManager::new(16);
+    let mut broad_phase = broad_phase();
+    let mut body_interface = BodyInterface::new(&mut body_manager, &mut broad_phase);
+    let mut settings = BodyCreationSettings::new(
+        Arc::new(SphereShape::new(1.0).unwrap()),
+        Vec3::ZERO,
+        Quat::IDENTITY,
+        MotionType::Static,
+        ObjectLayer(1),
+    );
+    let static_id = body_interface.create_and_add_body(&settings).unwrap();
+    settings.motion_type = MotionType::Dynamic;
+    let body_id = body_interface.create_body(&settings).unwrap();
+    assert_eq!(
+        body_interface.activate_body(body_id),
+        Err(BodyError::NotAdded)
+    );
+
+    // Static bodies are never active, other bodies are activated when they are added.
+    body_interface.activate_body(static_id).unwrap();
+    assert!(!body_interface.is_active(static_id));
+    body_interface.add_body(body_id).unwrap();
+    assert!(body_interface.is_active(body_id));
+
+    // A sleeping body loses its velocity.
+    body_interface
+        .set_linear_velocity(body_id, vec3(1.0, 0.0, 0.0))
+        .unwrap();
+    body_interface.deactivate_body(body_id).unwrap();
+    assert!(!body_interface.is_active(body_id));
+    assert_eq!(body_interface.linear_velocity(body_id), Some(Vec3::ZERO));
+    body_interface
+        .set_linear_velocity(body_id, Vec3::ZERO)
+        .unwrap();
+    assert!(!body_interface.is_active(body_id));
+    body_interface
+        .add_impulse(body_id, vec3(0.0, 1.0, 0.0))
+        .unwrap();
+    assert!(body_interface.is_active(body_id));
+
+    body_interface.remove_body(body_id).unwrap();
+    assert!(!body_interface.is_active(body_id));
+    assert_eq!(
+        body_interface.deactivate_body(body_id),
+        Err(BodyError::NotAdded)
+    );
+
+    body_interface
+        .set_motion_type(static_id, MotionType::Kinematic)
+        .unwrap();
+    assert!(body_interface.is_active(static_id));
+    body_interface
+        .set_motion_type(static_id, MotionType::Static)
+        .unwrap();
+    assert!(!body_interface.is_active(static_id));
+}
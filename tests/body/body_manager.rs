@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use phys_rs::{
+    body::{BodyCreationSettings, BodyError, BodyID, BodyManager, MotionType},
+    collision::ObjectLayer,
+    geometry::IndexedTriangle,
+    math::{vec3, Quat, Vec3},
+    shape::{BoxShape, MeshShape, OffsetCenterOfMassShape},
+};
+
+fn settings(motion_type: MotionType) -> BodyCreationSettings {
+    BodyCreationSettings::new(
+        Arc::new(BoxShape::new(Vec3::ONE).unwrap()),
+        vec3(1.0, 2.0, 3.0),
+        Quat::IDENTITY,
+        motion_type,
+        ObjectLayer(0),
+    )
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_validate() {
+    assert_eq!(settings(MotionType::Dynamic).validate(), Ok(()));
+
+    let mut invalid = settings(MotionType::Dynamic);
+    invalid.position.x = f32::NAN;
+    assert_eq!(invalid.validate(), Err(BodyError::InvalidPosition));
+
+    let mut invalid = settings(MotionType::Dynamic);
+    invalid.rotation = Quat::from_xyzw(0.0, 0.0, 0.0, 2.0);
+    assert_eq!(invalid.validate(), Err(BodyError::InvalidRotation));
+
+    let mut invalid = settings(MotionType::Dynamic);
+    invalid.friction = -0.1;
+    assert_eq!(invalid.validate(), Err(BodyError::InvalidFriction));
+
+    let mut invalid = settings(MotionType::Dynamic);
+    invalid.restitution = 1.5;
+    assert_eq!(invalid.validate(), Err(BodyError::InvalidRestitution));
+
+    // A mesh has no mass, so it can only be static or kinematic.
+    let vertices = [Vec3::ZERO, Vec3::X, Vec3::Z];
+    let mesh = MeshShape::new(&vertices, &[IndexedTriangle::new(0, 2, 1, 0)]).unwrap();
+    let mut mesh = BodyCreationSettings {
+        shape: Arc::new(mesh),
+        ..settings(MotionType::Static)
+    };
+    assert_eq!(mesh.validate(), Ok(()));
+    mesh.motion_type = MotionType::Kinematic;
+    assert_eq!(mesh.validate(), Ok(()));
+    mesh.motion_type = MotionType::Dynamic;
+    assert_eq!(mesh.validate(), Err(BodyError::InvalidMass));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_create_and_destroy() {
+    let mut body_manager = BodyManager::new(2);
+    let a = body_manager
+        .create_body(&settings(MotionType::Static))
+        .unwrap();
+    let b = body_manager
+        .create_body(&settings(MotionType::Dynamic))
+        .unwrap();
+    assert_eq!((a.index(), b.index()), (0, 1));
+    assert_eq!(body_manager.body_count(), 2);
+    assert_eq!(
+        body_manager.create_body(&settings(MotionType::Static)),
+        Err(BodyError::TooManyBodies)
+    );
+    assert!(body_manager.body(b).unwrap().is_dynamic());
+
+    // The index of a destroyed body is reused with a new sequence number.
+    let body = body_manager.destroy_body(a).unwrap();
+    assert_eq!(body.id(), a);
+    assert!(body_manager.body(a).is_none());
+    assert_eq!(
+        body_manager.destroy_body(a).err(),
+        Some(BodyError::InvalidBodyID)
+    );
+    let c = body_manager
+        .create_body(&settings(MotionType::Static))
+        .unwrap();
+    assert_eq!(c.index(), a.index());
+    assert_ne!(c, a);
+    assert!(body_manager.body(a).is_none());
+    assert_eq!(body_manager.body(c).unwrap().id(), c);
+    assert!(body_manager.body(BodyID::INVALID).is_none());
+
+    let ids: Vec<_> = body_manager.bodies().map(|body| body.id()).collect();
+    assert_eq!(ids, [c, b]);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_center_of_mass() {
+    let shape = OffsetCenterOfMassShape::new(Arc::new(BoxShape::new(Vec3::ONE).unwrap()), Vec3::X);
+    let rotation = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+    let settings = BodyCreationSettings {
+        shape: Arc::new(shape),
+        rotation,
+        ..settings(MotionType::Dynamic)
+    };
+    let mut body_manager = BodyManager::new(1);
+    let body_id = body_manager.create_body(&settings).unwrap();
+    let body = body_manager.body(body_id).unwrap();
+    assert!(body.position().abs_diff_eq(settings.position, 1.0e-6));
+    let center_of_mass = settings.position + rotation * Vec3::X;
+    assert!(body
+        .center_of_mass_position()
+        .abs_diff_eq(center_of_mass, 1.0e-6));
+    assert!(body
+        .world_bounds()
+        .min
+        .abs_diff_eq(settings.position - Vec3::ONE, 1.0e-5));
+}
@@ -0,0 +1,182 @@
+use std::f32::consts::PI;
+
+use phys_rs::{
+    body::MassProperties,
+    math::{vec3, Mat33, Quat, Vec3},
+    shape::{BoxShape, CapsuleShape, CylinderShape, Shape, SphereShape},
+};
+
+const DENSITY: f32 = 2.5;
+
+fn assert_inertia_eq(a: &Mat33, b: &Mat33) {
+    let tolerance = 1.0e-5 * b.diagonal().max_element().max(1.0);
+    assert!(a.abs_diff_eq(b, tolerance), "{a:?} != {b:?}");
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_sphere() {
+    let r = 0.75;
+    let shape = SphereShape::new(r).unwrap().with_density(DENSITY).unwrap();
+    let mass = 4.0 / 3.0 * PI * r * r * r * DENSITY;
+    let mass_properties = shape.mass_properties();
+    assert!((mass_properties.mass - mass).abs() < 1.0e-5 * mass);
+    assert_inertia_eq(
+        &mass_properties.inertia,
+        &Mat33::from_diagonal(Vec3::splat(0.4 * mass * r * r)),
+    );
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_box() {
+    let size = vec3(1.0, 2.0, 3.0);
+    let mut mass_properties = MassProperties::default();
+    mass_properties.set_mass_and_inertia_of_solid_box(size, DENSITY);
+    let mass = 6.0 * DENSITY;
+    assert_eq!(mass_properties.mass, mass);
+    assert_inertia_eq(
+        &mass_properties.inertia,
+        &Mat33::from_diagonal(vec3(4.0 + 9.0, 1.0 + 9.0, 1.0 + 4.0) * (mass / 12.0)),
+    );
+
+    let shape = BoxShape::new(size * 0.5)
+        .unwrap()
+        .with_density(DENSITY)
+        .unwrap();
+    assert_eq!(shape.mass_properties(), mass_properties);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_capsule() {
+    let (height, r) = (1.5, 0.5);
+    let shape = CapsuleShape::new(0.5 * height, r)
+        .unwrap()
+        .with_density(DENSITY)
+        .unwrap();
+
+    // A cylinder with a hemisphere at each end.
+    let cylinder_mass = PI * r * r * height * DENSITY;
+    let sphere_mass = 4.0 / 3.0 * PI * r * r * r * DENSITY;
+    let inertia_y = cylinder_mass * r * r / 2.0 + sphere_mass * 2.0 / 5.0 * r * r;
+    let inertia_x = cylinder_mass * (r * r / 4.0 + height * height / 12.0)
+        + sphere_mass * (2.0 / 5.0 * r * r + height * height / 4.0 + 3.0 / 8.0 * height * r);
+    let mass_properties = shape.mass_properties();
+    let mass = cylinder_mass + sphere_mass;
+    assert!((mass_properties.mass - mass).abs() < 1.0e-5 * mass);
+    assert_inertia_eq(
+        &mass_properties.inertia,
+        &Mat33::from_diagonal(vec3(inertia_x, inertia_y, inertia_x)),
+    );
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_cylinder() {
+    let (height, r) = (2.0, 0.5);
+    let shape = CylinderShape::new(0.5 * height, r)
+        .unwrap()
+        .with_density(DENSITY)
+        .unwrap();
+    let mass = PI * r * r * height * DENSITY;
+    let inertia_x = mass * (3.0 * r * r + height * height) / 12.0;
+    let mass_properties = shape.mass_properties();
+    assert!((mass_properties.mass - mass).abs() < 1.0e-5 * mass);
+    assert_inertia_eq(
+        &mass_properties.inertia,
+        &Mat33::from_diagonal(vec3(inertia_x, mass * r * r / 2.0, inertia_x)),
+    );
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_scale_to_mass() {
+    let mut mass_properties = MassProperties::default();
+    mass_properties.set_mass_and_inertia_of_solid_box(vec3(1.0, 2.0, 3.0), 1.0);
+    let mut heavier = mass_properties;
+    heavier.scale_to_mass(18.0);
+    assert_eq!(heavier.mass, 18.0);
+    assert_inertia_eq(&heavier.inertia, &(mass_properties.inertia * 3.0));
+
+    // Without mass there is nothing to scale.
+    let mut empty = MassProperties::default();
+    empty.scale_to_mass(5.0);
+    assert_eq!(empty, MassProperties::new(5.0, Mat33::ZERO));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_translate_rotate() {
+    let size = vec3(1.0, 2.0, 3.0);
+    let mut mass_properties = MassProperties::default();
+    mass_properties.set_mass_and_inertia_of_solid_box(size, 1.0);
+    let inertia = mass_properties.inertia.diagonal();
+
+    // A quarter turn around Z swaps the X and Y axes.
+    let mut rotated = mass_properties;
+    rotated.rotate(Quat::from_rotation_z(0.5 * PI));
+    assert_inertia_eq(
+        &rotated.inertia,
+        &Mat33::from_diagonal(vec3(inertia.y, inertia.x, inertia.z)),
+    );
+
+    // Moving along X leaves the X axis alone and adds the mass times the squared distance to
+    // the other axes.
+    let mut translated = mass_properties;
+    translated.translate(vec3(2.0, 0.0, 0.0));
+    assert_inertia_eq(
+        &translated.inertia,
+        &Mat33::from_diagonal(inertia + vec3(0.0, 4.0, 4.0) * mass_properties.mass),
+    );
+
+    // Moving diagonally introduces products of inertia.
+    let mut translated = mass_properties;
+    translated.translate(vec3(1.0, 1.0, 0.0));
+    assert!((translated.inertia.col(0).y + mass_properties.mass).abs() < 1.0e-5);
+    assert_eq!(translated.inertia, translated.inertia.transpose());
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_scale() {
+    let mut unit_box = MassProperties::default();
+    unit_box.set_mass_and_inertia_of_solid_box(Vec3::ONE, DENSITY);
+    let mut expected = MassProperties::default();
+    expected.set_mass_and_inertia_of_solid_box(vec3(2.0, 3.0, 4.0), DENSITY);
+
+    let mut scaled = unit_box;
+    scaled.scale(vec3(2.0, -3.0, 4.0));
+    assert!((scaled.mass - expected.mass).abs() < 1.0e-5 * expected.mass);
+    assert_inertia_eq(&scaled.inertia, &expected.inertia);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_decompose_principal_moments_of_inertia() {
+    let mut mass_properties = MassProperties::default();
+    mass_properties.set_mass_and_inertia_of_solid_box(vec3(3.0, 1.0, 2.0), 1.0);
+    let moments = mass_properties.inertia.diagonal();
+    let rotation = Quat::from_axis_angle(vec3(1.0, 2.0, -0.5).normalize(), 0.8);
+    mass_properties.rotate(rotation);
+
+    let (principal_rotation, diagonal) = mass_properties
+        .decompose_principal_moments_of_inertia()
+        .unwrap();
+    assert!((diagonal - vec3(moments.y, moments.z, moments.x)).length() < 1.0e-4);
+    assert!((principal_rotation.determinant() - 1.0).abs() < 1.0e-5);
+    let recomposed =
+        principal_rotation * Mat33::from_diagonal(diagonal) * principal_rotation.transpose();
+    assert_inertia_eq(&recomposed, &mass_properties.inertia);
+
+    // Equal moments decompose into any rotation.
+    let sphere = SphereShape::new(1.0).unwrap().mass_properties();
+    let (principal_rotation, diagonal) = sphere.decompose_principal_moments_of_inertia().unwrap();
+    assert_eq!(diagonal, sphere.inertia.diagonal());
+    assert!(
+        (principal_rotation * principal_rotation.transpose()).abs_diff_eq(&Mat33::IDENTITY, 1.0e-6)
+    );
+
+    let invalid = MassProperties::new(1.0, Mat33::from_diagonal(Vec3::splat(f32::NAN)));
+    assert!(invalid.decompose_principal_moments_of_inertia().is_none());
+}
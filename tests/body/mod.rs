@@ -0,0 +1,5 @@
+mod body_filter;
+mod body_interface;
+mod body_manager;
+mod mass_properties;
+mod motion_properties;
@@ -0,0 +1,166 @@
+use std::f32::consts::FRAC_PI_2;
+
+use phys_rs::{
+    body::{AllowedDOFs, MassProperties, MotionProperties},
+    math::{vec3, Mat33, Quat, Vec3},
+    shape::{BoxShape, Shape},
+};
+
+const DELTA_TIME: f32 = 1.0 / 60.0;
+
+fn box_motion(allowed_dofs: AllowedDOFs) -> MotionProperties {
+    let shape = BoxShape::new(vec3(1.0, 2.0, 3.0)).unwrap();
+    let mut motion = MotionProperties::new(&shape.mass_properties(), allowed_dofs);
+    motion.linear_damping = 0.0;
+    motion.angular_damping = 0.0;
+    motion
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_inverse_inertia() {
+    let shape = BoxShape::new(vec3(1.0, 2.0, 3.0)).unwrap();
+    let mass_properties = shape.mass_properties();
+    let motion = box_motion(AllowedDOFs::ALL);
+    assert!((motion.inverse_mass() * mass_properties.mass - 1.0).abs() < 1.0e-6);
+
+    let rotation = Quat::from_axis_angle(vec3(0.48, 0.6, 0.64), 0.9);
+    let inverse_inertia = motion.inverse_inertia_for_rotation(rotation);
+    let rotation_matrix = Mat33::from_quat(rotation);
+    let inertia = rotation_matrix * mass_properties.inertia * rotation_matrix.transpose();
+    assert!((inverse_inertia * inertia).abs_diff_eq(&Mat33::IDENTITY, 1.0e-5));
+
+    let vector = vec3(1.0, -2.0, 0.5);
+    assert!(motion
+        .multiply_world_space_inverse_inertia_by_vector(rotation, vector)
+        .abs_diff_eq(inverse_inertia * vector, 1.0e-6));
+
+    // A rotated inertia tensor is decomposed into its principal axes.
+    let mut rotated = mass_properties;
+    rotated.rotate(rotation);
+    let motion = MotionProperties::new(&rotated, AllowedDOFs::ALL);
+    assert!(motion
+        .inverse_inertia_for_rotation(Quat::IDENTITY)
+        .abs_diff_eq(&inverse_inertia, 1.0e-5));
+
+    // Without a mass forces do not move the body.
+    let motion = MotionProperties::new(&MassProperties::default(), AllowedDOFs::ALL);
+    assert_eq!(motion.inverse_mass(), 0.0);
+    assert_eq!(motion.inverse_inertia_diagonal(), Vec3::ZERO);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_gravity_and_integration() {
+    let gravity = vec3(0.0, -9.81, 0.0);
+    let mut motion = box_motion(AllowedDOFs::ALL);
+    let (mut position, mut rotation) = (Vec3::ZERO, Quat::IDENTITY);
+    let mut expected = 0.0;
+    for step in 1..=60 {
+        motion.apply_forces_gravity_and_damping(rotation, gravity, DELTA_TIME);
+        motion.integrate(&mut position, &mut rotation, DELTA_TIME);
+        // Semi implicit Euler moves with the velocity at the end of the step.
+        expected += gravity.y * step as f32 * DELTA_TIME * DELTA_TIME;
+    }
+    assert!((motion.linear_velocity().y - gravity.y).abs() < 1.0e-4);
+    assert!((position.y - expected).abs() < 1.0e-4);
+    assert_eq!(rotation, Quat::IDENTITY);
+
+    // A gravity factor of zero floats.
+    motion.gravity_factor = 0.0;
+    motion.set_linear_velocity(Vec3::ZERO);
+    motion.apply_forces_gravity_and_damping(rotation, gravity, DELTA_TIME);
+    assert_eq!(motion.linear_velocity(), Vec3::ZERO);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_rotation_integration() {
+    let mut motion = box_motion(AllowedDOFs::ALL);
+    motion.set_angular_velocity(vec3(0.0, 0.0, FRAC_PI_2));
+    let (mut position, mut rotation) = (Vec3::ZERO, Quat::IDENTITY);
+    for _ in 0..600 {
+        motion.integrate(&mut position, &mut rotation, DELTA_TIME / 10.0);
+    }
+    assert!(rotation.is_normalized());
+    assert!(rotation.abs_diff_eq(Quat::from_rotation_z(FRAC_PI_2), 1.0e-3));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_forces_and_impulses() {
+    let mut motion = box_motion(AllowedDOFs::ALL);
+    let mass = 1.0 / motion.inverse_mass();
+    motion.add_force(vec3(mass * 60.0, 0.0, 0.0));
+    motion.add_torque(vec3(0.0, 1.0, 0.0));
+    motion.apply_forces_gravity_and_damping(Quat::IDENTITY, Vec3::ZERO, DELTA_TIME);
+    assert!(motion.linear_velocity().abs_diff_eq(Vec3::X, 1.0e-5));
+    let angular = motion.multiply_world_space_inverse_inertia_by_vector(Quat::IDENTITY, Vec3::Y);
+    assert!(motion
+        .angular_velocity()
+        .abs_diff_eq(angular * DELTA_TIME, 1.0e-6));
+
+    // The accumulated force is only applied once.
+    assert_eq!(motion.accumulated_force(), Vec3::ZERO);
+    assert_eq!(motion.accumulated_torque(), Vec3::ZERO);
+    motion.apply_forces_gravity_and_damping(Quat::IDENTITY, Vec3::ZERO, DELTA_TIME);
+    assert!(motion.linear_velocity().abs_diff_eq(Vec3::X, 1.0e-5));
+
+    motion.add_impulse(vec3(0.0, mass, 0.0));
+    assert!(motion
+        .linear_velocity()
+        .abs_diff_eq(vec3(1.0, 1.0, 0.0), 1.0e-5));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_damping_and_clamping() {
+    let mut motion = box_motion(AllowedDOFs::ALL);
+    motion.linear_damping = 0.5;
+    motion.angular_damping = 1.0;
+    motion.set_linear_velocity(Vec3::X);
+    motion.set_angular_velocity(Vec3::Y);
+    motion.apply_forces_gravity_and_damping(Quat::IDENTITY, Vec3::ZERO, 0.1);
+    assert!(motion
+        .linear_velocity()
+        .abs_diff_eq(vec3(0.95, 0.0, 0.0), 1.0e-6));
+    assert!(motion
+        .angular_velocity()
+        .abs_diff_eq(vec3(0.0, 0.9, 0.0), 1.0e-6));
+
+    motion.max_linear_velocity = 2.0;
+    motion.max_angular_velocity = 1.0;
+    motion.set_linear_velocity_clamped(vec3(3.0, 4.0, 0.0));
+    motion.set_angular_velocity_clamped(vec3(0.0, 0.0, -5.0));
+    assert!(motion
+        .linear_velocity()
+        .abs_diff_eq(vec3(1.2, 1.6, 0.0), 1.0e-6));
+    assert!(motion.angular_velocity().abs_diff_eq(-Vec3::Z, 1.0e-6));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_allowed_dofs() {
+    let mut motion = box_motion(AllowedDOFs::PLANE_2D);
+    motion.set_linear_velocity(Vec3::ONE);
+    motion.set_angular_velocity(Vec3::ONE);
+    assert_eq!(motion.linear_velocity(), vec3(1.0, 1.0, 0.0));
+    assert_eq!(motion.angular_velocity(), Vec3::Z);
+
+    let rotation = Quat::from_rotation_x(0.3);
+    motion.add_torque(Vec3::ONE);
+    motion.apply_forces_gravity_and_damping(rotation, vec3(0.0, 0.0, -10.0), DELTA_TIME);
+    assert_eq!(motion.linear_velocity().z, 0.0);
+    let angular_velocity = motion.angular_velocity();
+    assert_eq!((angular_velocity.x, angular_velocity.y), (0.0, 0.0));
+    let inverse_inertia = motion.inverse_inertia_for_rotation(rotation);
+    assert_eq!(inverse_inertia * Vec3::X, Vec3::ZERO);
+    assert_eq!(inverse_inertia * Vec3::Y, Vec3::ZERO);
+
+    assert!(AllowedDOFs::ALL.contains(AllowedDOFs::PLANE_2D));
+    assert!(!AllowedDOFs::PLANE_2D.contains(AllowedDOFs::TRANSLATION_Z));
+    assert_eq!(
+        AllowedDOFs::TRANSLATION_X | AllowedDOFs::TRANSLATION_Y | AllowedDOFs::ROTATION_Z,
+        AllowedDOFs::PLANE_2D
+    );
+}
@@ -0,0 +1,336 @@
+use std::sync::Arc;
+
+use phys_rs::{
+    body::BodyID,
+    broad_phase::{
+        BroadPhase, BroadPhaseCastResult, BroadPhaseLayer, BroadPhaseLayerInterface,
+        BroadPhaseLayerInterfaceTable, DefaultBroadPhaseLayerFilter,
+        ObjectVsBroadPhaseLayerFilterTable,
+    },
+    collision::{
+        AllHitCollector, AnyHitCollector, ClosestHitCollector, DefaultObjectLayerFilter,
+        ObjectLayer, ObjectLayerPairFilter, ObjectLayerPairFilterTable, RayCast,
+    },
+    geometry::{ray_aabox, AABox},
+    math::{vec3, Vec3},
+};
+
+const OBJECT_LAYER_COUNT: u16 = 4;
+
+/// Creates an empty broadphase of the implementation under test.
+pub type NewBroadPhase = fn(Arc<dyn BroadPhaseLayerInterface>) -> Box<dyn BroadPhase>;
+
+struct Random(u32);
+
+impl Random {
+    fn next(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        (self.0 as f32 / u32::MAX as f32) * 2.0 - 1.0
+    }
+
+    fn index(&mut self, len: usize) -> usize {
+        ((self.next() * 0.5 + 0.5) * len as f32) as usize % len
+    }
+
+    fn vec3(&mut self) -> Vec3 {
+        vec3(self.next(), self.next(), self.next())
+    }
+
+    fn bounds(&mut self) -> AABox {
+        let center = self.vec3() * 50.0;
+        let half_extent = (self.vec3() + Vec3::splat(1.5)) * 0.5;
+        AABox::from_center_and_half_extent(center, half_extent)
+    }
+}
+
+/// Object layers 0 and 1 share the first broadphase layer, the others have their own.
+fn layer_interface() -> BroadPhaseLayerInterfaceTable {
+    let mut layer_interface = BroadPhaseLayerInterfaceTable::new(OBJECT_LAYER_COUNT, 3);
+    layer_interface.map_object_to_broad_phase_layer(ObjectLayer(2), BroadPhaseLayer(1));
+    layer_interface.map_object_to_broad_phase_layer(ObjectLayer(3), BroadPhaseLayer(2));
+    layer_interface
+}
+
+fn pair_filter() -> ObjectLayerPairFilterTable {
+    let mut pair_filter = ObjectLayerPairFilterTable::new(OBJECT_LAYER_COUNT);
+    for (layer1, layer2) in [(0, 1), (0, 2), (1, 1), (1, 3), (3, 3)] {
+        pair_filter.enable_collision(ObjectLayer(layer1), ObjectLayer(layer2));
+    }
+    pair_filter
+}
+
+/// The bodies that should be in the broadphase, indexed by the index of their ID.
+struct Scene {
+    bodies: Vec<Option<(BodyID, AABox)>>,
+    sequences: Vec<u8>,
+    pair_filter: ObjectLayerPairFilterTable,
+    object_vs_broad_phase_filter: ObjectVsBroadPhaseLayerFilterTable,
+}
+
+impl Scene {
+    fn new(len: usize, new_broad_phase: NewBroadPhase) -> (Self, Box<dyn BroadPhase>) {
+        let layer_interface = layer_interface();
+        let pair_filter = pair_filter();
+        let object_vs_broad_phase_filter = ObjectVsBroadPhaseLayerFilterTable::new(
+            &layer_interface,
+            &pair_filter,
+            OBJECT_LAYER_COUNT,
+        );
+        let scene = Self {
+            bodies: vec![None; len],
+            sequences: vec![0; len],
+            pair_filter,
+            object_vs_broad_phase_filter,
+        };
+        (scene, new_broad_phase(Arc::new(layer_interface)))
+    }
+
+    fn layer(body_id: BodyID) -> ObjectLayer {
+        ObjectLayer((body_id.index() % OBJECT_LAYER_COUNT as u32) as u16)
+    }
+
+    /// Adds bodies at all free indices in `indices`.
+    fn add(&mut self, broad_phase: &mut dyn BroadPhase, indices: &[usize], rng: &mut Random) {
+        let mut added = Vec::new();
+        for &index in indices {
+            if self.bodies[index].is_some() {
+                continue;
+            }
+            self.sequences[index] = self.sequences[index].wrapping_add(1);
+            let body_id = BodyID::new(index as u32, self.sequences[index]);
+            let bounds = rng.bounds();
+            self.bodies[index] = Some((body_id, bounds));
+            added.push((body_id, bounds, Self::layer(body_id)));
+        }
+        broad_phase.add_bodies(&added);
+    }
+
+    fn remove(&mut self, broad_phase: &mut dyn BroadPhase, indices: &[usize]) {
+        let removed: Vec<_> = indices
+            .iter()
+            .filter_map(|&index| self.bodies[index].take().map(|(body_id, _)| body_id))
+            .collect();
+        broad_phase.remove_bodies(&removed);
+    }
+
+    /// Moves the bodies at `indices` by a small amount, like a simulation would.
+    fn moved(&mut self, indices: &[usize], rng: &mut Random) -> Vec<(BodyID, AABox)> {
+        let mut moved = Vec::new();
+        for &index in indices {
+            if let Some((body_id, bounds)) = &mut self.bodies[index] {
+                *bounds = bounds.translated(rng.vec3() * 2.0);
+                moved.push((*body_id, *bounds));
+            }
+        }
+        moved
+    }
+
+    fn filter(&self, predicate: impl Fn(&AABox) -> bool) -> Vec<BodyID> {
+        self.filter_layers(|_| true, predicate)
+    }
+
+    fn filter_layers(
+        &self,
+        layer: impl Fn(ObjectLayer) -> bool,
+        predicate: impl Fn(&AABox) -> bool,
+    ) -> Vec<BodyID> {
+        let mut result: Vec<_> = self
+            .bodies
+            .iter()
+            .flatten()
+            .filter(|(body_id, bounds)| layer(Self::layer(*body_id)) && predicate(bounds))
+            .map(|&(body_id, _)| body_id)
+            .collect();
+        result.sort();
+        result
+    }
+
+    /// Compares every query of the broadphase against a brute force implementation.
+    fn check(&self, broad_phase: &dyn BroadPhase, rng: &mut Random) {
+        let bodies: Vec<_> = self.bodies.iter().flatten().collect();
+        assert_eq!(broad_phase.body_count(), bodies.len());
+        for &&(body_id, bounds) in &bodies {
+            assert_eq!(broad_phase.body_bounds(body_id), Some(bounds));
+            assert_eq!(
+                broad_phase.object_layer(body_id),
+                Some(Self::layer(body_id))
+            );
+        }
+        let sorted = |mut hits: Vec<BodyID>| {
+            hits.sort();
+            hits
+        };
+
+        for _ in 0..10 {
+            let bounds = AABox::from_center_and_half_extent(rng.vec3() * 50.0, Vec3::splat(8.0));
+            let mut collector = AllHitCollector::new();
+            broad_phase.collide_aabox(&bounds, &mut collector, &(), &(), &());
+            assert_eq!(
+                sorted(collector.hits),
+                self.filter(|body| body.overlaps(&bounds))
+            );
+
+            let (center, radius) = (rng.vec3() * 50.0, 10.0);
+            let mut collector = AllHitCollector::new();
+            broad_phase.collide_sphere(center, radius, &mut collector, &(), &(), &());
+            assert_eq!(
+                sorted(collector.hits),
+                self.filter(|body| body.distance_squared(center) <= radius * radius)
+            );
+
+            let point = match bodies.len() {
+                0 => Vec3::ZERO,
+                len => bodies[rng.index(len)].1.center(),
+            };
+            let mut collector = AllHitCollector::new();
+            broad_phase.collide_point(point, &mut collector, &(), &(), &());
+            assert_eq!(
+                sorted(collector.hits),
+                self.filter(|body| body.contains_point(point))
+            );
+
+            let ray = RayCast::new(rng.vec3() * 50.0, rng.vec3() * 100.0);
+            let fraction = |body: &AABox| ray_aabox(ray.origin, ray.direction, body.min, body.max);
+            let mut collector = AllHitCollector::<BroadPhaseCastResult>::new();
+            broad_phase.cast_ray(&ray, &mut collector, &(), &(), &());
+            let hits = collector.hits.iter().map(|hit| hit.body_id).collect();
+            assert_eq!(sorted(hits), self.filter(|body| fraction(body) <= 1.0));
+            for hit in &collector.hits {
+                let bounds = self.bodies[hit.body_id.index() as usize].unwrap().1;
+                assert!((hit.fraction - fraction(&bounds)).abs() < 1.0e-5);
+            }
+            let mut closest = ClosestHitCollector::new();
+            broad_phase.cast_ray(&ray, &mut closest, &(), &(), &());
+            let expected = collector
+                .hits
+                .iter()
+                .map(|hit| hit.fraction)
+                .reduce(f32::min);
+            assert_eq!(closest.hit.map(|hit| hit.fraction), expected);
+
+            // A query from a body in the first layer only finds the layers it collides with.
+            let layer = ObjectLayer(0);
+            let mut collector = AllHitCollector::new();
+            broad_phase.collide_aabox(
+                &bounds,
+                &mut collector,
+                &DefaultBroadPhaseLayerFilter::new(&self.object_vs_broad_phase_filter, layer),
+                &DefaultObjectLayerFilter::new(&self.pair_filter, layer),
+                &(),
+            );
+            assert_eq!(
+                sorted(collector.hits),
+                self.filter_layers(
+                    |other| self.pair_filter.should_collide(layer, other),
+                    |body| body.overlaps(&bounds)
+                )
+            );
+        }
+
+        // Every other body is active.
+        let active: Vec<_> = bodies.iter().step_by(2).map(|&&(id, _)| id).collect();
+        let mut pairs = Vec::new();
+        broad_phase.find_colliding_pairs(
+            &active,
+            0.0,
+            &self.object_vs_broad_phase_filter,
+            &self.pair_filter,
+            &(),
+            &mut |a, b| pairs.push((a, b)),
+        );
+        pairs.sort();
+        let mut expected = Vec::new();
+        for &body_id in &active {
+            let bounds = self.bodies[body_id.index() as usize].unwrap().1;
+            let layer = Self::layer(body_id);
+            let collides = |other| self.pair_filter.should_collide(layer, other);
+            for other in self.filter_layers(collides, |body| body.overlaps(&bounds)) {
+                if other != body_id && !(active.contains(&other) && other < body_id) {
+                    expected.push((body_id, other));
+                }
+            }
+        }
+        expected.sort();
+        assert_eq!(pairs, expected);
+    }
+}
+
+/// Adds, moves and removes bodies, and updates the broadphase every step when `rebuild` is set.
+pub fn stress(new_broad_phase: NewBroadPhase, rebuild: bool) {
+    const BODY_COUNT: usize = 1000;
+    let mut rng = Random(0x1234_5678);
+    let (mut scene, mut broad_phase) = Scene::new(BODY_COUNT, new_broad_phase);
+    let all: Vec<_> = (0..BODY_COUNT).collect();
+
+    // Add the bodies in batches, each of which is inserted next to the existing bodies.
+    for batch in all.chunks(100) {
+        scene.add(&mut *broad_phase, batch, &mut rng);
+    }
+    scene.check(&*broad_phase, &mut rng);
+
+    for _ in 0..10 {
+        let indices: Vec<_> = (0..300).map(|_| rng.index(BODY_COUNT)).collect();
+        let moved = scene.moved(&indices, &mut rng);
+        broad_phase.notify_bodies_aabb_changed(&moved);
+        let indices: Vec<_> = (0..50).map(|_| rng.index(BODY_COUNT)).collect();
+        scene.remove(&mut *broad_phase, &indices);
+        let indices: Vec<_> = (0..60).map(|_| rng.index(BODY_COUNT)).collect();
+        scene.add(&mut *broad_phase, &indices, &mut rng);
+        if rebuild {
+            broad_phase.update();
+        }
+        scene.check(&*broad_phase, &mut rng);
+    }
+
+    // Removing everything empties the broadphase.
+    scene.remove(&mut *broad_phase, &all);
+    scene.check(&*broad_phase, &mut rng);
+    scene.add(&mut *broad_phase, &all[..10], &mut rng);
+    broad_phase.optimize();
+    scene.check(&*broad_phase, &mut rng);
+}
+
+pub fn stale_body_ids(new_broad_phase: NewBroadPhase) {
+    let mut broad_phase = new_broad_phase(Arc::new(layer_interface()));
+    let bounds = AABox::from_center_and_half_extent(Vec3::ZERO, Vec3::ONE);
+    let old = BodyID::new(5, 0);
+    let new = BodyID::new(5, 1);
+    broad_phase.add_bodies(&[(old, bounds, ObjectLayer(0))]);
+    assert!(broad_phase.contains(old));
+    assert!(!broad_phase.contains(new));
+
+    // A stale ID does not remove the body that reused its index.
+    broad_phase.remove_bodies(&[old]);
+    broad_phase.add_bodies(&[(new, bounds, ObjectLayer(2))]);
+    broad_phase.remove_bodies(&[old]);
+    assert!(broad_phase.contains(new));
+
+    let mut collector = AnyHitCollector::new();
+    broad_phase.collide_point(Vec3::ZERO, &mut collector, &(), &(), &());
+    assert_eq!(collector.hit, Some(new));
+    assert_eq!(broad_phase.broad_phase_layer(new), Some(BroadPhaseLayer(1)));
+}
+
+/// Moves bodies from multiple threads at once.
+#[cfg(not(target_family = "wasm"))]
+pub fn concurrent_updates(new_broad_phase: NewBroadPhase) {
+    const BODY_COUNT: usize = 2000;
+    let mut rng = Random(0x8765_4321);
+    let (mut scene, mut broad_phase) = Scene::new(BODY_COUNT, new_broad_phase);
+    let all: Vec<_> = (0..BODY_COUNT).collect();
+    scene.add(&mut *broad_phase, &all, &mut rng);
+
+    // Every thread moves its own bodies, which share the state of their layer.
+    let moved = scene.moved(&all, &mut rng);
+    std::thread::scope(|scope| {
+        for chunk in moved.chunks(BODY_COUNT / 4) {
+            let broad_phase = &*broad_phase;
+            scope.spawn(move || broad_phase.notify_bodies_aabb_changed(chunk));
+        }
+    });
+    scene.check(&*broad_phase, &mut rng);
+    broad_phase.update();
+    scene.check(&*broad_phase, &mut rng);
+}
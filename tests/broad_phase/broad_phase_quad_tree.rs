@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use phys_rs::broad_phase::{BroadPhase, BroadPhaseLayerInterface, BroadPhaseQuadTree};
+
+use super::broad_phase::{stale_body_ids, stress};
+
+fn new_broad_phase(layer_interface: Arc<dyn BroadPhaseLayerInterface>) -> Box<dyn BroadPhase> {
+    Box::new(BroadPhaseQuadTree::new(layer_interface))
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_stress_incremental() {
+    stress(new_broad_phase, false);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_stress_rebuild() {
+    stress(new_broad_phase, true);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_stale_body_ids() {
+    stale_body_ids(new_broad_phase);
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn test_concurrent_updates() {
+    super::broad_phase::concurrent_updates(new_broad_phase);
+}
@@ -0,0 +1,5 @@
+#[allow(clippy::module_inception)]
+mod broad_phase;
+mod broad_phase_quad_tree;
+mod broad_phase_sweep_and_prune;
+mod object_vs_broad_phase_layer_filter_table;
@@ -0,0 +1,36 @@
+use phys_rs::{
+    broad_phase::{
+        BroadPhaseLayer, BroadPhaseLayerInterface, BroadPhaseLayerInterfaceTable,
+        ObjectVsBroadPhaseLayerFilter, ObjectVsBroadPhaseLayerFilterTable,
+    },
+    collision::{ObjectLayer, ObjectLayerPairFilterTable},
+};
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_object_vs_broad_phase_layer_filter_table() {
+    // Static bodies and debris are in their own broadphase layers, the rest moves.
+    let [static_layer, player, projectile, debris] = [0, 1, 2, 3].map(ObjectLayer);
+    let [static_tree, moving_tree, debris_tree] = [0, 1, 2].map(BroadPhaseLayer);
+    let mut layer_interface = BroadPhaseLayerInterfaceTable::new(4, 3);
+    layer_interface.map_object_to_broad_phase_layer(player, moving_tree);
+    layer_interface.map_object_to_broad_phase_layer(projectile, moving_tree);
+    layer_interface.map_object_to_broad_phase_layer(debris, debris_tree);
+    assert_eq!(layer_interface.broad_phase_layer_count(), 3);
+    assert_eq!(layer_interface.broad_phase_layer(static_layer), static_tree);
+    assert_eq!(layer_interface.broad_phase_layer(projectile), moving_tree);
+
+    let mut pair_filter = ObjectLayerPairFilterTable::new(4);
+    pair_filter.enable_collision(player, static_layer);
+    pair_filter.enable_collision(projectile, player);
+    pair_filter.enable_collision(debris, static_layer);
+
+    let filter = ObjectVsBroadPhaseLayerFilterTable::new(&layer_interface, &pair_filter, 4);
+    assert!(filter.should_collide(player, static_tree));
+    assert!(filter.should_collide(player, moving_tree));
+    assert!(!filter.should_collide(player, debris_tree));
+    assert!(filter.should_collide(projectile, moving_tree));
+    assert!(!filter.should_collide(projectile, static_tree));
+    assert!(filter.should_collide(static_layer, debris_tree));
+    assert!(!filter.should_collide(debris, moving_tree));
+}
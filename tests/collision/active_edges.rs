@@ -0,0 +1,135 @@
+use phys_rs::{
+    collision::{
+        fix_normal, is_edge_active, ActiveEdgeMode, AllHitCollector, CollideShapeSettings,
+        CollisionDispatch, TransformedShape,
+    },
+    geometry::IndexedTriangle,
+    math::{vec3, Quat, Vec3},
+    shape::{BoxShape, MeshShape},
+};
+
+/// A flat strip of `cells` 1 by 1 squares along the x axis, facing up.
+fn flat_strip(cells: u32) -> MeshShape {
+    let mut vertices = Vec::new();
+    for x in 0..=cells {
+        vertices.push(vec3(x as f32, 0.0, 0.0));
+        vertices.push(vec3(x as f32, 0.0, 1.0));
+    }
+    let mut triangles = Vec::new();
+    for x in 0..cells {
+        let v = x * 2;
+        triangles.push(IndexedTriangle::new(v, v + 1, v + 3, 0));
+        triangles.push(IndexedTriangle::new(v, v + 3, v + 2, 0));
+    }
+    MeshShape::new(&vertices, &triangles).unwrap()
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_is_edge_active() {
+    let cos_threshold = 5.0f32.to_radians().cos();
+    let tilted = |angle: f32| vec3(-angle.sin(), angle.cos(), 0.0);
+
+    // The edge runs along z, the first triangle lies on the positive x side of it and the
+    // second triangle is tilted down for positive angles.
+    assert!(!is_edge_active(Vec3::Y, Vec3::Y, Vec3::Z, cos_threshold));
+    assert!(is_edge_active(Vec3::Y, tilted(0.5), Vec3::Z, cos_threshold));
+    assert!(!is_edge_active(
+        Vec3::Y,
+        tilted(0.05),
+        Vec3::Z,
+        cos_threshold
+    ));
+    assert!(!is_edge_active(
+        Vec3::Y,
+        tilted(-0.5),
+        Vec3::Z,
+        cos_threshold
+    ));
+    assert!(is_edge_active(Vec3::Y, -Vec3::Y, Vec3::Z, cos_threshold));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_fix_normal() {
+    let vertices = [Vec3::ZERO, vec3(0.0, 0.0, 1.0), vec3(1.0, 0.0, 0.0)];
+    let triangle_normal = -Vec3::Y;
+    let normal = vec3(-1.0, -1.0, 0.0).normalize();
+    let point = vec3(0.0, 0.0, 0.5);
+
+    // The point is on the first edge.
+    assert_eq!(
+        fix_normal(vertices, triangle_normal, 0b110, point, normal, Vec3::ZERO),
+        triangle_normal
+    );
+    assert_eq!(
+        fix_normal(vertices, triangle_normal, 0b001, point, normal, Vec3::ZERO),
+        normal
+    );
+    assert_eq!(
+        fix_normal(vertices, triangle_normal, 0b111, point, normal, Vec3::ZERO),
+        normal
+    );
+
+    // A vertex keeps its normal if any of its edges is active.
+    assert_eq!(
+        fix_normal(
+            vertices,
+            triangle_normal,
+            0b010,
+            Vec3::ZERO,
+            normal,
+            Vec3::ZERO
+        ),
+        triangle_normal
+    );
+    assert_eq!(
+        fix_normal(
+            vertices,
+            triangle_normal,
+            0b100,
+            Vec3::ZERO,
+            normal,
+            Vec3::ZERO
+        ),
+        normal
+    );
+
+    // Objects that move along the edge normal more than along the triangle normal keep it.
+    let movement = vec3(-1.0, 0.0, 0.0);
+    assert_eq!(
+        fix_normal(vertices, triangle_normal, 0b000, point, normal, movement),
+        normal
+    );
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_no_ghost_collisions() {
+    let mesh = flat_strip(2);
+    let cube = BoxShape::new(vec3(0.5, 0.5, 0.4)).unwrap();
+    // The box sinks into the floor and overlaps the first square by a small amount.
+    let shape1 = TransformedShape::new(&cube, vec3(1.45, 0.4, 0.5), Quat::IDENTITY);
+    let shape2 = TransformedShape::new(&mesh, Vec3::ZERO, Quat::IDENTITY);
+    let dispatch = CollisionDispatch::new();
+    let mut settings = CollideShapeSettings {
+        active_edge_mode: ActiveEdgeMode::CollideWithAll,
+        ..Default::default()
+    };
+
+    // The edge between the squares pushes the box sideways.
+    let mut collector = AllHitCollector::new();
+    dispatch.collide_shape(&shape1, &shape2, &settings, &mut collector, &());
+    assert!(collector
+        .hits
+        .iter()
+        .any(|hit| hit.penetration_axis.y > -0.9));
+
+    settings.active_edge_mode = ActiveEdgeMode::CollideOnlyWithActive;
+    let mut collector = AllHitCollector::new();
+    dispatch.collide_shape(&shape1, &shape2, &settings, &mut collector, &());
+    assert!(!collector.hits.is_empty());
+    for hit in &collector.hits {
+        assert!((hit.penetration_axis + Vec3::Y).length() < 1.0e-3);
+    }
+}
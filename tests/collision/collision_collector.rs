@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use phys_rs::{
+    collision::{
+        AllHitCollector, AnyHitCollector, ClosestHitCollector, ClosestHitPerBodyCollector,
+        CollideShapeSettings, CollisionCollector, CollisionDispatch, RayCast, TransformedShape,
+    },
+    geometry::IndexedTriangle,
+    math::{vec3, Quat, Vec3},
+    shape::{CompoundSubShape, MeshShape, Shape, SphereShape, StaticCompoundShape},
+};
+
+/// Unit spheres at x = 0, 3, 6 and 9.
+fn sphere_row() -> StaticCompoundShape {
+    let sphere: Arc<dyn Shape> = Arc::new(SphereShape::new(1.0).unwrap());
+    let sub_shapes = [0.0, 3.0, 6.0, 9.0]
+        .map(|x| CompoundSubShape::new(sphere.clone(), vec3(x, 0.0, 0.0), Quat::IDENTITY));
+    StaticCompoundShape::new(&sub_shapes).unwrap()
+}
+
+/// Two 2 by 2 squares facing up at heights zero and one.
+fn stacked_mesh() -> MeshShape {
+    let mut vertices = Vec::new();
+    let mut triangles = Vec::new();
+    for y in [0.0, 1.0] {
+        let first = vertices.len() as u32;
+        vertices.extend_from_slice(&[
+            vec3(-1.0, y, -1.0),
+            vec3(1.0, y, -1.0),
+            vec3(1.0, y, 1.0),
+            vec3(-1.0, y, 1.0),
+        ]);
+        triangles.push(IndexedTriangle::new(first, first + 3, first + 2, 0));
+        triangles.push(IndexedTriangle::new(first, first + 2, first + 1, 0));
+    }
+    MeshShape::new(&vertices, &triangles).unwrap()
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_cast_ray_collectors() {
+    let compound = sphere_row();
+    let shape = TransformedShape::new(&compound, compound.center_of_mass(), Quat::IDENTITY);
+    let ray = RayCast::new(vec3(-5.0, 0.0, 0.0), vec3(20.0, 0.0, 0.0));
+
+    let mut closest = ClosestHitCollector::new();
+    shape.cast_ray(&ray, &mut closest);
+    let hit = closest.hit.unwrap();
+    assert!((hit.fraction - 0.2).abs() < 1.0e-5);
+    assert_eq!(compound.sub_shape_index(hit.sub_shape_id).0, 0);
+
+    let mut any = AnyHitCollector::new();
+    shape.cast_ray(&ray, &mut any);
+    assert!(any.had_hit());
+    assert!(any.should_early_out());
+
+    let mut all = AllHitCollector::new();
+    shape.cast_ray(&ray, &mut all);
+    all.sort();
+    assert_eq!(all.hits.len(), 4);
+    for (i, hit) in all.hits.iter().enumerate() {
+        assert!((hit.fraction - (0.2 + 0.15 * i as f32)).abs() < 1.0e-5);
+        assert_eq!(compound.sub_shape_index(hit.sub_shape_id).0, i as u32);
+    }
+
+    // Every triangle of a mesh is reported, not just the closest one.
+    let mesh = stacked_mesh();
+    let shape = TransformedShape::new(&mesh, Vec3::ZERO, Quat::IDENTITY);
+    let ray = RayCast::new(vec3(0.1, 2.0, 0.2), vec3(0.0, -3.0, 0.0));
+    let mut all = AllHitCollector::new();
+    shape.cast_ray(&ray, &mut all);
+    all.sort();
+    assert_eq!(all.hits.len(), 2);
+    assert!((all.hits[0].fraction - 1.0 / 3.0).abs() < 1.0e-5);
+    assert!((all.hits[1].fraction - 2.0 / 3.0).abs() < 1.0e-5);
+    assert_eq!(mesh.triangle(all.hits[0].sub_shape_id)[0].y, 1.0);
+    assert_eq!(mesh.triangle(all.hits[1].sub_shape_id)[0].y, 0.0);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_closest_hit_per_body() {
+    let compound = sphere_row();
+    let mesh = stacked_mesh();
+    let shapes = [
+        TransformedShape::new(&compound, compound.center_of_mass(), Quat::IDENTITY),
+        TransformedShape::new(&mesh, vec3(4.0, -0.5, 0.0), Quat::IDENTITY),
+    ];
+    let ray = RayCast::new(vec3(4.5, 5.0, 0.0), vec3(0.0, -10.0, 0.0));
+
+    // The ray misses the spheres and only the upper square of the mesh is kept.
+    let mut collector = ClosestHitPerBodyCollector::new();
+    for shape in &shapes {
+        collector.on_body();
+        shape.cast_ray(&ray, &mut collector);
+        collector.on_body_end();
+    }
+    assert_eq!(collector.hits.len(), 1);
+    assert!((collector.hits[0].fraction - 0.45).abs() < 1.0e-5);
+
+    // Every body keeps its own closest hit, the mesh is turned so that its squares are at
+    // x = 1.5 and x = 0.5.
+    let ray = RayCast::new(vec3(4.5, 0.0, 0.0), vec3(-10.0, 0.0, 0.0));
+    let rotation = Quat::from_rotation_z(core::f32::consts::FRAC_PI_2);
+    let shapes = [
+        shapes[0],
+        TransformedShape::new(&mesh, vec3(1.5, 0.0, 0.0), rotation),
+    ];
+    collector.reset();
+    for shape in &shapes {
+        collector.on_body();
+        shape.cast_ray(&ray, &mut collector);
+        collector.on_body_end();
+    }
+    collector.sort();
+    assert_eq!(collector.hits.len(), 2);
+    assert!((collector.hits[0].fraction - 0.05).abs() < 1.0e-5);
+    assert!((collector.hits[1].fraction - 0.3).abs() < 1.0e-5);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_collide_point_collectors() {
+    let sphere: Arc<dyn Shape> = Arc::new(SphereShape::new(1.0).unwrap());
+    let compound = StaticCompoundShape::new(&[
+        CompoundSubShape::new(sphere.clone(), vec3(0.0, 0.0, 0.0), Quat::IDENTITY),
+        CompoundSubShape::new(sphere.clone(), vec3(1.0, 0.0, 0.0), Quat::IDENTITY),
+        CompoundSubShape::new(sphere, vec3(5.0, 0.0, 0.0), Quat::IDENTITY),
+    ])
+    .unwrap();
+    let shape = TransformedShape::new(&compound, compound.center_of_mass(), Quat::IDENTITY);
+
+    let mut all = AllHitCollector::new();
+    shape.collide_point(vec3(0.5, 0.0, 0.0), &mut all);
+    let mut indices: Vec<_> = all
+        .hits
+        .iter()
+        .map(|hit| compound.sub_shape_index(hit.sub_shape_id).0)
+        .collect();
+    indices.sort();
+    assert_eq!(indices, [0, 1]);
+
+    let mut any = AnyHitCollector::new();
+    shape.collide_point(vec3(0.5, 0.0, 0.0), &mut any);
+    assert!(any.had_hit());
+
+    let mut any = AnyHitCollector::new();
+    shape.collide_point(vec3(3.0, 0.0, 0.0), &mut any);
+    assert!(!any.had_hit());
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_collide_shape_collectors() {
+    let compound = sphere_row();
+    let probe = SphereShape::new(1.0).unwrap();
+    let shape1 = TransformedShape::new(&probe, vec3(1.2, 0.0, 0.0), Quat::IDENTITY);
+    let shape2 = TransformedShape::new(&compound, compound.center_of_mass(), Quat::IDENTITY);
+    let dispatch = CollisionDispatch::new();
+    let settings = CollideShapeSettings::default();
+
+    // The deepest contact is the closest hit.
+    let mut closest = ClosestHitCollector::new();
+    dispatch.collide_shape(&shape1, &shape2, &settings, &mut closest, &());
+    let hit = closest.hit.unwrap();
+    assert!((hit.penetration_depth - 0.8).abs() < 1.0e-5);
+    assert_eq!(compound.sub_shape_index(hit.sub_shape_id2).0, 0);
+
+    let mut all = AllHitCollector::new();
+    dispatch.collide_shape(&shape1, &shape2, &settings, &mut all, &());
+    all.sort();
+    assert_eq!(all.hits.len(), 2);
+    assert!((all.hits[1].penetration_depth - 0.2).abs() < 1.0e-5);
+
+    // The reversed pair reports the same contacts through the collector.
+    let mut any = AnyHitCollector::new();
+    dispatch.collide_shape(&shape2, &shape1, &settings, &mut any, &());
+    let hit = any.hit.unwrap();
+    assert_eq!(hit.sub_shape_id2, shape1.sub_shape_id());
+    assert!(hit.penetration_depth > 0.0);
+}
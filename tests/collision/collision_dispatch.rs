@@ -0,0 +1,343 @@
+use std::sync::Arc;
+
+use phys_rs::{
+    body::MassProperties,
+    collision::{
+        AllHitCollector, CollideShapeResult, CollideShapeSettings, CollisionCollector,
+        CollisionDispatch, ContactManifold, RayCast, RayCastResult, TransformedShape,
+    },
+    geometry::AABox,
+    math::{vec3, Quat, Vec3},
+    shape::{
+        BoxShape, CompoundSubShape, HeightFieldSettings, HeightFieldShape, RotatedTranslatedShape,
+        ScaledShape, Shape, ShapeSubType, SphereShape, StaticCompoundShape, SubShapeID,
+    },
+};
+
+use super::{assert_near, floor_mesh, sphere_field};
+
+fn collide(shape1: &TransformedShape, shape2: &TransformedShape) -> Vec<CollideShapeResult> {
+    collide_with(shape1, shape2, &CollideShapeSettings::default())
+}
+
+fn collide_with(
+    shape1: &TransformedShape,
+    shape2: &TransformedShape,
+    settings: &CollideShapeSettings,
+) -> Vec<CollideShapeResult> {
+    let mut collector = AllHitCollector::new();
+    CollisionDispatch::new().collide_shape(shape1, shape2, settings, &mut collector, &());
+    collector.hits
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_sphere_vs_sphere() {
+    let sphere = SphereShape::new(1.0).unwrap();
+    let shape1 = TransformedShape::new(&sphere, Vec3::ZERO, Quat::IDENTITY);
+    let shape2 = TransformedShape::new(&sphere, vec3(0.0, 1.5, 0.0), Quat::IDENTITY)
+        .with_scale(Vec3::splat(0.5));
+
+    let results = collide(&shape1, &shape2);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].penetration_axis, Vec3::Y);
+    assert_eq!(results[0].penetration_depth, 0.0);
+    assert_eq!(results[0].contact_point1, Vec3::Y);
+    assert_eq!(results[0].contact_point2, Vec3::Y);
+
+    // Separated shapes are only reported within the maximum separation distance.
+    let shape2 = TransformedShape::new(&sphere, vec3(2.2, 0.0, 0.0), Quat::IDENTITY);
+    assert!(collide(&shape1, &shape2).is_empty());
+    let settings = CollideShapeSettings {
+        max_separation_distance: 0.5,
+        ..Default::default()
+    };
+    let results = collide_with(&shape1, &shape2, &settings);
+    assert_eq!(results.len(), 1);
+    assert!((results[0].penetration_depth + 0.2).abs() < 1.0e-5);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_convex_vs_convex() {
+    let cube = BoxShape::new(Vec3::ONE).unwrap();
+    let sphere = SphereShape::new(0.5).unwrap();
+
+    // Only the convex radius of the box overlaps, which GJK resolves.
+    let shape1 = TransformedShape::new(&cube, Vec3::ZERO, Quat::IDENTITY);
+    let shape2 = TransformedShape::new(&sphere, vec3(0.2, 1.45, -0.3), Quat::IDENTITY);
+    let results = collide(&shape1, &shape2);
+    assert_eq!(results.len(), 1);
+    assert_near(results[0].penetration_axis, Vec3::Y);
+    assert!((results[0].penetration_depth - 0.05).abs() < 1.0e-3);
+    assert_near(results[0].contact_point1, vec3(0.2, 1.0, -0.3));
+    assert_near(results[0].contact_point2, vec3(0.2, 0.95, -0.3));
+
+    // Deep penetration is resolved by EPA.
+    let rotation = Quat::from_rotation_y(0.3);
+    let shape1 = TransformedShape::new(&cube, vec3(100.0, 0.0, 0.0), rotation);
+    let shape2 = TransformedShape::new(&cube, vec3(100.0, 1.6, 0.0), rotation);
+    let settings = CollideShapeSettings {
+        collect_faces: true,
+        ..Default::default()
+    };
+    let results = collide_with(&shape1, &shape2, &settings);
+    assert_eq!(results.len(), 1);
+    let result = &results[0];
+    assert_near(result.penetration_axis, Vec3::Y);
+    assert!((result.penetration_depth - 0.4).abs() < 1.0e-3);
+    assert_eq!((result.face1.len(), result.face2.len()), (4, 4));
+    assert!(result
+        .face1
+        .iter()
+        .all(|vertex| (vertex.y - 1.0).abs() < 1.0e-5));
+    assert!(result
+        .face2
+        .iter()
+        .all(|vertex| (vertex.y - 0.6).abs() < 1.0e-5));
+
+    let manifold = ContactManifold::from_faces(
+        shape1.position,
+        result.contact_point1,
+        result.contact_point2,
+        result.penetration_axis,
+        0.05,
+        &result.face1,
+        &result.face2,
+    );
+    assert_eq!(manifold.points.len(), 4);
+    for point in &manifold.points {
+        assert!((point.penetration_depth - 0.4).abs() < 1.0e-3);
+    }
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_convex_vs_mesh() {
+    let mesh = floor_mesh();
+    let sphere = SphereShape::new(0.5).unwrap();
+    let floor = TransformedShape::new(&mesh, vec3(0.0, -1.0, 0.0), Quat::IDENTITY);
+    let ball = TransformedShape::new(&sphere, vec3(0.5, -0.6, 0.5), Quat::IDENTITY);
+
+    let results = collide(&ball, &floor);
+    assert!(!results.is_empty());
+    for result in &results {
+        assert_near(result.penetration_axis, -Vec3::Y);
+        assert!((result.penetration_depth - 0.1).abs() < 1.0e-3);
+        assert_near(result.contact_point2, vec3(0.5, -1.0, 0.5));
+        assert!(mesh
+            .triangle(result.sub_shape_id2)
+            .iter()
+            .all(|vertex| vertex.y == 0.0));
+    }
+
+    // The mesh against the sphere is the reversed pair.
+    let reversed = collide(&floor, &ball);
+    assert_eq!(reversed.len(), results.len());
+    for (reversed, result) in reversed.iter().zip(&results) {
+        assert_eq!(*reversed, result.reversed());
+    }
+
+    // A mirrored mesh faces the other way.
+    let mirrored = floor.with_scale(vec3(1.0, -1.0, 1.0));
+    let settings = CollideShapeSettings {
+        collect_faces: true,
+        ..Default::default()
+    };
+    let results = collide_with(&ball, &mirrored, &settings);
+    assert!(!results.is_empty());
+    let face = &results[0].face2;
+    assert!((face[1] - face[0]).cross(face[2] - face[0]).y < 0.0);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_convex_vs_height_field() {
+    let samples = [0.5; 25];
+    let mut settings = HeightFieldSettings::new(&samples, 5);
+    settings.offset = vec3(-2.0, 0.0, -2.0);
+    let height_field = HeightFieldShape::new(&settings).unwrap();
+    let cube = BoxShape::new(Vec3::splat(0.5)).unwrap();
+
+    let terrain = TransformedShape::new(&height_field, Vec3::ZERO, Quat::IDENTITY);
+    let crate_shape = TransformedShape::new(&cube, vec3(0.3, 0.9, 0.3), Quat::IDENTITY);
+    let results = collide(&crate_shape, &terrain);
+    assert!(!results.is_empty());
+    for result in &results {
+        assert_near(result.penetration_axis, -Vec3::Y);
+        assert!((result.penetration_depth - 0.1).abs() < 1.0e-3);
+    }
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_convex_vs_sdf() {
+    let sdf = sphere_field();
+    let sphere = SphereShape::new(0.5).unwrap();
+
+    // The field is scaled to a radius of two.
+    let field = TransformedShape::new(&sdf, vec3(5.0, 0.0, 0.0), Quat::IDENTITY)
+        .with_scale(Vec3::splat(2.0));
+    let ball = TransformedShape::new(&sphere, vec3(5.0, 2.3, 0.0), Quat::IDENTITY);
+    let results = collide(&ball, &field);
+    assert!(!results.is_empty());
+    let deepest = results
+        .iter()
+        .max_by(|a, b| a.penetration_depth.total_cmp(&b.penetration_depth))
+        .unwrap();
+    assert!((deepest.penetration_axis + Vec3::Y).length() < 0.01);
+    assert!((deepest.penetration_depth - 0.2).abs() < 0.02);
+    assert!((deepest.contact_point2 - vec3(5.0, 2.0, 0.0)).length() < 0.02);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_compound_and_decorated() {
+    let sphere: Arc<dyn Shape> = Arc::new(SphereShape::new(0.5).unwrap());
+    let cube: Arc<dyn Shape> = Arc::new(BoxShape::new(Vec3::splat(0.5)).unwrap());
+    let compound = StaticCompoundShape::new(&[
+        CompoundSubShape::new(sphere.clone(), vec3(-2.0, 0.0, 0.0), Quat::IDENTITY),
+        CompoundSubShape::new(cube.clone(), vec3(2.0, 0.0, 0.0), Quat::IDENTITY),
+    ])
+    .unwrap();
+    let probe = SphereShape::new(0.5).unwrap();
+
+    // Only the box is touched, the sub shape ID identifies it.
+    let center_of_mass = compound.center_of_mass();
+    let shape1 = TransformedShape::new(&compound, center_of_mass, Quat::IDENTITY);
+    let shape2 = TransformedShape::new(&probe, vec3(2.0, 0.9, 0.0), Quat::IDENTITY);
+    let results = collide(&shape1, &shape2);
+    assert_eq!(results.len(), 1);
+    assert_eq!(compound.sub_shape_index(results[0].sub_shape_id1).0, 1);
+    assert_near(results[0].penetration_axis, Vec3::Y);
+    assert!((results[0].penetration_depth - 0.1).abs() < 1.0e-3);
+
+    // A sphere against the compound uses the reversed pair.
+    let reversed = collide(&shape2, &shape1);
+    assert_eq!(reversed.len(), 1);
+    assert_eq!(reversed[0].sub_shape_id2, results[0].sub_shape_id1);
+    assert_near(reversed[0].penetration_axis, -Vec3::Y);
+
+    // Compounds collide with compounds by recursing into both.
+    let position = center_of_mass + vec3(0.0, 0.0, 0.9);
+    let shape2 = TransformedShape::new(&compound, position, Quat::IDENTITY);
+    assert_eq!(collide(&shape1, &shape2).len(), 2);
+
+    // Decorators place their inner shape.
+    let scaled = ScaledShape::new(sphere.clone(), Vec3::splat(2.0)).unwrap();
+    let shape1 = TransformedShape::new(&scaled, Vec3::ZERO, Quat::IDENTITY);
+    let shape2 = TransformedShape::new(&probe, vec3(1.4, 0.0, 0.0), Quat::IDENTITY);
+    let results = collide(&shape1, &shape2);
+    assert_eq!(results.len(), 1);
+    assert!((results[0].penetration_depth - 0.1).abs() < 1.0e-5);
+
+    let rotated = RotatedTranslatedShape::new(
+        Arc::new(BoxShape::new(vec3(2.0, 0.5, 0.5)).unwrap()),
+        Vec3::ZERO,
+        Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+    );
+    let shape1 = TransformedShape::new(&rotated, Vec3::ZERO, Quat::IDENTITY);
+    let shape2 = TransformedShape::new(&probe, vec3(0.0, 2.4, 0.0), Quat::IDENTITY);
+    let results = collide(&shape1, &shape2);
+    assert_eq!(results.len(), 1);
+    assert_near(results[0].penetration_axis, Vec3::Y);
+    assert!((results[0].penetration_depth - 0.1).abs() < 1.0e-3);
+}
+
+/// A shape that is defined by the test, it is a plane at y = 0.
+#[derive(Debug)]
+struct GroundPlane;
+
+impl Shape for GroundPlane {
+    fn sub_type(&self) -> ShapeSubType {
+        ShapeSubType::User(0)
+    }
+
+    fn local_bounds(&self) -> AABox {
+        AABox::new(vec3(-1.0e3, -1.0, -1.0e3), vec3(1.0e3, 0.0, 1.0e3))
+    }
+
+    fn inner_radius(&self) -> f32 {
+        0.0
+    }
+
+    fn volume(&self) -> f32 {
+        0.0
+    }
+
+    fn mass_properties(&self) -> MassProperties {
+        MassProperties::default()
+    }
+
+    fn surface_normal(&self, _sub_shape_id: SubShapeID, _local_position: Vec3) -> Vec3 {
+        Vec3::Y
+    }
+
+    fn cast_ray(&self, _ray: &RayCast, _hit: &mut RayCastResult) -> bool {
+        false
+    }
+
+    fn collide_point(&self, point: Vec3) -> bool {
+        point.y <= 0.0
+    }
+}
+
+fn collide_plane_vs_sphere(
+    _dispatch: &CollisionDispatch,
+    shape1: &TransformedShape,
+    shape2: &TransformedShape,
+    _settings: &CollideShapeSettings,
+    collector: &mut dyn CollisionCollector<CollideShapeResult>,
+) {
+    let radius = shape2.shape.downcast_ref::<SphereShape>().unwrap().radius();
+    let deepest = shape2.position - Vec3::Y * radius;
+    if deepest.y < shape1.position.y {
+        collector.add_hit(&CollideShapeResult {
+            contact_point1: vec3(deepest.x, shape1.position.y, deepest.z),
+            contact_point2: deepest,
+            penetration_axis: Vec3::Y,
+            penetration_depth: shape1.position.y - deepest.y,
+            ..Default::default()
+        });
+    }
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_user_shape() {
+    let mut dispatch = CollisionDispatch::new();
+    let plane = GroundPlane;
+    let sphere = SphereShape::new(1.0).unwrap();
+    let shape1 = TransformedShape::new(&plane, Vec3::ZERO, Quat::IDENTITY);
+    let shape2 = TransformedShape::new(&sphere, vec3(0.0, 0.75, 0.0), Quat::IDENTITY);
+    let settings = CollideShapeSettings::default();
+    let mut collector = AllHitCollector::new();
+
+    // Nothing is known about the shape until it is registered.
+    dispatch.collide_shape(&shape1, &shape2, &settings, &mut collector, &());
+    assert!(!collector.had_hit());
+
+    dispatch.register_sub_type(ShapeSubType::User(0));
+    dispatch.register_collide_shape(
+        ShapeSubType::User(0),
+        ShapeSubType::Sphere,
+        collide_plane_vs_sphere,
+    );
+    dispatch.collide_shape(&shape2, &shape1, &settings, &mut collector, &());
+    assert_eq!(collector.hits.len(), 1);
+    assert_eq!(collector.hits[0].penetration_axis, -Vec3::Y);
+    assert_eq!(collector.hits[0].penetration_depth, 0.25);
+    assert_eq!(collector.hits[0].contact_point1, vec3(0.0, -0.25, 0.0));
+
+    // Compounds containing the shape now collide through it.
+    let compound = StaticCompoundShape::new(&[CompoundSubShape::new(
+        Arc::new(GroundPlane),
+        vec3(0.0, 0.5, 0.0),
+        Quat::IDENTITY,
+    )])
+    .unwrap();
+    let shape1 = TransformedShape::new(&compound, Vec3::ZERO, Quat::IDENTITY);
+    collector.reset();
+    dispatch.collide_shape(&shape1, &shape2, &settings, &mut collector, &());
+    assert_eq!(collector.hits.len(), 1);
+}
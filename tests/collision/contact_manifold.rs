@@ -0,0 +1,119 @@
+use core::f32::consts::FRAC_PI_4;
+
+use phys_rs::{
+    collision::{ContactManifold, TransformedShape, MAX_CONTACT_POINTS},
+    math::{vec3, Quat, Vec3},
+    shape::{BoxShape, CapsuleShape, Shape},
+};
+
+fn manifold(
+    shape1: &dyn Shape,
+    position1: Vec3,
+    shape2: &dyn Shape,
+    rotation2: Quat,
+    position2: Vec3,
+    contact: (Vec3, Vec3, Vec3),
+) -> ContactManifold {
+    let (point1, point2, axis) = contact;
+    let (mut face1, mut face2) = (Vec::new(), Vec::new());
+    TransformedShape::new(shape1, position1, Quat::IDENTITY).get_supporting_face(axis, &mut face1);
+    TransformedShape::new(shape2, position2, rotation2).get_supporting_face(-axis, &mut face2);
+    ContactManifold::from_faces(position1, point1, point2, axis, 0.05, &face1, &face2)
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_box_on_box() {
+    let ground = BoxShape::new(Vec3::ONE).unwrap();
+    let cube = BoxShape::new(Vec3::splat(0.5)).unwrap();
+    let position1 = vec3(10.0, 0.0, 0.0);
+    let contact = (
+        position1 + vec3(0.0, 1.0, 0.0),
+        position1 + vec3(0.0, 0.99, 0.0),
+        Vec3::Y,
+    );
+    let position2 = position1 + vec3(0.0, 1.49, 0.0);
+    let manifold = manifold(
+        &ground,
+        position1,
+        &cube,
+        Quat::IDENTITY,
+        position2,
+        contact,
+    );
+
+    assert_eq!(manifold.base_offset, position1);
+    assert_eq!(manifold.normal, Vec3::Y);
+    assert_eq!(manifold.points.len(), 4);
+    for (i, point) in manifold.points.iter().enumerate() {
+        assert!((point.penetration_depth - 0.01).abs() < 1.0e-5);
+        assert!((point.position1.y - 1.0).abs() < 1.0e-5);
+        assert!((point.position2.y - 0.99).abs() < 1.0e-5);
+        assert!((point.position1.x.abs() - 0.5).abs() < 1.0e-5);
+        assert!((point.position1.z.abs() - 0.5).abs() < 1.0e-5);
+        assert!((manifold.world_position1(i) - position1 - point.position1).length() < 1.0e-5);
+    }
+    assert!((manifold.penetration_depth() - 0.01).abs() < 1.0e-5);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_rotated_box_is_pruned() {
+    // A cube rotated a quarter turn on a slightly smaller face overlaps in an octagon.
+    let ground = BoxShape::new(vec3(0.9, 1.0, 0.9)).unwrap();
+    let cube = BoxShape::new(Vec3::splat(0.8)).unwrap();
+    let contact = (vec3(0.0, 1.0, 0.0), vec3(0.0, 0.98, 0.0), Vec3::Y);
+    let rotation = Quat::from_rotation_y(FRAC_PI_4);
+    let manifold = manifold(
+        &ground,
+        Vec3::ZERO,
+        &cube,
+        rotation,
+        vec3(0.0, 1.78, 0.0),
+        contact,
+    );
+
+    assert_eq!(manifold.points.len(), MAX_CONTACT_POINTS);
+    for point in &manifold.points {
+        assert!((point.penetration_depth - 0.02).abs() < 1.0e-5);
+        assert!(point.position1.x.abs() <= 0.9 + 1.0e-5);
+        assert!(point.position1.z.abs() <= 0.9 + 1.0e-5);
+    }
+
+    // The kept points are in order around the polygon, so consecutive edges turn the same way.
+    let points: Vec<Vec3> = manifold.points.iter().map(|p| p.position1).collect();
+    for i in 0..points.len() {
+        let a = points[(i + 1) % points.len()] - points[i];
+        let b = points[(i + 2) % points.len()] - points[(i + 1) % points.len()];
+        assert!(a.cross(b).y < 0.0);
+    }
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_capsule_against_box() {
+    let ground = BoxShape::new(Vec3::ONE).unwrap();
+    let capsule = CapsuleShape::new(0.5, 0.5).unwrap();
+    let contact = (vec3(1.0, 0.0, 0.0), vec3(0.99, 0.0, 0.0), Vec3::X);
+    let manifold = manifold(
+        &ground,
+        Vec3::ZERO,
+        &capsule,
+        Quat::IDENTITY,
+        vec3(1.49, 0.0, 0.0),
+        contact,
+    );
+
+    assert_eq!(manifold.points.len(), 2);
+    for point in &manifold.points {
+        assert!((point.penetration_depth - 0.01).abs() < 1.0e-5);
+        assert!((point.position1.x - 1.0).abs() < 1.0e-5);
+        assert!((point.position1.y.abs() - 0.5).abs() < 1.0e-5);
+    }
+
+    // Without faces the contact points themselves are used.
+    let manifold =
+        ContactManifold::from_faces(Vec3::ZERO, contact.0, contact.1, Vec3::X, 0.05, &[], &[]);
+    assert_eq!(manifold.points.len(), 1);
+    assert_eq!(manifold.points[0].position1, contact.0);
+}
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use phys_rs::collision::{CollisionGroup, GroupFilter, GroupFilterTable};
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_ragdoll() {
+    // A chain of limbs where connected limbs do not collide.
+    const LIMB_COUNT: u32 = 5;
+    let mut table = GroupFilterTable::new(LIMB_COUNT);
+    for limb in 1..LIMB_COUNT {
+        table.disable_collision(limb - 1, limb);
+    }
+    for limb1 in 0..LIMB_COUNT {
+        for limb2 in 0..LIMB_COUNT {
+            let expected = limb1.abs_diff(limb2) > 1;
+            assert_eq!(table.is_collision_enabled(limb1, limb2), expected);
+        }
+    }
+
+    let filter: Arc<dyn GroupFilter> = Arc::new(table.clone());
+    let limb = |group_id, sub_group_id| CollisionGroup::new(filter.clone(), group_id, sub_group_id);
+    assert!(!limb(0, 1).can_collide(&limb(0, 2)));
+    assert!(limb(0, 1).can_collide(&limb(0, 3)));
+    assert!(!limb(0, 1).can_collide(&limb(0, 1)));
+
+    // Limbs of different ragdolls and bodies without a group always collide.
+    assert!(limb(0, 1).can_collide(&limb(1, 2)));
+    assert!(limb(0, 1).can_collide(&CollisionGroup::default()));
+    assert!(CollisionGroup::default().can_collide(&limb(0, 1)));
+    assert!(CollisionGroup::default().can_collide(&CollisionGroup::default()));
+
+    table.enable_collision(2, 1);
+    assert!(table.is_collision_enabled(1, 2));
+    assert_eq!(table.sub_group_count(), LIMB_COUNT);
+}
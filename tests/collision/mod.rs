@@ -0,0 +1,51 @@
+mod active_edges;
+mod collision_collector;
+mod collision_dispatch;
+mod contact_manifold;
+mod group_filter_table;
+mod object_layer_pair_filter_table;
+mod shape_cast;
+
+use phys_rs::{
+    geometry::IndexedTriangle,
+    math::{vec3, UVec3, Vec3},
+    shape::{MeshShape, SdfSettings, SdfShape},
+};
+
+pub fn assert_near(a: Vec3, b: Vec3) {
+    assert!((a - b).length() < 2.0e-3, "{a:?} != {b:?}");
+}
+
+/// A 4 by 4 square at height zero, facing up.
+pub fn floor_mesh() -> MeshShape {
+    let vertices = [
+        vec3(-2.0, 0.0, -2.0),
+        vec3(2.0, 0.0, -2.0),
+        vec3(2.0, 0.0, 2.0),
+        vec3(-2.0, 0.0, 2.0),
+    ];
+    let triangles = [
+        IndexedTriangle::new(0, 3, 2, 0),
+        IndexedTriangle::new(0, 2, 1, 0),
+    ];
+    MeshShape::new(&vertices, &triangles).unwrap()
+}
+
+/// A field of a sphere with radius one around the origin.
+pub fn sphere_field() -> SdfShape {
+    const SAMPLES: u32 = 31;
+    let mut distances = Vec::new();
+    for z in 0..SAMPLES {
+        for y in 0..SAMPLES {
+            for x in 0..SAMPLES {
+                let position = vec3(x as f32, y as f32, z as f32) * 0.1 - 1.5;
+                distances.push(position.length() - 1.0);
+            }
+        }
+    }
+    let settings = SdfSettings {
+        offset: Vec3::splat(-1.5),
+        ..SdfSettings::new(&distances, UVec3::splat(SAMPLES), 0.1)
+    };
+    SdfShape::new(&settings).unwrap()
+}
@@ -0,0 +1,30 @@
+use phys_rs::collision::{
+    DefaultObjectLayerFilter, ObjectLayer, ObjectLayerFilter, ObjectLayerPairFilter,
+    ObjectLayerPairFilterTable,
+};
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_object_layer_pair_filter_table() {
+    let [static_layer, moving, debris, trigger] = [0, 1, 2, 3].map(ObjectLayer);
+    let mut table = ObjectLayerPairFilterTable::new(4);
+    assert!(!table.should_collide(moving, moving));
+
+    table.enable_collision(static_layer, moving);
+    table.enable_collision(moving, moving);
+    table.enable_collision(static_layer, debris);
+    table.enable_collision(trigger, moving);
+    assert!(table.should_collide(moving, static_layer));
+    assert!(table.should_collide(moving, trigger));
+    assert!(table.should_collide(debris, static_layer));
+    assert!(!table.should_collide(debris, moving));
+    assert!(!table.should_collide(static_layer, static_layer));
+
+    table.disable_collision(moving, trigger);
+    assert!(!table.should_collide(trigger, moving));
+
+    let filter = DefaultObjectLayerFilter::new(&table, debris);
+    assert!(filter.should_collide(static_layer));
+    assert!(!filter.should_collide(debris));
+    assert!(().should_collide(debris));
+}
@@ -0,0 +1,231 @@
+use std::sync::Arc;
+
+use phys_rs::{
+    collision::{
+        AllHitCollector, BackFaceMode, ClosestHitCollector, CollisionDispatch, ShapeCast,
+        ShapeCastResult, ShapeCastSettings, TransformedShape,
+    },
+    math::{vec3, Quat, Vec3},
+    shape::{BoxShape, CompoundSubShape, Shape, SphereShape, StaticCompoundShape},
+};
+
+use super::{assert_near, floor_mesh, sphere_field};
+
+fn cast(
+    cast: &ShapeCast,
+    target: &TransformedShape,
+    settings: &ShapeCastSettings,
+) -> Option<ShapeCastResult> {
+    let mut collector = ClosestHitCollector::new();
+    CollisionDispatch::new().cast_shape(cast, target, settings, &mut collector, &());
+    collector.hit
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_cast_convex_vs_convex() {
+    let sphere = SphereShape::new(0.5).unwrap();
+    let cube = BoxShape::new(Vec3::ONE).unwrap();
+    let target = TransformedShape::new(&cube, vec3(5.0, 0.0, 0.0), Quat::IDENTITY);
+    let settings = ShapeCastSettings {
+        collect_faces: true,
+        ..Default::default()
+    };
+
+    let shape = TransformedShape::new(&sphere, Vec3::ZERO, Quat::IDENTITY);
+    let result = cast(
+        &ShapeCast::new(shape, vec3(10.0, 0.0, 0.0)),
+        &target,
+        &settings,
+    )
+    .unwrap();
+    assert!((result.fraction - 0.35).abs() < 1.0e-4);
+    assert_near(result.contact.penetration_axis, Vec3::X);
+    assert_near(result.contact.contact_point1, vec3(4.0, 0.0, 0.0));
+    assert_near(result.contact.contact_point2, vec3(4.0, 0.0, 0.0));
+    assert!(result.contact.penetration_depth.abs() < 1.0e-3);
+    assert!(result.contact.face1.is_empty());
+    assert_eq!(result.contact.face2.len(), 4);
+    assert!(result.contact.face2.iter().all(|vertex| vertex.x == 4.0));
+
+    // Moving the box into the sphere instead hits at the same fraction.
+    let reversed = cast(
+        &ShapeCast::new(target, vec3(-10.0, 0.0, 0.0)),
+        &shape,
+        &settings,
+    )
+    .unwrap();
+    assert!((reversed.fraction - 0.35).abs() < 1.0e-4);
+    assert_near(reversed.contact.penetration_axis, -Vec3::X);
+    assert_near(reversed.contact.contact_point1, vec3(0.5, 0.0, 0.0));
+
+    // A cast that stops short misses.
+    let short = ShapeCast::new(shape, vec3(3.0, 0.0, 0.0));
+    assert!(cast(&short, &target, &settings).is_none());
+
+    // A rotated box hits with its edge, which is rounded by the convex radius.
+    let rotation = Quat::from_rotation_z(core::f32::consts::FRAC_PI_4);
+    let shape = TransformedShape::new(&cube, vec3(0.0, 5.0, 0.0), rotation);
+    let target = TransformedShape::new(&cube, Vec3::ZERO, Quat::IDENTITY);
+    let result = cast(
+        &ShapeCast::new(shape, vec3(0.0, -5.0, 0.0)),
+        &target,
+        &settings,
+    )
+    .unwrap();
+    let radius = cube.convex_radius();
+    let corner = (1.0 - radius) * core::f32::consts::SQRT_2 + radius;
+    let expected = (4.0 - corner) / 5.0;
+    assert!((result.fraction - expected).abs() < 1.0e-3);
+    assert_near(result.contact.penetration_axis, -Vec3::Y);
+    let contact_point1 = result.contact.contact_point1;
+    assert_near(vec3(contact_point1.x, contact_point1.y, 0.0), Vec3::Y);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_cast_start_in_penetration() {
+    let sphere = SphereShape::new(0.5).unwrap();
+    let cube = BoxShape::new(Vec3::ONE).unwrap();
+    let shape = TransformedShape::new(&sphere, vec3(4.2, 0.0, 0.0), Quat::IDENTITY);
+    let target = TransformedShape::new(&cube, vec3(5.0, 0.0, 0.0), Quat::IDENTITY);
+    let mut settings = ShapeCastSettings {
+        return_deepest_point: true,
+        ..Default::default()
+    };
+
+    let forward = ShapeCast::new(shape, vec3(1.0, 0.0, 0.0));
+    let result = cast(&forward, &target, &settings).unwrap();
+    assert_eq!(result.fraction, 0.0);
+    assert_near(result.contact.penetration_axis, Vec3::X);
+    assert!((result.contact.penetration_depth - 0.7).abs() < 1.0e-3);
+    assert_near(result.contact.contact_point1, vec3(4.7, 0.0, 0.0));
+    assert_near(result.contact.contact_point2, vec3(4.0, 0.0, 0.0));
+
+    // Moving out of the box hits it from the inside.
+    let backward = ShapeCast::new(shape, vec3(-1.0, 0.0, 0.0));
+    assert!(cast(&backward, &target, &settings).is_none());
+    settings.back_face_mode_convex = BackFaceMode::CollideWithBackFaces;
+    let result = cast(&backward, &target, &settings).unwrap();
+    assert_eq!(result.fraction, 0.0);
+    assert_near(result.contact.penetration_axis, Vec3::X);
+
+    // Without the deepest point the cast direction is used as normal.
+    settings.return_deepest_point = false;
+    let result = cast(&forward, &target, &settings).unwrap();
+    assert_eq!(result.fraction, 0.0);
+    assert_eq!(result.contact.penetration_axis, Vec3::X);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_cast_convex_vs_mesh() {
+    let cube = BoxShape::new(Vec3::splat(0.5)).unwrap();
+    let mesh = floor_mesh();
+    let target = TransformedShape::new(&mesh, Vec3::ZERO, Quat::IDENTITY);
+    let mut settings = ShapeCastSettings {
+        collect_faces: true,
+        ..Default::default()
+    };
+
+    let shape = TransformedShape::new(&cube, vec3(0.5, 3.0, 0.25), Quat::IDENTITY);
+    let result = cast(
+        &ShapeCast::new(shape, vec3(0.0, -5.0, 0.0)),
+        &target,
+        &settings,
+    )
+    .unwrap();
+    assert!((result.fraction - 0.5).abs() < 1.0e-4);
+    assert_near(result.contact.penetration_axis, -Vec3::Y);
+    assert!(result.contact.contact_point2.y.abs() < 1.0e-3);
+    assert!(mesh.triangle(result.contact.sub_shape_id2)[0].y == 0.0);
+    assert_eq!(result.contact.face1.len(), 4);
+    assert_eq!(result.contact.face2.len(), 3);
+
+    // Both triangles are hit at the same time.
+    let mut collector = AllHitCollector::new();
+    CollisionDispatch::new().cast_shape(
+        &ShapeCast::new(shape, vec3(0.0, -5.0, 0.0)),
+        &target,
+        &settings,
+        &mut collector,
+        &(),
+    );
+    assert_eq!(collector.hits.len(), 2);
+    assert_ne!(
+        collector.hits[0].contact.sub_shape_id2,
+        collector.hits[1].contact.sub_shape_id2
+    );
+
+    // The floor is only hit from below when back faces are enabled.
+    let shape = TransformedShape::new(&cube, vec3(0.5, -3.0, 0.25), Quat::IDENTITY);
+    let up = ShapeCast::new(shape, vec3(0.0, 5.0, 0.0));
+    assert!(cast(&up, &target, &settings).is_none());
+    settings.back_face_mode_triangles = BackFaceMode::CollideWithBackFaces;
+    let result = cast(&up, &target, &settings).unwrap();
+    assert!((result.fraction - 0.5).abs() < 1.0e-4);
+    assert_near(result.contact.penetration_axis, Vec3::Y);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_cast_convex_vs_sdf() {
+    let sdf = sphere_field();
+    let sphere = SphereShape::new(0.5).unwrap();
+    let settings = ShapeCastSettings::default();
+
+    // The field is scaled to a radius of two, the ball touches it after moving 2.5.
+    let field = TransformedShape::new(&sdf, vec3(5.0, 0.0, 0.0), Quat::IDENTITY)
+        .with_scale(Vec3::splat(2.0));
+    let ball = TransformedShape::new(&sphere, vec3(5.0, 5.0, 0.0), Quat::IDENTITY);
+    let result = cast(
+        &ShapeCast::new(ball, vec3(0.0, -5.0, 0.0)),
+        &field,
+        &settings,
+    )
+    .unwrap();
+    assert!((result.fraction - 0.5).abs() < 0.01);
+    assert!((result.contact.penetration_axis + Vec3::Y).length() < 0.01);
+    assert!((result.contact.contact_point2 - vec3(5.0, 2.0, 0.0)).length() < 0.02);
+
+    // A cast that passes over the field misses.
+    let ball = TransformedShape::new(&sphere, vec3(0.0, 5.0, 0.0), Quat::IDENTITY);
+    let over = ShapeCast::new(ball, vec3(10.0, 0.0, 0.0));
+    assert!(cast(&over, &field, &settings).is_none());
+
+    // A ball that starts in the field hits at the start with its penetration.
+    let ball = TransformedShape::new(&sphere, vec3(5.0, 2.3, 0.0), Quat::IDENTITY);
+    let result = cast(
+        &ShapeCast::new(ball, vec3(0.0, -1.0, 0.0)),
+        &field,
+        &settings,
+    )
+    .unwrap();
+    assert_eq!(result.fraction, 0.0);
+    assert!((result.contact.penetration_depth - 0.2).abs() < 0.02);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_cast_compound() {
+    let sphere: Arc<dyn Shape> = Arc::new(SphereShape::new(0.5).unwrap());
+    let compound = StaticCompoundShape::new(&[
+        CompoundSubShape::new(sphere.clone(), vec3(0.0, 0.0, 0.0), Quat::IDENTITY),
+        CompoundSubShape::new(sphere, vec3(1.0, 0.0, 0.0), Quat::IDENTITY),
+    ])
+    .unwrap();
+    let cube = BoxShape::new(Vec3::ONE).unwrap();
+    let shape = TransformedShape::new(&compound, compound.center_of_mass(), Quat::IDENTITY);
+    let target = TransformedShape::new(&cube, vec3(5.0, 0.0, 0.0), Quat::IDENTITY);
+
+    // The sphere in front hits first.
+    let result = cast(
+        &ShapeCast::new(shape, vec3(10.0, 0.0, 0.0)),
+        &target,
+        &ShapeCastSettings::default(),
+    )
+    .unwrap();
+    assert!((result.fraction - 0.25).abs() < 1.0e-4);
+    assert_eq!(compound.sub_shape_index(result.contact.sub_shape_id1).0, 1);
+    assert_near(result.contact.contact_point1, vec3(4.0, 0.0, 0.0));
+}
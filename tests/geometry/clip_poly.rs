@@ -0,0 +1,86 @@
+use phys_rs::{
+    geometry::{clip_poly_vs_edge, clip_poly_vs_plane, clip_poly_vs_poly},
+    math::{vec3, Vec3},
+};
+
+fn square(half_extent: f32, y: f32) -> Vec<Vec3> {
+    vec![
+        vec3(-half_extent, y, -half_extent),
+        vec3(-half_extent, y, half_extent),
+        vec3(half_extent, y, half_extent),
+        vec3(half_extent, y, -half_extent),
+    ]
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_clip_poly_vs_plane() {
+    let mut clipped = Vec::new();
+    clip_poly_vs_plane(&square(1.0, 0.0), Vec3::ZERO, Vec3::X, &mut clipped);
+    assert_eq!(clipped.len(), 4);
+    assert!(clipped.iter().all(|vertex| vertex.x >= 0.0));
+    assert!(clipped.contains(&vec3(0.0, 0.0, 1.0)));
+    assert!(clipped.contains(&vec3(0.0, 0.0, -1.0)));
+
+    // Everything behind the plane is removed.
+    clip_poly_vs_plane(
+        &square(1.0, 0.0),
+        vec3(2.0, 0.0, 0.0),
+        Vec3::X,
+        &mut clipped,
+    );
+    assert!(clipped.is_empty());
+
+    // A segment keeps the part in front of the plane.
+    let segment = [vec3(-1.0, 0.0, 0.0), vec3(3.0, 0.0, 0.0)];
+    clip_poly_vs_plane(&segment, vec3(1.0, 0.0, 0.0), -Vec3::X, &mut clipped);
+    assert_eq!(clipped, [vec3(-1.0, 0.0, 0.0), vec3(1.0, 0.0, 0.0)]);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_clip_poly_vs_poly() {
+    // The squares are wound counter clockwise around +Y.
+    let mut clipped = Vec::new();
+    let shifted: Vec<Vec3> = square(1.0, 0.5)
+        .into_iter()
+        .map(|vertex| vertex + vec3(1.0, 0.0, 1.0))
+        .collect();
+    clip_poly_vs_poly(&shifted, &square(1.0, 0.0), Vec3::Y, &mut clipped);
+    assert_eq!(clipped.len(), 4);
+    for vertex in &clipped {
+        assert!(vertex.x >= -1.0e-6 && vertex.x <= 1.0 + 1.0e-6);
+        assert!(vertex.z >= -1.0e-6 && vertex.z <= 1.0 + 1.0e-6);
+        assert_eq!(vertex.y, 0.5);
+    }
+
+    // Disjoint polygons have no overlap.
+    let far: Vec<Vec3> = square(1.0, 0.0)
+        .into_iter()
+        .map(|vertex| vertex + vec3(5.0, 0.0, 0.0))
+        .collect();
+    clip_poly_vs_poly(&far, &square(1.0, 0.0), Vec3::Y, &mut clipped);
+    assert!(clipped.is_empty());
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_clip_poly_vs_edge() {
+    let mut clipped = Vec::new();
+
+    // An edge crossing the square is limited to the square.
+    let (start, end) = (vec3(-2.0, 1.0, 0.0), vec3(2.0, 1.0, 0.0));
+    clip_poly_vs_edge(&square(1.0, 0.0), start, end, Vec3::Y, &mut clipped);
+    assert_eq!(clipped.len(), 2);
+    for vertex in &clipped {
+        assert!((vertex.x.abs() - 1.0).abs() < 1.0e-6);
+        assert_eq!(vertex.z, 0.0);
+    }
+
+    // A short edge inside the square results in the projected end points.
+    let (start, end) = (vec3(-0.5, 1.0, 0.0), vec3(0.5, 1.0, 0.0));
+    clip_poly_vs_edge(&square(1.0, 0.0), start, end, Vec3::Y, &mut clipped);
+    assert_eq!(clipped.len(), 2);
+    assert!(clipped.contains(&vec3(-0.5, 0.0, 0.0)));
+    assert!(clipped.contains(&vec3(0.5, 0.0, 0.0)));
+}
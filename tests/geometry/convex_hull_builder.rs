@@ -0,0 +1,213 @@
+use phys_rs::geometry::{ConvexHull, ConvexHullBuilder, ConvexHullError};
+use phys_rs::math::{vec3, Vec3};
+
+const TOLERANCE: f32 = 1.0e-3;
+
+struct Random(u32);
+
+impl Random {
+    fn next(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        (self.0 as f32 / u32::MAX as f32) * 2.0 - 1.0
+    }
+
+    fn vec3(&mut self) -> Vec3 {
+        vec3(self.next(), self.next(), self.next())
+    }
+}
+
+fn assert_valid(hull: &ConvexHull, points: &[Vec3]) {
+    // Euler characteristic of a convex polyhedron.
+    assert_eq!(
+        hull.vertices.len() + hull.faces.len(),
+        hull.edge_count() + 2
+    );
+    assert_eq!(hull.faces.len(), hull.planes.len());
+    for point in points {
+        assert!(hull.contains_point(*point, 2.0 * TOLERANCE));
+    }
+    for (face, plane) in hull.faces.iter().zip(&hull.planes) {
+        assert!(plane.normal.is_normalized());
+        for &v in &face.vertices {
+            assert!(plane.signed_distance(hull.vertices[v as usize]).abs() <= 2.0 * TOLERANCE);
+        }
+    }
+}
+
+fn cube_corners() -> Vec<Vec3> {
+    let mut points = Vec::new();
+    for i in 0..8 {
+        points.push(vec3(
+            if i & 1 == 0 { -1.0 } else { 1.0 },
+            if i & 2 == 0 { -1.0 } else { 1.0 },
+            if i & 4 == 0 { -1.0 } else { 1.0 },
+        ));
+    }
+    points
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_tetrahedron() {
+    let points = [Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z, vec3(0.1, 0.1, 0.1)];
+    let hull = ConvexHullBuilder::new(&points).build(TOLERANCE).unwrap();
+    assert_eq!(4, hull.vertices.len());
+    assert_eq!(4, hull.faces.len());
+    assert!(hull.faces.iter().all(|f| f.vertices.len() == 3));
+    assert_valid(&hull, &points);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_cube() {
+    let mut random = Random(0x1234_5678);
+    let mut points = cube_corners();
+    // Points inside the cube and on its faces should not contribute to the hull.
+    for _ in 0..200 {
+        points.push(random.vec3() * 0.99);
+    }
+    for axis in 0..3 {
+        for _ in 0..20 {
+            let mut point = random.vec3();
+            point[axis] = if random.next() < 0.0 { -1.0 } else { 1.0 };
+            points.push(point);
+        }
+    }
+    let hull = ConvexHullBuilder::new(&points).build(TOLERANCE).unwrap();
+    assert_eq!(8, hull.vertices.len());
+    assert_eq!(6, hull.faces.len());
+    assert!(hull.faces.iter().all(|f| f.vertices.len() == 4));
+    for plane in &hull.planes {
+        assert!((plane.constant + 1.0).abs() < TOLERANCE);
+        assert!(Vec3::AXES
+            .iter()
+            .chain(&Vec3::NEG_AXES)
+            .any(|axis| axis.abs_diff_eq(plane.normal, 1.0e-5)));
+    }
+    assert_valid(&hull, &points);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_noisy_cube_merges_coplanar_faces() {
+    let mut random = Random(0xdead_beef);
+    let mut points = cube_corners();
+    for axis in 0..3 {
+        for _ in 0..50 {
+            let mut point = random.vec3() * 0.9;
+            point[axis] = if random.next() < 0.0 { -1.0 } else { 1.0 } + random.next() * 1.0e-4;
+            points.push(point);
+        }
+    }
+    let hull = ConvexHullBuilder::new(&points).build(TOLERANCE).unwrap();
+    assert_eq!(8, hull.vertices.len());
+    assert_eq!(6, hull.faces.len());
+    assert_valid(&hull, &points);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_prism_removes_degenerate_edges() {
+    // A hexagonal prism with extra points on its edges, which must not become vertices.
+    let mut points = Vec::new();
+    for i in 0..6 {
+        let angle = i as f32 * core::f32::consts::TAU / 6.0;
+        let next = (i + 1) as f32 * core::f32::consts::TAU / 6.0;
+        for y in [-1.0, 1.0] {
+            let a = vec3(angle.cos(), y, angle.sin());
+            let b = vec3(next.cos(), y, next.sin());
+            points.push(a);
+            points.push(a.lerp(b, 0.5));
+        }
+        points.push(vec3(angle.cos(), 0.0, angle.sin()));
+    }
+    let hull = ConvexHullBuilder::new(&points).build(TOLERANCE).unwrap();
+    assert_eq!(12, hull.vertices.len());
+    assert_eq!(8, hull.faces.len());
+    assert_eq!(
+        2,
+        hull.faces.iter().filter(|f| f.vertices.len() == 6).count()
+    );
+    assert_eq!(
+        6,
+        hull.faces.iter().filter(|f| f.vertices.len() == 4).count()
+    );
+    assert_valid(&hull, &points);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_octahedron() {
+    let mut points = Vec3::AXES.to_vec();
+    points.extend(Vec3::NEG_AXES);
+    points.push(Vec3::ZERO);
+    let hull = ConvexHullBuilder::new(&points).build(TOLERANCE).unwrap();
+    assert_eq!(6, hull.vertices.len());
+    assert_eq!(8, hull.faces.len());
+    assert_valid(&hull, &points);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_sphere() {
+    let mut random = Random(0x0bad_f00d);
+    let mut points = Vec::new();
+    while points.len() < 500 {
+        let point = random.vec3();
+        if point.length_squared() > 0.01 {
+            points.push(point.normalize());
+        }
+    }
+    let hull = ConvexHullBuilder::new(&points).build(TOLERANCE).unwrap();
+    assert!(hull.vertices.len() > 100);
+    assert_valid(&hull, &points);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_max_vertices() {
+    let mut random = Random(0x600d_cafe);
+    let points: Vec<Vec3> = (0..500).map(|_| random.vec3().normalize()).collect();
+    let hull = ConvexHullBuilder::new(&points)
+        .with_max_vertices(16)
+        .build(TOLERANCE)
+        .unwrap();
+    assert!(hull.vertices.len() <= 16);
+    assert_eq!(
+        hull.vertices.len() + hull.faces.len(),
+        hull.edge_count() + 2
+    );
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_errors() {
+    let build = |points: &[Vec3]| ConvexHullBuilder::new(points).build(TOLERANCE);
+    assert_eq!(
+        Err(ConvexHullError::TooFewPoints),
+        build(&[Vec3::ZERO, Vec3::X, Vec3::Y])
+    );
+    assert_eq!(Err(ConvexHullError::Degenerate), build(&[Vec3::ONE; 8]));
+    assert_eq!(
+        Err(ConvexHullError::Collinear),
+        build(&[
+            Vec3::ZERO,
+            Vec3::X,
+            Vec3::X * 2.0,
+            Vec3::X * 3.0,
+            vec3(4.0, 1.0e-4, 0.0)
+        ])
+    );
+    assert_eq!(
+        Err(ConvexHullError::Coplanar),
+        build(&[
+            Vec3::ZERO,
+            Vec3::X,
+            Vec3::Y,
+            Vec3::ONE - Vec3::Z,
+            vec3(0.5, 0.5, 1.0e-4)
+        ])
+    );
+}
@@ -0,0 +1,84 @@
+use phys_rs::{
+    geometry::{AddConvexRadius, EpaPenetrationDepth, PointConvexSupport, Support},
+    math::{vec3, Vec3},
+};
+
+/// An axis aligned box around `center`.
+struct Cuboid {
+    center: Vec3,
+    half_extent: Vec3,
+}
+
+impl Support for Cuboid {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        let sign = |value: f32| if value < 0.0 { -1.0 } else { 1.0 };
+        self.center
+            + self.half_extent * vec3(sign(direction.x), sign(direction.y), sign(direction.z))
+    }
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_penetration_depth() {
+    let a = Cuboid {
+        center: Vec3::ZERO,
+        half_extent: Vec3::ONE,
+    };
+    let b = Cuboid {
+        center: vec3(1.7, 0.5, 0.2),
+        half_extent: Vec3::ONE,
+    };
+    let mut epa = EpaPenetrationDepth::new();
+    let result = epa.penetration_depth(&a, &b, &a, &b, 0.0, 1.0e-4).unwrap();
+    assert!((result.penetration_axis - Vec3::X).length() < 1.0e-4);
+    assert!((result.penetration_depth - 0.3).abs() < 1.0e-4);
+    assert!((result.point_a.x - 1.0).abs() < 1.0e-4);
+    assert!((result.point_b.x - 0.7).abs() < 1.0e-4);
+
+    // Objects that are separated.
+    let b = Cuboid {
+        center: vec3(0.0, -2.5, 0.0),
+        half_extent: Vec3::ONE,
+    };
+    assert!(epa.penetration_depth(&a, &b, &a, &b, 0.0, 1.0e-4).is_none());
+    let result = epa.penetration_depth(&a, &b, &a, &b, 1.0, 1.0e-4).unwrap();
+    assert!((result.penetration_axis + Vec3::Y).length() < 1.0e-4);
+    assert!((result.penetration_depth + 0.5).abs() < 1.0e-4);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_convex_radius() {
+    // Two spheres given as points with a convex radius.
+    struct RoundedPoint(Vec3);
+    impl Support for RoundedPoint {
+        fn support(&self, _direction: Vec3) -> Vec3 {
+            self.0
+        }
+
+        fn convex_radius(&self) -> f32 {
+            1.0
+        }
+    }
+    let (center_a, center_b) = (Vec3::ZERO, vec3(0.0, 0.0, 1.5));
+    let a = RoundedPoint(center_a);
+    let b = RoundedPoint(center_b);
+    let full_a = AddConvexRadius::new(PointConvexSupport { point: center_a }, 1.0);
+    let full_b = AddConvexRadius::new(PointConvexSupport { point: center_b }, 1.0);
+    let mut epa = EpaPenetrationDepth::new();
+    let result = epa
+        .penetration_depth(&a, &b, &full_a, &full_b, 0.0, 1.0e-4)
+        .unwrap();
+    assert_eq!(result.penetration_axis, Vec3::Z);
+    assert_eq!(result.penetration_depth, 0.5);
+    assert_eq!(result.point_a, Vec3::Z);
+    assert_eq!(result.point_b, vec3(0.0, 0.0, 0.5));
+
+    // When the centers overlap the full objects are used.
+    let b = RoundedPoint(vec3(0.0, 0.0, 0.0));
+    let full_b = AddConvexRadius::new(PointConvexSupport { point: Vec3::ZERO }, 1.0);
+    let result = epa
+        .penetration_depth(&a, &b, &full_a, &full_b, 0.0, 1.0e-4)
+        .unwrap();
+    assert!((result.penetration_depth - 2.0).abs() < 0.1);
+}
@@ -0,0 +1,100 @@
+use phys_rs::{
+    geometry::{GjkClosestPoint, Support},
+    math::{vec3, Vec3},
+};
+
+/// An axis aligned box around `center`.
+struct Cuboid {
+    center: Vec3,
+    half_extent: Vec3,
+}
+
+impl Support for Cuboid {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        let sign = |value: f32| if value < 0.0 { -1.0 } else { 1.0 };
+        self.center
+            + self.half_extent * vec3(sign(direction.x), sign(direction.y), sign(direction.z))
+    }
+}
+
+/// A sphere given as a point with a convex radius.
+struct RoundedPoint(Vec3, f32);
+
+impl Support for RoundedPoint {
+    fn support(&self, _direction: Vec3) -> Vec3 {
+        self.0
+    }
+
+    fn convex_radius(&self) -> f32 {
+        self.1
+    }
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_cast_shape() {
+    let a = Cuboid {
+        center: Vec3::ZERO,
+        half_extent: Vec3::ONE,
+    };
+    let b = Cuboid {
+        center: vec3(0.5, 5.0, 0.0),
+        half_extent: Vec3::ONE,
+    };
+    let mut gjk = GjkClosestPoint::new();
+    let result = gjk
+        .cast_shape(&a, &b, vec3(0.0, 10.0, 0.0), 1.0e-4, 1.0)
+        .unwrap();
+    assert!((result.fraction - 0.3).abs() < 1.0e-4);
+    assert!((result.point_a.y - 4.0).abs() < 1.0e-3);
+    assert!((result.point_b.y - 4.0).abs() < 1.0e-3);
+    assert!(result.separating_axis.length() < 1.0e-3);
+
+    // Casts that stop short of the object, or move away from it, miss.
+    assert!(gjk
+        .cast_shape(&a, &b, vec3(0.0, 10.0, 0.0), 1.0e-4, 0.25)
+        .is_none());
+    assert!(gjk
+        .cast_shape(&a, &b, vec3(0.0, -10.0, 0.0), 1.0e-4, 1.0)
+        .is_none());
+    assert!(gjk
+        .cast_shape(&a, &b, vec3(10.0, 0.0, 0.0), 1.0e-4, 1.0)
+        .is_none());
+
+    // Objects that start out intersecting hit immediately.
+    let b = Cuboid {
+        center: vec3(0.5, 1.5, 0.0),
+        half_extent: Vec3::ONE,
+    };
+    let result = gjk
+        .cast_shape(&a, &b, vec3(0.0, 10.0, 0.0), 1.0e-4, 1.0)
+        .unwrap();
+    assert_eq!(result.fraction, 0.0);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_cast_shape_convex_radius() {
+    let a = RoundedPoint(Vec3::ZERO, 0.5);
+    let b = Cuboid {
+        center: vec3(5.0, 0.0, 0.0),
+        half_extent: Vec3::ONE,
+    };
+    let mut gjk = GjkClosestPoint::new();
+    let result = gjk
+        .cast_shape(&a, &b, vec3(10.0, 0.0, 0.0), 1.0e-4, 1.0)
+        .unwrap();
+    assert!((result.fraction - 0.35).abs() < 1.0e-4);
+    assert!((result.point_a - vec3(4.0, 0.0, 0.0)).length() < 1.0e-3);
+    assert!((result.point_b - vec3(4.0, 0.0, 0.0)).length() < 1.0e-3);
+    assert!((result.separating_axis - vec3(0.5, 0.0, 0.0)).length() < 1.0e-3);
+
+    // Two spheres that only overlap with their convex radius.
+    let b = RoundedPoint(vec3(0.0, 0.0, 0.8), 0.5);
+    let result = gjk
+        .cast_shape(&a, &b, vec3(0.0, 0.0, 1.0), 1.0e-4, 1.0)
+        .unwrap();
+    assert_eq!(result.fraction, 0.0);
+    assert!((result.point_a - vec3(0.0, 0.0, 0.5)).length() < 1.0e-4);
+    assert!((result.point_b - vec3(0.0, 0.0, 0.3)).length() < 1.0e-4);
+}
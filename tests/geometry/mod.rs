@@ -0,0 +1,5 @@
+mod clip_poly;
+mod convex_hull_builder;
+mod epa_penetration_depth;
+mod gjk_closest_point;
+mod ray_aabox;
@@ -0,0 +1,50 @@
+use phys_rs::{
+    geometry::{ray_aabox, ray_aabox4, AABox, AABox4, RayInvDirection},
+    math::{vec3, Vec3},
+};
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_ray_aabox() {
+    let min = vec3(-1.0, -2.0, -3.0);
+    let max = vec3(1.0, 2.0, 3.0);
+    assert_eq!(ray_aabox(vec3(-5.0, 0.0, 0.0), Vec3::X, min, max), 4.0);
+    assert_eq!(ray_aabox(Vec3::ZERO, Vec3::X, min, max), 0.0);
+    assert_eq!(ray_aabox(vec3(-5.0, 3.0, 0.0), Vec3::X, min, max), f32::MAX);
+    assert_eq!(
+        ray_aabox(vec3(-5.0, 0.0, 0.0), -Vec3::X, min, max),
+        f32::MAX
+    );
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_ray_aabox4() {
+    let boxes = [
+        AABox::new(vec3(-1.0, -1.0, -1.0), vec3(1.0, 1.0, 1.0)),
+        AABox::new(vec3(2.0, -1.0, -1.0), vec3(3.0, 1.0, 1.0)),
+        AABox::new(vec3(2.0, 2.0, -1.0), vec3(3.0, 3.0, 1.0)),
+        AABox::new(vec3(-4.0, -0.5, -0.5), vec3(-3.0, 0.5, 0.5)),
+    ];
+    let mut boxes4 = AABox4::INVALID;
+    for (i, b) in boxes.iter().enumerate() {
+        boxes4.set(i, b);
+        assert_eq!(boxes4.get(i), *b);
+    }
+
+    let rays = [
+        (vec3(-5.0, 0.0, 0.0), vec3(10.0, 0.0, 0.0)),
+        (vec3(-5.0, 0.1, 0.2), vec3(10.0, 2.0, 0.1)),
+        (vec3(2.5, -5.0, 0.0), vec3(0.0, 10.0, 0.0)),
+        (vec3(0.0, 0.0, 0.0), vec3(0.0, 0.0, -1.0)),
+    ];
+    for (origin, direction) in rays {
+        let fractions = ray_aabox4(origin, &RayInvDirection::new(direction), &boxes4);
+        for (i, b) in boxes.iter().enumerate() {
+            assert_eq!(fractions[i], ray_aabox(origin, direction, b.min, b.max));
+        }
+    }
+
+    let overlaps = boxes4.overlaps(&AABox::new(vec3(0.5, 0.0, 0.0), vec3(2.5, 2.5, 0.0)));
+    assert_eq!(overlaps, [true, true, true, false]);
+}
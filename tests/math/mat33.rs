@@ -0,0 +1,51 @@
+use phys_rs::math::{mat33, Mat33, Quat, Vec3};
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_new() {
+    let m = mat33(Vec3::X, Vec3::Y * 2.0, Vec3::Z * 3.0);
+    assert_eq!(m, Mat33::from_diagonal(Vec3::new(1.0, 2.0, 3.0)));
+    assert_eq!(m.diagonal(), Vec3::new(1.0, 2.0, 3.0));
+    assert_eq!(Mat33::default(), Mat33::IDENTITY);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_transpose() {
+    let m = Mat33::from([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+    assert_eq!(m.row(0), Vec3::new(1.0, 4.0, 7.0));
+    assert_eq!(
+        m.transpose(),
+        Mat33::from([[1.0, 4.0, 7.0], [2.0, 5.0, 8.0], [3.0, 6.0, 9.0]])
+    );
+    assert_eq!(m.determinant(), 0.0);
+    assert!(m.try_inverse().is_none());
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_inverse() {
+    let m = Mat33::from([[2.0, 0.0, 1.0], [1.0, 3.0, 0.0], [0.0, 1.0, 4.0]]);
+    let inverse = m.try_inverse().unwrap();
+    assert!((m * inverse).abs_diff_eq(&Mat33::IDENTITY, 1.0e-6));
+    assert!((inverse * m).abs_diff_eq(&Mat33::IDENTITY, 1.0e-6));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_from_quat() {
+    let q = Quat::from_axis_angle(Vec3::new(1.0, 2.0, 3.0).normalize(), 0.7);
+    let m = Mat33::from_quat(q);
+    let v = Vec3::new(0.3, -1.2, 2.5);
+    assert!((m * v).abs_diff_eq(q * v, 1.0e-5));
+    assert!((m * m.transpose()).abs_diff_eq(&Mat33::IDENTITY, 1.0e-5));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_cross_product() {
+    let a = Vec3::new(1.0, 2.0, 3.0);
+    let b = Vec3::new(-4.0, 0.5, 2.0);
+    assert!((Mat33::from_cross_product(a) * b).abs_diff_eq(a.cross(b), 1.0e-6));
+    assert_eq!(Mat33::from_outer_product(a, b) * Vec3::X, a * b.x);
+}
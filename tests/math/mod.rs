@@ -1,3 +1,5 @@
+mod mat33;
 mod quat;
+mod uvec3;
 mod vec3;
 mod vec4;
@@ -87,3 +87,38 @@ fn test_accessors() {
 fn test_invalid_accessors() {
     assert_eq!(1.0, Quat::ZERO[4]);
 }
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_rotation() {
+    use phys_rs::math::Vec3;
+
+    let q = Quat::from_rotation_z(core::f32::consts::FRAC_PI_2);
+    assert!((q * Vec3::X).abs_diff_eq(Vec3::Y, 1.0e-6));
+    assert!((q.inverse() * Vec3::Y).abs_diff_eq(Vec3::X, 1.0e-6));
+    assert!((q * q * Vec3::X).abs_diff_eq(-Vec3::X, 1.0e-6));
+
+    let (axis, angle) = Quat::from_axis_angle(Vec3::new(0.0, 0.6, 0.8), 1.25).to_axis_angle();
+    assert!(axis.abs_diff_eq(Vec3::new(0.0, 0.6, 0.8), 1.0e-5));
+    assert!((angle - 1.25).abs() < 1.0e-5);
+
+    let arc = Quat::from_rotation_arc(Vec3::X, Vec3::Z);
+    assert!((arc * Vec3::X).abs_diff_eq(Vec3::Z, 1.0e-6));
+    assert!(arc.is_normalized());
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_from_mat33() {
+    use phys_rs::math::{Mat33, Vec3};
+
+    // Cover every branch, including half turns around each axis.
+    let axes = [Vec3::X, Vec3::Y, Vec3::Z, Vec3::new(0.48, 0.6, 0.64)];
+    for axis in axes {
+        for angle in [0.0, 0.5, 2.0, core::f32::consts::PI] {
+            let q = Quat::from_axis_angle(axis, angle);
+            let m = Quat::from_mat33(&Mat33::from_quat(q));
+            assert!(m.dot(q).abs() > 1.0 - 1.0e-5, "{q:?} != {m:?}");
+        }
+    }
+}
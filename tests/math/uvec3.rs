@@ -0,0 +1,50 @@
+use phys_rs::math::{uvec3, vec3, UVec3};
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_new() {
+    const V0: UVec3 = uvec3(1, 2, 3);
+    assert_eq!([1, 2, 3], <[u32; 3]>::from(V0));
+    assert_eq!(V0, UVec3::from([1, 2, 3]));
+    assert_eq!(UVec3::splat(1), UVec3::ONE);
+    assert_eq!((V0[0], V0[1], V0[2]), (1, 2, 3));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_ops() {
+    let v = uvec3(4, 7, 9);
+    assert_eq!(v + uvec3(1, 2, 3), uvec3(5, 9, 12));
+    assert_eq!(v - 1, uvec3(3, 6, 8));
+    assert_eq!(v * 2, uvec3(8, 14, 18));
+    assert_eq!(v / 4, uvec3(1, 1, 2));
+    assert_eq!(v % 4, uvec3(0, 3, 1));
+    assert_eq!(v.min(uvec3(5, 5, 5)), uvec3(4, 5, 5));
+    assert_eq!(v.max(uvec3(5, 5, 5)), uvec3(5, 7, 9));
+    assert_eq!(v.min_element(), 4);
+    assert!(v.all_lt(uvec3(5, 8, 10)));
+    assert!(!v.all_lt(uvec3(5, 7, 10)));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_grid() {
+    let size = uvec3(2, 3, 4);
+    assert_eq!(size.element_product(), 24);
+    assert_eq!(
+        uvec3(u32::MAX, u32::MAX, 1).element_product(),
+        (u32::MAX as u64).pow(2)
+    );
+    assert_eq!(UVec3::ZERO.flatten(size), 0);
+    assert_eq!(uvec3(1, 2, 3).flatten(size), 23);
+    assert_eq!(uvec3(1, 0, 1).flatten(size), 7);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_rounding() {
+    let v = vec3(-0.5, 1.5, 2.0);
+    assert_eq!(UVec3::floor(v), uvec3(0, 1, 2));
+    assert_eq!(UVec3::ceil(v), uvec3(0, 2, 2));
+    assert_eq!(uvec3(1, 2, 3).as_vec3(), vec3(1.0, 2.0, 3.0));
+}
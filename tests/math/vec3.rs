@@ -222,3 +222,51 @@ fn test_basic_arithmetic_assignment() {
     a /= Vec3::splat(2.0);
     assert_eq!((1.0, 2.0, 3.0), a.into());
 }
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_length() {
+    let a = vec3(2.0, 3.0, 6.0);
+    assert_eq!(49.0, a.length_squared());
+    assert_eq!(7.0, a.length());
+    assert_eq!(1.0 / 7.0, a.length_recip());
+    assert_eq!(7.0, a.distance(Vec3::ZERO));
+    assert_eq!(49.0, Vec3::ZERO.distance_squared(a));
+    assert!(a.normalize().is_normalized());
+    assert!(a.normalize().abs_diff_eq(a / 7.0, 1.0e-6));
+    assert_eq!(Vec3::ZERO, Vec3::ZERO.normalize_or_zero());
+    assert!(!Vec3::ZERO.normalize().is_finite());
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_normalized_perpendicular() {
+    for v in [
+        Vec3::X,
+        Vec3::NEG_Y,
+        Vec3::Z,
+        vec3(2.0, 3.0, 6.0),
+        vec3(-1.0, 0.5, 0.0),
+    ] {
+        let perpendicular = v.normalized_perpendicular();
+        assert!(perpendicular.is_normalized());
+        assert!(perpendicular.dot(v).abs() < 1.0e-6);
+    }
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_neg() {
+    assert_eq!((-1.0, 2.0, -3.0), (-vec3(1.0, -2.0, 3.0)).into());
+    assert_eq!(Vec3::NEG_X, -Vec3::X);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_lerp() {
+    let a = vec3(0.0, 2.0, 4.0);
+    let b = vec3(2.0, 4.0, 8.0);
+    assert_eq!((1.0, 3.0, 6.0), a.lerp(b, 0.5).into());
+    assert_eq!(a, a.lerp(b, 0.0));
+    assert_eq!(b, a.lerp(b, 1.0));
+}
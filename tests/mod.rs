@@ -1,4 +1,10 @@
 #[cfg(target_family = "wasm")]
 wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
 
+mod body;
+mod broad_phase;
+mod collision;
+mod geometry;
 mod math;
+mod physics;
+mod shape;
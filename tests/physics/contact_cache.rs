@@ -0,0 +1,168 @@
+use phys_rs::{
+    body::BodyID,
+    math::{vec3, Vec3},
+    physics::{CachedContactPoint, ContactCache, PhysicsSystem, SubShapePair},
+    shape::SubShapeID,
+};
+
+use super::{add_box, add_floor, physics_system, quad_tree, simulate};
+
+fn pair(index1: u32, index2: u32) -> SubShapePair {
+    SubShapePair {
+        body_id1: BodyID::new(index1, 0),
+        sub_shape_id1: SubShapeID::EMPTY,
+        body_id2: BodyID::new(index2, 0),
+        sub_shape_id2: SubShapeID::from_raw(index1),
+    }
+}
+
+fn point(x: f32, normal_lambda: f32) -> CachedContactPoint {
+    CachedContactPoint {
+        local_position1: vec3(x, 0.0, 0.0),
+        local_position2: vec3(x, 1.0, 0.0),
+        normal_lambda,
+        ..Default::default()
+    }
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_insert_and_find() {
+    let mut cache = ContactCache::new(256, 1024);
+    for i in 0..256 {
+        let points = [point(0.0, i as f32), point(1.0, i as f32)];
+        assert!(cache.insert(pair(i, i + 1), points.into_iter()));
+    }
+    assert_eq!(cache.manifold_count(), 256);
+    assert_eq!(cache.point_count(), 512);
+    for i in 0..256 {
+        let points = cache.find(&pair(i, i + 1)).unwrap();
+        assert_eq!(points, [point(0.0, i as f32), point(1.0, i as f32)]);
+        assert!(cache.find(&pair(i + 1, i)).is_none());
+    }
+    assert!(cache
+        .iter()
+        .map(|(pair, _)| pair.body_id1.index())
+        .eq(0..256));
+
+    // Pairs are only cached once.
+    assert!(!cache.insert(pair(0, 1), [point(2.0, 0.0)].into_iter()));
+    assert_eq!(cache.find(&pair(0, 1)).unwrap().len(), 2);
+
+    cache.clear();
+    assert_eq!(cache.manifold_count(), 0);
+    assert_eq!(cache.point_count(), 0);
+    assert!(cache.find(&pair(0, 1)).is_none());
+    assert!(cache.insert(pair(0, 1), [point(2.0, 0.0)].into_iter()));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_limits() {
+    let mut cache = ContactCache::new(2, 3);
+    assert!(cache.insert(pair(0, 1), [point(0.0, 0.0), point(1.0, 0.0)].into_iter()));
+    assert!(!cache.insert(pair(1, 2), [point(0.0, 0.0), point(1.0, 0.0)].into_iter()));
+    assert!(cache.insert(pair(1, 2), [point(0.0, 0.0)].into_iter()));
+    assert!(!cache.insert(pair(2, 3), [].into_iter()));
+    assert_eq!(cache.manifold_count(), 2);
+    assert_eq!(cache.point_count(), 3);
+    assert!(cache.find(&pair(2, 3)).is_none());
+
+    let mut cache = ContactCache::new(0, 0);
+    assert!(!cache.insert(pair(0, 1), [].into_iter()));
+    assert!(cache.find(&pair(0, 1)).is_none());
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_find_nearby() {
+    let points = [point(0.0, 1.0), point(1.0, 2.0)];
+    let find = |position1: Vec3, position2: Vec3| {
+        CachedContactPoint::find_nearby(&points, position1, position2, 0.01)
+            .map(|point| point.normal_lambda)
+    };
+    assert_eq!(
+        find(vec3(1.005, 0.0, 0.0), vec3(1.0, 1.005, 0.0)),
+        Some(2.0)
+    );
+    assert_eq!(find(vec3(0.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0)), Some(1.0));
+    // Both points have to be close.
+    assert_eq!(find(vec3(0.0, 0.0, 0.0), vec3(0.0, 1.02, 0.0)), None);
+    assert_eq!(find(vec3(0.5, 0.0, 0.0), vec3(0.5, 1.0, 0.0)), None);
+}
+
+/// Creates a system with a box resting on a floor.
+fn resting_box() -> (PhysicsSystem, BodyID, BodyID) {
+    let mut physics_system = physics_system(quad_tree);
+    let floor = add_floor(&mut physics_system);
+    let body_id = add_box(&mut physics_system, vec3(0.0, 0.5, 0.0));
+    (physics_system, floor, body_id)
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_persistent_contact() {
+    let (mut physics_system, floor, body_id) = resting_box();
+    simulate(&mut physics_system, 10);
+
+    // The box carries its weight on its four corners.
+    let cache = physics_system.contact_constraint_manager().contact_cache();
+    assert_eq!(cache.manifold_count(), 1);
+    let (pair, points) = cache.iter().next().unwrap();
+    assert_eq!((pair.body_id1, pair.body_id2), (body_id, floor));
+    assert_eq!(points.len(), 4);
+    let mass = physics_system
+        .body(body_id)
+        .unwrap()
+        .shape()
+        .mass_properties()
+        .mass;
+    let weight = mass * 9.81 / 60.0;
+    let normal_lambda: f32 = points.iter().map(|point| point.normal_lambda).sum();
+    assert!((normal_lambda - weight).abs() < 0.01 * weight, "{points:?}");
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_body_removal() {
+    let (mut physics_system, _, body_id) = resting_box();
+    simulate(&mut physics_system, 1);
+    physics_system
+        .body_interface()
+        .remove_body(body_id)
+        .unwrap();
+    simulate(&mut physics_system, 1);
+    let cache = physics_system.contact_constraint_manager().contact_cache();
+    assert_eq!(cache.manifold_count(), 0);
+
+    // A new body at the same index does not pick up the contacts of the old one.
+    physics_system
+        .body_interface()
+        .destroy_body(body_id)
+        .unwrap();
+    simulate(&mut physics_system, 1);
+    let new_body_id = add_box(&mut physics_system, vec3(0.0, 0.5, 0.0));
+    assert_eq!(new_body_id.index(), body_id.index());
+    simulate(&mut physics_system, 10);
+    let cache = physics_system.contact_constraint_manager().contact_cache();
+    assert!(cache.iter().all(|(pair, _)| pair.body_id1 == new_body_id));
+    assert_eq!(cache.manifold_count(), 1);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_cache_settings() {
+    let (mut physics_system, _, body_id) = resting_box();
+    let mut settings = *physics_system.physics_settings();
+    settings.max_cached_manifolds = 0;
+    physics_system.set_physics_settings(settings);
+    simulate(&mut physics_system, 1);
+    let cache = physics_system.contact_constraint_manager().contact_cache();
+    assert_eq!(cache.max_manifolds(), 0);
+    assert_eq!(cache.manifold_count(), 0);
+
+    // Without a cache the box is still supported.
+    simulate(&mut physics_system, 60);
+    let position = physics_system.body(body_id).unwrap().position();
+    assert!((position.y - 0.5).abs() < 0.03, "{position:?}");
+}
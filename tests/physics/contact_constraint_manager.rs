@@ -0,0 +1,158 @@
+use phys_rs::{
+    body::Body,
+    math::{vec3, Quat},
+    physics::PhysicsSystem,
+    shape::SubShapeID,
+};
+
+use super::{add_box, add_floor, add_sphere, physics_system, quad_tree, simulate};
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_resting_contact() {
+    let mut physics_system = physics_system(quad_tree);
+    add_floor(&mut physics_system);
+    let body_id = add_box(&mut physics_system, vec3(0.0, 0.5, 0.0));
+    physics_system.update(1.0 / 60.0, 1);
+    assert_eq!(
+        physics_system
+            .contact_constraint_manager()
+            .constraint_count(),
+        1
+    );
+    simulate(&mut physics_system, 120);
+
+    let slop = physics_system.physics_settings().penetration_slop;
+    let body = physics_system.body(body_id).unwrap();
+    assert!((body.position().y - 0.5).abs() <= slop + 1.0e-3, "{body:?}");
+    assert!(body.linear_velocity().length() < 0.01, "{body:?}");
+    assert!(
+        body.rotation().dot(Quat::IDENTITY).abs() > 0.9999,
+        "{body:?}"
+    );
+    // The box fell asleep, which keeps its contact in the cache without solving it.
+    assert!(!body.is_active());
+    let contact_constraint_manager = physics_system.contact_constraint_manager();
+    assert_eq!(contact_constraint_manager.constraint_count(), 0);
+    assert_eq!(
+        contact_constraint_manager.contact_cache().manifold_count(),
+        1
+    );
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_falling_contact() {
+    let mut physics_system = physics_system(quad_tree);
+    add_floor(&mut physics_system);
+    let body_id = add_sphere(&mut physics_system, vec3(0.0, 3.0, 0.0));
+    simulate(&mut physics_system, 180);
+
+    let slop = physics_system.physics_settings().penetration_slop;
+    let body = physics_system.body(body_id).unwrap();
+    assert!((body.position().y - 0.5).abs() <= slop + 1.0e-3, "{body:?}");
+    assert!(body.linear_velocity().length() < 0.01, "{body:?}");
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_stack() {
+    // Without warm starting the solver does not converge on a stack within 10 iterations.
+    let mut physics_system = physics_system(quad_tree);
+    add_floor(&mut physics_system);
+    let body_ids: Vec<_> = (0..5)
+        .map(|i| add_box(&mut physics_system, vec3(0.0, 0.5 + i as f32, 0.0)))
+        .collect();
+    simulate(&mut physics_system, 300);
+
+    for (i, &body_id) in body_ids.iter().enumerate() {
+        let body = physics_system.body(body_id).unwrap();
+        let expected = vec3(0.0, 0.5 + i as f32, 0.0);
+        assert!(body.position().abs_diff_eq(expected, 0.1), "{body:?}");
+        assert!(body.linear_velocity().length() < 0.01, "{body:?}");
+    }
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_friction() {
+    let mut physics_system = physics_system(quad_tree);
+    add_floor(&mut physics_system);
+    let slowed = add_box(&mut physics_system, vec3(0.0, 0.5, 0.0));
+    let mut body_interface = physics_system.body_interface();
+    body_interface.set_friction(slowed, 1.0).unwrap();
+    body_interface
+        .set_linear_velocity(slowed, vec3(4.0, 0.0, 0.0))
+        .unwrap();
+    simulate(&mut physics_system, 120);
+
+    // With the default friction of 0.2 of the floor, the box slides for sqrt(0.2) * 9.81 m/s².
+    let body = physics_system.body(slowed).unwrap();
+    let deceleration = 0.2f32.sqrt() * 9.81;
+    let distance = 4.0 * 4.0 / (2.0 * deceleration);
+    assert!(body.linear_velocity().length() < 0.01, "{body:?}");
+    assert!((body.position().x - distance).abs() < 0.1, "{body:?}");
+    assert!(body.position().z.abs() < 0.01, "{body:?}");
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_combine_friction() {
+    fn frictionless(_: &Body, _: SubShapeID, _: &Body, _: SubShapeID) -> f32 {
+        0.0
+    }
+
+    let mut physics_system = physics_system(quad_tree);
+    add_floor(&mut physics_system);
+    physics_system
+        .contact_constraint_manager_mut()
+        .set_combine_friction(frictionless);
+    let body_id = add_box(&mut physics_system, vec3(0.0, 0.5, 0.0));
+    let mut body_interface = physics_system.body_interface();
+    body_interface
+        .set_linear_velocity(body_id, vec3(4.0, 0.0, 0.0))
+        .unwrap();
+    simulate(&mut physics_system, 60);
+
+    // Only the damping slows the box down.
+    let body = physics_system.body(body_id).unwrap();
+    assert!(body.linear_velocity().x > 3.7, "{body:?}");
+}
+
+/// Drops a sphere from 1.5m onto a floor and returns the highest upwards velocity after the
+/// impact.
+fn bounce(physics_system: &mut PhysicsSystem) -> f32 {
+    add_floor(physics_system);
+    let body_id = add_sphere(physics_system, vec3(0.0, 2.0, 0.0));
+    physics_system
+        .body_interface()
+        .set_restitution(body_id, 1.0)
+        .unwrap();
+    let mut max_velocity: f32 = 0.0;
+    for _ in 0..60 {
+        simulate(physics_system, 1);
+        let velocity = physics_system.body(body_id).unwrap().linear_velocity();
+        max_velocity = max_velocity.max(velocity.y);
+    }
+    max_velocity
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_restitution() {
+    let impact_velocity = (2.0 * 9.81 * 1.5f32).sqrt();
+    let max_velocity = bounce(&mut physics_system(quad_tree));
+    assert!(max_velocity > 0.9 * impact_velocity, "{max_velocity}");
+
+    fn combine_min(body1: &Body, _: SubShapeID, body2: &Body, _: SubShapeID) -> f32 {
+        body1.restitution.min(body2.restitution)
+    }
+
+    // The floor does not bounce.
+    let mut physics_system = physics_system(quad_tree);
+    physics_system
+        .contact_constraint_manager_mut()
+        .set_combine_restitution(combine_min);
+    let max_velocity = bounce(&mut physics_system);
+    assert!(max_velocity < 0.5, "{max_velocity}");
+}
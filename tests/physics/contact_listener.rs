@@ -0,0 +1,314 @@
+use std::sync::{Arc, Mutex};
+
+use phys_rs::{
+    body::{Body, BodyID, MotionQuality, MotionType},
+    collision::{CollideShapeResult, ContactManifold},
+    math::{vec3, Vec3},
+    physics::{ContactListener, ContactSettings, SubShapePair, ValidateResult},
+};
+
+use super::{
+    add_body, add_box, add_floor, add_sphere, physics_system, quad_tree, simulate, sphere, MOVING,
+    NON_MOVING,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Event {
+    Validated(BodyID, BodyID),
+    Added(BodyID, BodyID),
+    Persisted(BodyID, BodyID),
+    Removed(BodyID, BodyID),
+}
+
+/// Records all events and changes the contact settings through `modify`.
+struct Recorder {
+    events: Arc<Mutex<Vec<Event>>>,
+    validate_result: ValidateResult,
+    modify: fn(&Body, &Body, &mut ContactSettings),
+}
+
+impl Recorder {
+    fn new(validate_result: ValidateResult) -> (Box<Self>, Arc<Mutex<Vec<Event>>>) {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorder = Self {
+            events: events.clone(),
+            validate_result,
+            modify: |_, _, _| {},
+        };
+        (Box::new(recorder), events)
+    }
+}
+
+impl ContactListener for Recorder {
+    fn on_contact_validate(
+        &mut self,
+        body1: &Body,
+        body2: &Body,
+        base_offset: Vec3,
+        _result: &CollideShapeResult,
+    ) -> ValidateResult {
+        assert_eq!(base_offset, body1.center_of_mass_position());
+        let event = Event::Validated(body1.id(), body2.id());
+        self.events.lock().unwrap().push(event);
+        self.validate_result
+    }
+
+    fn on_contact_added(
+        &mut self,
+        body1: &Body,
+        body2: &Body,
+        manifold: &ContactManifold,
+        settings: &mut ContactSettings,
+    ) {
+        assert!(!manifold.points.is_empty());
+        let event = Event::Added(body1.id(), body2.id());
+        self.events.lock().unwrap().push(event);
+        (self.modify)(body1, body2, settings);
+    }
+
+    fn on_contact_persisted(
+        &mut self,
+        body1: &Body,
+        body2: &Body,
+        _manifold: &ContactManifold,
+        settings: &mut ContactSettings,
+    ) {
+        let event = Event::Persisted(body1.id(), body2.id());
+        self.events.lock().unwrap().push(event);
+        (self.modify)(body1, body2, settings);
+    }
+
+    fn on_contact_removed(&mut self, pair: &SubShapePair) {
+        let event = Event::Removed(pair.body_id1, pair.body_id2);
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_contact_events() {
+    let mut physics_system = physics_system(quad_tree);
+    let floor = add_floor(&mut physics_system);
+    let (recorder, events) = Recorder::new(ValidateResult::AcceptContact);
+    physics_system.set_contact_listener(Some(recorder));
+    let body_id = add_sphere(&mut physics_system, vec3(0.0, 0.5, 0.0));
+    simulate(&mut physics_system, 3);
+    physics_system
+        .body_interface()
+        .remove_body(body_id)
+        .unwrap();
+    simulate(&mut physics_system, 2);
+
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            Event::Validated(body_id, floor),
+            Event::Added(body_id, floor),
+            Event::Validated(body_id, floor),
+            Event::Persisted(body_id, floor),
+            Event::Validated(body_id, floor),
+            Event::Persisted(body_id, floor),
+            Event::Removed(body_id, floor),
+        ]
+    );
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_cache_settings_events() {
+    let mut physics_system = physics_system(quad_tree);
+    let floor = add_floor(&mut physics_system);
+    let (recorder, events) = Recorder::new(ValidateResult::AcceptContact);
+    physics_system.set_contact_listener(Some(recorder));
+    let body_id = add_sphere(&mut physics_system, vec3(0.0, 0.5, 0.0));
+    simulate(&mut physics_system, 1);
+
+    // A larger cache keeps the contact.
+    let mut settings = *physics_system.physics_settings();
+    settings.max_cached_manifolds += 1;
+    physics_system.set_physics_settings(settings);
+    events.lock().unwrap().clear();
+    simulate(&mut physics_system, 1);
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            Event::Validated(body_id, floor),
+            Event::Persisted(body_id, floor),
+        ]
+    );
+
+    // A cache without room removes it before it is added again.
+    settings.max_cached_manifolds = 0;
+    physics_system.set_physics_settings(settings);
+    events.lock().unwrap().clear();
+    simulate(&mut physics_system, 1);
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            Event::Validated(body_id, floor),
+            Event::Removed(body_id, floor),
+            Event::Added(body_id, floor),
+        ]
+    );
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_reject_contact() {
+    for validate_result in [
+        ValidateResult::RejectContact,
+        ValidateResult::RejectAllContactsForThisBodyPair,
+    ] {
+        let mut physics_system = physics_system(quad_tree);
+        let floor = add_floor(&mut physics_system);
+        let (recorder, events) = Recorder::new(validate_result);
+        physics_system.set_contact_listener(Some(recorder));
+        let body_id = add_sphere(&mut physics_system, vec3(0.0, 0.5, 0.0));
+        simulate(&mut physics_system, 2);
+
+        // The sphere falls through the floor.
+        assert!(physics_system.contacts().is_empty());
+        assert_eq!(
+            *events.lock().unwrap(),
+            [Event::Validated(body_id, floor); 2]
+        );
+        let position = physics_system.body(body_id).unwrap().position();
+        assert!(position.y < 0.5, "{position:?}");
+    }
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_sensor() {
+    let mut physics_system = physics_system(quad_tree);
+    let floor = add_floor(&mut physics_system);
+    let (mut recorder, events) = Recorder::new(ValidateResult::AcceptAllContactsForThisBodyPair);
+    recorder.modify = |_, _, settings| settings.is_sensor = true;
+    physics_system.set_contact_listener(Some(recorder));
+    let body_id = add_sphere(&mut physics_system, vec3(0.0, 0.5, 0.0));
+    simulate(&mut physics_system, 3);
+
+    // The sensor reports the contact but does not stop the sphere.
+    let velocity = physics_system.body(body_id).unwrap().linear_velocity();
+    assert!(
+        (velocity.y + 3.0 * 9.81 / 60.0).abs() < 1.0e-4,
+        "{velocity:?}"
+    );
+    assert_eq!(
+        events.lock().unwrap()[..4],
+        [
+            Event::Validated(body_id, floor),
+            Event::Added(body_id, floor),
+            Event::Validated(body_id, floor),
+            Event::Persisted(body_id, floor),
+        ]
+    );
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_inverse_mass_scale() {
+    for immovable in [false, true] {
+        let mut physics_system = physics_system(quad_tree);
+        add_floor(&mut physics_system);
+        physics_system.set_gravity(Vec3::ZERO);
+        if immovable {
+            // The second sphere acts as if it had an infinite mass.
+            let (mut recorder, _) = Recorder::new(ValidateResult::AcceptContact);
+            recorder.modify = |body1, _, settings| {
+                if body1.linear_velocity().x > 0.0 {
+                    settings.inverse_mass_scale2 = 0.0;
+                } else {
+                    settings.inverse_mass_scale1 = 0.0;
+                }
+            };
+            physics_system.set_contact_listener(Some(recorder));
+        }
+        let moving = add_sphere(&mut physics_system, vec3(-2.0, 2.0, 0.0));
+        let resting = add_sphere(&mut physics_system, vec3(0.0, 2.0, 0.0));
+        physics_system
+            .body_interface()
+            .set_linear_velocity(moving, vec3(6.0, 0.0, 0.0))
+            .unwrap();
+        simulate(&mut physics_system, 30);
+
+        let moving = physics_system.body(moving).unwrap().linear_velocity();
+        let resting = physics_system.body(resting).unwrap().linear_velocity();
+        if immovable {
+            assert!(moving.length() < 0.01, "{moving:?}");
+            assert!(resting.length() < 0.01, "{resting:?}");
+        } else {
+            assert!(moving.abs_diff_eq(vec3(3.0, 0.0, 0.0), 0.01), "{moving:?}");
+            assert!(
+                resting.abs_diff_eq(vec3(3.0, 0.0, 0.0), 0.01),
+                "{resting:?}"
+            );
+        }
+    }
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_surface_velocity() {
+    let mut physics_system = physics_system(quad_tree);
+    add_floor(&mut physics_system);
+    let (mut recorder, _) = Recorder::new(ValidateResult::AcceptContact);
+    // The floor is a conveyor belt that moves along x.
+    recorder.modify = |_, _, settings| {
+        settings.combined_friction = 1.0;
+        settings.relative_linear_surface_velocity = vec3(2.0, 0.0, 0.0);
+    };
+    physics_system.set_contact_listener(Some(recorder));
+    let body_id = add_box(&mut physics_system, vec3(0.0, 0.5, 0.0));
+    simulate(&mut physics_system, 60);
+
+    let body = physics_system.body(body_id).unwrap();
+    let velocity = body.linear_velocity();
+    assert!(
+        velocity.abs_diff_eq(vec3(2.0, 0.0, 0.0), 0.01),
+        "{velocity:?}"
+    );
+    assert!(body.position().x > 1.5, "{body:?}");
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_linear_cast_contact() {
+    let mut physics_system = physics_system(quad_tree);
+    physics_system.set_gravity(Vec3::ZERO);
+    let (recorder, events) = Recorder::new(ValidateResult::AcceptContact);
+    physics_system.set_contact_listener(Some(recorder));
+    let wall = add_body(
+        &mut physics_system,
+        &sphere(vec3(8.0, 0.0, 0.0), MotionType::Static, NON_MOVING),
+    );
+    let mut settings = sphere(Vec3::ZERO, MotionType::Dynamic, MOVING);
+    settings.linear_velocity = vec3(600.0, 0.0, 0.0);
+    settings.motion_quality = MotionQuality::LinearCast;
+    let bullet = add_body(&mut physics_system, &settings);
+
+    // The sweep adds the contact, which is solved and cached like any other.
+    simulate(&mut physics_system, 1);
+    assert_eq!(
+        *events.lock().unwrap(),
+        [Event::Validated(bullet, wall), Event::Added(bullet, wall)]
+    );
+    let cache = physics_system.contact_constraint_manager().contact_cache();
+    let (pair, points) = cache.iter().next().unwrap();
+    assert_eq!((pair.body_id1, pair.body_id2), (bullet, wall));
+    assert!(points[0].normal_lambda > 0.0, "{points:?}");
+    let position = physics_system.body(bullet).unwrap().position();
+    assert!((position.x - 7.0).abs() < 1.0e-3, "{position:?}");
+
+    // The discrete contact continues from it during the next step.
+    simulate(&mut physics_system, 1);
+    assert_eq!(
+        events.lock().unwrap()[2..],
+        [
+            Event::Validated(bullet, wall),
+            Event::Persisted(bullet, wall)
+        ]
+    );
+    let velocity = physics_system.body(bullet).unwrap().linear_velocity();
+    assert!(velocity.length() < 0.01, "{velocity:?}");
+}
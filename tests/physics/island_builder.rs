@@ -0,0 +1,43 @@
+use phys_rs::physics::IslandBuilder;
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_islands() {
+    let mut island_builder = IslandBuilder::new();
+    assert_eq!(island_builder.island_count(), 0);
+
+    island_builder.prepare(7);
+    island_builder.link_bodies(5, 1);
+    island_builder.link_bodies(3, 6);
+    island_builder.link_bodies(1, 6);
+    island_builder.link_bodies(4, 2);
+    island_builder.link_bodies(2, 4);
+    island_builder.finalize();
+    assert!(island_builder
+        .islands()
+        .eq([&[0][..], &[1, 3, 5, 6], &[2, 4]]));
+
+    // Preparing starts over with unlinked bodies.
+    island_builder.prepare(3);
+    island_builder.link_bodies(2, 2);
+    island_builder.finalize();
+    assert_eq!(island_builder.island_count(), 3);
+    assert_eq!(island_builder.island(2), [2]);
+
+    island_builder.prepare(0);
+    island_builder.finalize();
+    assert_eq!(island_builder.island_count(), 0);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_chain() {
+    let mut island_builder = IslandBuilder::new();
+    island_builder.prepare(1000);
+    for index in (1..1000).rev() {
+        island_builder.link_bodies(index, index - 1);
+    }
+    island_builder.finalize();
+    assert_eq!(island_builder.island_count(), 1);
+    assert!(island_builder.island(0).iter().copied().eq(0..1000));
+}
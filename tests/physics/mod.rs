@@ -0,0 +1,124 @@
+mod contact_cache;
+mod contact_constraint_manager;
+mod contact_listener;
+mod island_builder;
+mod physics_system;
+
+use std::sync::Arc;
+
+use phys_rs::{
+    body::{BodyCreationSettings, BodyID, MotionType},
+    broad_phase::{
+        BroadPhase, BroadPhaseLayer, BroadPhaseLayerInterface, BroadPhaseLayerInterfaceTable,
+        BroadPhaseQuadTree, BroadPhaseSweepAndPrune, ObjectVsBroadPhaseLayerFilterTable,
+    },
+    collision::{ObjectLayer, ObjectLayerPairFilterTable},
+    math::{vec3, Quat, Vec3},
+    physics::PhysicsSystem,
+    shape::{BoxShape, SphereShape},
+};
+
+pub const NON_MOVING: ObjectLayer = ObjectLayer(0);
+pub const MOVING: ObjectLayer = ObjectLayer(1);
+/// A layer that only collides with non moving bodies.
+pub const DEBRIS: ObjectLayer = ObjectLayer(2);
+
+pub type NewBroadPhase = fn(Arc<dyn BroadPhaseLayerInterface>) -> Box<dyn BroadPhase>;
+
+pub fn quad_tree(layer_interface: Arc<dyn BroadPhaseLayerInterface>) -> Box<dyn BroadPhase> {
+    Box::new(BroadPhaseQuadTree::new(layer_interface))
+}
+
+pub fn sweep_and_prune(layer_interface: Arc<dyn BroadPhaseLayerInterface>) -> Box<dyn BroadPhase> {
+    Box::new(BroadPhaseSweepAndPrune::new(layer_interface))
+}
+
+/// Maps non moving bodies to the first broadphase layer and all other bodies to the second.
+pub fn layer_interface() -> BroadPhaseLayerInterfaceTable {
+    let mut layer_interface = BroadPhaseLayerInterfaceTable::new(3, 2);
+    layer_interface.map_object_to_broad_phase_layer(MOVING, BroadPhaseLayer(1));
+    layer_interface.map_object_to_broad_phase_layer(DEBRIS, BroadPhaseLayer(1));
+    layer_interface
+}
+
+/// Creates an empty system for 64 bodies with the broadphase created by `new_broad_phase`.
+pub fn physics_system(new_broad_phase: NewBroadPhase) -> PhysicsSystem {
+    let layer_interface = layer_interface();
+    let mut pair_filter = ObjectLayerPairFilterTable::new(3);
+    pair_filter.enable_collision(NON_MOVING, MOVING);
+    pair_filter.enable_collision(MOVING, MOVING);
+    pair_filter.enable_collision(NON_MOVING, DEBRIS);
+    let object_vs_broad_phase_filter =
+        ObjectVsBroadPhaseLayerFilterTable::new(&layer_interface, &pair_filter, 3);
+    PhysicsSystem::new(
+        64,
+        new_broad_phase(Arc::new(layer_interface)),
+        Arc::new(object_vs_broad_phase_filter),
+        Arc::new(pair_filter),
+    )
+}
+
+/// A sphere with a radius of 0.5 and without damping.
+pub fn sphere(
+    position: Vec3,
+    motion_type: MotionType,
+    object_layer: ObjectLayer,
+) -> BodyCreationSettings {
+    let mut settings = BodyCreationSettings::new(
+        Arc::new(SphereShape::new(0.5).unwrap()),
+        position,
+        Quat::IDENTITY,
+        motion_type,
+        object_layer,
+    );
+    settings.linear_damping = 0.0;
+    settings
+}
+
+/// A dynamic box with sides of one.
+pub fn unit_box(position: Vec3) -> BodyCreationSettings {
+    BodyCreationSettings::new(
+        Arc::new(BoxShape::new(Vec3::splat(0.5)).unwrap()),
+        position,
+        Quat::IDENTITY,
+        MotionType::Dynamic,
+        MOVING,
+    )
+}
+
+pub fn add_body(physics_system: &mut PhysicsSystem, settings: &BodyCreationSettings) -> BodyID {
+    physics_system
+        .body_interface()
+        .create_and_add_body(settings)
+        .unwrap()
+}
+
+/// Adds a static floor with its top at height zero.
+pub fn add_floor(physics_system: &mut PhysicsSystem) -> BodyID {
+    let settings = BodyCreationSettings::new(
+        Arc::new(BoxShape::new(vec3(50.0, 0.5, 50.0)).unwrap()),
+        vec3(0.0, -0.5, 0.0),
+        Quat::IDENTITY,
+        MotionType::Static,
+        NON_MOVING,
+    );
+    add_body(physics_system, &settings)
+}
+
+pub fn add_box(physics_system: &mut PhysicsSystem, position: Vec3) -> BodyID {
+    add_body(physics_system, &unit_box(position))
+}
+
+pub fn add_sphere(physics_system: &mut PhysicsSystem, position: Vec3) -> BodyID {
+    add_body(
+        physics_system,
+        &sphere(position, MotionType::Dynamic, MOVING),
+    )
+}
+
+/// Simulates `steps` steps of 1/60th of a second.
+pub fn simulate(physics_system: &mut PhysicsSystem, steps: u32) {
+    for _ in 0..steps {
+        physics_system.update(1.0 / 60.0, 1);
+    }
+}
@@ -0,0 +1,539 @@
+use std::sync::{Arc, Mutex};
+
+use phys_rs::{
+    body::{
+        BodyActivationListener, BodyCreationSettings, BodyID, IgnoreSingleBodyFilter,
+        MotionQuality, MotionType,
+    },
+    collision::{AnyHitCollector, CollisionGroup, GroupFilterTable},
+    math::{vec3, Quat, Vec3},
+    physics::{PhysicsSettings, PhysicsSystem},
+    shape::{BoxShape, SphereShape},
+};
+
+use super::{
+    add_floor, add_sphere, physics_system, quad_tree, simulate, sphere, sweep_and_prune, unit_box,
+    DEBRIS, MOVING, NON_MOVING,
+};
+
+/// Drops a sphere for one second and returns where it ended up.
+fn fall(physics_system: &mut PhysicsSystem, steps: u32, collision_steps: u32) -> (BodyID, Vec3) {
+    let start = vec3(0.0, 100.0, 0.0);
+    let body_id = add_sphere(physics_system, start);
+    for _ in 0..steps {
+        physics_system.update(1.0 / steps as f32, collision_steps);
+    }
+    (body_id, physics_system.body(body_id).unwrap().position())
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_free_fall() {
+    for new_broad_phase in [quad_tree, sweep_and_prune] {
+        let mut physics_system = physics_system(new_broad_phase);
+        let gravity = physics_system.gravity();
+        let (body_id, position) = fall(&mut physics_system, 60, 1);
+
+        // Semi implicit Euler moves with the velocity at the end of every step.
+        let delta_time = 1.0 / 60.0;
+        let expected = 100.0 + gravity.y * delta_time * delta_time * (60.0 * 61.0 / 2.0);
+        assert!((position.y - expected).abs() < 1.0e-3, "{position:?}");
+        let velocity = physics_system.body(body_id).unwrap().linear_velocity();
+        assert!(velocity.abs_diff_eq(gravity, 1.0e-4));
+
+        // The broadphase follows the body.
+        let mut collector = AnyHitCollector::new();
+        physics_system
+            .broad_phase()
+            .collide_point(position, &mut collector, &(), &(), &());
+        assert_eq!(collector.hit, Some(body_id));
+    }
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_sub_steps() {
+    let mut fine = physics_system(quad_tree);
+    let (_, expected) = fall(&mut fine, 120, 1);
+
+    // Two collision steps per update are the same as updating twice as often.
+    let mut collision_steps = physics_system(quad_tree);
+    let (_, position) = fall(&mut collision_steps, 60, 2);
+    assert!(position.abs_diff_eq(expected, 1.0e-3));
+
+    let mut integration_sub_steps = physics_system(quad_tree);
+    integration_sub_steps.set_physics_settings(PhysicsSettings {
+        integration_sub_steps: 2,
+        ..Default::default()
+    });
+    let (_, position) = fall(&mut integration_sub_steps, 60, 1);
+    assert!(position.abs_diff_eq(expected, 1.0e-3));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_no_collision_steps() {
+    let mut physics_system = physics_system(quad_tree);
+    let (_, position) = fall(&mut physics_system, 60, 0);
+    assert_eq!(position, vec3(0.0, 100.0, 0.0));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_motion_types() {
+    let mut physics_system = physics_system(quad_tree);
+    let mut body_interface = physics_system.body_interface();
+    let static_id = body_interface
+        .create_and_add_body(&sphere(Vec3::ZERO, MotionType::Static, NON_MOVING))
+        .unwrap();
+    let mut settings = sphere(vec3(5.0, 0.0, 0.0), MotionType::Kinematic, MOVING);
+    settings.linear_velocity = Vec3::X;
+    let kinematic_id = body_interface.create_and_add_body(&settings).unwrap();
+    // Bodies that are not added are not simulated.
+    let removed_id = body_interface
+        .create_body(&sphere(vec3(-5.0, 0.0, 0.0), MotionType::Dynamic, MOVING))
+        .unwrap();
+
+    simulate(&mut physics_system, 60);
+    let position = |body_id| physics_system.body(body_id).unwrap().position();
+    assert_eq!(position(static_id), Vec3::ZERO);
+    assert!(position(kinematic_id).abs_diff_eq(vec3(6.0, 0.0, 0.0), 1.0e-4));
+    assert_eq!(position(removed_id), vec3(-5.0, 0.0, 0.0));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_contacts() {
+    let mut physics_system = physics_system(quad_tree);
+    physics_system.set_gravity(Vec3::ZERO);
+    let floor_id = add_floor(&mut physics_system);
+    let mut body_interface = physics_system.body_interface();
+    let sphere_id = body_interface
+        .create_and_add_body(&sphere(vec3(0.0, 0.45, 0.0), MotionType::Dynamic, MOVING))
+        .unwrap();
+    let debris_id = body_interface
+        .create_and_add_body(&sphere(vec3(0.5, 0.45, 0.0), MotionType::Dynamic, DEBRIS))
+        .unwrap();
+    // Far above the floor, outside of the speculative contact distance.
+    body_interface
+        .create_and_add_body(&sphere(vec3(5.0, 2.0, 0.0), MotionType::Dynamic, MOVING))
+        .unwrap();
+
+    physics_system.update(1.0 / 60.0, 1);
+    let mut pairs: Vec<_> = physics_system
+        .contacts()
+        .iter()
+        .map(|contact| (contact.body_id1, contact.body_id2))
+        .collect();
+    pairs.sort();
+    // The debris overlaps the sphere but is only allowed to collide with the floor.
+    assert_eq!(pairs, [(sphere_id, floor_id), (debris_id, floor_id)]);
+    let contact = &physics_system.contacts()[0];
+    assert!(contact.manifold.normal.abs_diff_eq(-Vec3::Y, 1.0e-4));
+    assert!((contact.manifold.penetration_depth() - 0.05).abs() < 1.0e-3);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_collision_groups() {
+    let mut physics_system = physics_system(quad_tree);
+    physics_system.set_gravity(Vec3::ZERO);
+    let mut group_filter = GroupFilterTable::new(2);
+    group_filter.disable_collision(0, 1);
+    let group_filter = Arc::new(group_filter);
+
+    let mut body_interface = physics_system.body_interface();
+    let mut settings = sphere(Vec3::ZERO, MotionType::Dynamic, MOVING);
+    settings.collision_group = CollisionGroup::new(group_filter.clone(), 0, 0);
+    body_interface.create_and_add_body(&settings).unwrap();
+    settings.position = vec3(0.5, 0.0, 0.0);
+    settings.collision_group = CollisionGroup::new(group_filter.clone(), 0, 1);
+    body_interface.create_and_add_body(&settings).unwrap();
+    physics_system.update(1.0 / 60.0, 1);
+    assert!(physics_system.contacts().is_empty());
+
+    // Bodies in another group do collide.
+    let mut body_interface = physics_system.body_interface();
+    settings.position = vec3(-0.6, 0.0, 0.0);
+    settings.collision_group = CollisionGroup::new(group_filter, 1, 1);
+    body_interface.create_and_add_body(&settings).unwrap();
+    physics_system.update(1.0 / 60.0, 1);
+    assert_eq!(physics_system.contacts().len(), 1);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_body_filter() {
+    let mut physics_system = physics_system(quad_tree);
+    physics_system.set_gravity(Vec3::ZERO);
+    let floor_id = add_floor(&mut physics_system);
+    let mut body_interface = physics_system.body_interface();
+    let settings = sphere(vec3(0.0, 0.45, 0.0), MotionType::Dynamic, MOVING);
+    body_interface.create_and_add_body(&settings).unwrap();
+    physics_system.update(1.0 / 60.0, 1);
+    assert_eq!(physics_system.contacts().len(), 1);
+
+    // An ignored body takes no part in collision detection.
+    physics_system.set_body_filter(Some(Box::new(IgnoreSingleBodyFilter(floor_id))));
+    physics_system.update(1.0 / 60.0, 1);
+    assert!(physics_system.contacts().is_empty());
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Activation {
+    Activated(BodyID),
+    Deactivated(BodyID),
+}
+
+#[derive(Default)]
+struct ActivationRecorder(Arc<Mutex<Vec<Activation>>>);
+
+impl BodyActivationListener for ActivationRecorder {
+    fn on_body_activated(&mut self, body_id: BodyID, _user_data: u64) {
+        self.0.lock().unwrap().push(Activation::Activated(body_id));
+    }
+
+    fn on_body_deactivated(&mut self, body_id: BodyID, _user_data: u64) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(Activation::Deactivated(body_id));
+    }
+}
+
+fn record_activations(physics_system: &mut PhysicsSystem) -> Arc<Mutex<Vec<Activation>>> {
+    let recorder = ActivationRecorder::default();
+    let activations = recorder.0.clone();
+    physics_system.set_body_activation_listener(Some(Box::new(recorder)));
+    activations
+}
+
+/// Simulates until all bodies are asleep and returns the number of steps it took.
+fn simulate_until_asleep(physics_system: &mut PhysicsSystem, max_steps: u32) -> Option<u32> {
+    (1..=max_steps).find(|_| {
+        simulate(physics_system, 1);
+        physics_system
+            .body_manager()
+            .bodies()
+            .all(|body| !body.is_active())
+    })
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_sleeping() {
+    let mut physics_system = physics_system(quad_tree);
+    let activations = record_activations(&mut physics_system);
+    add_floor(&mut physics_system);
+    let mut body_interface = physics_system.body_interface();
+    let body_id = body_interface
+        .create_and_add_body(&unit_box(vec3(0.0, 0.5, 0.0)))
+        .unwrap();
+    assert!(body_interface.is_active(body_id));
+
+    // The box rests from the start, so it sleeps once the sleep time has passed.
+    let time_before_sleep = physics_system.physics_settings().time_before_sleep;
+    let steps = simulate_until_asleep(&mut physics_system, 120).unwrap();
+    assert!(steps <= (time_before_sleep * 60.0) as u32 + 2, "{steps}");
+    assert_eq!(
+        *activations.lock().unwrap(),
+        [
+            Activation::Activated(body_id),
+            Activation::Deactivated(body_id)
+        ]
+    );
+
+    // A sleeping body does not move, even when gravity changes.
+    let position = physics_system.body(body_id).unwrap().position();
+    physics_system.set_gravity(vec3(5.0, -9.81, 0.0));
+    physics_system.update(1.0 / 60.0, 1);
+    assert_eq!(physics_system.body(body_id).unwrap().position(), position);
+    assert!(physics_system.contacts().is_empty());
+
+    // An impulse wakes it up.
+    physics_system
+        .body_interface()
+        .add_impulse(body_id, vec3(0.0, 1.0, 0.0))
+        .unwrap();
+    physics_system.update(1.0 / 60.0, 1);
+    let body = physics_system.body(body_id).unwrap();
+    assert!(body.is_active());
+    assert!(body.position().x > position.x, "{body:?}");
+    assert_eq!(
+        activations.lock().unwrap()[2..],
+        [Activation::Activated(body_id)]
+    );
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_islands() {
+    let mut physics_system = physics_system(quad_tree);
+    add_floor(&mut physics_system);
+    let mut body_interface = physics_system.body_interface();
+    let stack: Vec<_> = [0.5, 1.5, 2.5]
+        .into_iter()
+        .map(|y| {
+            body_interface
+                .create_and_add_body(&unit_box(vec3(-3.0, y, 0.0)))
+                .unwrap()
+        })
+        .collect();
+    let single = body_interface
+        .create_and_add_body(&unit_box(vec3(3.0, 0.5, 0.0)))
+        .unwrap();
+    physics_system.update(1.0 / 60.0, 1);
+
+    // The boxes of the stack touch each other, the floor does not link them.
+    let island_builder = physics_system.island_builder();
+    assert!(island_builder.islands().eq([&[0, 1, 2][..], &[3]]));
+
+    // Every island goes to sleep as a whole.
+    let activations = record_activations(&mut physics_system);
+    simulate_until_asleep(&mut physics_system, 300).unwrap();
+    let activations = activations.lock().unwrap();
+    let deactivations: Vec<_> = stack
+        .iter()
+        .map(|&body_id| {
+            activations
+                .iter()
+                .position(|&activation| activation == Activation::Deactivated(body_id))
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(deactivations[1], deactivations[0] + 1);
+    assert_eq!(deactivations[2], deactivations[0] + 2);
+    assert!(activations.contains(&Activation::Deactivated(single)));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_wake_on_contact() {
+    let mut physics_system = physics_system(quad_tree);
+    add_floor(&mut physics_system);
+    let mut body_interface = physics_system.body_interface();
+    let bottom = body_interface
+        .create_and_add_body(&unit_box(vec3(0.0, 0.5, 0.0)))
+        .unwrap();
+    let top = body_interface
+        .create_and_add_body(&unit_box(vec3(0.0, 1.5, 0.0)))
+        .unwrap();
+    simulate_until_asleep(&mut physics_system, 120).unwrap();
+    let cache = physics_system.contact_constraint_manager().contact_cache();
+    assert_eq!(cache.manifold_count(), 2);
+
+    // A sphere that lands on the stack wakes up the top box, which wakes up the bottom box
+    // during the same step.
+    let activations = record_activations(&mut physics_system);
+    let sphere_id = add_sphere(&mut physics_system, vec3(0.0, 3.0, 0.0));
+    while !physics_system.body(top).unwrap().is_active() {
+        physics_system.update(1.0 / 60.0, 1);
+    }
+    assert!(physics_system.body(bottom).unwrap().is_active());
+    assert_eq!(
+        *activations.lock().unwrap(),
+        [
+            Activation::Activated(sphere_id),
+            Activation::Activated(top),
+            Activation::Activated(bottom)
+        ]
+    );
+    assert_eq!(physics_system.contacts().len(), 3);
+
+    // The stack carries the sphere and falls asleep again.
+    simulate_until_asleep(&mut physics_system, 300).unwrap();
+    let body = physics_system.body(top).unwrap();
+    assert!((body.position().y - 1.5).abs() < 0.05, "{body:?}");
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_allow_sleeping() {
+    let mut physics_system = physics_system(quad_tree);
+    add_floor(&mut physics_system);
+    let mut body_interface = physics_system.body_interface();
+    body_interface
+        .create_and_add_body(&unit_box(vec3(0.0, 0.5, 0.0)))
+        .unwrap();
+    let mut settings = unit_box(vec3(0.0, 1.5, 0.0));
+    settings.allow_sleeping = false;
+    let awake = body_interface.create_and_add_body(&settings).unwrap();
+
+    // The box that is not allowed to sleep keeps its island awake.
+    assert_eq!(simulate_until_asleep(&mut physics_system, 120), None);
+    physics_system
+        .body_interface()
+        .set_position(awake, vec3(5.0, 0.5, 0.0))
+        .unwrap();
+    assert_eq!(simulate_until_asleep(&mut physics_system, 120), None);
+    let sleeping = physics_system
+        .body_manager()
+        .bodies()
+        .filter(|body| body.is_dynamic() && !body.is_active())
+        .count();
+    assert_eq!(sleeping, 1);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_sleeping_disabled() {
+    let mut physics_system = physics_system(quad_tree);
+    physics_system.set_physics_settings(PhysicsSettings {
+        allow_sleeping: false,
+        ..Default::default()
+    });
+    add_floor(&mut physics_system);
+    let mut body_interface = physics_system.body_interface();
+    body_interface
+        .create_and_add_body(&unit_box(vec3(0.0, 0.5, 0.0)))
+        .unwrap();
+    assert_eq!(simulate_until_asleep(&mut physics_system, 120), None);
+}
+
+/// Fires a sphere with a radius of 5cm at 500 m/s at a wall that is 1cm thick, 5m away.
+fn fire_bullet(
+    motion_quality: MotionQuality,
+    restitution: f32,
+    wall_motion_type: MotionType,
+) -> (PhysicsSystem, BodyID, BodyID) {
+    let mut physics_system = physics_system(quad_tree);
+    physics_system.set_gravity(Vec3::ZERO);
+    let mut body_interface = physics_system.body_interface();
+    let wall = body_interface
+        .create_and_add_body(&BodyCreationSettings::new(
+            Arc::new(BoxShape::new(vec3(0.005, 1.0, 1.0)).unwrap()),
+            vec3(5.0, 0.0, 0.0),
+            Quat::IDENTITY,
+            wall_motion_type,
+            MOVING,
+        ))
+        .unwrap();
+    let mut settings = BodyCreationSettings::new(
+        Arc::new(SphereShape::new(0.05).unwrap()),
+        Vec3::ZERO,
+        Quat::IDENTITY,
+        MotionType::Dynamic,
+        MOVING,
+    );
+    settings.linear_velocity = vec3(500.0, 0.0, 0.0);
+    settings.linear_damping = 0.0;
+    settings.restitution = restitution;
+    settings.motion_quality = motion_quality;
+    let bullet = body_interface.create_and_add_body(&settings).unwrap();
+    simulate(&mut physics_system, 10);
+    (physics_system, bullet, wall)
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_discrete_tunneling() {
+    // The bullet moves 8.3m per step, so it never overlaps the wall.
+    let (physics_system, bullet, _) = fire_bullet(MotionQuality::Discrete, 0.0, MotionType::Static);
+    let body = physics_system.body(bullet).unwrap();
+    assert!(body.position().x > 80.0, "{body:?}");
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_linear_cast() {
+    let (physics_system, bullet, wall) =
+        fire_bullet(MotionQuality::LinearCast, 0.0, MotionType::Static);
+    let body = physics_system.body(bullet).unwrap();
+    assert!(body.position().x < 5.0, "{body:?}");
+    assert!((body.position().x - 4.945).abs() < 0.02, "{body:?}");
+    assert!(body.linear_velocity().length() < 0.01, "{body:?}");
+    let contact = &physics_system.contacts()[0];
+    assert_eq!((contact.body_id1, contact.body_id2), (bullet, wall));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_linear_cast_restitution() {
+    let (physics_system, bullet, _) =
+        fire_bullet(MotionQuality::LinearCast, 1.0, MotionType::Static);
+    let body = physics_system.body(bullet).unwrap();
+    assert!(body.position().x < 0.0, "{body:?}");
+    assert!(body.linear_velocity().x < -490.0, "{body:?}");
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_linear_cast_dynamic() {
+    let (physics_system, bullet, wall) =
+        fire_bullet(MotionQuality::LinearCast, 0.0, MotionType::Dynamic);
+    let bullet = physics_system.body(bullet).unwrap();
+    let wall = physics_system.body(wall).unwrap();
+
+    // The wall is pushed away and the bullet stays in front of it.
+    assert!(bullet.position().x < wall.position().x, "{bullet:?}");
+    let bullet_mass = bullet.shape().mass_properties().mass;
+    let wall_mass = wall.shape().mass_properties().mass;
+    let momentum = bullet_mass * bullet.linear_velocity().x + wall_mass * wall.linear_velocity().x;
+    assert!(
+        (momentum - bullet_mass * 500.0).abs() < 0.01 * bullet_mass * 500.0,
+        "{momentum}"
+    );
+    assert!(wall.linear_velocity().x > 1.0, "{wall:?}");
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_linear_cast_body_filter() {
+    // The bullet passes the wall when the filter rejects either of them.
+    for ignore_bullet in [false, true] {
+        let mut physics_system = physics_system(quad_tree);
+        physics_system.set_gravity(Vec3::ZERO);
+        let mut body_interface = physics_system.body_interface();
+        let wall = body_interface
+            .create_and_add_body(&sphere(vec3(8.0, 0.0, 0.0), MotionType::Static, NON_MOVING))
+            .unwrap();
+        let mut settings = sphere(Vec3::ZERO, MotionType::Dynamic, MOVING);
+        settings.linear_velocity = vec3(600.0, 0.0, 0.0);
+        settings.motion_quality = MotionQuality::LinearCast;
+        let bullet = body_interface.create_and_add_body(&settings).unwrap();
+        let ignored = if ignore_bullet { bullet } else { wall };
+        physics_system.set_body_filter(Some(Box::new(IgnoreSingleBodyFilter(ignored))));
+        simulate(&mut physics_system, 2);
+
+        let body = physics_system.body(bullet).unwrap();
+        assert!(body.position().x > 15.0, "{body:?}");
+        assert!(physics_system.contacts().is_empty());
+    }
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_speculative_contacts() {
+    for (new_broad_phase, speculative_contact_distance) in [
+        (quad_tree as fn(_) -> _, 0.0),
+        (quad_tree, 0.02),
+        (sweep_and_prune, 0.0),
+        (sweep_and_prune, 0.02),
+    ] {
+        let mut physics_system = physics_system(new_broad_phase);
+        physics_system.set_gravity(Vec3::ZERO);
+        physics_system.set_physics_settings(PhysicsSettings {
+            speculative_contact_distance,
+            ..Default::default()
+        });
+        add_floor(&mut physics_system);
+        let mut body_interface = physics_system.body_interface();
+        let mut settings = sphere(vec3(0.0, 0.51, 0.0), MotionType::Dynamic, MOVING);
+        settings.linear_velocity = vec3(0.0, -3.0, 0.0);
+        let body_id = body_interface.create_and_add_body(&settings).unwrap();
+        physics_system.update(1.0 / 60.0, 1);
+
+        // Within the speculative distance the sphere only keeps the velocity that closes the
+        // gap of 1cm, without it the sphere moves 5cm into the floor.
+        let body = physics_system.body(body_id).unwrap();
+        if speculative_contact_distance > 0.0 {
+            assert!((body.position().y - 0.5).abs() < 1.0e-3, "{body:?}");
+            assert!(
+                (body.linear_velocity().y + 0.01 * 60.0).abs() < 1.0e-3,
+                "{body:?}"
+            );
+        } else {
+            assert!(body.position().y < 0.47, "{body:?}");
+        }
+    }
+}
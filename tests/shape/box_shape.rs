@@ -0,0 +1,86 @@
+use phys_rs::{
+    collision::{RayCast, RayCastResult},
+    math::Vec3,
+    shape::{BoxShape, ConvexShape, Shape, ShapeError, SubShapeID, SupportMode},
+};
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_mass_properties() {
+    let shape = BoxShape::new(Vec3::new(1.0, 2.0, 3.0)).unwrap();
+    assert_eq!(shape.volume(), 48.0);
+
+    let mass_properties = shape.mass_properties();
+    let mass = 48.0 * 1000.0;
+    assert_eq!(mass_properties.mass, mass);
+    let inertia = Vec3::new(4.0 + 9.0, 1.0 + 9.0, 1.0 + 4.0) * (mass / 3.0);
+    assert!(mass_properties.inertia.diagonal().abs_diff_eq(inertia, 1.0));
+    assert_eq!(mass_properties.inertia.x_axis.y, 0.0);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_ray_cast() {
+    let shape = BoxShape::new(Vec3::new(1.0, 2.0, 3.0)).unwrap();
+    let mut hit = RayCastResult::default();
+    let ray = RayCast::new(Vec3::new(0.0, 0.0, -5.0), Vec3::new(0.0, 0.0, 4.0));
+    assert!(shape.cast_ray(&ray, &mut hit));
+    assert!((hit.fraction - 0.5).abs() < 1.0e-6);
+
+    let normal = shape.surface_normal(SubShapeID::EMPTY, ray.point_on_ray(hit.fraction));
+    assert_eq!(normal, -Vec3::Z);
+
+    let ray = RayCast::new(Vec3::new(2.0, 0.0, -5.0), Vec3::new(0.0, 0.0, 10.0));
+    assert!(!shape.cast_ray(&ray, &mut RayCastResult::default()));
+
+    assert!(shape.collide_point(Vec3::new(0.9, -1.9, 2.9)));
+    assert!(!shape.collide_point(Vec3::new(1.1, 0.0, 0.0)));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_support() {
+    let shape = BoxShape::with_convex_radius(Vec3::new(1.0, 2.0, 3.0), 0.5).unwrap();
+    let include = shape.get_support_function(SupportMode::IncludeConvexRadius, Vec3::ONE);
+    assert_eq!(include.support(Vec3::ONE), Vec3::new(1.0, 2.0, 3.0));
+    assert_eq!(include.convex_radius(), 0.0);
+
+    let exclude = shape.get_support_function(SupportMode::ExcludeConvexRadius, Vec3::ONE);
+    assert_eq!(exclude.support(-Vec3::ONE), Vec3::new(-0.5, -1.5, -2.5));
+    assert_eq!(exclude.convex_radius(), 0.5);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_errors() {
+    assert_eq!(
+        BoxShape::new(Vec3::new(1.0, 0.0, 1.0)),
+        Err(ShapeError::InvalidHalfExtent)
+    );
+    assert_eq!(
+        BoxShape::with_convex_radius(Vec3::ONE, 1.5),
+        Err(ShapeError::InvalidConvexRadius)
+    );
+    assert_eq!(
+        BoxShape::new(Vec3::splat(0.01)).unwrap().convex_radius(),
+        0.01
+    );
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_supporting_face() {
+    let shape = BoxShape::new(Vec3::new(1.0, 2.0, 3.0)).unwrap();
+    let mut face = Vec::new();
+    shape.get_supporting_face(Vec3::new(0.1, -1.0, 0.2), Vec3::ONE, &mut face);
+    assert_eq!(face.len(), 4);
+    assert!(face.iter().all(|vertex| vertex.y == -2.0));
+
+    // The face is wound counter clockwise seen from outside.
+    let normal = (face[1] - face[0]).cross(face[2] - face[1]);
+    assert!(normal.y < 0.0);
+
+    shape.get_supporting_face(Vec3::X, Vec3::new(2.0, 1.0, 1.0), &mut face);
+    assert!(face.iter().all(|vertex| vertex.x == 2.0));
+    assert!((face[1] - face[0]).cross(face[2] - face[1]).x > 0.0);
+}
@@ -0,0 +1,67 @@
+use core::f32::consts::PI;
+
+use phys_rs::{
+    collision::{RayCast, RayCastResult},
+    math::Vec3,
+    shape::{CapsuleShape, ConvexShape, Shape, ShapeError, SubShapeID},
+};
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_mass_properties() {
+    let (h, r) = (1.0, 0.5);
+    let shape = CapsuleShape::new(h, r).unwrap().with_density(1.0).unwrap();
+    let cylinder_volume = PI * r * r * 2.0 * h;
+    let sphere_volume = 4.0 / 3.0 * PI * r * r * r;
+    assert!((shape.volume() - (cylinder_volume + sphere_volume)).abs() < 1.0e-5);
+
+    // The inertia of the cylinder plus two hemispheres moved to the ends of the cylinder.
+    let mass_properties = shape.mass_properties();
+    let inertia_y = cylinder_volume * r * r / 2.0 + sphere_volume * 0.4 * r * r;
+    let inertia_x = cylinder_volume * (r * r / 4.0 + h * h / 3.0)
+        + sphere_volume * (0.4 * r * r + h * h + 0.75 * h * r);
+    assert!(mass_properties
+        .inertia
+        .diagonal()
+        .abs_diff_eq(Vec3::new(inertia_x, inertia_y, inertia_x), 1.0e-5));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_ray_cast() {
+    let shape = CapsuleShape::new(1.0, 0.5).unwrap();
+    let mut hit = RayCastResult::default();
+    let ray = RayCast::new(Vec3::new(0.0, 3.0, 0.0), Vec3::new(0.0, -3.0, 0.0));
+    assert!(shape.cast_ray(&ray, &mut hit));
+    assert!((hit.fraction - 0.5).abs() < 1.0e-5);
+
+    let normal = shape.surface_normal(SubShapeID::EMPTY, Vec3::new(0.5, 0.5, 0.0));
+    assert!(normal.abs_diff_eq(Vec3::X, 1.0e-6));
+    let normal = shape.surface_normal(SubShapeID::EMPTY, Vec3::new(0.0, 1.5, 0.0));
+    assert!(normal.abs_diff_eq(Vec3::Y, 1.0e-6));
+
+    assert!(shape.collide_point(Vec3::new(0.0, 1.4, 0.0)));
+    assert!(!shape.collide_point(Vec3::new(0.45, 1.45, 0.0)));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_errors() {
+    assert_eq!(CapsuleShape::new(1.0, 0.0), Err(ShapeError::InvalidRadius));
+    assert_eq!(CapsuleShape::new(-1.0, 1.0), Err(ShapeError::InvalidHeight));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_supporting_face() {
+    let shape = CapsuleShape::new(1.0, 0.5).unwrap();
+    let mut face = Vec::new();
+    shape.get_supporting_face(Vec3::X, Vec3::ONE, &mut face);
+    assert_eq!(face, [Vec3::new(0.5, 1.0, 0.0), Vec3::new(0.5, -1.0, 0.0)]);
+
+    // Any other direction is supported by a single point on a hemisphere.
+    shape.get_supporting_face(Vec3::new(1.0, 0.5, 0.0), Vec3::ONE, &mut face);
+    assert!(face.is_empty());
+    shape.get_supporting_face(Vec3::Y, Vec3::ONE, &mut face);
+    assert!(face.is_empty());
+}
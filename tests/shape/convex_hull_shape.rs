@@ -0,0 +1,161 @@
+use phys_rs::{
+    collision::{RayCast, RayCastResult},
+    geometry::ConvexHullError,
+    math::{vec3, Mat33, Vec3},
+    shape::{
+        BoxShape, ConvexHullShape, ConvexShape, Shape, ShapeError, SubShapeID, SupportMode,
+        MAX_POINTS_IN_HULL,
+    },
+};
+
+fn box_corners(half_extent: Vec3, offset: Vec3) -> Vec<Vec3> {
+    (0..8)
+        .map(|i| {
+            offset
+                + vec3(
+                    if i & 1 == 0 {
+                        -half_extent.x
+                    } else {
+                        half_extent.x
+                    },
+                    if i & 2 == 0 {
+                        -half_extent.y
+                    } else {
+                        half_extent.y
+                    },
+                    if i & 4 == 0 {
+                        -half_extent.z
+                    } else {
+                        half_extent.z
+                    },
+                )
+        })
+        .collect()
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_box_mass_properties() {
+    let half_extent = vec3(1.0, 2.0, 3.0);
+    let offset = vec3(5.0, -1.0, 2.0);
+    let hull = ConvexHullShape::new(&box_corners(half_extent, offset)).unwrap();
+    let reference = BoxShape::new(half_extent).unwrap();
+
+    assert!((hull.volume() - reference.volume()).abs() < 1.0e-4);
+    assert!(hull.center_of_mass().abs_diff_eq(offset, 1.0e-5));
+    assert!((hull.inner_radius() - 1.0).abs() < 1.0e-5);
+    assert!(hull.local_bounds().min.abs_diff_eq(-half_extent, 1.0e-5));
+
+    let mass_properties = hull.mass_properties();
+    let expected = reference.mass_properties();
+    assert!((mass_properties.mass - expected.mass).abs() < 1.0e-1);
+    assert!(mass_properties.inertia.abs_diff_eq(&expected.inertia, 5.0));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_tetrahedron_mass_properties() {
+    let points = [Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z];
+    let shape = ConvexHullShape::new(&points)
+        .unwrap()
+        .with_density(1.0)
+        .unwrap();
+    assert!((shape.volume() - 1.0 / 6.0).abs() < 1.0e-6);
+    assert!(shape
+        .center_of_mass()
+        .abs_diff_eq(Vec3::splat(0.25), 1.0e-6));
+
+    // The analytic inertia of the unit tetrahedron around its center of mass.
+    let (a, b) = (1.0 / 80.0, 1.0 / 480.0);
+    let expected = Mat33::from([[a, b, b], [b, a, b], [b, b, a]]);
+    assert!(shape
+        .mass_properties()
+        .inertia
+        .abs_diff_eq(&expected, 1.0e-6));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_ray_cast() {
+    let shape = ConvexHullShape::new(&box_corners(Vec3::ONE, Vec3::ZERO)).unwrap();
+    let mut hit = RayCastResult::default();
+    let ray = RayCast::new(vec3(-3.0, 0.5, 0.5), vec3(4.0, 0.0, 0.0));
+    assert!(shape.cast_ray(&ray, &mut hit));
+    assert!((hit.fraction - 0.5).abs() < 1.0e-6);
+
+    let normal = shape.surface_normal(SubShapeID::EMPTY, ray.point_on_ray(hit.fraction));
+    assert!(normal.abs_diff_eq(-Vec3::X, 1.0e-6));
+
+    let ray = RayCast::new(vec3(-3.0, 1.5, 0.5), vec3(4.0, 0.0, 0.0));
+    assert!(!shape.cast_ray(&ray, &mut RayCastResult::default()));
+
+    let mut hit = RayCastResult::default();
+    assert!(shape.cast_ray(&RayCast::new(Vec3::ZERO, Vec3::X), &mut hit));
+    assert_eq!(hit.fraction, 0.0);
+
+    assert!(shape.collide_point(vec3(0.9, -0.9, 0.9)));
+    assert!(!shape.collide_point(vec3(1.1, 0.0, 0.0)));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_support() {
+    let points = box_corners(Vec3::ONE, Vec3::ZERO);
+    let shape = ConvexHullShape::with_convex_radius(&points, 0.25).unwrap();
+
+    let include = shape.get_support_function(SupportMode::IncludeConvexRadius, Vec3::ONE);
+    assert!(include.support(Vec3::ONE).abs_diff_eq(Vec3::ONE, 1.0e-6));
+    assert_eq!(include.convex_radius(), 0.0);
+
+    let exclude = shape.get_support_function(SupportMode::ExcludeConvexRadius, vec3(2.0, 1.0, 1.0));
+    assert!(exclude
+        .support(-Vec3::ONE)
+        .abs_diff_eq(vec3(-1.5, -0.75, -0.75), 1.0e-5));
+    assert_eq!(exclude.convex_radius(), 0.25);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_supporting_face() {
+    let shape = ConvexHullShape::new(&box_corners(Vec3::ONE, Vec3::ZERO)).unwrap();
+    let mut vertices = Vec::new();
+    shape.get_supporting_face(vec3(0.9, 0.1, -0.2), vec3(1.0, 2.0, 3.0), &mut vertices);
+    assert_eq!(vertices.len(), 4);
+    assert!(vertices.iter().all(|v| (v.x - 1.0).abs() < 1.0e-6));
+
+    // The face is wound counter clockwise around its outward normal.
+    let normal = (vertices[1] - vertices[0]).cross(vertices[2] - vertices[0]);
+    assert!(normal.x > 0.0);
+
+    shape.get_supporting_face(Vec3::Y, vec3(1.0, -1.0, 1.0), &mut vertices);
+    let normal = (vertices[1] - vertices[0]).cross(vertices[2] - vertices[0]);
+    assert!(normal.y > 0.0);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_errors() {
+    let points = [Vec3::ZERO, Vec3::X, Vec3::Y, vec3(1.0, 1.0, 0.0)];
+    assert_eq!(
+        ConvexHullShape::new(&points),
+        Err(ShapeError::ConvexHull(ConvexHullError::Coplanar))
+    );
+    assert_eq!(
+        ConvexHullShape::with_convex_radius(&box_corners(Vec3::ONE, Vec3::ZERO), 1.5),
+        Err(ShapeError::InvalidConvexRadius)
+    );
+
+    // Points on a sphere all end up on the hull.
+    let sphere: Vec<Vec3> = (0..MAX_POINTS_IN_HULL + 44)
+        .map(|i| {
+            let y = 1.0 - 2.0 * (i as f32 + 0.5) / (MAX_POINTS_IN_HULL + 44) as f32;
+            let radius = (1.0 - y * y).sqrt();
+            let angle = i as f32 * 2.399_963;
+            vec3(radius * angle.cos(), y, radius * angle.sin())
+        })
+        .collect();
+    assert_eq!(
+        ConvexHullShape::new(&sphere),
+        Err(ShapeError::TooManyPoints)
+    );
+}
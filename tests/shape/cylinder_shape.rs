@@ -0,0 +1,74 @@
+use core::f32::consts::PI;
+
+use phys_rs::{
+    collision::{RayCast, RayCastResult},
+    math::Vec3,
+    shape::{ConvexShape, CylinderShape, Shape, ShapeError, SubShapeID, SupportMode},
+};
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_mass_properties() {
+    let (h, r) = (1.5, 0.5);
+    let shape = CylinderShape::new(h, r).unwrap().with_density(2.0).unwrap();
+    let volume = PI * r * r * 2.0 * h;
+    assert!((shape.volume() - volume).abs() < 1.0e-5);
+
+    let mass_properties = shape.mass_properties();
+    let mass = volume * 2.0;
+    assert!((mass_properties.mass - mass).abs() < 1.0e-5);
+    let inertia_x = mass * (3.0 * r * r + 4.0 * h * h) / 12.0;
+    let inertia_y = mass * r * r / 2.0;
+    assert!(mass_properties
+        .inertia
+        .diagonal()
+        .abs_diff_eq(Vec3::new(inertia_x, inertia_y, inertia_x), 1.0e-5));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_ray_cast() {
+    let shape = CylinderShape::new(1.0, 0.5).unwrap();
+    let mut hit = RayCastResult::default();
+    let ray = RayCast::new(Vec3::new(-2.5, 0.9, 0.0), Vec3::new(4.0, 0.0, 0.0));
+    assert!(shape.cast_ray(&ray, &mut hit));
+    assert!((hit.fraction - 0.5).abs() < 1.0e-5);
+
+    let normal = shape.surface_normal(SubShapeID::EMPTY, ray.point_on_ray(hit.fraction));
+    assert!(normal.abs_diff_eq(-Vec3::X, 1.0e-6));
+    let normal = shape.surface_normal(SubShapeID::EMPTY, Vec3::new(0.1, -1.0, 0.1));
+    assert!(normal.abs_diff_eq(-Vec3::Y, 1.0e-6));
+
+    assert!(shape.collide_point(Vec3::new(0.3, 0.9, 0.3)));
+    assert!(!shape.collide_point(Vec3::new(0.4, 0.0, 0.4)));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_support() {
+    let shape = CylinderShape::with_convex_radius(1.0, 0.5, 0.1).unwrap();
+    let include = shape.get_support_function(SupportMode::IncludeConvexRadius, Vec3::ONE);
+    let point = include.support(Vec3::new(1.0, 1.0, 0.0));
+    assert!(point.abs_diff_eq(Vec3::new(0.5, 1.0, 0.0), 1.0e-6));
+    assert_eq!(
+        CylinderShape::with_convex_radius(1.0, 0.5, 0.6),
+        Err(ShapeError::InvalidConvexRadius)
+    );
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_supporting_face() {
+    let shape = CylinderShape::new(1.0, 0.5).unwrap();
+    let mut face = Vec::new();
+
+    // Along the axis the face is the cap.
+    shape.get_supporting_face(Vec3::new(0.1, 1.0, 0.0), Vec3::ONE, &mut face);
+    assert_eq!(face.len(), 8);
+    assert!(face.iter().all(|vertex| vertex.y == 1.0));
+    assert!((face[1] - face[0]).cross(face[2] - face[1]).y > 0.0);
+
+    // Sideways it is a line along the side.
+    shape.get_supporting_face(Vec3::new(1.0, 0.1, 0.0), Vec3::ONE, &mut face);
+    assert_eq!(face, [Vec3::new(0.5, 1.0, 0.0), Vec3::new(0.5, -1.0, 0.0)]);
+}
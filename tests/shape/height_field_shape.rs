@@ -0,0 +1,318 @@
+use phys_rs::{
+    collision::{RayCast, RayCastResult},
+    geometry::ray_triangle,
+    math::{vec3, Quat, Vec3},
+    shape::{
+        HeightFieldSettings, HeightFieldShape, Shape, ShapeError, SphereShape, SubShapeID,
+        NO_COLLISION_VALUE,
+    },
+};
+
+struct Random(u32);
+
+impl Random {
+    fn next(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        (self.0 as f32 / u32::MAX as f32) * 2.0 - 1.0
+    }
+
+    fn vec3(&mut self) -> Vec3 {
+        vec3(self.next(), self.next(), self.next())
+    }
+}
+
+/// Rolling hills of `size` by `size` samples.
+fn hills(size: u32) -> Vec<f32> {
+    (0..size * size)
+        .map(|i| {
+            let (x, y) = ((i % size) as f32, (i / size) as f32);
+            (x * 0.3).sin() * 2.0 + (y * 0.2).cos() * 1.5
+        })
+        .collect()
+}
+
+fn brute_force_ray(shape: &HeightFieldShape, ray: &RayCast) -> f32 {
+    let cells = shape.sample_count() - 1;
+    let mut closest = f32::MAX;
+    for y in 0..cells {
+        for x in 0..cells {
+            let [p00, p10, p01, p11] = [(x, y), (x + 1, y), (x, y + 1), (x + 1, y + 1)]
+                .map(|(x, y)| shape.get_position(x, y));
+            for [v0, v1, v2] in [[p00, p01, p11], [p00, p11, p10]] {
+                closest = closest.min(ray_triangle(ray.origin, ray.direction, v0, v1, v2));
+            }
+        }
+    }
+    closest
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_ray_cast() {
+    let samples = hills(65);
+    let mut settings = HeightFieldSettings::new(&samples, 65);
+    settings.offset = vec3(-32.0, 0.0, -32.0);
+    settings.block_size = 4;
+    let shape = HeightFieldShape::new(&settings).unwrap();
+    assert_eq!(shape.mass_properties().mass, 0.0);
+
+    let mut random = Random(0x8765_4321);
+    let mut hits = 0;
+    for _ in 0..500 {
+        let origin = vec3(0.0, 4.0, 0.0) + random.vec3() * vec3(40.0, 6.0, 40.0);
+        let direction = random.vec3() * 60.0;
+        let ray = RayCast::new(origin, direction);
+        let expected = brute_force_ray(&shape, &ray);
+
+        let mut hit = RayCastResult::default();
+        if shape.cast_ray(&ray, &mut hit) {
+            hits += 1;
+            assert!((hit.fraction - expected).abs() < 1.0e-5);
+        } else {
+            assert!(expected > 1.0);
+        }
+    }
+    assert!(hits > 50);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_quantization() {
+    let samples = hills(33);
+    for bits_per_sample in [2, 4, 8] {
+        let mut settings = HeightFieldSettings::new(&samples, 33);
+        settings.bits_per_sample = bits_per_sample;
+        let shape = HeightFieldShape::new(&settings).unwrap();
+
+        // Every block spans at most a fraction of the total height range, which is divided into
+        // `2^bits - 1` levels.
+        let tolerance = 7.0 / ((1 << bits_per_sample) - 2) as f32;
+        for y in 0..33 {
+            for x in 0..33 {
+                let expected = samples[(y * 33 + x) as usize];
+                assert!((shape.get_height(x, y) - expected).abs() <= tolerance);
+            }
+        }
+    }
+
+    // Flat terrain is represented exactly.
+    let flat = [3.5; 25];
+    let shape = HeightFieldShape::new(&HeightFieldSettings::new(&flat, 5)).unwrap();
+    assert_eq!(shape.get_height(2, 3), 3.5);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_holes_and_materials() {
+    let mut samples = [0.0; 5 * 5];
+    samples[2 * 5 + 2] = NO_COLLISION_VALUE;
+    let materials: Vec<u8> = (0..16).collect();
+    let mut settings = HeightFieldSettings::new(&samples, 5);
+    settings.scale = vec3(2.0, 1.0, 2.0);
+    settings.material_indices = &materials;
+    let shape = HeightFieldShape::new(&settings).unwrap();
+    assert!(shape.is_no_collision(2, 2));
+    assert_eq!(shape.get_height(2, 2), NO_COLLISION_VALUE);
+
+    // The six triangles that use the hole have no collision.
+    let down = vec3(0.0, -2.0, 0.0);
+    for (x, z) in [(3.0, 3.0), (4.5, 3.5), (3.5, 4.5), (5.0, 5.0)] {
+        let mut hit = RayCastResult::default();
+        assert!(!shape.cast_ray(&RayCast::new(vec3(x, 1.0, z), down), &mut hit));
+    }
+    for (x, z) in [(5.5, 2.5), (2.5, 5.5)] {
+        let mut hit = RayCastResult::default();
+        assert!(shape.cast_ray(&RayCast::new(vec3(x, 1.0, z), down), &mut hit));
+    }
+
+    // Cell (3, 1) is not affected.
+    let ray = RayCast::new(vec3(7.0, 1.0, 2.5), down);
+    let mut hit = RayCastResult::default();
+    assert!(shape.cast_ray(&ray, &mut hit));
+    assert!((hit.fraction - 0.5).abs() < 1.0e-6);
+    assert_eq!(shape.material_index(hit.sub_shape_id), 4 + 3);
+    let normal = shape.surface_normal(hit.sub_shape_id, ray.point_on_ray(hit.fraction));
+    assert!((normal - Vec3::Y).length() < 1.0e-6);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_set_heights() {
+    let samples = hills(17);
+    let mut shape = HeightFieldShape::new(&HeightFieldSettings::new(&samples, 17)).unwrap();
+
+    // Dig a pit and punch a hole into the terrain.
+    let mut pit = [-3.0; 16];
+    pit[15] = NO_COLLISION_VALUE;
+    shape.set_heights(5, 7, 4, 4, &pit);
+    assert!(shape.is_no_collision(8, 10));
+    assert!((shape.get_height(5, 7) + 3.0).abs() < 0.05);
+    assert!((shape.get_height(4, 7) - samples[7 * 17 + 4]).abs() < 0.05);
+
+    let mut hit = RayCastResult::default();
+    let ray = RayCast::new(vec3(5.6, 10.0, 7.2), vec3(0.0, -20.0, 0.0));
+    assert!(shape.cast_ray(&ray, &mut hit));
+    assert!((ray.point_on_ray(hit.fraction).y + 3.0).abs() < 0.05);
+
+    // The updated terrain matches a brute force test against the new heights.
+    let mut random = Random(0x1357_9bdf);
+    for _ in 0..200 {
+        let origin = vec3(8.0, 2.0, 8.0) + random.vec3() * vec3(10.0, 4.0, 10.0);
+        let ray = RayCast::new(origin, random.vec3() * 20.0);
+        let mut hit = RayCastResult::default();
+        let expected = brute_force_ray(&shape, &ray);
+        if shape.cast_ray(&ray, &mut hit) {
+            assert!((hit.fraction - expected).abs() < 1.0e-5);
+        } else {
+            assert!(expected > 1.0);
+        }
+    }
+
+    // Heights outside of the original range are clamped.
+    shape.set_heights(0, 0, 1, 1, &[100.0]);
+    assert!(shape.get_height(0, 0) < 4.0);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_collide_convex() {
+    let samples = hills(33);
+    let shape = HeightFieldShape::new(&HeightFieldSettings::new(&samples, 33)).unwrap();
+    let sphere = SphereShape::new(0.5).unwrap();
+
+    let mut hit = RayCastResult::default();
+    let ray = RayCast::new(vec3(12.3, 10.0, 20.6), vec3(0.0, -20.0, 0.0));
+    assert!(shape.cast_ray(&ray, &mut hit));
+    let surface = ray.point_on_ray(hit.fraction);
+    assert!(shape.collide_point(surface - vec3(0.0, 0.3, 0.0)));
+    assert!(!shape.collide_point(surface + vec3(0.0, 0.3, 0.0)));
+
+    let position = surface + vec3(0.0, 0.6, 0.0);
+
+    let mut closest = f32::MAX;
+    let mut found = Vec::new();
+    shape.collide_convex(&sphere, Quat::IDENTITY, position, 0.5, |id, result| {
+        closest = closest.min(result.distance_squared.sqrt());
+        found.push(id);
+    });
+    assert!(!found.is_empty());
+    assert!(found.len() < 32);
+    assert!(closest <= 0.1 + 1.0e-3);
+
+    let mut count = 0;
+    shape.collide_convex(
+        &sphere,
+        Quat::IDENTITY,
+        vec3(10.0, 10.0, 10.0),
+        0.5,
+        |_, _| count += 1,
+    );
+    assert_eq!(count, 0);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_save_restore() {
+    let mut samples = hills(21);
+    samples[30] = NO_COLLISION_VALUE;
+    let materials: Vec<u8> = (0..400).map(|i| (i % 7) as u8).collect();
+    let mut settings = HeightFieldSettings::new(&samples, 21);
+    settings.block_size = 8;
+    settings.bits_per_sample = 6;
+    settings.material_indices = &materials;
+    let shape = HeightFieldShape::new(&settings).unwrap();
+    let mut data = Vec::new();
+    shape.save(&mut data);
+
+    let restored = HeightFieldShape::restore(&data).unwrap();
+    assert_eq!(restored, shape);
+
+    let mut resaved = Vec::new();
+    restored.save(&mut resaved);
+    assert_eq!(resaved, data);
+
+    assert_eq!(
+        HeightFieldShape::restore(&data[..data.len() - 1]),
+        Err(ShapeError::InvalidData)
+    );
+    let mut corrupt = data.clone();
+    corrupt[0] = b'X';
+    assert_eq!(
+        HeightFieldShape::restore(&corrupt),
+        Err(ShapeError::InvalidData)
+    );
+    data.push(0);
+    assert_eq!(
+        HeightFieldShape::restore(&data),
+        Err(ShapeError::InvalidData)
+    );
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_sample_packing() {
+    let samples = hills(32);
+    let saved_len = |bits_per_sample| {
+        let mut settings = HeightFieldSettings::new(&samples, 32);
+        settings.bits_per_sample = bits_per_sample;
+        let mut data = Vec::new();
+        HeightFieldShape::new(&settings).unwrap().save(&mut data);
+        data.len()
+    };
+    let header_len = saved_len(8) - 32 * 32;
+    assert_eq!(saved_len(4) - header_len, 32 * 32 / 2);
+    assert_eq!(saved_len(2) - header_len, 32 * 32 / 4);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_errors() {
+    let samples = [0.0; 9];
+    let new = |settings: HeightFieldSettings| HeightFieldShape::new(&settings);
+    assert_eq!(
+        new(HeightFieldSettings::new(&samples, 4)),
+        Err(ShapeError::InvalidSampleCount)
+    );
+    assert_eq!(
+        new(HeightFieldSettings {
+            block_size: 3,
+            ..HeightFieldSettings::new(&samples, 3)
+        }),
+        Err(ShapeError::InvalidBlockSize)
+    );
+    assert_eq!(
+        new(HeightFieldSettings {
+            bits_per_sample: 9,
+            ..HeightFieldSettings::new(&samples, 3)
+        }),
+        Err(ShapeError::InvalidBitsPerSample)
+    );
+    assert_eq!(
+        new(HeightFieldSettings {
+            scale: vec3(1.0, 0.0, 1.0),
+            ..HeightFieldSettings::new(&samples, 3)
+        }),
+        Err(ShapeError::InvalidScale)
+    );
+    assert_eq!(
+        new(HeightFieldSettings {
+            material_indices: &[0; 3],
+            ..HeightFieldSettings::new(&samples, 3)
+        }),
+        Err(ShapeError::InvalidSampleCount)
+    );
+    assert_eq!(
+        new(HeightFieldSettings::new(&[0.0, 1.0, f32::NAN, 0.0], 2)),
+        Err(ShapeError::InvalidHeight)
+    );
+
+    // A height field with only holes has no triangles.
+    let holes = [NO_COLLISION_VALUE; 9];
+    let shape = new(HeightFieldSettings::new(&holes, 3)).unwrap();
+    let mut hit = RayCastResult::default();
+    let ray = RayCast::new(vec3(1.0, 1.0, 1.0), vec3(0.0, -2.0, 0.0));
+    assert!(!shape.cast_ray(&ray, &mut hit));
+    assert_eq!(shape.material_index(SubShapeID::from_raw(0)), 0);
+}
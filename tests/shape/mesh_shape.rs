@@ -0,0 +1,286 @@
+use phys_rs::{
+    collision::{RayCast, RayCastResult},
+    geometry::{ray_triangle, AABox, IndexedTriangle},
+    math::{vec3, Quat, Vec3},
+    shape::{
+        MeshShape, Shape, ShapeError, SphereShape, SubShapeID,
+        DEFAULT_ACTIVE_EDGE_COS_THRESHOLD_ANGLE,
+    },
+};
+
+struct Random(u32);
+
+impl Random {
+    fn next(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        (self.0 as f32 / u32::MAX as f32) * 2.0 - 1.0
+    }
+
+    fn vec3(&mut self) -> Vec3 {
+        vec3(self.next(), self.next(), self.next())
+    }
+}
+
+/// A bumpy grid of `size` by `size` cells in the XZ plane, the material of each triangle is the
+/// index of its cell.
+fn grid(size: u32) -> (Vec<Vec3>, Vec<IndexedTriangle>) {
+    let mut vertices = Vec::new();
+    for z in 0..=size {
+        for x in 0..=size {
+            let height = ((x * 7 + z * 13) % 5) as f32 * 0.1;
+            vertices.push(vec3(x as f32, height, z as f32));
+        }
+    }
+    let mut triangles = Vec::new();
+    for z in 0..size {
+        for x in 0..size {
+            let v = z * (size + 1) + x;
+            let cell = z * size + x;
+            triangles.push(IndexedTriangle::new(v, v + size + 1, v + 1, cell));
+            triangles.push(IndexedTriangle::new(
+                v + 1,
+                v + size + 1,
+                v + size + 2,
+                cell,
+            ));
+        }
+    }
+    (vertices, triangles)
+}
+
+fn cube() -> (Vec<Vec3>, Vec<IndexedTriangle>) {
+    let vertices = (0..8)
+        .map(|i| {
+            vec3(
+                if i & 1 == 0 { -1.0 } else { 1.0 },
+                if i & 2 == 0 { -1.0 } else { 1.0 },
+                if i & 4 == 0 { -1.0 } else { 1.0 },
+            )
+        })
+        .collect();
+    let quads = [
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+    ];
+    let triangles = quads
+        .iter()
+        .flat_map(|&[a, b, c, d]| {
+            [
+                IndexedTriangle::new(a, b, c, 0),
+                IndexedTriangle::new(a, c, d, 0),
+            ]
+        })
+        .collect();
+    (vertices, triangles)
+}
+
+fn brute_force_ray(vertices: &[Vec3], triangles: &[IndexedTriangle], ray: &RayCast) -> f32 {
+    triangles
+        .iter()
+        .map(|t| {
+            let [v0, v1, v2] = t.indices.map(|i| vertices[i as usize]);
+            ray_triangle(ray.origin, ray.direction, v0, v1, v2)
+        })
+        .fold(f32::MAX, f32::min)
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_ray_cast() {
+    let (vertices, triangles) = grid(64);
+    let shape = MeshShape::new(&vertices, &triangles).unwrap();
+    assert_eq!(shape.triangle_count(), triangles.len());
+    assert_eq!(shape.mass_properties().mass, 0.0);
+
+    let mut random = Random(0x1234_5678);
+    let mut hits = 0;
+    for _ in 0..500 {
+        let origin = vec3(32.0, 5.0, 32.0) + random.vec3() * 40.0;
+        let direction = random.vec3() * 80.0;
+        let ray = RayCast::new(origin, direction);
+        let expected = brute_force_ray(&vertices, &triangles, &ray);
+
+        let mut hit = RayCastResult::default();
+        if shape.cast_ray(&ray, &mut hit) {
+            hits += 1;
+            assert!((hit.fraction - expected).abs() < 1.0e-5);
+        } else {
+            assert!(expected > 1.0);
+        }
+    }
+    assert!(hits > 50);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_materials() {
+    let (vertices, triangles) = grid(16);
+    let shape = MeshShape::new(&vertices, &triangles).unwrap();
+
+    // Hit the center of cell (5, 9) straight from above.
+    let ray = RayCast::new(vec3(5.25, 10.0, 9.25), vec3(0.0, -20.0, 0.0));
+    let mut hit = RayCastResult::default();
+    assert!(shape.cast_ray(&ray, &mut hit));
+    assert_eq!(shape.material_index(hit.sub_shape_id), 9 * 16 + 5);
+
+    let normal = shape.surface_normal(hit.sub_shape_id, ray.point_on_ray(hit.fraction));
+    assert!(normal.y > 0.0);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_collide_point() {
+    let (vertices, triangles) = cube();
+    let shape = MeshShape::new(&vertices, &triangles).unwrap();
+    assert!(shape.collide_point(vec3(0.1, 0.2, 0.3)));
+    assert!(shape.collide_point(vec3(-0.9, -0.7, 0.8)));
+    assert!(!shape.collide_point(vec3(1.1, 0.0, 0.0)));
+    assert!(!shape.collide_point(vec3(0.0, -1.5, 0.0)));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_collide_convex() {
+    let (vertices, triangles) = grid(32);
+    let shape = MeshShape::new(&vertices, &triangles).unwrap();
+    let sphere = SphereShape::new(0.5).unwrap();
+
+    // A sphere hovering above the grid touches only the nearby triangles.
+    let position = vec3(10.3, 1.0, 20.6);
+    let mut closest = f32::MAX;
+    let mut found = Vec::new();
+    shape.collide_convex(&sphere, Quat::IDENTITY, position, 0.5, |id, result| {
+        closest = closest.min(result.distance_squared.sqrt());
+        found.push(id);
+    });
+    assert!(!found.is_empty());
+    assert!(found.len() < 32);
+
+    // The closest distance matches a ray cast straight down (the grid is nearly flat there).
+    let mut hit = RayCastResult::default();
+    assert!(shape.cast_ray(&RayCast::new(position, vec3(0.0, -2.0, 0.0)), &mut hit));
+    assert!(closest <= hit.fraction * 2.0 - 0.5 + 1.0e-3);
+
+    let mut count = 0;
+    shape.collide_convex(
+        &sphere,
+        Quat::IDENTITY,
+        vec3(10.0, 5.0, 10.0),
+        0.5,
+        |_, _| count += 1,
+    );
+    assert_eq!(count, 0);
+}
+
+/// Returns the active edges of every triangle of `shape`.
+fn active_edges(shape: &MeshShape) -> Vec<u8> {
+    let mut active_edges = Vec::new();
+    let bounds = AABox::from_center_and_half_extent(Vec3::ZERO, Vec3::splat(100.0));
+    shape.collide_triangles(&bounds, |sub_shape_id, _| {
+        active_edges.push(shape.active_edges(sub_shape_id));
+    });
+    active_edges
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_active_edges() {
+    // The edges of a cube are sharp, the diagonals of its faces are flat.
+    let (vertices, triangles) = cube();
+    let shape = MeshShape::new(&vertices, &triangles).unwrap();
+    let active_edges_of_cube = active_edges(&shape);
+    assert_eq!(active_edges_of_cube.len(), 12);
+    assert!(active_edges_of_cube
+        .iter()
+        .all(|edges| edges.count_ones() == 2));
+
+    // A square that is folded along its diagonal, the fold is a ridge for negative heights.
+    let square = |height: f32| {
+        [
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+            vec3(1.0, 0.0, 1.0),
+            vec3(0.0, height, 1.0),
+        ]
+    };
+    let triangles = [
+        IndexedTriangle::new(0, 3, 2, 0),
+        IndexedTriangle::new(0, 2, 1, 0),
+    ];
+    let inner_edges = |height: f32, cos_threshold_angle: f32| {
+        let shape =
+            MeshShape::with_active_edge_threshold(&square(height), &triangles, cos_threshold_angle)
+                .unwrap();
+        // The outer edges are always active.
+        active_edges(&shape)
+            .iter()
+            .map(|edges| edges.count_ones() - 2)
+            .sum::<u32>()
+    };
+    assert_eq!(inner_edges(0.0, DEFAULT_ACTIVE_EDGE_COS_THRESHOLD_ANGLE), 0);
+    assert_eq!(
+        inner_edges(-0.2, DEFAULT_ACTIVE_EDGE_COS_THRESHOLD_ANGLE),
+        2
+    );
+    assert_eq!(inner_edges(-0.2, 0.9), 0);
+    assert_eq!(inner_edges(0.2, DEFAULT_ACTIVE_EDGE_COS_THRESHOLD_ANGLE), 0);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_save_restore() {
+    let (vertices, triangles) = grid(20);
+    let shape = MeshShape::new(&vertices, &triangles).unwrap();
+    let mut data = Vec::new();
+    shape.save(&mut data);
+
+    let restored = MeshShape::restore(&data).unwrap();
+    assert_eq!(restored, shape);
+
+    // Saving is deterministic, so baked data can be compared byte for byte.
+    let mut resaved = Vec::new();
+    restored.save(&mut resaved);
+    assert_eq!(resaved, data);
+
+    assert_eq!(
+        MeshShape::restore(&data[..data.len() - 1]),
+        Err(ShapeError::InvalidData)
+    );
+    let mut corrupt = data.clone();
+    corrupt[0] = b'X';
+    assert_eq!(MeshShape::restore(&corrupt), Err(ShapeError::InvalidData));
+    data.push(0);
+    assert_eq!(MeshShape::restore(&data), Err(ShapeError::InvalidData));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_errors() {
+    let vertices = [Vec3::ZERO, Vec3::X, Vec3::Z];
+    assert_eq!(
+        MeshShape::new(&vertices, &[IndexedTriangle::new(0, 1, 3, 0)]),
+        Err(ShapeError::InvalidTriangleIndex)
+    );
+    assert_eq!(
+        MeshShape::new(&vertices, &[IndexedTriangle::new(0, 1, 1, 0)]),
+        Err(ShapeError::EmptyMesh)
+    );
+
+    // Degenerate triangles are dropped.
+    let shape = MeshShape::new(
+        &vertices,
+        &[
+            IndexedTriangle::new(0, 0, 1, 0),
+            IndexedTriangle::new(0, 2, 1, 7),
+        ],
+    )
+    .unwrap();
+    assert_eq!(shape.triangle_count(), 1);
+    assert_eq!(shape.material_index(SubShapeID::from_raw(0)), 7);
+}
@@ -0,0 +1,24 @@
+mod box_shape;
+mod capsule_shape;
+mod convex_hull_shape;
+mod cylinder_shape;
+mod height_field_shape;
+mod mesh_shape;
+mod mutable_compound_shape;
+mod offset_center_of_mass_shape;
+mod rotated_translated_shape;
+mod scaled_shape;
+mod sdf_shape;
+mod sphere_shape;
+mod static_compound_shape;
+mod sub_shape_id;
+mod tapered_capsule_shape;
+
+/// Maps a bit to the sign of a box corner coordinate.
+pub fn sign(bit: i32) -> f32 {
+    if bit == 0 {
+        -1.0
+    } else {
+        1.0
+    }
+}
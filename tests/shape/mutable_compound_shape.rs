@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use phys_rs::{
+    collision::{RayCast, RayCastResult},
+    math::{vec3, Quat, Vec3},
+    shape::{
+        BoxShape, CompoundSubShape, MutableCompoundShape, Shape, ShapeError, ShapeSubType,
+        SphereShape, StaticCompoundShape,
+    },
+};
+
+fn cast_down(shape: &dyn Shape, x: f32) -> Option<RayCastResult> {
+    let mut hit = RayCastResult::default();
+    let ray = RayCast::new(vec3(x, 10.0, 0.0), vec3(0.0, -20.0, 0.0));
+    shape.cast_ray(&ray, &mut hit).then_some(hit)
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_modify() {
+    let sphere: Arc<dyn Shape> = Arc::new(SphereShape::new(1.0).unwrap());
+    let cube: Arc<dyn Shape> = Arc::new(BoxShape::new(Vec3::ONE).unwrap());
+    let mut shape = MutableCompoundShape::new(&[]).unwrap();
+    assert_eq!(shape.sub_type(), ShapeSubType::MutableCompound);
+    assert_eq!(shape.mass_properties().mass, 0.0);
+    assert!(cast_down(&shape, 0.0).is_none());
+
+    for i in 0..6 {
+        let index = shape
+            .add_shape(
+                sphere.clone(),
+                vec3(3.0 * i as f32, 0.0, 0.0),
+                Quat::IDENTITY,
+                i,
+            )
+            .unwrap();
+        assert_eq!(index, i);
+    }
+    assert_eq!(shape.sub_shape_id_bits(), 3);
+    let hit = cast_down(&shape, 12.0).unwrap();
+    assert!((hit.fraction - 0.45).abs() < 1.0e-5);
+    assert_eq!(shape.sub_shape_index(hit.sub_shape_id).0, 4);
+
+    // Removing a child moves the ones after it down.
+    shape.remove_shape(1);
+    assert_eq!(shape.sub_shapes().len(), 5);
+    assert!(cast_down(&shape, 3.0).is_none());
+    let hit = cast_down(&shape, 12.0).unwrap();
+    let (index, _) = shape.sub_shape_index(hit.sub_shape_id);
+    assert_eq!(index, 3);
+    assert_eq!(shape.sub_shapes()[index as usize].user_data, 4);
+
+    // Move a child up and swap another for a cube.
+    shape.modify_shape(3, vec3(12.0, 2.0, 0.0), Quat::IDENTITY);
+    let hit = cast_down(&shape, 12.0).unwrap();
+    assert!((hit.fraction - 0.35).abs() < 1.0e-5);
+    shape
+        .replace_shape(0, cube, vec3(0.0, -1.0, 0.0), Quat::IDENTITY)
+        .unwrap();
+    let hit = cast_down(&shape, 0.9).unwrap();
+    assert!((hit.fraction - 0.5).abs() < 1.0e-5);
+    assert!(shape.collide_point(vec3(0.9, -1.5, 0.0)));
+    assert!(!shape.collide_point(vec3(3.0, 0.0, 0.0)));
+    assert!(shape.local_bounds().max.y >= 3.0);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_center_of_mass() {
+    let cube: Arc<dyn Shape> = Arc::new(BoxShape::new(Vec3::ONE).unwrap());
+    let sub_shapes = [
+        CompoundSubShape::new(cube.clone(), vec3(1.0, 0.0, 0.0), Quat::IDENTITY),
+        CompoundSubShape::new(
+            cube.clone(),
+            vec3(1.0, 4.0, 0.0),
+            Quat::from_rotation_x(0.4),
+        ),
+    ];
+    let mut shape = MutableCompoundShape::new(&sub_shapes).unwrap();
+    let reference = StaticCompoundShape::new(&sub_shapes).unwrap();
+    assert!((shape.center_of_mass() - vec3(1.0, 2.0, 0.0)).length() < 1.0e-6);
+    assert_eq!(shape.mass_properties(), reference.mass_properties());
+
+    // Adding a child keeps the center of mass until it is adjusted.
+    shape
+        .add_shape(cube, vec3(3.0, -2.0, 0.0), Quat::IDENTITY, 0)
+        .unwrap();
+    assert!((shape.center_of_mass() - vec3(1.0, 2.0, 0.0)).length() < 1.0e-6);
+    shape.adjust_center_of_mass();
+    let center_of_mass = vec3(2.0, 4.0 / 3.0, 0.0);
+    assert!((shape.center_of_mass() - center_of_mass).length() < 1.0e-5);
+    let position = vec3(4.0, 0.0, 0.0) - center_of_mass;
+    assert!((shape.sub_shapes()[2].position - position).length() < 1.0e-5);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_errors() {
+    let mut shape: Arc<dyn Shape> = Arc::new(SphereShape::new(1.0).unwrap());
+    for _ in 0..4 {
+        let sub_shapes: Vec<CompoundSubShape> = (0..256)
+            .map(|i| CompoundSubShape::new(shape.clone(), vec3(i as f32, 0.0, 0.0), Quat::IDENTITY))
+            .collect();
+        shape = Arc::new(StaticCompoundShape::new(&sub_shapes).unwrap());
+    }
+
+    // A compound with a single child does not need any bits to identify it.
+    let mut compound = MutableCompoundShape::new(&[CompoundSubShape::new(
+        shape.clone(),
+        Vec3::ZERO,
+        Quat::IDENTITY,
+    )])
+    .unwrap();
+    assert_eq!(compound.sub_shape_id_bits(), 32);
+    assert_eq!(
+        compound.add_shape(shape, Vec3::X, Quat::IDENTITY, 0),
+        Err(ShapeError::SubShapeIDOverflow)
+    );
+    assert_eq!(compound.sub_shapes().len(), 1);
+    assert_eq!(compound.sub_shape_id_bits(), 32);
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use phys_rs::{
+    collision::{RayCast, RayCastResult},
+    math::{vec3, Quat, Vec3},
+    shape::{BoxShape, OffsetCenterOfMassShape, Shape, SupportMode},
+};
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_offset() {
+    let inner = Arc::new(BoxShape::new(vec3(2.0, 1.0, 1.0)).unwrap());
+    let offset = vec3(0.0, -0.5, 0.0);
+    let shape = OffsetCenterOfMassShape::new(inner.clone(), offset);
+    assert_eq!(shape.center_of_mass(), offset);
+    assert_eq!(shape.mass_properties(), inner.mass_properties());
+
+    // Queries are relative to the new center of mass, so the shape appears moved up.
+    let bounds = shape.local_bounds();
+    assert_eq!(bounds.min, vec3(-2.0, -0.5, -1.0));
+    assert_eq!(bounds.max, vec3(2.0, 1.5, 1.0));
+    let world_bounds = shape.world_bounds(Quat::IDENTITY, Vec3::X, Vec3::splat(2.0));
+    assert_eq!(world_bounds.min, vec3(-3.0, -1.0, -2.0));
+    assert_eq!(world_bounds.max, vec3(5.0, 3.0, 2.0));
+
+    let mut hit = RayCastResult::default();
+    let ray = RayCast::new(vec3(0.0, 5.0, 0.0), vec3(0.0, -5.0, 0.0));
+    assert!(shape.cast_ray(&ray, &mut hit));
+    assert!((hit.fraction - 0.7).abs() < 1.0e-5);
+    let normal = shape.surface_normal(hit.sub_shape_id, ray.point_on_ray(hit.fraction));
+    assert!((normal - Vec3::Y).length() < 1.0e-5);
+
+    assert!(shape.collide_point(vec3(0.0, 1.4, 0.0)));
+    assert!(!shape.collide_point(vec3(0.0, -0.6, 0.0)));
+
+    let convex = shape.as_convex().unwrap();
+    let support = convex.get_support_function(SupportMode::IncludeConvexRadius, Vec3::ONE);
+    assert!((support.support(Vec3::ONE) - vec3(2.0, 1.5, 1.0)).length() < 1.0e-5);
+    let mut face = Vec::new();
+    convex.get_supporting_face(-Vec3::Y, Vec3::ONE, &mut face);
+    assert!(face.iter().all(|vertex| (vertex.y + 0.5).abs() < 1.0e-5));
+}
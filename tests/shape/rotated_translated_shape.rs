@@ -0,0 +1,110 @@
+use std::{f32::consts::FRAC_PI_2, sync::Arc};
+
+use phys_rs::{
+    collision::{RayCast, RayCastResult},
+    math::{vec3, Quat, Vec3},
+    shape::{
+        BoxShape, CapsuleShape, ConvexHullShape, RotatedTranslatedShape, ScaledShape, Shape,
+        SupportMode,
+    },
+};
+
+use super::sign;
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_mass_properties() {
+    // Rotating a box a quarter turn around Z swaps its X and Y axes.
+    let inner = Arc::new(BoxShape::new(vec3(1.0, 2.0, 3.0)).unwrap());
+    let shape =
+        RotatedTranslatedShape::new(inner, vec3(5.0, 0.0, 0.0), Quat::from_rotation_z(FRAC_PI_2));
+    let expected = BoxShape::new(vec3(2.0, 1.0, 3.0)).unwrap();
+    assert_eq!(shape.center_of_mass(), vec3(5.0, 0.0, 0.0));
+    assert_eq!(shape.volume(), expected.volume());
+
+    let mass_properties = shape.mass_properties();
+    let expected = expected.mass_properties();
+    assert_eq!(mass_properties.mass, expected.mass);
+    assert!(mass_properties
+        .inertia
+        .abs_diff_eq(&expected.inertia, expected.mass * 1.0e-5));
+
+    let bounds = shape.local_bounds();
+    assert!((bounds.max - vec3(2.0, 1.0, 3.0)).length() < 1.0e-5);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_queries() {
+    // A capsule lying along the X axis.
+    let inner = Arc::new(CapsuleShape::new(2.0, 0.5).unwrap());
+    let shape =
+        RotatedTranslatedShape::new(inner, vec3(0.0, 1.0, 0.0), Quat::from_rotation_z(FRAC_PI_2));
+    assert_eq!(shape.center_of_mass(), vec3(0.0, 1.0, 0.0));
+
+    let mut hit = RayCastResult::default();
+    let ray = RayCast::new(vec3(-5.0, 0.0, 0.0), vec3(10.0, 0.0, 0.0));
+    assert!(shape.cast_ray(&ray, &mut hit));
+    assert!((hit.fraction - 0.25).abs() < 1.0e-5);
+    let normal = shape.surface_normal(hit.sub_shape_id, ray.point_on_ray(hit.fraction));
+    assert!((normal + Vec3::X).length() < 1.0e-4);
+
+    assert!(shape.collide_point(vec3(2.3, 0.0, 0.0)));
+    assert!(!shape.collide_point(vec3(0.0, 0.7, 0.0)));
+
+    let convex = shape.as_convex().unwrap();
+    let support = convex.get_support_function(SupportMode::IncludeConvexRadius, Vec3::ONE);
+    assert!((support.support(Vec3::X) - vec3(2.5, 0.0, 0.0)).length() < 1.0e-5);
+    assert!((support.support(Vec3::Y).y - 0.5).abs() < 1.0e-5);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_scale() {
+    let inner = Arc::new(BoxShape::new(vec3(1.0, 2.0, 3.0)).unwrap());
+    let rotated: Arc<dyn Shape> = Arc::new(RotatedTranslatedShape::new(
+        inner,
+        Vec3::ZERO,
+        Quat::from_rotation_z(FRAC_PI_2),
+    ));
+
+    // The scale on X ends up on the Y axis of the box.
+    let shape = ScaledShape::new(rotated.clone(), vec3(3.0, 1.0, 1.0)).unwrap();
+    let expected = BoxShape::new(vec3(6.0, 1.0, 3.0)).unwrap();
+    assert!((shape.volume() - expected.volume()).abs() < 1.0e-3);
+    assert!((shape.local_bounds().max - vec3(6.0, 1.0, 3.0)).length() < 1.0e-4);
+    let bounds = rotated.world_bounds(Quat::IDENTITY, Vec3::ZERO, vec3(3.0, 1.0, 1.0));
+    assert!((bounds.max - vec3(6.0, 1.0, 3.0)).length() < 1.0e-4);
+
+    let convex = shape.as_convex().unwrap();
+    let support = convex.get_support_function(SupportMode::IncludeConvexRadius, Vec3::ONE);
+    assert!((support.support(Vec3::ONE) - vec3(6.0, 1.0, 3.0)).length() < 1.0e-4);
+
+    // The same holds for the faces of a hull.
+    let corners: Vec<Vec3> = (0..8)
+        .map(|i| vec3(1.0, 2.0, 3.0) * vec3(sign(i & 1), sign(i & 2), sign(i & 4)))
+        .collect();
+    let hull = Arc::new(ConvexHullShape::new(&corners).unwrap());
+    let rotated = Arc::new(RotatedTranslatedShape::new(
+        hull,
+        Vec3::ZERO,
+        Quat::from_rotation_z(FRAC_PI_2),
+    ));
+    let shape = ScaledShape::new(rotated, vec3(3.0, 1.0, 1.0)).unwrap();
+    let mut face = Vec::new();
+    shape
+        .as_convex()
+        .unwrap()
+        .get_supporting_face(Vec3::X, Vec3::ONE, &mut face);
+    assert_eq!(face.len(), 4);
+    assert!(face.iter().all(|vertex| (vertex.x - 6.0).abs() < 1.0e-4));
+
+    // A non uniform scale that is not aligned with the box would shear it.
+    let tilted: Arc<dyn Shape> = Arc::new(RotatedTranslatedShape::new(
+        Arc::new(BoxShape::new(Vec3::ONE).unwrap()),
+        Vec3::ZERO,
+        Quat::from_rotation_z(0.4),
+    ));
+    assert!(!tilted.is_valid_scale(vec3(3.0, 1.0, 1.0)));
+    assert!(tilted.is_valid_scale(vec3(1.0, 1.0, 3.0)));
+}
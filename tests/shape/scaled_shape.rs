@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use phys_rs::{
+    collision::{RayCast, RayCastResult},
+    math::{vec3, Quat, Vec3},
+    shape::{
+        BoxShape, CompoundSubShape, ConvexHullShape, CylinderShape, ScaledShape, Shape, ShapeError,
+        ShapeSubType, ShapeType, SphereShape, StaticCompoundShape, SupportMode,
+    },
+};
+
+use super::sign;
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_mass_properties() {
+    // A scaled cube has the same mass properties as the equivalent box.
+    let cube = Arc::new(BoxShape::with_convex_radius(Vec3::ONE, 0.0).unwrap());
+    let shape = ScaledShape::new(cube, vec3(2.0, -3.0, 4.0)).unwrap();
+    let expected = BoxShape::with_convex_radius(vec3(2.0, 3.0, 4.0), 0.0).unwrap();
+    assert_eq!(shape.sub_type(), ShapeSubType::Scaled);
+    assert_eq!(shape.shape_type(), ShapeType::Decorated);
+    assert!((shape.volume() - expected.volume()).abs() < 1.0e-3);
+    assert_eq!(shape.inner_radius(), 2.0);
+
+    let mass_properties = shape.mass_properties();
+    let expected = expected.mass_properties();
+    assert!((mass_properties.mass - expected.mass).abs() < 1.0);
+    assert!(mass_properties
+        .inertia
+        .abs_diff_eq(&expected.inertia, expected.mass * 1.0e-5));
+
+    // The scale is applied to the center of mass of the inner shape.
+    let hull = ConvexHullShape::new(&[Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z]).unwrap();
+    let center_of_mass = hull.center_of_mass();
+    let shape = ScaledShape::new(Arc::new(hull), vec3(2.0, 2.0, -1.0)).unwrap();
+    assert!((shape.center_of_mass() - center_of_mass * vec3(2.0, 2.0, -1.0)).length() < 1.0e-6);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_queries() {
+    let cube = Arc::new(BoxShape::new(Vec3::ONE).unwrap());
+    let shape = ScaledShape::new(cube, vec3(2.0, 3.0, -4.0)).unwrap();
+    let bounds = shape.local_bounds();
+    assert!((bounds.min - vec3(-2.0, -3.0, -4.0)).length() < 1.0e-6);
+    assert!((bounds.max - vec3(2.0, 3.0, 4.0)).length() < 1.0e-6);
+
+    let mut hit = RayCastResult::default();
+    let ray = RayCast::new(vec3(0.0, 0.0, -8.0), vec3(0.0, 0.0, 8.0));
+    assert!(shape.cast_ray(&ray, &mut hit));
+    assert!((hit.fraction - 0.5).abs() < 1.0e-5);
+
+    let normal = shape.surface_normal(hit.sub_shape_id, ray.point_on_ray(hit.fraction));
+    assert!((normal + Vec3::Z).length() < 1.0e-5);
+    let normal = shape.surface_normal(hit.sub_shape_id, vec3(1.9, 2.99, 0.0));
+    assert!((normal - Vec3::Y).length() < 1.0e-5);
+
+    assert!(shape.collide_point(vec3(1.9, -2.9, 3.9)));
+    assert!(!shape.collide_point(vec3(2.1, 0.0, 0.0)));
+
+    let convex = shape.as_convex().unwrap();
+    let support = convex.get_support_function(SupportMode::IncludeConvexRadius, Vec3::ONE);
+    assert!((support.support(Vec3::ONE) - vec3(2.0, 3.0, 4.0)).length() < 1.0e-5);
+
+    // Mirroring a hull keeps its faces wound counter clockwise.
+    let corners: Vec<Vec3> = (0..8)
+        .map(|i| vec3(sign(i & 1), sign(i & 2), sign(i & 4)))
+        .collect();
+    let hull = Arc::new(ConvexHullShape::new(&corners).unwrap());
+    let shape = ScaledShape::new(hull, vec3(2.0, 3.0, -4.0)).unwrap();
+    let mut face = Vec::new();
+    shape
+        .as_convex()
+        .unwrap()
+        .get_supporting_face(Vec3::Y, Vec3::ONE, &mut face);
+    assert_eq!(face.len(), 4);
+    assert!(face.iter().all(|vertex| (vertex.y - 3.0).abs() < 1.0e-5));
+    let normal = (face[1] - face[0]).cross(face[2] - face[0]);
+    assert!(normal.y > 0.0);
+
+    // Scaling the scaled shape again combines the scales.
+    let world_bounds = shape.world_bounds(Quat::IDENTITY, Vec3::X, Vec3::splat(0.5));
+    assert!((world_bounds.max - vec3(2.0, 1.5, 2.0)).length() < 1.0e-5);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_valid_scale() {
+    let sphere: Arc<dyn Shape> = Arc::new(SphereShape::new(1.0).unwrap());
+    let scaled = ScaledShape::new(sphere.clone(), vec3(2.0, -2.0, 2.0)).unwrap();
+    assert!((scaled.volume() - sphere.volume() * 8.0).abs() < 1.0e-3);
+    assert_eq!(
+        ScaledShape::new(sphere.clone(), vec3(1.0, 2.0, 1.0)).unwrap_err(),
+        ShapeError::InvalidScale
+    );
+    assert_eq!(
+        ScaledShape::new(sphere, vec3(1.0, 0.0, 1.0)).unwrap_err(),
+        ShapeError::InvalidScale
+    );
+
+    // Cylinders can be stretched along their axis.
+    let cylinder: Arc<dyn Shape> = Arc::new(CylinderShape::new(1.0, 0.5).unwrap());
+    assert!(ScaledShape::new(cylinder.clone(), vec3(2.0, 5.0, -2.0)).is_ok());
+    assert!(ScaledShape::new(cylinder.clone(), vec3(2.0, 5.0, 1.0)).is_err());
+
+    // Children of compounds are scaled in their own space.
+    let compound: Arc<dyn Shape> = Arc::new(
+        StaticCompoundShape::new(&[CompoundSubShape::new(
+            cylinder,
+            Vec3::ZERO,
+            Quat::from_rotation_z(core::f32::consts::FRAC_PI_2),
+        )])
+        .unwrap(),
+    );
+    assert!(ScaledShape::new(compound.clone(), vec3(5.0, 2.0, 2.0)).is_ok());
+    assert!(ScaledShape::new(compound.clone(), vec3(2.0, 5.0, 2.0)).is_err());
+    let rotated: Arc<dyn Shape> = Arc::new(
+        StaticCompoundShape::new(&[CompoundSubShape::new(
+            Arc::new(BoxShape::new(Vec3::ONE).unwrap()),
+            Vec3::ZERO,
+            Quat::from_rotation_z(0.3),
+        )])
+        .unwrap(),
+    );
+    assert!(ScaledShape::new(rotated.clone(), Vec3::splat(3.0)).is_ok());
+    assert!(ScaledShape::new(rotated, vec3(1.0, 2.0, 1.0)).is_err());
+}
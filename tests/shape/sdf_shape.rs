@@ -0,0 +1,296 @@
+use std::f32::consts::PI;
+
+use phys_rs::{
+    collision::{RayCast, RayCastResult},
+    geometry::IndexedTriangle,
+    math::{uvec3, vec3, Quat, UVec3, Vec3},
+    shape::{
+        BoxShape, MeshShape, SdfContact, SdfSettings, SdfShape, Shape, ShapeError, ShapeSubType,
+        SphereShape, SubShapeID,
+    },
+};
+
+const SAMPLES: u32 = 31;
+const CELL_SIZE: f32 = 0.1;
+
+/// The distances of a unit sphere on a grid from -1.5 to 1.5.
+fn sphere_distances() -> Vec<f32> {
+    let mut distances = Vec::new();
+    for z in 0..SAMPLES {
+        for y in 0..SAMPLES {
+            for x in 0..SAMPLES {
+                let position = vec3(x as f32, y as f32, z as f32) * CELL_SIZE - 1.5;
+                distances.push(position.length() - 1.0);
+            }
+        }
+    }
+    distances
+}
+
+fn sphere_settings(distances: &[f32]) -> SdfSettings<'_> {
+    SdfSettings {
+        offset: Vec3::splat(-1.5),
+        ..SdfSettings::new(distances, UVec3::splat(SAMPLES), CELL_SIZE)
+    }
+}
+
+/// A closed box mesh with half extent `half_extent`.
+fn box_mesh(half_extent: Vec3) -> MeshShape {
+    let vertices: Vec<Vec3> = (0..8)
+        .map(|i| {
+            let sign = |bit: u32| if i & bit == 0 { -1.0 } else { 1.0 };
+            half_extent * vec3(sign(1), sign(2), sign(4))
+        })
+        .collect();
+    let faces = [
+        [0, 2, 6, 4],
+        [1, 5, 7, 3],
+        [0, 4, 5, 1],
+        [2, 3, 7, 6],
+        [0, 1, 3, 2],
+        [4, 6, 7, 5],
+    ];
+    let triangles: Vec<IndexedTriangle> = faces
+        .iter()
+        .flat_map(|&[a, b, c, d]| {
+            [
+                IndexedTriangle::new(a, b, c, 0),
+                IndexedTriangle::new(a, c, d, 0),
+            ]
+        })
+        .collect();
+    MeshShape::new(&vertices, &triangles).unwrap()
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_invalid_settings() {
+    let distances = sphere_distances();
+    let settings = sphere_settings(&distances);
+    assert_eq!(
+        SdfShape::new(&SdfSettings {
+            sample_count: uvec3(SAMPLES, SAMPLES, SAMPLES - 1),
+            ..settings
+        }),
+        Err(ShapeError::InvalidSampleCount)
+    );
+    assert_eq!(
+        SdfShape::new(&SdfSettings {
+            cell_size: 0.0,
+            ..settings
+        }),
+        Err(ShapeError::InvalidCellSize)
+    );
+    assert_eq!(
+        SdfShape::new(&SdfSettings {
+            density: -1.0,
+            ..settings
+        }),
+        Err(ShapeError::InvalidDensity)
+    );
+    assert_eq!(
+        SdfShape::from_mesh(&box_mesh(Vec3::ONE), 1.0e-4),
+        Err(ShapeError::InvalidCellSize)
+    );
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_sphere() {
+    let distances = sphere_distances();
+    let shape = SdfShape::new(&sphere_settings(&distances)).unwrap();
+    assert_eq!(shape.sub_type(), ShapeSubType::Sdf);
+    assert!(shape.center_of_mass().length() < 1.0e-3);
+    assert!((shape.volume() - 4.0 / 3.0 * PI).abs() < 0.02 * 4.0 / 3.0 * PI);
+    assert!((shape.inner_radius() - 1.0).abs() < 0.01);
+    let mass_properties = shape.mass_properties();
+    let inertia = 0.4 * mass_properties.mass;
+    assert!((mass_properties.inertia.diagonal() - Vec3::splat(inertia)).length() < 0.03 * inertia);
+
+    for point in [
+        vec3(0.3, 0.2, -0.1),
+        vec3(1.2, 0.0, 0.4),
+        vec3(0.0, -0.97, 0.05),
+    ] {
+        assert!((shape.distance(point) - (point.length() - 1.0)).abs() < 0.01);
+        let normal = shape.surface_normal(SubShapeID::EMPTY, point);
+        assert!(normal.dot(point.normalize()) > 0.999);
+    }
+    // Outside of the grid the distance keeps growing.
+    assert!((shape.distance(vec3(3.0, 0.0, 0.0)) - 2.0).abs() < 0.01);
+
+    assert!(shape.collide_point(vec3(0.5, 0.5, 0.5)));
+    assert!(!shape.collide_point(vec3(0.7, 0.7, 0.7)));
+    assert!(!shape.collide_point(vec3(5.0, 0.0, 0.0)));
+
+    let mut hit = RayCastResult::default();
+    let ray = RayCast::new(vec3(-3.0, 0.2, 0.0), vec3(6.0, 0.0, 0.0));
+    assert!(shape.cast_ray(&ray, &mut hit));
+    let expected = (3.0 - (1.0f32 - 0.04).sqrt()) / 6.0;
+    assert!((hit.fraction - expected).abs() < 1.0e-3);
+    assert_eq!(hit.sub_shape_id, SubShapeID::EMPTY);
+
+    // A closer hit is kept, as are rays that stop short of the surface or miss.
+    let mut closer = RayCastResult {
+        fraction: 0.1,
+        ..hit
+    };
+    assert!(!shape.cast_ray(&ray, &mut closer));
+    let mut hit = RayCastResult::default();
+    assert!(!shape.cast_ray(
+        &RayCast::new(vec3(-3.0, 0.2, 0.0), vec3(1.5, 0.0, 0.0)),
+        &mut hit
+    ));
+    assert!(!shape.cast_ray(
+        &RayCast::new(vec3(-3.0, 1.2, 0.0), vec3(6.0, 0.0, 0.0)),
+        &mut hit
+    ));
+
+    // Rays that start inside hit immediately.
+    assert!(shape.cast_ray(
+        &RayCast::new(vec3(0.1, 0.0, 0.0), vec3(6.0, 0.0, 0.0)),
+        &mut hit
+    ));
+    assert_eq!(hit.fraction, 0.0);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_sparse() {
+    let distances = sphere_distances();
+    let dense = SdfShape::new(&sphere_settings(&distances)).unwrap();
+    let sparse = SdfShape::new(&SdfSettings {
+        narrow_band: 0.3,
+        ..sphere_settings(&distances)
+    })
+    .unwrap();
+    assert_eq!(dense.dense_brick_count(), 8 * 8 * 8);
+    assert!(sparse.dense_brick_count() < dense.dense_brick_count());
+
+    // Near the surface the fields agree, further away the sparse field is conservative.
+    for point in [
+        vec3(0.9, 0.1, 0.0),
+        vec3(0.2, -0.8, 0.5),
+        vec3(0.0, 0.0, 1.1),
+    ] {
+        assert!((sparse.distance(point) - dense.distance(point)).abs() < 1.0e-5);
+    }
+    for point in [Vec3::ZERO, vec3(1.4, 1.4, 1.4)] {
+        assert!(sparse.distance(point).abs() <= dense.distance(point).abs() + 1.0e-5);
+        assert_eq!(
+            sparse.distance(point).signum(),
+            dense.distance(point).signum()
+        );
+    }
+
+    for origin in [
+        vec3(-3.0, 0.3, 0.1),
+        vec3(0.2, 3.0, -0.4),
+        vec3(2.0, 2.0, 2.0),
+    ] {
+        let ray = RayCast::new(origin, -origin * 2.0);
+        let mut dense_hit = RayCastResult::default();
+        let mut sparse_hit = RayCastResult::default();
+        assert!(dense.cast_ray(&ray, &mut dense_hit));
+        assert!(sparse.cast_ray(&ray, &mut sparse_hit));
+        assert!((dense_hit.fraction - sparse_hit.fraction).abs() < 1.0e-4);
+    }
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_collide_convex() {
+    let distances = sphere_distances();
+    let shape = SdfShape::new(&sphere_settings(&distances)).unwrap();
+    let ball = SphereShape::new(0.25).unwrap();
+    let cube = BoxShape::new(Vec3::splat(0.25)).unwrap();
+
+    // A ball resting on top of the sphere, 0.1 inside of it.
+    let mut contacts: Vec<SdfContact> = Vec::new();
+    shape.collide_convex(
+        &ball,
+        Quat::IDENTITY,
+        vec3(0.0, 1.15, 0.0),
+        0.0,
+        |contact| contacts.push(*contact),
+    );
+    let deepest = contacts
+        .iter()
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))
+        .unwrap();
+    assert!((deepest.distance + 0.1).abs() < 0.01);
+    assert!(deepest.normal.dot(Vec3::Y) > 0.99);
+    assert!((deepest.point_a - vec3(0.0, 0.9, 0.0)).length() < 0.01);
+    assert!((deepest.point_b - Vec3::Y).length() < 0.01);
+    assert!(contacts.iter().all(|contact| contact.distance <= 0.0));
+
+    // A cube with its bottom face 0.1 inside of the sphere touches it with its corners.
+    contacts.clear();
+    shape.collide_convex(
+        &cube,
+        Quat::IDENTITY,
+        vec3(0.0, 1.15, 0.0),
+        0.0,
+        |contact| contacts.push(*contact),
+    );
+    let corner_distance = vec3(0.25, 0.9, 0.25).length() - 1.0;
+    assert_eq!(contacts.len(), 4);
+    for contact in &contacts {
+        assert!((contact.point_a.y - 0.9).abs() < 1.0e-5);
+        assert!((contact.distance - corner_distance).abs() < 0.01);
+        assert!(contact.normal.dot(contact.point_a.normalize()) > 0.99);
+    }
+
+    // A cube that hovers above the sphere only collides within the separation distance.
+    contacts.clear();
+    let rotation = Quat::from_rotation_y(0.3);
+    shape.collide_convex(&cube, rotation, vec3(0.0, 1.45, 0.0), 0.1, |contact| {
+        contacts.push(*contact)
+    });
+    assert!(contacts.is_empty());
+    shape.collide_convex(&cube, rotation, vec3(0.0, 1.45, 0.0), 0.3, |contact| {
+        contacts.push(*contact)
+    });
+    assert!(!contacts.is_empty());
+    assert!(contacts
+        .iter()
+        .all(|contact| contact.distance > 0.15 && contact.distance <= 0.3));
+
+    // Shapes outside of the grid are skipped altogether.
+    contacts.clear();
+    shape.collide_convex(&cube, Quat::IDENTITY, vec3(5.0, 0.0, 0.0), 0.1, |contact| {
+        contacts.push(*contact)
+    });
+    assert!(contacts.is_empty());
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_bake_mesh() {
+    let half_extent = vec3(1.0, 0.5, 0.75);
+    let mesh = box_mesh(half_extent);
+
+    let shape = SdfShape::from_mesh(&mesh, 0.05).unwrap();
+    let size = half_extent * 2.0;
+    let volume = size.x * size.y * size.z;
+    assert!((shape.volume() - volume).abs() < 0.02 * volume);
+    assert!(shape.center_of_mass().length() < 1.0e-2);
+    let bricks = ((shape.sample_count() + 3) / 4).element_product();
+    assert!((shape.dense_brick_count() as u64) < bricks);
+
+    // Distances are exact near the surface and conservative further away.
+    assert!((shape.distance(vec3(1.1, 0.0, 0.0)) - 0.1).abs() < 0.01);
+    assert!((shape.distance(vec3(0.0, 0.0, -0.7)) + 0.05).abs() < 0.01);
+    assert!(shape.distance(Vec3::ZERO) < -0.1);
+    assert!(shape.collide_point(vec3(0.9, 0.4, 0.7)));
+    assert!(!shape.collide_point(vec3(1.05, 0.0, 0.0)));
+
+    let mut hit = RayCastResult::default();
+    assert!(shape.cast_ray(
+        &RayCast::new(vec3(0.3, 3.0, 0.2), vec3(0.0, -6.0, 0.0)),
+        &mut hit
+    ));
+    assert!((hit.fraction - 2.5 / 6.0).abs() < 2.0e-3);
+    let normal = shape.surface_normal(hit.sub_shape_id, vec3(0.3, 0.5, 0.2));
+    assert!(normal.dot(Vec3::Y) > 0.99);
+}
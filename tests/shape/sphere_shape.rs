@@ -0,0 +1,67 @@
+use core::f32::consts::PI;
+
+use phys_rs::{
+    collision::{RayCast, RayCastResult},
+    math::Vec3,
+    shape::{ConvexShape, Shape, ShapeError, SphereShape, SubShapeID, SupportMode},
+};
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_mass_properties() {
+    let shape = SphereShape::new(2.0).unwrap().with_density(500.0).unwrap();
+    let volume = 4.0 / 3.0 * PI * 8.0;
+    assert!((shape.volume() - volume).abs() < 1.0e-4);
+
+    let mass_properties = shape.mass_properties();
+    let mass = volume * 500.0;
+    assert!((mass_properties.mass - mass).abs() < 1.0e-1);
+    let inertia = 0.4 * mass * 4.0;
+    assert!(mass_properties
+        .inertia
+        .diagonal()
+        .abs_diff_eq(Vec3::splat(inertia), 1.0));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_ray_cast() {
+    let shape = SphereShape::new(1.0).unwrap();
+    let mut hit = RayCastResult::default();
+    let ray = RayCast::new(Vec3::new(-3.0, 0.0, 0.0), Vec3::new(4.0, 0.0, 0.0));
+    assert!(shape.cast_ray(&ray, &mut hit));
+    assert!((hit.fraction - 0.5).abs() < 1.0e-6);
+
+    // A hit further away than the current hit is ignored.
+    let ray = RayCast::new(Vec3::new(0.0, 5.0, 0.0), Vec3::new(0.0, -8.0, 0.0));
+    assert!(!shape.cast_ray(&ray, &mut hit));
+
+    let normal = shape.surface_normal(SubShapeID::EMPTY, ray.point_on_ray(0.5));
+    assert!(normal.abs_diff_eq(Vec3::Y, 1.0e-6));
+    assert!(shape.collide_point(Vec3::splat(0.5)));
+    assert!(!shape.collide_point(Vec3::splat(0.6)));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_support() {
+    let shape = SphereShape::new(1.5).unwrap();
+    let include = shape.get_support_function(SupportMode::IncludeConvexRadius, Vec3::ONE);
+    assert!(include.support(Vec3::X).abs_diff_eq(Vec3::X * 1.5, 1.0e-6));
+    assert_eq!(include.convex_radius(), 0.0);
+
+    let exclude = shape.get_support_function(SupportMode::ExcludeConvexRadius, Vec3::splat(2.0));
+    assert_eq!(exclude.support(Vec3::X), Vec3::ZERO);
+    assert_eq!(exclude.convex_radius(), 3.0);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_errors() {
+    assert_eq!(SphereShape::new(0.0), Err(ShapeError::InvalidRadius));
+    assert_eq!(SphereShape::new(f32::NAN), Err(ShapeError::InvalidRadius));
+    assert_eq!(
+        SphereShape::new(1.0).unwrap().with_density(-1.0),
+        Err(ShapeError::InvalidDensity)
+    );
+}
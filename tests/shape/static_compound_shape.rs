@@ -0,0 +1,205 @@
+use std::{f32::consts::FRAC_PI_4, sync::Arc};
+
+use phys_rs::{
+    collision::{RayCast, RayCastResult},
+    geometry::{AABox, IndexedTriangle},
+    math::{vec3, Quat, Vec3},
+    shape::{
+        BoxShape, CompoundSubShape, MeshShape, Shape, ShapeError, ShapeSubType, ShapeType,
+        SphereShape, StaticCompoundShape, SubShapeID,
+    },
+};
+
+struct Random(u32);
+
+impl Random {
+    fn next(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        (self.0 as f32 / u32::MAX as f32) * 2.0 - 1.0
+    }
+
+    fn vec3(&mut self) -> Vec3 {
+        vec3(self.next(), self.next(), self.next())
+    }
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_mass_properties() {
+    // Two cubes side by side have the same mass properties as a single box around them.
+    let cube: Arc<dyn Shape> = Arc::new(BoxShape::new(Vec3::ONE).unwrap());
+    let shape = StaticCompoundShape::new(&[
+        CompoundSubShape::new(cube.clone(), vec3(0.0, 0.0, 0.0), Quat::IDENTITY),
+        CompoundSubShape::new(cube, vec3(2.0, 0.0, 0.0), Quat::from_rotation_y(0.3)),
+    ])
+    .unwrap();
+    let expected = BoxShape::new(vec3(2.0, 1.0, 1.0)).unwrap();
+    assert_eq!(shape.sub_type(), ShapeSubType::StaticCompound);
+    assert_eq!(shape.shape_type(), ShapeType::Compound);
+    assert!((shape.center_of_mass() - vec3(1.0, 0.0, 0.0)).length() < 1.0e-6);
+    assert!((shape.volume() - expected.volume()).abs() < 1.0e-4);
+
+    let mass_properties = shape.mass_properties();
+    let expected_mass_properties = expected.mass_properties();
+    assert!((mass_properties.mass - expected_mass_properties.mass).abs() < 1.0e-1);
+    assert!(mass_properties.inertia.abs_diff_eq(
+        &expected_mass_properties.inertia,
+        expected_mass_properties.mass * 1.0e-5
+    ));
+
+    // The children are stored relative to the center of mass.
+    assert!((shape.sub_shapes()[0].position - vec3(-1.0, 0.0, 0.0)).length() < 1.0e-6);
+    let bounds = shape.local_bounds();
+    assert!(bounds.min.x < -1.9 && bounds.max.x > 1.9);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_ray_cast() {
+    let mut random = Random(0x2468_ace0);
+    let sub_shapes: Vec<CompoundSubShape> = (0..100)
+        .map(|i| {
+            let shape: Arc<dyn Shape> = if i % 2 == 0 {
+                Arc::new(SphereShape::new(0.5 + random.next().abs()).unwrap())
+            } else {
+                Arc::new(BoxShape::new(Vec3::splat(0.5) + random.vec3().abs()).unwrap())
+            };
+            let rotation = Quat::from_axis_angle(random.vec3().normalize(), random.next());
+            CompoundSubShape::new(shape, random.vec3() * 20.0, rotation).with_user_data(i)
+        })
+        .collect();
+    let shape = StaticCompoundShape::new(&sub_shapes).unwrap();
+
+    let mut hits = 0;
+    for _ in 0..500 {
+        let origin = random.vec3() * 25.0;
+        let ray = RayCast::new(origin, (random.vec3() * 15.0 - origin) * 2.0);
+        let mut expected = RayCastResult::default();
+        let mut expected_index = None;
+        for (index, sub_shape) in shape.sub_shapes().iter().enumerate() {
+            let local_ray = RayCast::new(
+                sub_shape.to_local(ray.origin),
+                sub_shape.rotation.conjugate() * ray.direction,
+            );
+            if sub_shape.shape.cast_ray(&local_ray, &mut expected) {
+                expected_index = Some(index as u32);
+            }
+        }
+
+        let mut hit = RayCastResult::default();
+        assert_eq!(shape.cast_ray(&ray, &mut hit), expected_index.is_some());
+        if let Some(index) = expected_index {
+            hits += 1;
+            assert!((hit.fraction - expected.fraction).abs() < 1.0e-5);
+            let (hit_index, remainder) = shape.sub_shape_index(hit.sub_shape_id);
+            assert_eq!(hit_index, index);
+            assert!(remainder.is_empty());
+            assert_eq!(shape.sub_shapes()[index as usize].user_data, index);
+
+            // The normal points against the ray, unless it started inside of a child.
+            if hit.fraction == 0.0 {
+                continue;
+            }
+            let position = ray.point_on_ray(hit.fraction);
+            let normal = shape.surface_normal(hit.sub_shape_id, position);
+            assert!(normal.dot(ray.direction) <= 1.0e-5);
+        }
+    }
+    assert!(hits > 50);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_nested_sub_shape_ids() {
+    let vertices = [Vec3::ZERO, Vec3::Z, Vec3::X, vec3(1.0, 0.0, 1.0)];
+    let triangles = [
+        IndexedTriangle::new(0, 1, 2, 3),
+        IndexedTriangle::new(2, 1, 3, 4),
+    ];
+    let mesh: Arc<dyn Shape> = Arc::new(MeshShape::new(&vertices, &triangles).unwrap());
+    let sphere: Arc<dyn Shape> = Arc::new(SphereShape::new(0.5).unwrap());
+    let inner = Arc::new(
+        StaticCompoundShape::new(&[
+            CompoundSubShape::new(sphere.clone(), vec3(5.0, 0.0, 0.0), Quat::IDENTITY),
+            CompoundSubShape::new(sphere.clone(), vec3(5.0, 0.0, 5.0), Quat::IDENTITY),
+            CompoundSubShape::new(mesh, vec3(0.0, -1.0, 0.0), Quat::IDENTITY),
+        ])
+        .unwrap(),
+    );
+    assert_eq!(inner.sub_shape_id_bits(), 2 + 1);
+    let shape = StaticCompoundShape::new(&[
+        CompoundSubShape::new(sphere, vec3(-5.0, 0.0, 0.0), Quat::IDENTITY),
+        CompoundSubShape::new(inner.clone(), vec3(0.0, 2.0, 0.0), Quat::IDENTITY),
+    ])
+    .unwrap();
+    assert_eq!(shape.sub_shape_id_bits(), 1 + 3);
+
+    // Hit the second triangle of the mesh from above.
+    let origin = shape.sub_shapes()[1].position + inner.sub_shapes()[2].position;
+    let ray = RayCast::new(origin + vec3(0.8, 1.0, 0.8), vec3(0.0, -2.0, 0.0));
+    let mut hit = RayCastResult::default();
+    assert!(shape.cast_ray(&ray, &mut hit));
+    assert!((hit.fraction - 0.5).abs() < 1.0e-5);
+    assert_eq!(shape.material_index(hit.sub_shape_id), 4);
+
+    let (index, remainder) = shape.sub_shape_index(hit.sub_shape_id);
+    assert_eq!(index, 1);
+    let (index, remainder) = inner.sub_shape_index(remainder);
+    assert_eq!(index, 2);
+    assert_eq!(remainder.pop_id(1), (1, SubShapeID::EMPTY));
+
+    assert!(shape.collide_point(shape.sub_shapes()[0].position + vec3(0.2, 0.3, 0.0)));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_collide_point() {
+    let sphere: Arc<dyn Shape> = Arc::new(SphereShape::new(1.0).unwrap());
+    let cube: Arc<dyn Shape> = Arc::new(BoxShape::new(Vec3::ONE).unwrap());
+    let shape = StaticCompoundShape::new(&[
+        CompoundSubShape::new(sphere, vec3(-2.0, 0.0, 0.0), Quat::IDENTITY),
+        CompoundSubShape::new(cube, vec3(2.0, 0.0, 0.0), Quat::from_rotation_z(FRAC_PI_4)),
+    ])
+    .unwrap();
+    let com = shape.center_of_mass();
+    assert!(shape.collide_point(vec3(-2.5, 0.0, 0.0) - com));
+    assert!(shape.collide_point(vec3(2.0, 1.3, 0.0) - com));
+    assert!(!shape.collide_point(vec3(2.9, 0.9, 0.0) - com));
+    assert!(!shape.collide_point(vec3(0.0, 0.0, 0.0) - com));
+
+    let mut found = Vec::new();
+    shape.collide_sub_shapes(
+        &AABox::new(vec3(1.0, -0.1, -0.1) - com, vec3(1.5, 0.1, 0.1) - com),
+        |index| found.push(index),
+    );
+    assert_eq!(found, [1]);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_errors() {
+    assert_eq!(
+        StaticCompoundShape::new(&[]).unwrap_err(),
+        ShapeError::EmptyCompound
+    );
+
+    // Every level of 256 children takes 8 bits.
+    let mut shape: Arc<dyn Shape> = Arc::new(SphereShape::new(1.0).unwrap());
+    for _ in 0..4 {
+        let sub_shapes: Vec<CompoundSubShape> = (0..256)
+            .map(|i| CompoundSubShape::new(shape.clone(), vec3(i as f32, 0.0, 0.0), Quat::IDENTITY))
+            .collect();
+        shape = Arc::new(StaticCompoundShape::new(&sub_shapes).unwrap());
+    }
+    assert_eq!(shape.sub_shape_id_bits(), 32);
+    let sub_shapes = [
+        CompoundSubShape::new(shape.clone(), Vec3::ZERO, Quat::IDENTITY),
+        CompoundSubShape::new(shape, Vec3::X, Quat::IDENTITY),
+    ];
+    assert_eq!(
+        StaticCompoundShape::new(&sub_shapes).unwrap_err(),
+        ShapeError::SubShapeIDOverflow
+    );
+}
@@ -0,0 +1,51 @@
+use phys_rs::shape::{SubShapeID, SubShapeIDCreator};
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_push_pop() {
+    let creator = SubShapeIDCreator::new().push_id(5, 3).push_id(0, 1);
+    assert_eq!(creator.num_bits_written(), 4);
+    let id = creator.push_id(1234, 12).id();
+
+    let (value, id) = id.pop_id(3);
+    assert_eq!(value, 5);
+    let (value, id) = id.pop_id(1);
+    assert_eq!(value, 0);
+    let (value, id) = id.pop_id(12);
+    assert_eq!(value, 1234);
+    assert!(id.is_empty());
+
+    // Popping from an empty identifier returns all set bits.
+    assert_eq!(SubShapeID::EMPTY.pop_id(4), (15, SubShapeID::EMPTY));
+    assert_eq!(SubShapeID::EMPTY.pop_id(0), (0, SubShapeID::EMPTY));
+    assert!(SubShapeIDCreator::new().id().is_empty());
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_push_sub_shape_id() {
+    // A child identifies its own sub shapes without knowing about its parent.
+    let child = SubShapeIDCreator::new().push_id(6, 3).push_id(2, 2).id();
+    let id = SubShapeIDCreator::new()
+        .push_id(9, 4)
+        .push_sub_shape_id(child);
+
+    let (value, id) = id.pop_id(4);
+    assert_eq!(value, 9);
+    assert_eq!(id, child);
+
+    let full = SubShapeIDCreator::new()
+        .push_id(0, 30)
+        .push_sub_shape_id(SubShapeID::EMPTY);
+    assert_eq!(full.pop_id(30).1, SubShapeID::EMPTY);
+}
+
+#[should_panic]
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_overflow() {
+    let child = SubShapeIDCreator::new().push_id(0, 30).id();
+    SubShapeIDCreator::new()
+        .push_id(0, 3)
+        .push_sub_shape_id(child);
+}
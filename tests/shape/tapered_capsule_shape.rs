@@ -0,0 +1,77 @@
+use core::f32::consts::PI;
+
+use phys_rs::{
+    collision::{RayCast, RayCastResult},
+    math::Vec3,
+    shape::{CapsuleShape, Shape, ShapeError, SubShapeID, TaperedCapsuleShape},
+};
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_equal_radii() {
+    let tapered = TaperedCapsuleShape::new(1.0, 0.5, 0.5).unwrap();
+    let capsule = CapsuleShape::new(1.0, 0.5).unwrap();
+    assert!((tapered.volume() - capsule.volume()).abs() < 1.0e-5);
+    assert!(tapered.center_of_mass().abs_diff_eq(Vec3::ZERO, 1.0e-6));
+    assert!(tapered
+        .mass_properties()
+        .inertia
+        .abs_diff_eq(&capsule.mass_properties().inertia, 1.0e-1));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_volume() {
+    let (h, rt, rb) = (1.0_f32, 0.3_f32, 0.6_f32);
+    let shape = TaperedCapsuleShape::new(h, rt, rb).unwrap();
+
+    // Two spherical caps and the cone frustum that touches both spheres.
+    let sin_alpha = (rb - rt) / (2.0 * h);
+    let cos_alpha = (1.0 - sin_alpha * sin_alpha).sqrt();
+    let cap = |r: f32, k: f32| PI * k * k * (3.0 * r - k) / 3.0;
+    let (r0, r1) = (rb * cos_alpha, rt * cos_alpha);
+    let frustum = PI * 2.0 * h * cos_alpha * cos_alpha / 3.0 * (r0 * r0 + r0 * r1 + r1 * r1);
+    let volume = cap(rb, rb * (1.0 + sin_alpha)) + frustum + cap(rt, rt * (1.0 - sin_alpha));
+    assert!((shape.volume() - volume).abs() < 1.0e-5);
+
+    // The larger bottom sphere pulls the center of mass down.
+    assert!(shape.center_of_mass().y < 0.0);
+    let bounds = shape.local_bounds();
+    assert!((bounds.min.y + shape.center_of_mass().y + h + rb).abs() < 1.0e-5);
+    assert!((bounds.max.y + shape.center_of_mass().y - h - rt).abs() < 1.0e-5);
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_ray_cast() {
+    let shape = TaperedCapsuleShape::new(1.0, 0.3, 0.6).unwrap();
+    let com = shape.center_of_mass();
+
+    // Hit the bottom sphere from below.
+    let origin = Vec3::new(0.0, -3.0, 0.0) - com;
+    let ray = RayCast::new(origin, Vec3::new(0.0, 2.0, 0.0));
+    let mut hit = RayCastResult::default();
+    assert!(shape.cast_ray(&ray, &mut hit));
+    assert!((hit.fraction - 0.7).abs() < 1.0e-3);
+
+    let normal = shape.surface_normal(SubShapeID::EMPTY, ray.point_on_ray(hit.fraction));
+    assert!(normal.abs_diff_eq(-Vec3::Y, 1.0e-3));
+    let normal = shape.surface_normal(SubShapeID::EMPTY, Vec3::new(0.0, 1.3, 0.0) - com);
+    assert!(normal.abs_diff_eq(Vec3::Y, 1.0e-3));
+
+    assert!(shape.collide_point(Vec3::new(0.5, -1.0, 0.0) - com));
+    assert!(!shape.collide_point(Vec3::new(0.5, 1.0, 0.0) - com));
+}
+
+#[cfg_attr(not(target_family = "wasm"), test)]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test::wasm_bindgen_test)]
+fn test_errors() {
+    assert_eq!(
+        TaperedCapsuleShape::new(0.1, 0.1, 0.5),
+        Err(ShapeError::InvalidRadius)
+    );
+    assert_eq!(
+        TaperedCapsuleShape::new(0.0, 0.1, 0.1),
+        Err(ShapeError::InvalidHeight)
+    );
+}